@@ -1,3 +1,6 @@
+import io
+import re
+
 from driver import (
     Architecture_aarch64,
     Architecture_amd64,
@@ -5,6 +8,31 @@ from driver import (
 )
 from parse import parse_file
 
+
+def twelve_rounds(input, loop_bound, replacement):
+    """
+    Rewrite a Keccak-f1600 implementation into one of Keccak-p[1600,12],
+    as used by TurboSHAKE and KangarooTwelve.
+
+    These are the same except for the number of rounds, so this replaces
+    the single instruction matching the `loop_bound` regex (which fixes the
+    number of rounds) with `replacement`.  Callers then supply the last 12
+    round constants.
+    """
+    text = input.read()
+    text, n = re.subn(loop_bound, replacement, text)
+    assert n == 1, f"{loop_bound!r} matched {n} times"
+    for old, new in (
+        ("Keccak-f1600 permutation for SHA3", "Keccak-p[1600,12] permutation for TurboSHAKE"),
+        ("rc[24]", "rc[12]"),
+        ("rc[static 24]", "rc[static 12]"),
+        ("all 24 rounds", "12 rounds"),
+        ("Now 24 rounds", "Now 12 rounds"),
+    ):
+        text = text.replace(old, new)
+    return io.StringIO(text)
+
+
 if __name__ == "__main__":
     with (
         open("../../thirdparty/s2n-bignum/x86/sha3/sha3_keccak_f1600.S") as input,
@@ -84,3 +112,88 @@ if __name__ == "__main__":
             rust_decl="fn sha3_keccak2_f1600(a: &mut [[u64; 25]; 2], rc: &[u64; 24])",
         )
         parse_file(input, d)
+
+    with (
+        open("../../thirdparty/s2n-bignum/x86/sha3/sha3_keccak_f1600.S") as input,
+        open("../../graviola/src/low/x86_64/sha3_keccak_p1600_12.rs", "w") as output,
+    ):
+        input = twelve_rounds(input, r"cmp\s+r8, 24", "cmp r8, 12")
+        d = RustDriver(output, Architecture_amd64)
+        d.emit_rust_function(
+            "sha3_keccak_f1600",
+            parameter_map=[
+                ("inout", "a.as_mut_ptr() => _"),
+                ("inout", "rc.as_ptr() => _"),
+            ],
+            rust_decl="fn sha3_keccak_p1600_12(a: &mut [u64; 25], rc: &[u64; 12])",
+        )
+        parse_file(input, d)
+
+    with (
+        open("../../thirdparty/s2n-bignum/x86/sha3/sha3_keccak4_f1600_alt.S") as input,
+        open("../../graviola/src/low/x86_64/sha3_keccak4_p1600_12_alt.rs", "w") as output,
+    ):
+        input = twelve_rounds(input, r"cmp\s+r10, 0x18", "cmp r10, 0xc")
+        d = RustDriver(output, Architecture_amd64)
+        d.emit_rust_function(
+            "sha3_keccak4_f1600_alt",
+            parameter_map=[
+                ("inout", "a.as_mut_ptr() => _"),
+                ("inout", "rc.as_ptr() => _"),
+                ("inout", "rho8.as_ptr() => _"),
+                ("inout", "rho56.as_ptr() => _"),
+            ],
+            rust_decl="fn sha3_keccak4_p1600_12(a: &mut [[u64; 25]; 4], rc: &[u64; 12], rho8: &[u64; 4], rho56: &[u64; 4])",
+        )
+        parse_file(input, d)
+
+    with (
+        open("../../thirdparty/s2n-bignum/arm/sha3/sha3_keccak_f1600.S") as input,
+        open("../../graviola/src/low/aarch64/sha3_keccak_p1600_12.rs", "w") as output,
+    ):
+        # nb. the first round is peeled out of the loop
+        input = twelve_rounds(input, r"cmp\s+x25, #0x17", "cmp x25, #0xb")
+        d = RustDriver(output, Architecture_aarch64)
+        d.emit_rust_function(
+            "sha3_keccak_f1600",
+            parameter_map=[
+                ("inout", "a.as_mut_ptr() => _"),
+                ("inout", "rc.as_ptr() => _"),
+            ],
+            rust_decl="fn sha3_keccak_p1600_12(a: &mut [u64; 25], rc: &[u64; 12])",
+        )
+        parse_file(input, d)
+
+    with (
+        open("../../thirdparty/s2n-bignum/arm/sha3/sha3_keccak_f1600_alt.S") as input,
+        open("../../graviola/src/low/aarch64/sha3_keccak_p1600_12_alt.rs", "w") as output,
+    ):
+        input = twelve_rounds(input, r"mov\s+x2, #24", "mov x2, #12")
+        d = RustDriver(output, Architecture_aarch64)
+        d.emit_rust_function(
+            "sha3_keccak_f1600_alt",
+            parameter_map=[
+                ("inout", "a.as_mut_ptr() => _"),
+                ("inout", "rc.as_ptr() => _"),
+            ],
+            func_attrs='#[target_feature(enable = "sha3")]\n',
+            rust_decl="unsafe fn sha3_keccak_p1600_12(a: &mut [u64; 25], rc: &[u64; 12])",
+        )
+        parse_file(input, d)
+
+    with (
+        open("../../thirdparty/s2n-bignum/arm/sha3/sha3_keccak2_f1600.S") as input,
+        open("../../graviola/src/low/aarch64/sha3_keccak2_p1600_12.rs", "w") as output,
+    ):
+        input = twelve_rounds(input, r"mov\s+x2, #24", "mov x2, #12")
+        d = RustDriver(output, Architecture_aarch64)
+        d.emit_rust_function(
+            "sha3_keccak2_f1600",
+            parameter_map=[
+                ("inout", "a.as_mut_ptr() => _"),
+                ("inout", "rc.as_ptr() => _"),
+            ],
+            func_attrs='#[target_feature(enable = "sha3")]\n',
+            rust_decl="fn sha3_keccak2_p1600_12(a: &mut [[u64; 25]; 2], rc: &[u64; 12])",
+        )
+        parse_file(input, d)
@@ -0,0 +1,78 @@
+mod criterion;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+fn test_graviola_aes_gcm(key: &graviola::aead::AesGcm, aad: &[u8], plain: &[u8]) {
+    let mut ct = plain.to_vec();
+    let mut tag = [0u8; 16];
+    key.encrypt(&[0u8; 12], aad, &mut ct, &mut tag);
+}
+
+fn test_graviola_aegis128x<const LANES: usize>(
+    key: &graviola::aead::Aegis128X<LANES>,
+    aad: &[u8],
+    plain: &[u8],
+) {
+    let mut ct = plain.to_vec();
+    let mut tag = [0u8; 16];
+    key.encrypt(&[0u8; 16], aad, &mut ct, &mut tag);
+}
+
+fn test_graviola_aegis256(key: &graviola::aead::Aegis256, aad: &[u8], plain: &[u8]) {
+    let mut ct = plain.to_vec();
+    let mut tag = [0u8; 16];
+    key.encrypt(&[0u8; 32], aad, &mut ct, &mut tag);
+}
+
+fn bench_aegis(c: &mut Criterion) {
+    let aad = [0u8; 32];
+
+    let mut group = c.benchmark_group("aegis");
+    for (size, size_name) in [(32, "32B"), (2048, "2KB"), (16384, "16KB")] {
+        let input = vec![0u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("graviola-aes-128-gcm", size_name),
+            &input,
+            |b, input| {
+                let key = graviola::aead::AesGcm::new(&[0u8; 16]);
+                b.iter(|| test_graviola_aes_gcm(&key, &aad, input));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("graviola-aegis-128l", size_name),
+            &input,
+            |b, input| {
+                let key = graviola::aead::Aegis128L::new([0u8; 16]);
+                b.iter(|| test_graviola_aegis128x(&key, &aad, input));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("graviola-aegis-128x2", size_name),
+            &input,
+            |b, input| {
+                let key = graviola::aead::Aegis128X2::new([0u8; 16]);
+                b.iter(|| test_graviola_aegis128x(&key, &aad, input));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("graviola-aegis-128x4", size_name),
+            &input,
+            |b, input| {
+                let key = graviola::aead::Aegis128X4::new([0u8; 16]);
+                b.iter(|| test_graviola_aegis128x(&key, &aad, input));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("graviola-aegis-256", size_name),
+            &input,
+            |b, input| {
+                let key = graviola::aead::Aegis256::new([0u8; 32]);
+                b.iter(|| test_graviola_aegis256(&key, &aad, input));
+            },
+        );
+    }
+}
+
+criterion_group!(benches, bench_aegis);
+criterion_main!(benches);
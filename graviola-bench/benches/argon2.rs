@@ -0,0 +1,51 @@
+mod criterion;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+// OWASP's first recommended configuration: 19MiB, 2 passes, 1 lane.
+const M_COST: u32 = 19 * 1024;
+const T_COST: u32 = 2;
+
+fn test_rustcrypto(password: &[u8], salt: &[u8], p: u32) {
+    use argon2::{Algorithm, Argon2, Params, Version};
+    let params = Params::new(M_COST, T_COST, p, Some(32)).unwrap();
+    let mut out = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password, salt, &mut out)
+        .unwrap();
+    black_box(out);
+}
+
+fn test_graviola(password: &[u8], salt: &[u8], p: u32, threads: u32) {
+    let params = graviola::kdf::Argon2Params::new(M_COST, T_COST, p)
+        .unwrap()
+        .with_threads(threads);
+    let mut out = [0u8; 32];
+    graviola::kdf::argon2id(password, salt, &params, &mut out).unwrap();
+    black_box(out);
+}
+
+fn argon2id(c: &mut Criterion) {
+    let mut group = c.benchmark_group("argon2id");
+    let password = b"correct horse battery staple";
+    let salt = [0x5a; 16];
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(20);
+
+    for p in [1, 4] {
+        group.bench_function(BenchmarkId::new("rustcrypto", p), |b| {
+            b.iter(|| test_rustcrypto(password, &salt, p))
+        });
+        group.bench_function(BenchmarkId::new("graviola", p), |b| {
+            b.iter(|| test_graviola(password, &salt, p, 1))
+        });
+    }
+
+    group.bench_function(BenchmarkId::new("graviola-threads", 4), |b| {
+        b.iter(|| test_graviola(password, &salt, 4, 4))
+    });
+}
+
+criterion_group!(benches, argon2id);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+mod criterion;
+use std::hint::black_box;
+use std::num::NonZeroU32;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+const ITERATIONS: u32 = 1000;
+
+fn test_ring_sha256(password: &[u8], salt: &[u8]) {
+    let mut out = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(ITERATIONS).unwrap(),
+        salt,
+        password,
+        &mut out,
+    );
+    black_box(out);
+}
+
+fn test_aws_sha256(password: &[u8], salt: &[u8]) {
+    let mut out = [0u8; 32];
+    aws_lc_rs::pbkdf2::derive(
+        aws_lc_rs::pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(ITERATIONS).unwrap(),
+        salt,
+        password,
+        &mut out,
+    );
+    black_box(out);
+}
+
+fn test_graviola_sha256(password: &[u8], salt: &[u8]) {
+    let mut out = [0u8; 32];
+    graviola::kdf::pbkdf2::<graviola::hashing::Sha256>(password, salt, ITERATIONS, &mut out)
+        .unwrap();
+    black_box(out);
+}
+
+fn test_ring_sha512(password: &[u8], salt: &[u8]) {
+    let mut out = [0u8; 64];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA512,
+        NonZeroU32::new(ITERATIONS).unwrap(),
+        salt,
+        password,
+        &mut out,
+    );
+    black_box(out);
+}
+
+fn test_aws_sha512(password: &[u8], salt: &[u8]) {
+    let mut out = [0u8; 64];
+    aws_lc_rs::pbkdf2::derive(
+        aws_lc_rs::pbkdf2::PBKDF2_HMAC_SHA512,
+        NonZeroU32::new(ITERATIONS).unwrap(),
+        salt,
+        password,
+        &mut out,
+    );
+    black_box(out);
+}
+
+fn test_graviola_sha512(password: &[u8], salt: &[u8]) {
+    let mut out = [0u8; 64];
+    graviola::kdf::pbkdf2::<graviola::hashing::Sha512>(password, salt, ITERATIONS, &mut out)
+        .unwrap();
+    black_box(out);
+}
+
+fn sha256(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pbkdf2-sha256");
+    let password = b"correct horse battery staple";
+    let salt = [0x5a; 16];
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function(BenchmarkId::new("ring", ITERATIONS), |b| {
+        b.iter(|| test_ring_sha256(password, &salt))
+    });
+    group.bench_function(BenchmarkId::new("aws-lc-rs", ITERATIONS), |b| {
+        b.iter(|| test_aws_sha256(password, &salt))
+    });
+    group.bench_function(BenchmarkId::new("graviola", ITERATIONS), |b| {
+        b.iter(|| test_graviola_sha256(password, &salt))
+    });
+}
+
+fn sha512(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pbkdf2-sha512");
+    let password = b"correct horse battery staple";
+    let salt = [0x5a; 16];
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function(BenchmarkId::new("ring", ITERATIONS), |b| {
+        b.iter(|| test_ring_sha512(password, &salt))
+    });
+    group.bench_function(BenchmarkId::new("aws-lc-rs", ITERATIONS), |b| {
+        b.iter(|| test_aws_sha512(password, &salt))
+    });
+    group.bench_function(BenchmarkId::new("graviola", ITERATIONS), |b| {
+        b.iter(|| test_graviola_sha512(password, &salt))
+    });
+}
+
+criterion_group!(benches, sha256, sha512);
+criterion_main!(benches);
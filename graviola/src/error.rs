@@ -26,6 +26,12 @@ pub enum Error {
     /// Presented AEAD tag/aad/ciphertext/nonce was wrong
     DecryptFailed,
 
+    /// A hash function is too weak for the requested use.
+    WeakHash,
+
+    /// An exported hash state was malformed, or for a different hash function.
+    BadHashState,
+
     /// An ASN.1 encoding/decoding error.
     Asn1Error(crate::high::asn1::Error),
 
@@ -81,6 +87,8 @@ impl core::fmt::Display for Error {
             ),
             Self::BadSignature => write!(f, "presented signature is invalid"),
             Self::DecryptFailed => write!(f, "presented AEAD tag/aad/ciphertext/nonce was wrong"),
+            Self::WeakHash => write!(f, "a hash function is too weak for the requested use"),
+            Self::BadHashState => write!(f, "an exported hash state was malformed"),
             Self::Asn1Error(e) => write!(f, "an ASN.1 encoding/decoding error: {e}"),
             Self::KeyFormatError(e) => write!(f, "a key formatting/validation error: {e}"),
         }
@@ -122,6 +130,14 @@ mod tests {
             format!("{}", Error::DecryptFailed),
             "presented AEAD tag/aad/ciphertext/nonce was wrong"
         );
+        assert_eq!(
+            format!("{}", Error::WeakHash),
+            "a hash function is too weak for the requested use"
+        );
+        assert_eq!(
+            format!("{}", Error::BadHashState),
+            "an exported hash state was malformed"
+        );
         assert_eq!(
             format!(
                 "{}",
@@ -0,0 +1,906 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! The Argon2id password hashing function.
+//!
+//! Argon2 is standardized in [RFC9106](https://datatracker.ietf.org/doc/html/rfc9106).
+//! Only the Argon2id variant, at version 0x13, is supported.
+//!
+//! Password hashes are stored and verified in the
+//! [PHC string format](https://github.com/P-H-C/phc-string-format/blob/master/phc-sf-spec.md).
+
+use crate::Error;
+use crate::low::{Entry, argon2_fill_block, ct_equal, zeroise};
+use crate::mid::blake2::Blake2bContext;
+use crate::mid::rng::{RandomSource, SystemRandom};
+
+/// Cost parameters for Argon2id.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Argon2Params {
+    m: u32,
+    t: u32,
+    p: u32,
+    threads: u32,
+}
+
+impl Argon2Params {
+    /// Make and validate a new set of Argon2id parameters.
+    ///
+    /// `m` is the memory size in kibibytes, `t` is the number of
+    /// passes over that memory, and `p` is the degree of parallelism
+    /// (the number of independent lanes).
+    ///
+    /// This returns `Error::OutOfRange` if:
+    ///
+    /// - `t` is zero,
+    /// - `p` is zero or not less than 2<sup>24</sup>,
+    /// - `m` is less than `8 * p`, or
+    /// - the memory required cannot be addressed on this platform.
+    ///
+    /// The result uses one thread; see [`Argon2Params::with_threads()`].
+    pub fn new(m: u32, t: u32, p: u32) -> Result<Self, Error> {
+        if t == 0 || p == 0 || p >= 1 << 24 || u64::from(m) < u64::from(p) * 8 {
+            return Err(Error::OutOfRange);
+        }
+
+        let params = Self {
+            m,
+            t,
+            p,
+            threads: 1,
+        };
+        params
+            .blocks()
+            .checked_mul(BLOCK_LEN)
+            .ok_or(Error::OutOfRange)?;
+        Ok(params)
+    }
+
+    /// Use up to `threads` threads to fill memory.
+    ///
+    /// Lanes are filled in parallel, so no more than `p` threads
+    /// are useful.  This does not affect the output.  Zero is
+    /// treated as one.
+    pub fn with_threads(self, threads: u32) -> Self {
+        Self {
+            threads: threads.clamp(1, self.p),
+            ..self
+        }
+    }
+
+    /// The number of bytes of memory used by [`argon2id()`] with these parameters.
+    ///
+    /// This is approximately `m` kibibytes, rounded down to a multiple of `4 * p`.
+    pub fn memory_required(&self) -> usize {
+        self.blocks() * BLOCK_LEN
+    }
+
+    /// Total number of blocks, m' in RFC9106.
+    fn blocks(&self) -> usize {
+        self.lanes() * self.lane_len()
+    }
+
+    fn lanes(&self) -> usize {
+        self.p as usize
+    }
+
+    /// Blocks per lane, q in RFC9106.
+    fn lane_len(&self) -> usize {
+        self.segment_len() * SYNC_POINTS
+    }
+
+    fn segment_len(&self) -> usize {
+        (self.m / (self.p * SYNC_POINTS as u32)) as usize
+    }
+}
+
+/// Derive key material from `password` and `salt`, writing it to `out`.
+///
+/// This is Argon2id with the costs given in `params`.  The length of
+/// `out` determines the amount of key material produced.
+///
+/// Memory is allocated for the duration of this call; see
+/// [`Argon2Params::memory_required()`].
+///
+/// This returns `Error::WrongLength` if `salt` is shorter than 8 bytes,
+/// or `out` is shorter than 4 bytes.  It returns `Error::OutOfRange`
+/// if any input is longer than Argon2 allows, or the required memory
+/// cannot be allocated.
+///
+/// ```
+/// use graviola::kdf::{Argon2Params, argon2id};
+///
+/// let params = Argon2Params::new(19 * 1024, 2, 1).unwrap();
+/// let mut key = [0u8; 32];
+/// argon2id(b"password", b"saltsalt", &params, &mut key).unwrap();
+/// ```
+pub fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    params: &Argon2Params,
+    out: &mut [u8],
+) -> Result<(), Error> {
+    argon2id_keyed(password, salt, &[], &[], params, out)
+}
+
+/// Derive key material from `password` and `salt`, writing it to `out`.
+///
+/// This is like [`argon2id()`], but also includes the optional
+/// `secret` value (K in RFC9106) and `associated_data` (X in RFC9106).
+pub fn argon2id_keyed(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    params: &Argon2Params,
+    out: &mut [u8],
+) -> Result<(), Error> {
+    let _entry = Entry::new_secret();
+
+    if salt.len() < MIN_SALT_LEN || out.len() < MIN_OUTPUT_LEN {
+        return Err(Error::WrongLength);
+    }
+
+    let h0 = initial_hash(password, salt, secret, associated_data, params, out.len())?;
+
+    let blocks = params.blocks();
+    let mut memory = Vec::new();
+    memory
+        .try_reserve_exact(blocks)
+        .map_err(|_| Error::OutOfRange)?;
+    memory.resize(blocks, [0u64; 128]);
+
+    let memory_ref = Memory::new(&mut memory, params);
+    fill_memory(memory_ref, &h0, params);
+
+    // C = B[0][q-1] XOR B[1][q-1] XOR ... XOR B[p-1][q-1]
+    let mut c = [0u64; 128];
+    let last_column = &memory[(SYNC_POINTS - 1) * params.lanes() * params.segment_len()..];
+    for lane in last_column.chunks_exact(params.segment_len()) {
+        for (c, b) in c.iter_mut().zip(lane.last().unwrap()) {
+            *c ^= *b;
+        }
+    }
+
+    let mut c_bytes = [0u8; BLOCK_LEN];
+    block_to_bytes(&c, &mut c_bytes);
+    hash_long(out, &[&c_bytes]);
+
+    zeroise(&mut c);
+    zeroise(&mut c_bytes);
+    zeroise(memory.as_flattened_mut());
+    Ok(())
+}
+
+/// Hash `password` for storage, returning a PHC-format string.
+///
+/// A random 16-byte salt is generated, and the output is 32 bytes.
+/// The result looks like
+/// `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`, and can later be
+/// checked with [`argon2id_verify_password()`].
+///
+/// ```
+/// use graviola::kdf::{Argon2Params, argon2id_hash_password, argon2id_verify_password};
+///
+/// let params = Argon2Params::new(19 * 1024, 2, 1).unwrap();
+/// let stored = argon2id_hash_password(b"hunter2", &params).unwrap();
+/// assert!(argon2id_verify_password(b"hunter2", &stored).is_ok());
+/// assert!(argon2id_verify_password(b"hunter3", &stored).is_err());
+/// ```
+pub fn argon2id_hash_password(password: &[u8], params: &Argon2Params) -> Result<String, Error> {
+    let mut salt = [0u8; 16];
+    SystemRandom.fill(&mut salt)?;
+
+    let mut hash = [0u8; 32];
+    argon2id(password, &salt, params, &mut hash)?;
+    let encoded = encode_phc(params, &salt, &hash);
+    zeroise(&mut hash);
+    Ok(encoded)
+}
+
+/// Check `password` against the PHC-format string `phc`.
+///
+/// `phc` would typically have been produced by [`argon2id_hash_password()`],
+/// though any Argon2id version 0x13 PHC string is accepted.  The
+/// parameters, salt and output length are all taken from `phc`.
+///
+/// This returns `Error::BadSignature` if the password is incorrect, or
+/// `Error::OutOfRange` if `phc` is malformed or uses unsupported
+/// parameters.
+pub fn argon2id_verify_password(password: &[u8], phc: &str) -> Result<(), Error> {
+    let (params, salt, expected) = decode_phc(phc).ok_or(Error::OutOfRange)?;
+    if salt.len() < MIN_SALT_LEN || expected.len() < MIN_OUTPUT_LEN {
+        return Err(Error::OutOfRange);
+    }
+
+    let mut actual = vec![0u8; expected.len()];
+    argon2id(password, &salt, &params, &mut actual)?;
+    let equal = ct_equal(&actual, &expected);
+    zeroise(&mut actual);
+
+    if equal {
+        Ok(())
+    } else {
+        Err(Error::BadSignature)
+    }
+}
+
+/// H_0 from RFC9106 section 3.2.
+fn initial_hash(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    params: &Argon2Params,
+    output_len: usize,
+) -> Result<[u8; 64], Error> {
+    let length = |bytes: &[u8]| u32::try_from(bytes.len()).map_err(|_| Error::OutOfRange);
+    let output_len = u32::try_from(output_len).map_err(|_| Error::OutOfRange)?;
+
+    let mut ctx = Blake2bContext::new(64).unwrap();
+    ctx.update(&params.p.to_le_bytes());
+    ctx.update(&output_len.to_le_bytes());
+    ctx.update(&params.m.to_le_bytes());
+    ctx.update(&params.t.to_le_bytes());
+    ctx.update(&VERSION.to_le_bytes());
+    ctx.update(&TYPE_ID.to_le_bytes());
+    for input in [password, salt, secret, associated_data] {
+        ctx.update(&length(input)?.to_le_bytes());
+        ctx.update(input);
+    }
+
+    let mut h0 = [0u8; 64];
+    ctx.finish(&mut h0);
+    Ok(h0)
+}
+
+/// The variable-length hash function H' from RFC9106 section 3.3,
+/// on the concatenation of `inputs`.  `out` must be at least 4 bytes.
+fn hash_long(out: &mut [u8], inputs: &[&[u8]]) {
+    let out_len = (out.len() as u32).to_le_bytes();
+
+    if out.len() <= Blake2bContext::MAX_OUTPUT_SZ {
+        let mut ctx = Blake2bContext::new(out.len()).unwrap();
+        ctx.update(&out_len);
+        for input in inputs {
+            ctx.update(input);
+        }
+        ctx.finish(out);
+        return;
+    }
+
+    // V_1 = H^(64)(LE32(T)||A)
+    let mut v = [0u8; 64];
+    let mut ctx = Blake2bContext::new(64).unwrap();
+    ctx.update(&out_len);
+    for input in inputs {
+        ctx.update(input);
+    }
+    ctx.finish(&mut v);
+
+    // W_i is the first 32 bytes of V_i; V_(i+1) = H^(64)(V_i)
+    let (mut written, mut remain) = out.split_at_mut(32);
+    written.copy_from_slice(&v[..32]);
+    while remain.len() > 64 {
+        let mut ctx = Blake2bContext::new(64).unwrap();
+        ctx.update(&v);
+        ctx.finish(&mut v);
+        (written, remain) = remain.split_at_mut(32);
+        written.copy_from_slice(&v[..32]);
+    }
+
+    // V_(r+1) = H^(T-32*r)(V_r)
+    let mut ctx = Blake2bContext::new(remain.len()).unwrap();
+    ctx.update(&v);
+    ctx.finish(remain);
+    zeroise(&mut v);
+}
+
+/// The memory matrix, arranged so that the segments of a slice
+/// are contiguous, and may be filled in parallel.
+///
+/// Block `j` of lane `i` is in slice `j / segment_len`.  Each slice
+/// holds a segment of `segment_len` blocks for each lane in turn.
+struct Memory<'a> {
+    blocks: &'a mut [Block],
+    lanes: usize,
+    segment_len: usize,
+}
+
+impl<'a> Memory<'a> {
+    fn new(blocks: &'a mut [Block], params: &Argon2Params) -> Self {
+        Self {
+            blocks,
+            lanes: params.lanes(),
+            segment_len: params.segment_len(),
+        }
+    }
+
+    /// Split the memory into the segments for `slice`, which
+    /// are written, and the rest, which are only read.
+    fn split(&mut self, slice: usize) -> (Reference<'_>, impl Iterator<Item = &mut [Block]>) {
+        let slice_len = self.lanes * self.segment_len;
+        let (before, rest) = self.blocks.split_at_mut(slice * slice_len);
+        let (current, after) = rest.split_at_mut(slice_len);
+        (
+            Reference {
+                before,
+                after,
+                slice,
+                lanes: self.lanes,
+                segment_len: self.segment_len,
+            },
+            current.chunks_exact_mut(self.segment_len),
+        )
+    }
+}
+
+/// Read-only access to the memory outside the slice being filled.
+struct Reference<'a> {
+    before: &'a [Block],
+    after: &'a [Block],
+    slice: usize,
+    lanes: usize,
+    segment_len: usize,
+}
+
+impl Reference<'_> {
+    /// Block `index` of `lane`, which must not be in the current slice.
+    fn get(&self, lane: usize, index: usize) -> &Block {
+        let slice = index / self.segment_len;
+        let offset = (slice * self.lanes + lane) * self.segment_len + index % self.segment_len;
+
+        match slice.cmp(&self.slice) {
+            core::cmp::Ordering::Less => &self.before[offset],
+            core::cmp::Ordering::Greater => {
+                &self.after[offset - (self.slice + 1) * self.lanes * self.segment_len]
+            }
+            core::cmp::Ordering::Equal => unreachable!(),
+        }
+    }
+}
+
+fn fill_memory(mut memory: Memory<'_>, h0: &[u8; 64], params: &Argon2Params) {
+    // B[i][0] = H'^(1024)(H_0 || LE32(0) || LE32(i))
+    // B[i][1] = H'^(1024)(H_0 || LE32(1) || LE32(i))
+    {
+        let (_, segments) = memory.split(0);
+        let mut bytes = [0u8; BLOCK_LEN];
+        for (lane, segment) in segments.enumerate() {
+            for (j, block) in segment[..2].iter_mut().enumerate() {
+                hash_long(
+                    &mut bytes,
+                    &[h0, &(j as u32).to_le_bytes(), &(lane as u32).to_le_bytes()],
+                );
+                block_from_bytes(&bytes, block);
+            }
+        }
+        zeroise(&mut bytes);
+    }
+
+    let threads = params.threads as usize;
+    let lanes_per_thread = params.lanes().div_ceil(threads);
+    let groups = params.lanes().div_ceil(lanes_per_thread);
+
+    for pass in 0..params.t {
+        for slice in 0..SYNC_POINTS {
+            let (reference, segments) = memory.split(slice);
+            let position = Position {
+                params,
+                pass,
+                slice,
+            };
+
+            if threads == 1 {
+                for (lane, segment) in segments.enumerate() {
+                    fill_segment(&position, &reference, lane, segment);
+                }
+                continue;
+            }
+
+            // Lanes within a slice are independent, so are divided
+            // between threads.  The threads are joined at the end of
+            // each slice.
+            let mut segments = segments.enumerate();
+            let mut work = (0..groups)
+                .map(|_| segments.by_ref().take(lanes_per_thread).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+                .into_iter();
+            let (position, reference) = (&position, &reference);
+            std::thread::scope(|scope| {
+                let ours = work.next().unwrap();
+                for theirs in work {
+                    scope.spawn(move || {
+                        // cpu state is per-thread
+                        let _entry = Entry::new_secret();
+                        fill_lanes(position, reference, theirs)
+                    });
+                }
+                fill_lanes(position, reference, ours);
+            });
+        }
+    }
+}
+
+fn fill_lanes(
+    position: &Position<'_>,
+    reference: &Reference<'_>,
+    lanes: Vec<(usize, &mut [Block])>,
+) {
+    for (lane, segment) in lanes {
+        fill_segment(position, reference, lane, segment);
+    }
+}
+
+/// Where we are in the computation.
+struct Position<'a> {
+    params: &'a Argon2Params,
+    pass: u32,
+    slice: usize,
+}
+
+fn fill_segment(
+    position: &Position<'_>,
+    reference: &Reference<'_>,
+    lane: usize,
+    segment: &mut [Block],
+) {
+    let Position {
+        params,
+        pass,
+        slice,
+    } = *position;
+    let lanes = params.lanes();
+    let segment_len = params.segment_len();
+    let lane_len = params.lane_len();
+
+    // Argon2id uses data-independent addressing for the first half
+    // of the first pass, and data-dependent addressing thereafter.
+    let data_independent = pass == 0 && slice < SYNC_POINTS / 2;
+
+    let mut input = [0u64; 128];
+    let mut address = [0u64; 128];
+    if data_independent {
+        input[0] = u64::from(pass);
+        input[1] = lane as u64;
+        input[2] = slice as u64;
+        input[3] = params.blocks() as u64;
+        input[4] = u64::from(params.t);
+        input[5] = u64::from(TYPE_ID);
+    }
+
+    // the first two blocks of each lane are already filled
+    let start = if pass == 0 && slice == 0 {
+        if data_independent {
+            next_addresses(&mut input, &mut address);
+        }
+        2
+    } else {
+        0
+    };
+
+    for index in start..segment_len {
+        let (done, rest) = segment.split_at_mut(index);
+        let next = &mut rest[0];
+        let prev = match done.last() {
+            Some(prev) => prev,
+            None => reference.get(lane, (slice * segment_len + lane_len - 1) % lane_len),
+        };
+
+        let pseudo_rand = if data_independent {
+            if index % ADDRESSES_PER_BLOCK == 0 {
+                next_addresses(&mut input, &mut address);
+            }
+            address[index % ADDRESSES_PER_BLOCK]
+        } else {
+            prev[0]
+        };
+
+        let ref_lane = if pass == 0 && slice == 0 {
+            lane
+        } else {
+            ((pseudo_rand >> 32) % lanes as u64) as usize
+        };
+        let ref_index = reference_index(position, index, pseudo_rand as u32, ref_lane == lane);
+
+        // same-lane references into the current segment are only to
+        // blocks already filled in this segment.
+        let ref_block = if ref_index / segment_len == slice {
+            debug_assert_eq!(ref_lane, lane);
+            &done[ref_index % segment_len]
+        } else {
+            reference.get(ref_lane, ref_index)
+        };
+
+        argon2_fill_block(prev, ref_block, next, pass > 0);
+    }
+
+    zeroise(&mut address);
+}
+
+/// Map J_1 to the index of the reference block within its lane,
+/// as described in RFC9106 section 3.4.1.2.
+fn reference_index(position: &Position<'_>, index: usize, j1: u32, same_lane: bool) -> usize {
+    let segment_len = position.params.segment_len();
+    let lane_len = position.params.lane_len();
+
+    // the set W of blocks which may be referenced: all blocks
+    // computed so far, except the previous block, and those
+    // in the current slice of other lanes.  In later passes, this
+    // excludes the current slice which is being overwritten.
+    let finished = match position.pass {
+        0 => position.slice * segment_len,
+        _ => lane_len - segment_len,
+    };
+    let area_size = match same_lane {
+        true => finished + index - 1,
+        false if index == 0 => finished - 1,
+        false => finished,
+    } as u64;
+
+    // x = J_1^2 / 2^32, y = (|W| * x) / 2^32, zz = |W| - 1 - y
+    let x = (u64::from(j1) * u64::from(j1)) >> 32;
+    let y = (area_size * x) >> 32;
+    let relative = (area_size - 1 - y) as usize;
+
+    let start = match position.pass {
+        0 => 0,
+        _ if position.slice == SYNC_POINTS - 1 => 0,
+        _ => (position.slice + 1) * segment_len,
+    };
+    (start + relative) % lane_len
+}
+
+/// Generate the next block of pseudo-random values for
+/// data-independent addressing.
+fn next_addresses(input: &mut Block, address: &mut Block) {
+    input[6] += 1;
+    argon2_fill_block(&ZERO_BLOCK, input, address, false);
+    let first = *address;
+    argon2_fill_block(&ZERO_BLOCK, &first, address, false);
+}
+
+fn block_from_bytes(bytes: &[u8; BLOCK_LEN], block: &mut Block) {
+    for (word, bytes) in block.iter_mut().zip(bytes.chunks_exact(8)) {
+        *word = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+}
+
+fn block_to_bytes(block: &Block, bytes: &mut [u8; BLOCK_LEN]) {
+    for (word, bytes) in block.iter().zip(bytes.chunks_exact_mut(8)) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+fn encode_phc(params: &Argon2Params, salt: &[u8], hash: &[u8]) -> String {
+    format!(
+        "$argon2id$v={}$m={},t={},p={}${}${}",
+        VERSION,
+        params.m,
+        params.t,
+        params.p,
+        base64_encode(salt),
+        base64_encode(hash)
+    )
+}
+
+fn decode_phc(phc: &str) -> Option<(Argon2Params, Vec<u8>, Vec<u8>)> {
+    let mut fields = phc.strip_prefix("$argon2id$")?.split('$');
+
+    let version = fields.next()?.strip_prefix("v=")?;
+    if decode_decimal(version)? != VERSION {
+        return None;
+    }
+
+    let mut costs = fields.next()?.split(',');
+    let m = decode_decimal(costs.next()?.strip_prefix("m=")?)?;
+    let t = decode_decimal(costs.next()?.strip_prefix("t=")?)?;
+    let p = decode_decimal(costs.next()?.strip_prefix("p=")?)?;
+    if costs.next().is_some() {
+        return None;
+    }
+
+    let salt = base64_decode(fields.next()?)?;
+    let hash = base64_decode(fields.next()?)?;
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some((Argon2Params::new(m, t, p).ok()?, salt, hash))
+}
+
+/// Decimal integers in PHC strings have no sign or leading zeroes.
+fn decode_decimal(s: &str) -> Option<u32> {
+    match s.as_bytes() {
+        [] | [b'0', _, ..] => None,
+        digits if digits.iter().all(u8::is_ascii_digit) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Standard base64 alphabet, without padding, as used in PHC strings.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let word = u32::from_be_bytes([0, buf[0], buf[1], buf[2]]);
+        for i in 0..=chunk.len() {
+            let sextet = (word >> (18 - 6 * i)) & 0x3f;
+            out.push(char::from(BASE64_ALPHABET[sextet as usize]));
+        }
+    }
+    out
+}
+
+/// Inverse of `base64_encode()`.
+///
+/// Non-canonical encodings (with non-zero trailing bits) are rejected.
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() / 4 * 3 + 2);
+    for chunk in s.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+
+        let mut word = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let sextet = BASE64_ALPHABET.iter().position(|a| a == c)? as u32;
+            word |= sextet << (18 - 6 * i);
+        }
+
+        let bytes = &word.to_be_bytes()[1..chunk.len()];
+        if word & (0xff_ffff >> (8 * bytes.len())) != 0 {
+            return None;
+        }
+        out.extend_from_slice(bytes);
+    }
+    Some(out)
+}
+
+type Block = [u64; 128];
+
+const BLOCK_LEN: usize = 1024;
+const ZERO_BLOCK: Block = [0u64; 128];
+const ADDRESSES_PER_BLOCK: usize = 128;
+const SYNC_POINTS: usize = 4;
+const MIN_SALT_LEN: usize = 8;
+const MIN_OUTPUT_LEN: usize = 4;
+const VERSION: u32 = 0x13;
+const TYPE_ID: u32 = 2;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc9106_vector() {
+        // RFC9106 section 5.3
+        let mut out = [0u8; 32];
+        argon2id_keyed(
+            &[0x01; 32],
+            &[0x02; 16],
+            &[0x03; 8],
+            &[0x04; 12],
+            &Argon2Params::new(32, 3, 4).unwrap(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            *b"\x0d\x64\x0d\xf5\x8d\x78\x76\x6c\x08\xc0\x37\xa3\x4a\x8b\x53\xc9\
+               \xd0\x1e\xf0\x45\x2d\x75\xb6\x5e\xb5\x25\x20\xe9\x6b\x01\xe6\x59"
+        );
+    }
+
+    #[test]
+    fn reference_vectors() {
+        // from the argon2 reference implementation's test.c
+        check(
+            1 << 8,
+            2,
+            1,
+            b"password",
+            b"somesalt",
+            "9dfeb910e80bad0311fee20f9c0e2b12c17987b4cac90c2ef54d5b3021c68bfe",
+        );
+        check(
+            1 << 8,
+            2,
+            2,
+            b"password",
+            b"somesalt",
+            "6d093c501fd5999645e0ea3bf620d7b8be7fd2db59c20d9fff9539da2bf57037",
+        );
+    }
+
+    #[test]
+    fn reference_slow_vectors() {
+        if std::env::var_os("SLOW_TESTS").is_none() {
+            return;
+        }
+
+        check(
+            1 << 16,
+            2,
+            1,
+            b"password",
+            b"somesalt",
+            "09316115d5cf24ed5a15a31a3ba326e5cf32edc24702987c02b6566f61913cf7",
+        );
+        check(
+            1 << 16,
+            1,
+            1,
+            b"password",
+            b"somesalt",
+            "f6a5adc1ba723dddef9b5ac1d464e180fcd9dffc9d1cbf76cca2fed795d9ca98",
+        );
+        check(
+            1 << 16,
+            4,
+            1,
+            b"password",
+            b"somesalt",
+            "9025d48e68ef7395cca9079da4c4ec3affb3c8911fe4f86d1a2520856f63172c",
+        );
+        check(
+            1 << 16,
+            2,
+            1,
+            b"differentpassword",
+            b"somesalt",
+            "0b84d652cf6b0c4beaef0dfe278ba6a80df6696281d7e0d2891b817d8c458fde",
+        );
+        check(
+            1 << 16,
+            2,
+            1,
+            b"password",
+            b"diffsalt",
+            "bdf32b05ccc42eb15d58fd19b1f856b113da1e9a5874fdcc544308565aa8141c",
+        );
+    }
+
+    #[test]
+    fn threads_do_not_affect_output() {
+        let params = Argon2Params::new(256, 3, 6).unwrap();
+        let mut expected = [0u8; 100];
+        argon2id(b"password", b"somesalt", &params, &mut expected).unwrap();
+
+        for threads in [0, 2, 3, 4, 6, 100] {
+            let mut out = [0u8; 100];
+            argon2id(
+                b"password",
+                b"somesalt",
+                &params.with_threads(threads),
+                &mut out,
+            )
+            .unwrap();
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn phc_encoding() {
+        let params = Argon2Params::new(1 << 8, 2, 1).unwrap();
+        let hash = hex::decode("9dfeb910e80bad0311fee20f9c0e2b12c17987b4cac90c2ef54d5b3021c68bfe")
+            .unwrap();
+        let phc =
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4";
+        assert_eq!(encode_phc(&params, b"somesalt", &hash), phc);
+        assert_eq!(
+            decode_phc(phc),
+            Some((params, b"somesalt".to_vec(), hash.to_vec()))
+        );
+
+        assert_eq!(argon2id_verify_password(b"password", phc), Ok(()));
+        assert_eq!(
+            argon2id_verify_password(b"Password", phc),
+            Err(Error::BadSignature)
+        );
+    }
+
+    #[test]
+    fn phc_round_trip() {
+        let params = Argon2Params::new(64, 1, 2).unwrap();
+        let phc = argon2id_hash_password(b"hello", &params).unwrap();
+        assert!(phc.starts_with("$argon2id$v=19$m=64,t=1,p=2$"));
+        assert_eq!(argon2id_verify_password(b"hello", &phc), Ok(()));
+        assert_eq!(
+            argon2id_verify_password(b"world", &phc),
+            Err(Error::BadSignature)
+        );
+
+        // salts are random
+        assert_ne!(argon2id_hash_password(b"hello", &params).unwrap(), phc);
+    }
+
+    #[test]
+    fn phc_invalid() {
+        for phc in [
+            "",
+            "$argon2i$v=19$m=256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$m=256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=16$m=256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$t=2,m=256,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=0256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=+256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=256,t=0,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=256,t=2,p=1,k=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ=$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHR$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4$",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNh$nf65EOgLrQMR/uIPnA4rEsF5h7TKyQwu9U1bMCHGi/4",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ$nf65EO",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ$nf65E",
+        ] {
+            assert_eq!(
+                argon2id_verify_password(b"password", phc),
+                Err(Error::OutOfRange),
+                "{phc}"
+            );
+        }
+    }
+
+    #[test]
+    fn base64() {
+        for len in 0..8 {
+            let bytes = &b"\xff\x00\x80\x7f\xfe\x01\x55\xaa"[..len];
+            let encoded = base64_encode(bytes);
+            assert_eq!(encoded.len(), (len * 4).div_ceil(3));
+            assert_eq!(base64_decode(&encoded).unwrap(), bytes);
+        }
+        assert_eq!(base64_encode(b"somesalt"), "c29tZXNhbHQ");
+        assert_eq!(base64_decode("c29tZXNhbHQ!"), None);
+    }
+
+    #[test]
+    fn invalid_params() {
+        assert_eq!(Argon2Params::new(32, 0, 1), Err(Error::OutOfRange));
+        assert_eq!(Argon2Params::new(32, 1, 0), Err(Error::OutOfRange));
+        assert_eq!(Argon2Params::new(31, 1, 4), Err(Error::OutOfRange));
+        assert!(Argon2Params::new(32, 1, 4).is_ok());
+        assert_eq!(
+            Argon2Params::new(u32::MAX, 1, 1 << 24),
+            Err(Error::OutOfRange)
+        );
+        assert!(Argon2Params::new(u32::MAX, 1, (1 << 24) - 1).is_ok());
+
+        let params = Argon2Params::new(32, 1, 1).unwrap();
+        assert_eq!(
+            argon2id(b"password", b"salt", &params, &mut [0u8; 32]),
+            Err(Error::WrongLength)
+        );
+        assert_eq!(
+            argon2id(b"password", b"saltsalt", &params, &mut [0u8; 3]),
+            Err(Error::WrongLength)
+        );
+    }
+
+    #[test]
+    fn memory_required() {
+        assert_eq!(
+            Argon2Params::new(19 * 1024, 2, 1)
+                .unwrap()
+                .memory_required(),
+            19 * 1024 * 1024
+        );
+        // rounded down to a multiple of 4 * p blocks
+        assert_eq!(
+            Argon2Params::new(100, 1, 3).unwrap().memory_required(),
+            96 * 1024
+        );
+    }
+
+    fn check(m: u32, t: u32, p: u32, password: &[u8], salt: &[u8], expected: &str) {
+        let mut out = [0u8; 32];
+        let params = Argon2Params::new(m, t, p).unwrap();
+        argon2id(password, salt, &params, &mut out).unwrap();
+        assert_eq!(out.to_vec(), hex::decode(expected).unwrap());
+    }
+}
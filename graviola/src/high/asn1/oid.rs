@@ -28,6 +28,12 @@ asn1_oid! {
     }
 }
 
+asn1_oid! {
+    id_sha1 OBJECT IDENTIFIER ::= {
+        iso(1) identified_organization(3) oiw(14) secsig(3) algorithms(2) 26
+    }
+}
+
 asn1_oid! {
     id_sha256 OBJECT IDENTIFIER ::= {
         joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
@@ -49,6 +55,55 @@ asn1_oid! {
     }
 }
 
+asn1_oid! {
+    id_sha224 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 4
+    }
+}
+
+asn1_oid! {
+    id_sha512_224 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 5
+    }
+}
+
+asn1_oid! {
+    id_sha512_256 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 6
+    }
+}
+
+asn1_oid! {
+    id_sha3_224 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 7
+    }
+}
+
+asn1_oid! {
+    id_sha3_256 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 8
+    }
+}
+
+asn1_oid! {
+    id_sha3_384 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 9
+    }
+}
+
+asn1_oid! {
+    id_sha3_512 OBJECT IDENTIFIER ::= {
+        joint_iso_itu_t(2) country(16) us(840) organization(1) gov(101)
+        csor(3) nistalgorithm(4) hashalgs(2) 10
+    }
+}
+
 asn1_oid! {
     id_ed25519 OBJECT IDENTIFIER ::= { 1 3 101 112 }
 }
@@ -0,0 +1,234 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! Hash-based key derivation from a shared secret.
+//!
+//! These are the one-step ("Concat") KDF from
+//! [SP800-56C](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-56Cr2.pdf)
+//! section 4, and the ANSI X9.63 KDF from
+//! [SEC1](https://www.secg.org/sec1-v2.pdf) section 3.6.1.
+//!
+//! Both derive key material from a shared secret `Z` (such as the
+//! output of an ECDH key agreement) and some public context,
+//! by hashing them along with a 32-bit big-endian counter.  They differ
+//! only in where that counter goes.
+
+use super::hash::{Hash, HashContext, HashOutput};
+use super::hmac::Hmac;
+use crate::Error;
+use crate::low::zeroise;
+
+/// Derive key material from shared secret `z` and `fixed_info`, writing it to `out`.
+///
+/// This is the SP800-56C one-step KDF, using hash function `H` as
+/// the auxiliary function.  Each block of output is:
+///
+/// `K(i) = H([i]_32 || z || fixed_info)`
+///
+/// This is the KDF used by JWE's `ECDH-ES` (see RFC7518 section 4.6.2).
+///
+/// `Error::OutOfRange` is returned if `out` is longer than this KDF allows.
+///
+/// ```
+/// use graviola::hashing::Sha256;
+/// use graviola::kdf::concat_kdf;
+///
+/// let shared_secret = [0x42; 32];
+/// let mut key = [0u8; 16];
+/// concat_kdf::<Sha256>(&shared_secret, b"fixed info", &mut key).unwrap();
+/// ```
+pub fn concat_kdf<H: Hash>(z: &[u8], fixed_info: &[u8], out: &mut [u8]) -> Result<(), Error> {
+    derive(H::zeroed_output().as_ref().len(), out, |counter| {
+        let mut ctx = H::new();
+        ctx.update(&counter);
+        ctx.update(z);
+        ctx.update(fixed_info);
+        ctx.finish()
+    })
+}
+
+/// Derive key material from shared secret `z` and `fixed_info`, writing it to `out`.
+///
+/// This is the SP800-56C one-step KDF, using HMAC with hash
+/// function `H`, keyed with `salt`, as the auxiliary function.  Each
+/// block of output is:
+///
+/// `K(i) = HMAC(salt, [i]_32 || z || fixed_info)`
+///
+/// SP800-56C says that, absent a salt from the protocol, a string
+/// of zero bytes is used.  An empty `salt` is equivalent to that.
+///
+/// `Error::OutOfRange` is returned if `out` is longer than this KDF allows.
+pub fn concat_kdf_hmac<H: Hash>(
+    salt: &[u8],
+    z: &[u8],
+    fixed_info: &[u8],
+    out: &mut [u8],
+) -> Result<(), Error> {
+    let prf = Hmac::<H>::new(salt);
+    derive(H::zeroed_output().as_ref().len(), out, |counter| {
+        let mut ctx = prf.clone();
+        ctx.update(counter);
+        ctx.update(z);
+        ctx.update(fixed_info);
+        ctx.finish()
+    })
+}
+
+/// Derive key material from shared secret `z` and `shared_info`, writing it to `out`.
+///
+/// This is the ANSI X9.63 KDF, using hash function `H`.  Each block of
+/// output is:
+///
+/// `K(i) = H(z || [i]_32 || shared_info)`
+///
+/// This is the KDF used by ECIES in SEC1.
+///
+/// `Error::OutOfRange` is returned if `out` is longer than this KDF allows.
+///
+/// ```
+/// use graviola::hashing::Sha256;
+/// use graviola::kdf::x963_kdf;
+/// use graviola::key_agreement::p256::{PrivateKey, PublicKey};
+///
+/// let ours = PrivateKey::new_random().unwrap();
+/// let theirs = PrivateKey::new_random().unwrap();
+/// let their_public =
+///     PublicKey::from_x962_uncompressed(&theirs.public_key_uncompressed()).unwrap();
+///
+/// let shared_secret = ours.diffie_hellman(&their_public).unwrap();
+/// let mut key = [0u8; 16];
+/// x963_kdf::<Sha256>(&shared_secret.0, b"shared info", &mut key).unwrap();
+/// ```
+pub fn x963_kdf<H: Hash>(z: &[u8], shared_info: &[u8], out: &mut [u8]) -> Result<(), Error> {
+    derive(H::zeroed_output().as_ref().len(), out, |counter| {
+        let mut ctx = H::new();
+        ctx.update(z);
+        ctx.update(&counter);
+        ctx.update(shared_info);
+        ctx.finish()
+    })
+}
+
+/// Fills `out` with `h_len`-byte blocks from `block()`, which is
+/// given the big-endian counter for each block.
+fn derive(
+    h_len: usize,
+    out: &mut [u8],
+    block: impl Fn([u8; 4]) -> HashOutput,
+) -> Result<(), Error> {
+    // the counter starts at 1, and may not wrap
+    if out.len().div_ceil(h_len) > u32::MAX as usize {
+        return Err(Error::OutOfRange);
+    }
+
+    for (i, chunk) in out.chunks_mut(h_len).enumerate() {
+        let mut k = block((i as u32 + 1).to_be_bytes());
+        chunk.copy_from_slice(&k.as_ref()[..chunk.len()]);
+        zeroise(k.as_mut());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::high::hash::{Sha256, Sha384, Sha512};
+
+    #[test]
+    fn rfc7518_vector() {
+        // RFC7518 appendix C
+        let z = [
+            158, 86, 217, 29, 129, 113, 53, 211, 114, 131, 66, 131, 191, 132, 38, 156, 251, 49,
+            110, 163, 218, 128, 106, 72, 246, 218, 167, 121, 140, 254, 144, 196,
+        ];
+        let other_info =
+            b"\x00\x00\x00\x07A128GCM\x00\x00\x00\x05Alice\x00\x00\x00\x03Bob\x00\x00\x00\x80";
+        let mut out = [0u8; 16];
+        concat_kdf::<Sha256>(&z, other_info, &mut out).unwrap();
+        assert_eq!(
+            out,
+            [
+                86, 170, 141, 234, 248, 35, 109, 32, 92, 34, 40, 205, 113, 167, 16, 26
+            ]
+        );
+    }
+
+    #[test]
+    fn concat_kdf_vectors() {
+        // from python cryptography's ConcatKDFHash
+        let mut out = [0u8; 80];
+        concat_kdf::<Sha384>(b"shared secret", b"fixed info", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x1f\x51\x94\xf7\x73\x08\x4b\xdd\x67\x25\xd3\x75\xbe\x15\x87\x5b\
+               \xef\xa3\x57\xe9\x47\x76\xe9\xd8\x26\x55\xe1\x77\x8c\xb6\xfc\xb2\
+               \x5c\x21\x44\x3a\x49\x2e\x78\xeb\xbd\xbf\x39\xf3\xa2\x0d\xc5\x7b\
+               \x96\xf0\x1d\x28\x44\x23\x85\x0d\xa5\xb7\x31\x21\xe8\xc5\xc6\x71\
+               \x3f\x5f\x67\x48\x94\x27\xea\x96\x2a\xf3\xe0\xb3\x0a\xee\xcf\xd8"
+        );
+    }
+
+    #[test]
+    fn concat_kdf_hmac_vectors() {
+        // from python cryptography's ConcatKDFHMAC
+        let mut out = [0u8; 100];
+        concat_kdf_hmac::<Sha256>(b"salt", b"shared secret", b"fixed info", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x84\x78\x0f\xc8\x14\x5e\x14\xc5\x25\xf6\xe0\x40\xdb\x7f\xf8\xc0\
+               \xc8\x0a\xc7\x1b\x9b\x73\x95\x7b\xfa\x54\xb1\x6e\xa3\x7a\xc0\x2f\
+               \x1c\xb6\x59\x0d\x5c\x76\x1c\x12\x19\x19\x44\xd0\x13\xd2\xe3\x35\
+               \xd1\xeb\x1d\x8f\xa7\x80\x27\xdd\x3b\x69\x1d\x35\xc7\xfd\x58\x82\
+               \xcf\x24\x3b\xee\x4e\xfc\x20\x4f\x5c\x04\x51\x72\xd9\x7c\x7e\xe8\
+               \xf9\xd2\x2f\x94\x5f\x5d\xc2\x12\xd2\x0f\x23\x9a\x79\xab\x61\x9f\
+               \x85\xbf\x1b\x06"
+        );
+
+        let mut out = [0u8; 32];
+        concat_kdf_hmac::<Sha512>(b"", b"shared secret", b"", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\xa1\x5e\x78\x3c\x35\x1a\xad\x7f\xee\x93\x4a\x38\x2b\x4f\x71\x18\
+               \xc1\x97\x23\x6a\xdc\xda\x54\x60\xd8\x29\x45\x64\x89\x0f\xcb\x7a"
+        );
+
+        // empty salt is equivalent to a block of zeroes
+        let mut zero_salt = [0u8; 32];
+        concat_kdf_hmac::<Sha512>(&[0u8; 128], b"shared secret", b"", &mut zero_salt).unwrap();
+        assert_eq!(out, zero_salt);
+    }
+
+    #[test]
+    fn x963_vectors() {
+        // CAVP ansx963_2001.rsp, SHA-256, COUNT = 0
+        let mut out = [0u8; 16];
+        x963_kdf::<Sha256>(
+            b"\x96\xc0\x56\x19\xd5\x6c\x32\x8a\xb9\x5f\xe8\x4b\x18\x26\x4b\x08\
+              \x72\x5b\x85\xe3\x3f\xd3\x4f\x08",
+            b"",
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            *b"\x44\x30\x24\xc3\xda\xe6\x6b\x95\xe6\xf5\x67\x06\x01\x55\x8f\x71"
+        );
+
+        // from python cryptography's X963KDF
+        let mut out = [0u8; 100];
+        x963_kdf::<Sha512>(b"shared secret", b"shared info", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x88\xaa\x4a\xcb\x53\xf8\xb2\xab\xdd\x37\x1c\x6a\xb6\x83\x2e\xfd\
+               \x03\x32\xf3\x90\x56\xba\x22\x19\xa0\x95\x33\x1f\x08\x3d\x2f\x62\
+               \x58\x5f\x21\x5c\x28\x81\x8c\xd7\x9f\xca\x46\x96\xd1\x54\xba\xc7\
+               \x3b\x32\x74\x1c\xb8\x31\x3a\x08\xc4\x8e\xd4\x61\xa5\xba\xa1\x22\
+               \xb6\xf9\x8f\xfc\xab\xdd\x0a\x91\x87\x36\x95\x42\x9e\xf1\x8c\xa0\
+               \x43\x95\x8a\x3b\xd7\x40\xaf\x27\x9a\x74\x20\xba\x85\x14\xa6\xad\
+               \xd8\x4f\x5f\x50"
+        );
+    }
+}
@@ -164,6 +164,9 @@ impl<C: Curve> SigningKey<C> {
     /// `signature` is the output buffer; `Error::WrongLength` is returned
     /// if it is not long enough.  The used prefix of this buffer is returned
     /// on success.
+    ///
+    /// `Error::WeakHash` is returned if `H` may not be used for signing
+    /// (see [`Hash::ALLOWED_FOR_SIGNING`]).
     pub fn sign<'a, H: Hash>(
         &self,
         message: &[&[u8]],
@@ -225,6 +228,10 @@ impl<C: Curve> SigningKey<C> {
         random: &[u8],
         signature: &'a mut [u8],
     ) -> Result<&'a [u8], Error> {
+        if !H::ALLOWED_FOR_SIGNING {
+            return Err(Error::WeakHash);
+        }
+
         let output = signature
             .get_mut(..C::Scalar::LEN_BYTES * 2)
             .ok_or(Error::WrongLength)?;
@@ -447,6 +454,9 @@ mod tests {
         let mut buffer = [0u8; 256];
         let message = [&b"hello"[..], &b"world"[..]];
 
+        let signature = sk.sign::<hash::Sha224>(&message, &mut buffer).unwrap();
+        vk.verify::<hash::Sha224>(&message, signature).unwrap();
+
         let signature = sk.sign::<hash::Sha256>(&message, &mut buffer).unwrap();
         vk.verify::<hash::Sha256>(&message, signature).unwrap();
 
@@ -456,6 +466,18 @@ mod tests {
         let signature = sk.sign::<hash::Sha512>(&message, &mut buffer).unwrap();
         vk.verify::<hash::Sha512>(&message, signature).unwrap();
 
+        let signature = sk.sign::<hash::Sha512_256>(&message, &mut buffer).unwrap();
+        vk.verify::<hash::Sha512_256>(&message, signature).unwrap();
+
+        let signature = sk.sign::<hash::Sha3_256>(&message, &mut buffer).unwrap();
+        vk.verify::<hash::Sha3_256>(&message, signature).unwrap();
+
+        let signature = sk.sign::<hash::Sha3_384>(&message, &mut buffer).unwrap();
+        vk.verify::<hash::Sha3_384>(&message, signature).unwrap();
+
+        let signature = sk.sign::<hash::Sha3_512>(&message, &mut buffer).unwrap();
+        vk.verify::<hash::Sha3_512>(&message, signature).unwrap();
+
         if std::env::var_os("SLOW_TESTS").is_some() {
             // check for invalid asn1 with p=1/256
             for _ in 0..1024 {
@@ -487,6 +509,82 @@ mod tests {
         assert_eq!(pkcs8_der, encoded);
     }
 
+    #[test]
+    fn verifies_sha3_signatures() {
+        // from python cryptography, with private key 0x1234567890abcdef
+        let vk = VerifyingKey::<curve::P256>::from_x962_uncompressed(
+            b"\x04\x9f\xad\x84\xae\xae\x08\xbb\xef\x7f\x01\x00\x14\xd8\x2c\xef\x6a\x09\xde\x2b\x0c\xf8\x71\xb5\xce\x0c\x4f\x1d\x13\xa5\x9a\x59\
+              \x34\x07\xcb\x45\x76\x9f\x10\x70\xe2\xc2\x47\x0f\xe5\xb1\xbf\xe6\x31\x33\xc0\xb0\xcd\xc6\x4e\xa4\xbf\x37\x91\xa8\xec\x2a\x07\xfd\x4f",
+        )
+        .unwrap();
+        vk.verify_asn1::<hash::Sha3_256>(
+            &[b"hello world"],
+            b"\x30\x46\x02\x21\x00\x9c\xe4\x5a\x64\x27\x20\xb6\xa7\xec\xe7\x40\xdf\x45\x5c\x6c\xfd\x69\xf9\x2b\x40\xc6\x69\x27\x09\x74\x44\x69\xc5\
+              \x90\xb1\x03\x6b\x02\x21\x00\xbc\x8a\x98\xa6\xb2\x9a\x9b\x22\x9e\xb5\x73\x43\x36\xea\xa7\xd9\x5f\xb3\x6a\xf9\xbc\x73\x3f\x7c\x69\xea\
+              \x11\xdc\x0b\xb2\x68\x02",
+        )
+        .unwrap();
+
+        let vk = VerifyingKey::<curve::P384>::from_x962_uncompressed(
+            b"\x04\xfb\xf9\x60\x95\x91\x5e\xb5\x87\x39\x0f\x85\x0e\xc9\x5b\x35\xf6\x18\xae\x12\x1c\x72\xa7\x08\x08\xad\x8f\x9f\x4c\x26\x1b\x60\
+              \x2b\x63\x54\x17\x43\xe3\x95\xf7\xc7\x78\xe5\x2b\xb2\x8c\x18\xdc\x83\x01\x6a\x6f\xf3\xf7\x2c\x93\x68\x32\x6a\xd5\x0a\x3b\xd1\x48\xa5\
+              \x3c\xd2\x21\x3b\xb4\x80\xe4\x73\x0a\xd0\x19\xdb\x87\xae\x10\x09\x95\xec\x65\xdf\x01\x1e\x19\x81\x1a\x15\xdc\x9d\xdf\xc9\xab\x3c",
+        )
+        .unwrap();
+        vk.verify_asn1::<hash::Sha3_384>(
+            &[b"hello world"],
+            b"\x30\x65\x02\x31\x00\xf5\x94\xa4\xd7\x56\xd1\xe5\x5b\x54\x01\xfe\xc2\x0f\x16\x98\xfb\x69\x10\xf7\xda\xb6\x4f\xfb\x68\x61\x17\x87\xa9\
+              \xdb\x49\xfc\x32\xd0\xf0\xad\xd6\x58\xeb\xe2\xa6\x27\x64\xbb\xde\x3b\xb8\x93\x01\x02\x30\x11\x17\xf4\x4a\x51\x9c\xf5\x74\x00\xe5\x5d\
+              \x96\x93\x6a\x74\x08\xd1\xa0\x02\x36\xb0\x3f\x0d\x97\x7c\x40\x0b\xbd\xeb\x37\x44\xe5\xf0\xea\x03\x8d\xce\x5d\x47\x80\x83\xce\x76\xe6\
+              \xb5\xf9\x79\xae",
+        )
+        .unwrap();
+
+        // but not with a different hash
+        assert_eq!(
+            vk.verify_asn1::<hash::Sha384>(
+                &[b"hello world"],
+                b"\x30\x65\x02\x31\x00\xf5\x94\xa4\xd7\x56\xd1\xe5\x5b\x54\x01\xfe\xc2\x0f\x16\x98\xfb\x69\x10\xf7\xda\xb6\x4f\xfb\x68\x61\x17\x87\xa9\
+                  \xdb\x49\xfc\x32\xd0\xf0\xad\xd6\x58\xeb\xe2\xa6\x27\x64\xbb\xde\x3b\xb8\x93\x01\x02\x30\x11\x17\xf4\x4a\x51\x9c\xf5\x74\x00\xe5\x5d\
+                  \x96\x93\x6a\x74\x08\xd1\xa0\x02\x36\xb0\x3f\x0d\x97\x7c\x40\x0b\xbd\xeb\x37\x44\xe5\xf0\xea\x03\x8d\xce\x5d\x47\x80\x83\xce\x76\xe6\
+                  \xb5\xf9\x79\xae",
+            ),
+            Err(Error::BadSignature)
+        );
+    }
+
+    #[cfg(feature = "legacy-sha1")]
+    #[test]
+    fn sha1_verify_only() {
+        let sk =
+            SigningKey::<curve::P256>::from_pkcs8_der(include_bytes!("ecdsa/secp256r1.pkcs8.der"))
+                .unwrap();
+        let mut public_key_buf = [0u8; 128];
+        let vk = VerifyingKey::<curve::P256>::from_x962_uncompressed(
+            sk.private_key
+                .public_key_encode_uncompressed(&mut public_key_buf)
+                .unwrap(),
+        )
+        .unwrap();
+
+        // signature from openssl
+        let signature = b"\x30\x46\x02\x21\x00\xd8\xb7\x95\x12\x3c\xfa\x59\x6c\xdc\xeb\x2e\x37\x5d\xd5\xfc\xf8\x1c\xb0\x34\x82\x0e\x03\xf7\x81\xa7\x5f\
+                          \x64\x47\xf6\x7a\x41\x26\x02\x21\x00\xf1\xc1\xa7\xe0\x86\x67\xf6\x9b\xd5\x48\x08\x17\x29\xd9\xed\xf9\x19\x05\xbd\xd3\x94\xc2\
+                          \xf0\x2c\x2a\x06\x3c\xa9\xc3\xe9\x55\x1c";
+        vk.verify_asn1::<hash::Sha1>(&[b"hello world"], signature)
+            .unwrap();
+        assert_eq!(
+            vk.verify_asn1::<hash::Sha1>(&[b"hello worle"], signature),
+            Err(Error::BadSignature)
+        );
+
+        let mut signature = [0u8; 64];
+        assert_eq!(
+            sk.sign::<hash::Sha1>(&[b"hello world"], &mut signature),
+            Err(Error::WeakHash)
+        );
+    }
+
     #[test]
     fn rejects_invalid_asn1_sigs() {
         let private_key =
@@ -557,6 +655,20 @@ mod tests {
         };
         let mut signature = [0u8; 64];
 
+        k.rfc6979_sign_with_random::<hash::Sha224>(&[b"sample"], &[], &mut signature)
+            .unwrap();
+        assert_eq!(
+            signature,
+            [
+                0x53, 0xb2, 0xff, 0xf5, 0xd1, 0x75, 0x2b, 0x2c, 0x68, 0x9d, 0xf2, 0x57, 0xc0, 0x4c,
+                0x40, 0xa5, 0x87, 0xfa, 0xba, 0xbb, 0x3f, 0x6f, 0xc2, 0x70, 0x2f, 0x13, 0x43, 0xaf,
+                0x7c, 0xa9, 0xaa, 0x3f, 0xb9, 0xaf, 0xb6, 0x4f, 0xdc, 0x03, 0xdc, 0x1a, 0x13, 0x1c,
+                0x7d, 0x23, 0x86, 0xd1, 0x1e, 0x34, 0x9f, 0x07, 0x0a, 0xa4, 0x32, 0xa4, 0xac, 0xc9,
+                0x18, 0xbe, 0xa9, 0x88, 0xbf, 0x75, 0xc7, 0x4c,
+            ]
+        );
+        v.verify::<hash::Sha224>(&[b"sample"], &signature).unwrap();
+
         k.rfc6979_sign_with_random::<hash::Sha256>(&[b"sample"], &[], &mut signature)
             .unwrap();
         assert_eq!(
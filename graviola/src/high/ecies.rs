@@ -0,0 +1,602 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! Elliptic curve integrated encryption scheme (ECIES).
+//!
+//! This follows [SEC1](https://www.secg.org/sec1-v2.pdf) section 5.1,
+//! using an AEAD in place of the separate symmetric cipher and MAC.
+//!
+//! To encrypt a message for a recipient's public key:
+//!
+//! 1. An ephemeral key pair is generated, and its public key encoded as `enc`.
+//!    For P-256 and P-384 this is the X9.62 uncompressed encoding; for X25519
+//!    it is the 32-byte RFC7748 encoding.
+//! 2. Diffie-Hellman between the ephemeral private key and the recipient's
+//!    public key gives a shared secret `Z`.
+//! 3. The ANSI X9.63 KDF is applied to `Z`, with `enc` as the `SharedInfo`,
+//!    to produce an AEAD key followed by its nonce.  The nonce is a 16-byte
+//!    IV for AES-GCM, and 12 bytes for ChaCha20-Poly1305.  The hash
+//!    function is SHA-384 for P-384, and SHA-256 otherwise.
+//! 4. The message is encrypted using the AEAD with the caller's
+//!    associated data.
+//!
+//! The wire format is `enc || ciphertext || tag`.
+//!
+//! With P-256 and AES-128-GCM, or P-384 and AES-256-GCM, and empty
+//! associated data, this is the "variable IV" X9.63 ECIES used by Apple's
+//! `SecKeyCreateEncryptedData` (`eciesEncryptionStandardVariableIVX963SHA256AESGCM`
+//! and `eciesEncryptionStandardVariableIVX963SHA384AESGCM`).  There is no
+//! external standard for the X25519 or ChaCha20-Poly1305 combinations:
+//! those are specific to this crate.
+
+use super::concat_kdf::x963_kdf;
+use super::hash::{Hash, Sha256, Sha384};
+use crate::Error;
+use crate::low::{Entry, zeroise};
+use crate::mid::aes_gcm::AesGcm;
+use crate::mid::chacha20poly1305::ChaCha20Poly1305;
+use crate::mid::rng::{RandomSource, SystemRandom};
+use crate::mid::{p256, p384, x25519};
+
+/// A key agreement group that can be used for ECIES.
+pub trait Group: private::Sealed {
+    /// The recipient's public key type.
+    type PublicKey;
+
+    /// The recipient's private key type.
+    type PrivateKey;
+
+    /// The hash function used with the X9.63 KDF.
+    type Hash: Hash;
+
+    /// Length of the encoded ephemeral public key, which
+    /// prefixes every ciphertext.
+    const ENCAPSULATED_LEN: usize;
+
+    /// Generate an ephemeral key pair using `rng`, and do the
+    /// Diffie-Hellman operation with `recipient`.
+    ///
+    /// The ephemeral public key is written to `enc`, which must be
+    /// `ENCAPSULATED_LEN` bytes.  The X9.63 KDF output is written to `keys`.
+    fn encapsulate(
+        recipient: &Self::PublicKey,
+        rng: &mut dyn RandomSource,
+        enc: &mut [u8],
+        keys: &mut [u8],
+    ) -> Result<(), Error>;
+
+    /// Do the Diffie-Hellman operation between `recipient` and the
+    /// encoded ephemeral public key `enc`.
+    ///
+    /// The X9.63 KDF output is written to `keys`.
+    fn decapsulate(recipient: &Self::PrivateKey, enc: &[u8], keys: &mut [u8]) -> Result<(), Error>;
+}
+
+/// ECIES using P-256, with SHA-256.
+pub struct P256;
+
+impl Group for P256 {
+    type PublicKey = p256::PublicKey;
+    type PrivateKey = p256::StaticPrivateKey;
+    type Hash = Sha256;
+    const ENCAPSULATED_LEN: usize = p256::PublicKey::BYTES;
+
+    fn encapsulate(
+        recipient: &Self::PublicKey,
+        rng: &mut dyn RandomSource,
+        enc: &mut [u8],
+        keys: &mut [u8],
+    ) -> Result<(), Error> {
+        let ephemeral = p256::PrivateKey::generate(rng)?;
+        enc.copy_from_slice(&ephemeral.public_key_uncompressed());
+        let z = ephemeral.diffie_hellman(recipient)?;
+        x963_kdf::<Self::Hash>(&z.0, enc, keys)
+    }
+
+    fn decapsulate(recipient: &Self::PrivateKey, enc: &[u8], keys: &mut [u8]) -> Result<(), Error> {
+        let ephemeral = p256::PublicKey::from_x962_uncompressed(enc)?;
+        let z = recipient.diffie_hellman(&ephemeral)?;
+        x963_kdf::<Self::Hash>(&z.0, enc, keys)
+    }
+}
+
+/// ECIES using P-384, with SHA-384.
+pub struct P384;
+
+impl Group for P384 {
+    type PublicKey = p384::PublicKey;
+    type PrivateKey = p384::StaticPrivateKey;
+    type Hash = Sha384;
+    const ENCAPSULATED_LEN: usize = p384::PublicKey::BYTES;
+
+    fn encapsulate(
+        recipient: &Self::PublicKey,
+        rng: &mut dyn RandomSource,
+        enc: &mut [u8],
+        keys: &mut [u8],
+    ) -> Result<(), Error> {
+        let ephemeral = p384::PrivateKey::generate(rng)?;
+        enc.copy_from_slice(&ephemeral.public_key_uncompressed());
+        let z = ephemeral.diffie_hellman(recipient)?;
+        x963_kdf::<Self::Hash>(&z.0, enc, keys)
+    }
+
+    fn decapsulate(recipient: &Self::PrivateKey, enc: &[u8], keys: &mut [u8]) -> Result<(), Error> {
+        let ephemeral = p384::PublicKey::from_x962_uncompressed(enc)?;
+        let z = recipient.diffie_hellman(&ephemeral)?;
+        x963_kdf::<Self::Hash>(&z.0, enc, keys)
+    }
+}
+
+/// ECIES using X25519, with SHA-256.
+pub struct X25519;
+
+impl Group for X25519 {
+    type PublicKey = x25519::PublicKey;
+    type PrivateKey = x25519::StaticPrivateKey;
+    type Hash = Sha256;
+    const ENCAPSULATED_LEN: usize = x25519::PublicKey::BYTES;
+
+    fn encapsulate(
+        recipient: &Self::PublicKey,
+        rng: &mut dyn RandomSource,
+        enc: &mut [u8],
+        keys: &mut [u8],
+    ) -> Result<(), Error> {
+        let ephemeral = x25519::PrivateKey::generate(rng)?;
+        enc.copy_from_slice(&ephemeral.public_key().as_bytes());
+        let z = ephemeral.diffie_hellman(recipient)?;
+        x963_kdf::<Self::Hash>(&z.0, enc, keys)
+    }
+
+    fn decapsulate(recipient: &Self::PrivateKey, enc: &[u8], keys: &mut [u8]) -> Result<(), Error> {
+        let ephemeral = x25519::PublicKey::try_from_slice(enc)?;
+        let z = recipient.diffie_hellman(&ephemeral)?;
+        x963_kdf::<Self::Hash>(&z.0, enc, keys)
+    }
+}
+
+/// The AEAD used to encrypt the message.
+///
+/// This is not included in the wire format: the sender and
+/// recipient must agree on it in advance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cipher {
+    /// AES-128-GCM.
+    Aes128Gcm,
+
+    /// AES-256-GCM.
+    Aes256Gcm,
+
+    /// ChaCha20-Poly1305.
+    ChaCha20Poly1305,
+}
+
+impl Cipher {
+    fn key_len(self) -> usize {
+        match self {
+            Self::Aes128Gcm => 16,
+            Self::Aes256Gcm | Self::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    fn nonce_len(self) -> usize {
+        match self {
+            Self::Aes128Gcm | Self::Aes256Gcm => 16,
+            Self::ChaCha20Poly1305 => 12,
+        }
+    }
+
+    fn encrypt(
+        self,
+        keys: &[u8],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 16],
+    ) -> Result<(), Error> {
+        let (key, nonce) = self.split_keys(keys);
+        match self {
+            Self::Aes128Gcm | Self::Aes256Gcm => {
+                AesGcm::new(key).encrypt_with_iv(nonce, aad, cipher_inout, tag_out)
+            }
+            Self::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(key.try_into().unwrap()).encrypt(
+                    nonce.try_into().unwrap(),
+                    aad,
+                    cipher_inout,
+                    tag_out,
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn decrypt(
+        self,
+        keys: &[u8],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let (key, nonce) = self.split_keys(keys);
+        match self {
+            Self::Aes128Gcm | Self::Aes256Gcm => {
+                AesGcm::new(key).decrypt_with_iv(nonce, aad, cipher_inout, tag)
+            }
+            Self::ChaCha20Poly1305 => ChaCha20Poly1305::new(key.try_into().unwrap()).decrypt(
+                nonce.try_into().unwrap(),
+                aad,
+                cipher_inout,
+                tag,
+            ),
+        }
+    }
+
+    fn keys_len(self) -> usize {
+        self.key_len() + self.nonce_len()
+    }
+
+    fn split_keys(self, keys: &[u8]) -> (&[u8], &[u8]) {
+        keys.split_at(self.key_len())
+    }
+}
+
+/// Returns the length of the ciphertext for a `plaintext_len`-byte message.
+pub fn ciphertext_len<G: Group>(plaintext_len: usize) -> usize {
+    G::ENCAPSULATED_LEN + plaintext_len + TAG_LEN
+}
+
+/// Encrypt `plaintext` to `recipient`.
+///
+/// `aad` is the additionally-authenticated data.  It may be empty.
+///
+/// The ciphertext is written to the front of `out`, and that prefix
+/// is returned.  `out` must be at least [`ciphertext_len()`] bytes,
+/// otherwise `Error::WrongLength` is returned.
+///
+/// ```
+/// use graviola::ecies::{self, Cipher, P256};
+/// use graviola::key_agreement::p256::{PublicKey, StaticPrivateKey};
+///
+/// let recipient = StaticPrivateKey::new_random().unwrap();
+/// let public_key =
+///     PublicKey::from_x962_uncompressed(&recipient.public_key_uncompressed()).unwrap();
+///
+/// let mut ciphertext = [0u8; 128];
+/// let ciphertext = ecies::encrypt::<P256>(
+///     &public_key,
+///     Cipher::Aes128Gcm,
+///     b"",
+///     b"hello world",
+///     &mut ciphertext,
+/// )
+/// .unwrap();
+///
+/// let mut plaintext = [0u8; 128];
+/// let plaintext = ecies::decrypt::<P256>(
+///     &recipient,
+///     Cipher::Aes128Gcm,
+///     b"",
+///     ciphertext,
+///     &mut plaintext,
+/// )
+/// .unwrap();
+/// assert_eq!(plaintext, b"hello world");
+/// ```
+pub fn encrypt<'a, G: Group>(
+    recipient: &G::PublicKey,
+    cipher: Cipher,
+    aad: &[u8],
+    plaintext: &[u8],
+    out: &'a mut [u8],
+) -> Result<&'a [u8], Error> {
+    let _entry = Entry::new_secret();
+    encrypt_with_rng::<G>(recipient, cipher, aad, plaintext, out, &mut SystemRandom)
+}
+
+/// Decrypt and verify `ciphertext`, which was encrypted to `recipient`.
+///
+/// `aad` is the additionally-authenticated data, and `cipher` the
+/// AEAD; these must match what was used for encryption.
+///
+/// The plaintext is written to the front of `out`, and that prefix
+/// is returned.  `out` must be at least as long as the plaintext,
+/// otherwise `Error::WrongLength` is returned.
+///
+/// `Error::DecryptFailed` is returned if the ciphertext is
+/// invalid, truncated, or was modified.
+pub fn decrypt<'a, G: Group>(
+    recipient: &G::PrivateKey,
+    cipher: Cipher,
+    aad: &[u8],
+    ciphertext: &[u8],
+    out: &'a mut [u8],
+) -> Result<&'a [u8], Error> {
+    let _entry = Entry::new_secret();
+    let plaintext_len = ciphertext
+        .len()
+        .checked_sub(G::ENCAPSULATED_LEN + TAG_LEN)
+        .ok_or(Error::DecryptFailed)?;
+    let out = out.get_mut(..plaintext_len).ok_or(Error::WrongLength)?;
+
+    let (enc, rest) = ciphertext.split_at(G::ENCAPSULATED_LEN);
+    let (body, tag) = rest.split_at(plaintext_len);
+
+    let mut keys = [0u8; MAX_KEYS_LEN];
+    let keys = &mut keys[..cipher.keys_len()];
+    // nb. an invalid `enc` is indistinguishable from other tampering
+    G::decapsulate(recipient, enc, keys).map_err(|_| Error::DecryptFailed)?;
+
+    out.copy_from_slice(body);
+    let result = cipher.decrypt(keys, aad, out, tag);
+    zeroise(keys);
+    result.map(|()| &*out)
+}
+
+fn encrypt_with_rng<'a, G: Group>(
+    recipient: &G::PublicKey,
+    cipher: Cipher,
+    aad: &[u8],
+    plaintext: &[u8],
+    out: &'a mut [u8],
+    rng: &mut dyn RandomSource,
+) -> Result<&'a [u8], Error> {
+    let out = out
+        .get_mut(..ciphertext_len::<G>(plaintext.len()))
+        .ok_or(Error::WrongLength)?;
+    let (enc, rest) = out.split_at_mut(G::ENCAPSULATED_LEN);
+    let (body, tag) = rest.split_at_mut(plaintext.len());
+
+    let mut keys = [0u8; MAX_KEYS_LEN];
+    let keys = &mut keys[..cipher.keys_len()];
+    G::encapsulate(recipient, rng, enc, keys)?;
+
+    body.copy_from_slice(plaintext);
+    let result = cipher.encrypt(keys, aad, body, tag.try_into().unwrap());
+    zeroise(keys);
+    result.map(|()| &*out)
+}
+
+const TAG_LEN: usize = 16;
+const MAX_KEYS_LEN: usize = 32 + 16;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::P256 {}
+    impl Sealed for super::P384 {}
+    impl Sealed for super::X25519 {}
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::mid::rng::SliceRandomSource;
+
+    #[test]
+    fn p256_aes128gcm_vector() {
+        // from python cryptography's X963KDF and AESGCM, with a 16-byte IV
+        let recipient = p256::StaticPrivateKey::from_bytes(
+            b"\x00\x00\x00\x00\x00\x00\x00\x00\x12\x34\x56\x78\x90\xab\xcd\xef\
+              \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x2a",
+        )
+        .unwrap();
+        let ephemeral = b"\x00\x00\x00\x00\x00\x00\x00\x00\xfe\xdc\xba\x09\x87\x65\x43\x21\
+                          \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x07";
+        let expected = b"\x04\x0f\x3d\x0a\xef\x0c\xef\x7f\x18\x88\x8e\xb1\xab\x9e\xa5\xdd\
+                         \x9b\x4d\xe0\xdf\x99\x4b\x23\xf8\xb2\x67\xe1\x5a\x5d\x42\x0b\x22\
+                         \x77\xdd\x97\x48\x98\x88\x5a\xec\x78\xb9\x64\x25\xb2\x76\xc4\x28\
+                         \x65\xa6\x17\x44\xb7\x89\x10\xa7\x4d\x0a\x43\xb7\xf7\x55\xb3\x62\
+                         \xc3\x09\x53\x95\x1b\x92\xf4\xc2\x0e\xf3\x14\xc9\xcf\x57\x72\x17\
+                         \x92\x39\x86\x57\x6c\xe0\xee\x5d\xb8\xe0\xda\xe5";
+        let public_key =
+            p256::PublicKey::from_x962_uncompressed(&recipient.public_key_uncompressed()).unwrap();
+        check_vector::<P256>(
+            &public_key,
+            &recipient,
+            Cipher::Aes128Gcm,
+            b"",
+            ephemeral,
+            expected,
+        );
+    }
+
+    #[test]
+    fn p384_aes256gcm_vector() {
+        // from python cryptography's X963KDF and AESGCM, with a 16-byte IV
+        let recipient = p384::StaticPrivateKey::from_bytes(
+            b"\x00\x00\x00\x00\x00\x00\x00\x00\x12\x34\x56\x78\x90\xab\xcd\xef\
+              \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+              \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x2a",
+        )
+        .unwrap();
+        let ephemeral = b"\x00\x00\x00\x00\x00\x00\x00\x00\xfe\xdc\xba\x09\x87\x65\x43\x21\
+                          \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+                          \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x07";
+        let expected = b"\x04\x13\x1a\xcc\xc9\x07\xb6\x18\xb2\x37\xaa\x0b\x76\x05\x4f\xaf\
+                         \x6f\xf4\x7f\xa7\xf8\xdf\x7d\xfa\xb8\x1e\x9a\xc4\x4a\x1d\x7e\x39\
+                         \xf5\xf0\xa9\x77\xb4\x10\x37\x2f\x70\x03\xc1\x74\xe2\x05\x9c\x78\
+                         \x08\x9a\xd1\x09\x88\x2c\xbe\x23\x4a\xb8\x94\xcb\x29\x8e\x11\xce\
+                         \x75\xd8\x50\x0a\xfb\x03\x4d\xe1\xce\xaa\xda\x2c\x08\x32\x45\xc7\
+                         \x00\xc5\x08\xd5\x4d\x4e\x3f\xfc\x7f\x6b\xe5\xce\x90\x3a\x04\xcf\
+                         \x5f\xf5\x89\xd6\x31\x16\x91\xf6\x15\x9c\xad\x5f\x6c\x65\x10\x30\
+                         \x7f\x81\x62\x16\x5b\xb3\x32\xf9\x5b\xe9\xa4\xf5";
+        let public_key =
+            p384::PublicKey::from_x962_uncompressed(&recipient.public_key_uncompressed()).unwrap();
+        check_vector::<P384>(
+            &public_key,
+            &recipient,
+            Cipher::Aes256Gcm,
+            b"",
+            ephemeral,
+            expected,
+        );
+    }
+
+    #[test]
+    fn x25519_chacha20poly1305_vector() {
+        // from python cryptography's X963KDF and ChaCha20Poly1305
+        let recipient = x25519::StaticPrivateKey::from_array(&[0x11; 32]);
+        let expected = b"\x0f\xaa\x68\x4e\xd2\x88\x67\xb9\x7f\x4a\x6a\x2d\xee\x5d\xf8\xce\
+                         \x97\x4e\x76\xb7\x01\x8e\x3f\x22\xa1\xc4\xcf\x26\x78\x57\x0f\x20\
+                         \xca\xc6\x91\xbc\x56\xa7\xe8\xa8\x9f\xec\xf2\x33\x7d\xbe\x2c\x3d\
+                         \x3f\x6f\x9f\xfd\xcb\x47\xf6\x42\xe7\xb4\x3b";
+        check_vector::<X25519>(
+            &recipient.public_key(),
+            &recipient,
+            Cipher::ChaCha20Poly1305,
+            b"aad",
+            &[0x22; 32],
+            expected,
+        );
+    }
+
+    fn check_vector<G: Group>(
+        public_key: &G::PublicKey,
+        private_key: &G::PrivateKey,
+        cipher: Cipher,
+        aad: &[u8],
+        ephemeral: &[u8],
+        expected: &[u8],
+    ) {
+        let mut out = [0u8; 256];
+        let ciphertext = encrypt_with_rng::<G>(
+            public_key,
+            cipher,
+            aad,
+            b"hello world",
+            &mut out,
+            &mut SliceRandomSource(ephemeral),
+        )
+        .unwrap();
+        assert_eq!(ciphertext, expected);
+
+        let mut out = [0u8; 11];
+        let plaintext = decrypt::<G>(private_key, cipher, aad, expected, &mut out).unwrap();
+        assert_eq!(plaintext, b"hello world");
+    }
+
+    #[test]
+    fn round_trip() {
+        let recipient = x25519::StaticPrivateKey::new_random().unwrap();
+        let public_key = recipient.public_key();
+
+        for cipher in [
+            Cipher::Aes128Gcm,
+            Cipher::Aes256Gcm,
+            Cipher::ChaCha20Poly1305,
+        ] {
+            for len in [0, 1, 15, 16, 17, 100] {
+                let message = [0x5a; 100];
+                let mut ciphertext = [0u8; 148];
+                let ciphertext = encrypt::<X25519>(
+                    &public_key,
+                    cipher,
+                    b"aad",
+                    &message[..len],
+                    &mut ciphertext,
+                )
+                .unwrap();
+                assert_eq!(ciphertext.len(), ciphertext_len::<X25519>(len));
+
+                let mut plaintext = [0u8; 100];
+                let plaintext =
+                    decrypt::<X25519>(&recipient, cipher, b"aad", ciphertext, &mut plaintext)
+                        .unwrap();
+                assert_eq!(plaintext, &message[..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn tampering() {
+        let recipient = p256::StaticPrivateKey::new_random().unwrap();
+        let public_key =
+            p256::PublicKey::from_x962_uncompressed(&recipient.public_key_uncompressed()).unwrap();
+
+        let mut ciphertext = [0u8; 65 + 4 + 16];
+        encrypt::<P256>(
+            &public_key,
+            Cipher::Aes256Gcm,
+            b"aad",
+            b"test",
+            &mut ciphertext,
+        )
+        .unwrap();
+
+        let mut out = [0u8; 4];
+        assert_eq!(
+            decrypt::<P256>(&recipient, Cipher::Aes256Gcm, b"aad", &ciphertext, &mut out).unwrap(),
+            b"test"
+        );
+
+        // every byte is covered, including the ephemeral public key
+        for i in 0..ciphertext.len() {
+            let mut corrupt = ciphertext;
+            corrupt[i] ^= 0x01;
+            assert_eq!(
+                decrypt::<P256>(&recipient, Cipher::Aes256Gcm, b"aad", &corrupt, &mut out),
+                Err(Error::DecryptFailed)
+            );
+        }
+
+        // wrong aad, wrong cipher
+        assert_eq!(
+            decrypt::<P256>(&recipient, Cipher::Aes256Gcm, b"", &ciphertext, &mut out),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(
+            decrypt::<P256>(&recipient, Cipher::Aes128Gcm, b"aad", &ciphertext, &mut out),
+            Err(Error::DecryptFailed)
+        );
+
+        // truncated
+        assert_eq!(
+            decrypt::<P256>(
+                &recipient,
+                Cipher::Aes256Gcm,
+                b"aad",
+                &ciphertext[..65 + 15],
+                &mut out
+            ),
+            Err(Error::DecryptFailed)
+        );
+    }
+
+    #[test]
+    fn output_too_small() {
+        let recipient = x25519::StaticPrivateKey::new_random().unwrap();
+        let mut out = [0u8; 32 + 4 + 15];
+        assert_eq!(
+            encrypt::<X25519>(
+                &recipient.public_key(),
+                Cipher::ChaCha20Poly1305,
+                b"",
+                b"test",
+                &mut out
+            ),
+            Err(Error::WrongLength)
+        );
+
+        let mut ciphertext = [0u8; 32 + 4 + 16];
+        encrypt::<X25519>(
+            &recipient.public_key(),
+            Cipher::ChaCha20Poly1305,
+            b"",
+            b"test",
+            &mut ciphertext,
+        )
+        .unwrap();
+        assert_eq!(
+            decrypt::<X25519>(
+                &recipient,
+                Cipher::ChaCha20Poly1305,
+                b"",
+                &ciphertext,
+                &mut [0u8; 3]
+            ),
+            Err(Error::WrongLength)
+        );
+    }
+}
@@ -4,19 +4,45 @@
 use core::ops::{Deref, DerefMut};
 
 use crate::low::ct_equal;
-use crate::mid::sha2;
+#[cfg(feature = "legacy-sha1")]
+use crate::mid::sha1;
+use crate::mid::{blake2, blake3, sha2, sha3};
 
 /// Output from a hash function.
 ///
 /// This has one variant per supported hash function.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum HashOutput {
+    /// Output from SHA224
+    Sha224([u8; sha2::Sha224Context::OUTPUT_SZ]),
     /// Output from SHA256
     Sha256([u8; sha2::Sha256Context::OUTPUT_SZ]),
     /// Output from SHA384
     Sha384([u8; sha2::Sha384Context::OUTPUT_SZ]),
     /// Output from SHA512
     Sha512([u8; sha2::Sha512Context::OUTPUT_SZ]),
+    /// Output from SHA512/224
+    Sha512_224([u8; sha2::Sha512_224Context::OUTPUT_SZ]),
+    /// Output from SHA512/256
+    Sha512_256([u8; sha2::Sha512_256Context::OUTPUT_SZ]),
+    /// Output from SHA3-224
+    Sha3_224([u8; sha3::Sha3_224Context::OUTPUT_SZ]),
+    /// Output from SHA3-256
+    Sha3_256([u8; sha3::Sha3_256Context::OUTPUT_SZ]),
+    /// Output from SHA3-384
+    Sha3_384([u8; sha3::Sha3_384Context::OUTPUT_SZ]),
+    /// Output from SHA3-512
+    Sha3_512([u8; sha3::Sha3_512Context::OUTPUT_SZ]),
+    /// Output from BLAKE2b-512
+    Blake2b512([u8; blake2::Blake2bContext::MAX_OUTPUT_SZ]),
+    /// Output from BLAKE2s-256
+    Blake2s256([u8; blake2::Blake2sContext::MAX_OUTPUT_SZ]),
+    /// Output from BLAKE3
+    Blake3([u8; blake3::Blake3Context::OUTPUT_SZ]),
+    /// Output from SHA-1
+    #[cfg(feature = "legacy-sha1")]
+    Sha1([u8; sha1::Sha1Context::OUTPUT_SZ]),
 }
 
 impl HashOutput {
@@ -43,9 +69,21 @@ impl HashOutput {
 impl PartialEq for HashOutput {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
+            (Self::Sha224(s), Self::Sha224(o)) => ct_equal(s, o),
             (Self::Sha256(s), Self::Sha256(o)) => ct_equal(s, o),
             (Self::Sha384(s), Self::Sha384(o)) => ct_equal(s, o),
             (Self::Sha512(s), Self::Sha512(o)) => ct_equal(s, o),
+            (Self::Sha512_224(s), Self::Sha512_224(o)) => ct_equal(s, o),
+            (Self::Sha512_256(s), Self::Sha512_256(o)) => ct_equal(s, o),
+            (Self::Sha3_224(s), Self::Sha3_224(o)) => ct_equal(s, o),
+            (Self::Sha3_256(s), Self::Sha3_256(o)) => ct_equal(s, o),
+            (Self::Sha3_384(s), Self::Sha3_384(o)) => ct_equal(s, o),
+            (Self::Sha3_512(s), Self::Sha3_512(o)) => ct_equal(s, o),
+            (Self::Blake2b512(s), Self::Blake2b512(o)) => ct_equal(s, o),
+            (Self::Blake2s256(s), Self::Blake2s256(o)) => ct_equal(s, o),
+            (Self::Blake3(s), Self::Blake3(o)) => ct_equal(s, o),
+            #[cfg(feature = "legacy-sha1")]
+            (Self::Sha1(s), Self::Sha1(o)) => ct_equal(s, o),
             _ => false,
         }
     }
@@ -54,9 +92,21 @@ impl PartialEq for HashOutput {
 impl AsRef<[u8]> for HashOutput {
     fn as_ref(&self) -> &[u8] {
         match self {
+            Self::Sha224(v) => v,
             Self::Sha256(v) => v,
             Self::Sha384(v) => v,
             Self::Sha512(v) => v,
+            Self::Sha512_224(v) => v,
+            Self::Sha512_256(v) => v,
+            Self::Sha3_224(v) => v,
+            Self::Sha3_256(v) => v,
+            Self::Sha3_384(v) => v,
+            Self::Sha3_512(v) => v,
+            Self::Blake2b512(v) => v,
+            Self::Blake2s256(v) => v,
+            Self::Blake3(v) => v,
+            #[cfg(feature = "legacy-sha1")]
+            Self::Sha1(v) => v,
         }
     }
 }
@@ -64,9 +114,21 @@ impl AsRef<[u8]> for HashOutput {
 impl AsMut<[u8]> for HashOutput {
     fn as_mut(&mut self) -> &mut [u8] {
         match self {
+            Self::Sha224(v) => v,
             Self::Sha256(v) => v,
             Self::Sha384(v) => v,
             Self::Sha512(v) => v,
+            Self::Sha512_224(v) => v,
+            Self::Sha512_256(v) => v,
+            Self::Sha3_224(v) => v,
+            Self::Sha3_256(v) => v,
+            Self::Sha3_384(v) => v,
+            Self::Sha3_512(v) => v,
+            Self::Blake2b512(v) => v,
+            Self::Blake2s256(v) => v,
+            Self::Blake3(v) => v,
+            #[cfg(feature = "legacy-sha1")]
+            Self::Sha1(v) => v,
         }
     }
 }
@@ -74,7 +136,7 @@ impl AsMut<[u8]> for HashOutput {
 /// One block of hash function input.
 #[derive(Copy, Clone)]
 pub struct HashBlock {
-    buf: [u8; 128],
+    buf: [u8; MAX_BLOCK_SZ],
     len: usize,
 }
 
@@ -82,12 +144,15 @@ impl HashBlock {
     /// Creates a new `HashBlock`, containing `len` zeroed bytes.
     fn new(len: usize) -> Self {
         Self {
-            buf: [0u8; 128],
+            buf: [0u8; MAX_BLOCK_SZ],
             len,
         }
     }
 }
 
+// SHA3-224 has the largest block size (rate) of the supported hash functions.
+const MAX_BLOCK_SZ: usize = sha3::Sha3_224Context::BLOCK_SZ;
+
 impl Deref for HashBlock {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
@@ -119,6 +184,31 @@ pub trait Hash {
 
     /// Return a zeroed [`HashOutput`] of the correct size.
     fn zeroed_output() -> HashOutput;
+
+    /// Whether this hash function may be used to produce new signatures.
+    ///
+    /// Signing functions return `Error::WeakHash` if this is `false`.
+    const ALLOWED_FOR_SIGNING: bool = true;
+
+    /// Do PBKDF2 iterations 2 to `iterations` for several output blocks
+    /// at once.
+    ///
+    /// This is an implementation detail of [`pbkdf2()`](crate::kdf::pbkdf2).
+    /// `inner` and `outer` are the HMAC contexts after absorbing the
+    /// padded key.  On entry, each output-sized chunk of `inout` is U_1
+    /// for one output block; on exit it is T for that block.
+    ///
+    /// Returns `false`, having done nothing, if this hash function has
+    /// no multi-lane implementation.
+    #[doc(hidden)]
+    fn pbkdf2_iterate(
+        _inner: &Self::Context,
+        _outer: &Self::Context,
+        _iterations: u32,
+        _inout: &mut [u8],
+    ) -> bool {
+        false
+    }
 }
 
 /// A generic trait over supported hash function contexts.
@@ -136,6 +226,47 @@ pub trait HashContext: Clone {
     fn finish(self) -> HashOutput;
 }
 
+/// This is SHA224.
+///
+/// SHA224 is standardized in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+#[derive(Clone)]
+pub struct Sha224;
+
+impl Hash for Sha224 {
+    type Context = Sha224Context;
+
+    fn new() -> Self::Context {
+        Sha224Context(sha2::Sha224Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha2::Sha256Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha224([0u8; sha2::Sha224Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha224Context(sha2::Sha224Context);
+
+impl HashContext for Sha224Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha224(self.0.finish())
+    }
+}
+
 /// This is SHA256.
 ///
 /// SHA256 is standardized in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
@@ -162,6 +293,16 @@ impl Hash for Sha256 {
     fn zeroed_output() -> HashOutput {
         HashOutput::Sha256([0u8; sha2::Sha256Context::OUTPUT_SZ])
     }
+
+    fn pbkdf2_iterate(
+        inner: &Self::Context,
+        outer: &Self::Context,
+        iterations: u32,
+        inout: &mut [u8],
+    ) -> bool {
+        sha2::Sha256Context::pbkdf2_iterate(&inner.0, &outer.0, iterations, inout);
+        true
+    }
 }
 
 #[derive(Clone)]
@@ -244,6 +385,16 @@ impl Hash for Sha512 {
     fn zeroed_output() -> HashOutput {
         HashOutput::Sha512([0u8; sha2::Sha512Context::OUTPUT_SZ])
     }
+
+    fn pbkdf2_iterate(
+        inner: &Self::Context,
+        outer: &Self::Context,
+        iterations: u32,
+        inout: &mut [u8],
+    ) -> bool {
+        sha2::Sha512Context::pbkdf2_iterate(&inner.0, &outer.0, iterations, inout);
+        true
+    }
 }
 
 #[derive(Clone)]
@@ -259,6 +410,441 @@ impl HashContext for Sha512Context {
     }
 }
 
+/// This is SHA512/224.
+///
+/// SHA512/224 is standardized in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+#[derive(Clone)]
+pub struct Sha512_224;
+
+impl Hash for Sha512_224 {
+    type Context = Sha512_224Context;
+
+    fn new() -> Self::Context {
+        Sha512_224Context(sha2::Sha512_224Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha2::Sha512Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha512_224([0u8; sha2::Sha512_224Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha512_224Context(sha2::Sha512_224Context);
+
+impl HashContext for Sha512_224Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha512_224(self.0.finish())
+    }
+}
+
+/// This is SHA512/256.
+///
+/// SHA512/256 is standardized in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+#[derive(Clone)]
+pub struct Sha512_256;
+
+impl Hash for Sha512_256 {
+    type Context = Sha512_256Context;
+
+    fn new() -> Self::Context {
+        Sha512_256Context(sha2::Sha512_256Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha2::Sha512Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha512_256([0u8; sha2::Sha512_256Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha512_256Context(sha2::Sha512_256Context);
+
+impl HashContext for Sha512_256Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha512_256(self.0.finish())
+    }
+}
+
+/// This is SHA3-224.
+///
+/// SHA3-224 is standardized in [FIPS202](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf).
+#[derive(Clone)]
+pub struct Sha3_224;
+
+impl Hash for Sha3_224 {
+    type Context = Sha3_224Context;
+
+    fn new() -> Self::Context {
+        Sha3_224Context(sha3::Sha3_224Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha3::Sha3_224Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha3_224([0u8; sha3::Sha3_224Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha3_224Context(sha3::Sha3_224Context);
+
+impl HashContext for Sha3_224Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha3_224(self.0.finish())
+    }
+}
+
+/// This is SHA3-256.
+///
+/// SHA3-256 is standardized in [FIPS202](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf).
+#[derive(Clone)]
+pub struct Sha3_256;
+
+impl Hash for Sha3_256 {
+    type Context = Sha3_256Context;
+
+    fn new() -> Self::Context {
+        Sha3_256Context(sha3::Sha3_256Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha3::Sha3_256Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha3_256([0u8; sha3::Sha3_256Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha3_256Context(sha3::Sha3_256Context);
+
+impl HashContext for Sha3_256Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha3_256(self.0.finish())
+    }
+}
+
+/// This is SHA3-384.
+///
+/// SHA3-384 is standardized in [FIPS202](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf).
+#[derive(Clone)]
+pub struct Sha3_384;
+
+impl Hash for Sha3_384 {
+    type Context = Sha3_384Context;
+
+    fn new() -> Self::Context {
+        Sha3_384Context(sha3::Sha3_384Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha3::Sha3_384Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha3_384([0u8; sha3::Sha3_384Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha3_384Context(sha3::Sha3_384Context);
+
+impl HashContext for Sha3_384Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha3_384(self.0.finish())
+    }
+}
+
+/// This is SHA3-512.
+///
+/// SHA3-512 is standardized in [FIPS202](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf).
+#[derive(Clone)]
+pub struct Sha3_512;
+
+impl Hash for Sha3_512 {
+    type Context = Sha3_512Context;
+
+    fn new() -> Self::Context {
+        Sha3_512Context(sha3::Sha3_512Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha3::Sha3_512Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha3_512([0u8; sha3::Sha3_512Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha3_512Context(sha3::Sha3_512Context);
+
+impl HashContext for Sha3_512Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha3_512(self.0.finish())
+    }
+}
+
+/// This is BLAKE2b-512.
+///
+/// This is BLAKE2b with a 64-byte output, and no key.  BLAKE2b is
+/// described in [RFC7693](https://datatracker.ietf.org/doc/html/rfc7693).
+/// See [`blake2::Blake2bContext`] for other output lengths, and keyed hashing.
+#[derive(Clone)]
+pub struct Blake2b512;
+
+impl Hash for Blake2b512 {
+    type Context = Blake2b512Context;
+
+    fn new() -> Self::Context {
+        Blake2b512Context(
+            blake2::Blake2bContext::new(blake2::Blake2bContext::MAX_OUTPUT_SZ).unwrap(),
+        )
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(blake2::Blake2bContext::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Blake2b512([0u8; blake2::Blake2bContext::MAX_OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Blake2b512Context(blake2::Blake2bContext);
+
+impl HashContext for Blake2b512Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        let mut out = [0u8; blake2::Blake2bContext::MAX_OUTPUT_SZ];
+        self.0.finish(&mut out);
+        HashOutput::Blake2b512(out)
+    }
+}
+
+/// This is BLAKE2s-256.
+///
+/// This is BLAKE2s with a 32-byte output, and no key.  BLAKE2s is
+/// described in [RFC7693](https://datatracker.ietf.org/doc/html/rfc7693).
+/// See [`blake2::Blake2sContext`] for other output lengths, and keyed hashing.
+#[derive(Clone)]
+pub struct Blake2s256;
+
+impl Hash for Blake2s256 {
+    type Context = Blake2s256Context;
+
+    fn new() -> Self::Context {
+        Blake2s256Context(
+            blake2::Blake2sContext::new(blake2::Blake2sContext::MAX_OUTPUT_SZ).unwrap(),
+        )
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(blake2::Blake2sContext::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Blake2s256([0u8; blake2::Blake2sContext::MAX_OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Blake2s256Context(blake2::Blake2sContext);
+
+impl HashContext for Blake2s256Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        let mut out = [0u8; blake2::Blake2sContext::MAX_OUTPUT_SZ];
+        self.0.finish(&mut out);
+        HashOutput::Blake2s256(out)
+    }
+}
+
+/// This is BLAKE3.
+///
+/// This is BLAKE3 in its default hashing mode, with a 32-byte output.
+/// See [`blake3::Blake3Context`] for keyed hashing, key derivation,
+/// and other output lengths.
+#[derive(Clone)]
+pub struct Blake3;
+
+impl Hash for Blake3 {
+    type Context = Blake3Context;
+
+    fn new() -> Self::Context {
+        Blake3Context(blake3::Blake3Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(blake3::Blake3Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Blake3([0u8; blake3::Blake3Context::OUTPUT_SZ])
+    }
+}
+
+#[derive(Clone)]
+pub struct Blake3Context(blake3::Blake3Context);
+
+impl HashContext for Blake3Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Blake3(self.0.finish())
+    }
+}
+
+/// This is SHA-1, with collision detection.
+///
+/// SHA-1 is broken, and this is only for verifying legacy signatures:
+/// signing with it is refused.  See [`sha1`] for details of the collision
+/// detection.
+///
+/// SHA-1 is standardized in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+#[cfg(feature = "legacy-sha1")]
+#[derive(Clone)]
+pub struct Sha1;
+
+#[cfg(feature = "legacy-sha1")]
+impl Hash for Sha1 {
+    type Context = Sha1Context;
+
+    // SHA-1 is only for verifying legacy signatures
+    const ALLOWED_FOR_SIGNING: bool = false;
+
+    fn new() -> Self::Context {
+        Sha1Context(sha1::Sha1Context::new())
+    }
+
+    fn hash(bytes: &[u8]) -> HashOutput {
+        let mut ctx = Self::new();
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    fn zeroed_block() -> HashBlock {
+        HashBlock::new(sha1::Sha1Context::BLOCK_SZ)
+    }
+
+    fn zeroed_output() -> HashOutput {
+        HashOutput::Sha1([0u8; sha1::Sha1Context::OUTPUT_SZ])
+    }
+}
+
+#[cfg(feature = "legacy-sha1")]
+#[derive(Clone)]
+pub struct Sha1Context(sha1::Sha1Context);
+
+#[cfg(feature = "legacy-sha1")]
+impl HashContext for Sha1Context {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    fn finish(self) -> HashOutput {
+        HashOutput::Sha1(self.0.finish())
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -9,12 +9,21 @@ use super::hash::{Hash, HashContext, HashOutput};
 use crate::Error;
 
 /// An in-progress HMAC computation, using hash function `H`.
-#[derive(Clone)]
 pub struct Hmac<H: Hash> {
     inner: H::Context,
     outer: H::Context,
 }
 
+// nb. `#[derive(Clone)]` would needlessly require `H: Clone`.
+impl<H: Hash> Clone for Hmac<H> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            outer: self.outer.clone(),
+        }
+    }
+}
+
 impl<H: Hash> Hmac<H> {
     /// Create a new [`Hmac<H>`] using the given key material.
     pub fn new(key: impl AsRef<[u8]>) -> Self {
@@ -46,6 +55,11 @@ impl<H: Hash> Hmac<H> {
         Self { inner, outer }
     }
 
+    /// The inner and outer hash contexts, after absorbing the padded key.
+    pub(crate) fn keyed_contexts(&self) -> (&H::Context, &H::Context) {
+        (&self.inner, &self.outer)
+    }
+
     /// Add data to be signed.
     pub fn update(&mut self, bytes: impl AsRef<[u8]>) {
         self.inner.update(bytes.as_ref());
@@ -76,7 +90,10 @@ impl<H: Hash> Hmac<H> {
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
-    use crate::high::hash::{Sha256, Sha384, Sha512};
+    use crate::high::hash::{
+        Blake2b512, Blake2s256, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha224, Sha256, Sha384,
+        Sha512, Sha512_224, Sha512_256,
+    };
     use crate::test::*;
 
     #[test]
@@ -95,6 +112,72 @@ mod tests {
         h.update(b"world");
         assert_eq!(h.finish(),
                    HashOutput::Sha512(*b"\x66\x68\xed\x2f\x7d\x01\x6c\x5f\x12\xd7\x80\x8f\xc4\xf2\xd1\xdc\x48\x51\x62\x2d\x7f\x15\x61\x6d\xe9\x47\xa8\x23\xb3\xee\x67\xd7\x61\xb9\x53\xf0\x95\x60\xda\x30\x1f\x83\x29\x02\x02\r\xd1\xc6\x4f\x49\x6d\xf3\x7e\xb7\xac\x4f\xd2\xfe\xee\xb6\x7d\x77\xba\x9b"));
+
+        // RFC4231 test case 2
+        let mut h = Hmac::<Sha224>::new(b"Jefe");
+        h.update(b"what do ya want for nothing?");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha224(*b"\xa3\x0e\x01\x09\x8b\xc6\xdb\xbf\x45\x69\x0f\x3a\x7e\x9e\x6d\x0f\x8b\xbe\xa2\xa3\x9e\x61\x48\x00\x8f\xd0\x5e\x44"));
+
+        let mut h = Hmac::<Sha512_224>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha512_224(*b"\xd3\xd3\x15\x09\x75\xdc\x06\x1e\x3a\x43\xe5\xd4\x3c\x14\x09\x98\xa5\x78\xa3\xdf\x71\x2a\x46\x2c\x2e\x6b\x89\xc7"));
+
+        let mut h = Hmac::<Sha512_256>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha512_256(*b"\xf7\xca\x33\x12\x5e\xab\x8b\x9f\x53\x8a\x60\x79\x79\xac\xae\xac\x54\x25\xb2\x3a\x6b\xac\xb6\x64\x77\xd8\x24\x0e\xc6\x8d\x1b\x67"));
+    }
+
+    #[test]
+    fn sha3() {
+        // from python's hmac module
+        let mut h = Hmac::<Sha3_224>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha3_224(*b"\x86\x09\xf9\xec\xa5\x52\x06\xb8\x6e\x2f\x30\x84\x49\xd2\xc3\x42\xd7\x0e\xb0\x3b\x84\x16\xca\xe8\x35\xb2\xd4\xd2"));
+
+        let mut h = Hmac::<Sha3_256>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha3_256(*b"\xb3\xb3\x75\x71\x9b\x8f\x26\xb3\x72\xe1\x7f\x41\xd2\x05\xfc\xd0\x67\x1c\x67\xbf\x9a\x32\x6a\x2b\xd4\x00\x08\xd4\x8d\xab\xc0\xa2"));
+
+        let mut h = Hmac::<Sha3_384>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha3_384(*b"\xc7\xea\xfc\x06\x25\x5d\xc6\x22\x00\xdc\xc5\x07\x03\xb5\x5b\x82\x9f\x91\x4f\xa4\x5c\xa0\xd6\xfa\x7a\x40\xc6\xc0\xd8\x04\xca\x9f\x9d\xaa\xc0\x94\x96\x36\x87\x57\x6e\x38\xbd\x34\x41\xf9\x1b\x9e"));
+
+        let mut h = Hmac::<Sha3_512>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha3_512(*b"\xf9\x10\xb7\x2c\xe2\x2c\xf0\x1a\x23\x69\x0b\xae\x62\x1a\xbc\xa8\x4d\xc0\x1f\x86\x8a\xc1\x14\x74\xfd\x06\xa1\x18\xd7\xd9\x0b\x3d\x18\x3c\x20\x91\x16\xc7\xce\x33\x25\xc3\xe9\x7a\x81\x7f\xde\x9e\xbe\x1b\xf7\x35\xfe\x2f\x61\x44\xad\x16\x74\x39\xe6\xe9\xe7\x94"));
+
+        // the block size of SHA3-224 is 144 bytes: exercise keys either side of that
+        let key = (0..145).map(|i| i as u8).collect::<Vec<_>>();
+        let mut h = Hmac::<Sha3_224>::new(&key);
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha3_224(*b"\x4e\xd0\x15\xd7\x14\x4c\x08\x97\x04\x35\x18\xfb\xba\x2b\xe8\x82\x8e\x23\x61\x6d\x55\x29\xdf\x24\x68\x38\x02\x65"));
+
+        let mut h = Hmac::<Sha3_224>::new(&key[..144]);
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Sha3_224(*b"\x83\x45\x1d\x68\x1e\x60\xed\x37\x09\x1a\xf4\xd5\x6f\xaa\x44\x11\xf3\x07\x63\x05\x07\x53\xa3\xd6\x4b\xe4\x03\x7b"));
+    }
+
+    #[test]
+    fn blake2() {
+        // from python's hmac module
+        let mut h = Hmac::<Blake2b512>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Blake2b512(*b"\x0e\x1e\x53\xce\x9d\x17\x8d\x28\xde\x85\x7b\x17\x66\x9c\x17\xac\x88\x6a\x65\x7e\xde\x8e\xc5\xc0\x52\x7c\xe1\xcb\xab\xa1\xb0\x93\x36\x18\x9d\xbf\x70\x8c\x2c\x3d\x60\x19\x40\xbd\x7b\xa2\xbf\xae\x7d\x60\x2f\xde\x3a\x49\xc8\x0e\x67\x5c\xd3\xcd\x45\x82\x24\xd2"));
+
+        let mut h = Hmac::<Blake2s256>::new(b"hello");
+        h.update(b"world");
+        assert_eq!(h.finish(),
+                   HashOutput::Blake2s256(*b"\xc6\x50\x82\xb4\xd7\x8b\x3b\x54\x11\x9e\xa3\x19\xef\x2a\x21\x41\x14\x44\xb6\x1d\x32\xc2\xe9\xa5\xc5\x6f\x3f\xd0\xea\x44\xb6\xb4"));
     }
 
     #[test]
@@ -0,0 +1,203 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! Key-based key derivation functions (KBKDF).
+//!
+//! These are standardized in [SP800-108](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-108r1-upd1.pdf).
+//! This implementation only supports HMAC as the PRF.
+//!
+//! The fixed input data is formatted as SP800-108 section 4
+//! suggests: `Label || 0x00 || Context || [L]_32`, with a 32-bit
+//! big-endian counter `[i]_32` before it.  This matches (for example)
+//! OpenSSL's `KBKDF` and Windows' `BCRYPT_SP800108_CTR_HMAC_ALGORITHM`.
+
+use super::hash::Hash;
+use super::hmac::Hmac;
+use crate::Error;
+use crate::low::zeroise;
+
+/// Derive key material from `key`, `label` and `context`, writing it to `out`.
+///
+/// This is SP800-108 KBKDF in counter mode, using HMAC with hash
+/// function `H` as the PRF.  Each block of output is:
+///
+/// `K(i) = HMAC(key, [i]_32 || label || 0x00 || context || [L]_32)`
+///
+/// where `L` is the length of `out` in bits.
+///
+/// `Error::OutOfRange` is returned if `out` is longer than
+/// 2<sup>29</sup> - 1 bytes (so its length in bits does not fit in `[L]_32`).
+///
+/// ```
+/// use graviola::hashing::Sha256;
+/// use graviola::kdf::kbkdf_counter_hmac;
+///
+/// let mut key = [0u8; 32];
+/// kbkdf_counter_hmac::<Sha256>(b"secret", b"label", b"context", &mut key).unwrap();
+/// ```
+pub fn kbkdf_counter_hmac<H: Hash>(
+    key: &[u8],
+    label: &[u8],
+    context: &[u8],
+    out: &mut [u8],
+) -> Result<(), Error> {
+    let length_bits = output_bits(out)?;
+    let prf = Hmac::<H>::new(key);
+    let h_len = H::zeroed_output().as_ref().len();
+
+    for (i, chunk) in out.chunks_mut(h_len).enumerate() {
+        // cannot overflow: the number of blocks is less than `length_bits`
+        let counter = i as u32 + 1;
+
+        let mut ctx = prf.clone();
+        ctx.update(counter.to_be_bytes());
+        fixed_input(&mut ctx, label, context, length_bits);
+
+        let mut k = ctx.finish();
+        chunk.copy_from_slice(&k.as_ref()[..chunk.len()]);
+        zeroise(k.as_mut());
+    }
+
+    Ok(())
+}
+
+/// Derive key material from `key`, `iv`, `label` and `context`, writing it to `out`.
+///
+/// This is SP800-108 KBKDF in feedback mode, using HMAC with hash
+/// function `H` as the PRF.  Each block of output is:
+///
+/// `K(i) = HMAC(key, K(i-1) || [i]_32 || label || 0x00 || context || [L]_32)`
+///
+/// where `K(0)` is `iv` (which may be empty), and `L` is the length of
+/// `out` in bits.
+///
+/// `Error::OutOfRange` is returned if `out` is longer than
+/// 2<sup>29</sup> - 1 bytes (so its length in bits does not fit in `[L]_32`).
+pub fn kbkdf_feedback_hmac<H: Hash>(
+    key: &[u8],
+    iv: &[u8],
+    label: &[u8],
+    context: &[u8],
+    out: &mut [u8],
+) -> Result<(), Error> {
+    let length_bits = output_bits(out)?;
+    let prf = Hmac::<H>::new(key);
+    let h_len = H::zeroed_output().as_ref().len();
+    let mut k = None;
+
+    for (i, chunk) in out.chunks_mut(h_len).enumerate() {
+        // cannot overflow: the number of blocks is less than `length_bits`
+        let counter = i as u32 + 1;
+
+        let mut ctx = prf.clone();
+        match &k {
+            None => ctx.update(iv),
+            Some(k) => ctx.update(k),
+        }
+        ctx.update(counter.to_be_bytes());
+        fixed_input(&mut ctx, label, context, length_bits);
+
+        let next = k.insert(ctx.finish());
+        chunk.copy_from_slice(&next.as_ref()[..chunk.len()]);
+    }
+
+    if let Some(mut k) = k {
+        zeroise(k.as_mut());
+    }
+    Ok(())
+}
+
+/// `Label || 0x00 || Context || [L]_32`
+fn fixed_input<H: Hash>(ctx: &mut Hmac<H>, label: &[u8], context: &[u8], length_bits: u32) {
+    ctx.update(label);
+    ctx.update([0x00]);
+    ctx.update(context);
+    ctx.update(length_bits.to_be_bytes());
+}
+
+fn output_bits(out: &[u8]) -> Result<u32, Error> {
+    out.len()
+        .checked_mul(8)
+        .and_then(|bits| u32::try_from(bits).ok())
+        .ok_or(Error::OutOfRange)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::high::hash::{Sha256, Sha384, Sha512};
+
+    #[test]
+    fn counter_mode() {
+        // from python cryptography's KBKDFHMAC (rlen=4, llen=4, CounterLocation.BeforeFixed)
+        let mut out = [0u8; 42];
+        kbkdf_counter_hmac::<Sha256>(b"secret key", b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x03\x87\x6a\x7f\xbe\x68\xaa\xe8\xd0\x7c\x36\x16\x0d\x6c\x38\xc6\
+               \x88\x87\xda\xe1\xc2\x87\xb2\x5a\x69\xaa\xcc\xc6\xe8\xa5\x12\xe1\
+               \x4c\xee\xf8\xff\x55\x3d\x1a\x61\x79\xad"
+        );
+
+        let mut out = [0u8; 100];
+        kbkdf_counter_hmac::<Sha384>(b"secret key", b"", b"", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x45\x42\x2e\x67\xa7\xbf\x73\xe0\xd6\x99\xf8\x51\x43\x38\x77\x7a\
+               \x8f\x09\x45\xd8\x23\x8b\x0f\x10\x07\x4d\xf3\xb5\x3e\x6c\xbf\x95\
+               \x3d\x79\x94\x09\x11\xb8\xfa\x10\x7f\xf9\x69\xf6\x80\xe9\x2c\x73\
+               \x8d\x20\x8f\xfa\x48\x6b\x4b\xb7\xb2\x08\x6c\x3f\xac\x61\xc0\x13\
+               \x55\xdd\x8d\xc6\xe7\xb8\xe9\xc6\x59\x44\xb2\x55\x31\xb2\x50\x77\
+               \xf2\xe4\x7f\x8c\x63\x03\xa5\xb2\x13\xb1\xf1\x01\x3e\x7a\x9c\xfe\
+               \x86\x3b\xca\x10"
+        );
+
+        let mut out = [0u8; 64];
+        kbkdf_counter_hmac::<Sha512>(&[0x0b; 100], b"label", b"", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x96\x88\x6b\xc5\xf7\xa7\x62\x0d\x2d\xa3\xab\x7e\xe1\x60\x8a\x26\
+               \x84\xa9\xa3\x0b\x7e\xb2\xdc\x17\xbb\xb3\xed\x99\x02\x85\xcd\x6a\
+               \x8e\xe7\xb3\xfe\x7a\x8a\xdb\x81\x35\x4c\xbd\x16\x80\x00\x88\xa8\
+               \x02\xe3\x60\xd3\xd6\xd2\x30\x0b\x9b\x5d\x5d\x88\x54\x59\xcc\x94"
+        );
+    }
+
+    #[test]
+    fn feedback_mode() {
+        // from a direct computation with python's hmac module
+        let mut out = [0u8; 80];
+        kbkdf_feedback_hmac::<Sha256>(b"secret key", b"iv", b"label", b"context", &mut out)
+            .unwrap();
+        assert_eq!(
+            out,
+            *b"\x06\xba\x03\x5f\xbf\x64\xf1\x7b\xfb\x74\x31\xff\x6a\x40\x39\x2d\
+               \x02\xce\xb2\x68\xa4\x0c\x79\x68\x5c\x4f\xce\xa7\xfd\x3c\x84\x9e\
+               \x3e\x5b\x62\x19\x2a\x3b\xea\xe8\x52\xb5\x3d\x75\x13\x19\xce\x1d\
+               \x99\x93\xe6\x28\x69\x39\x73\xb2\xce\x3b\x2b\xc1\x5a\x86\xba\xab\
+               \x00\xb3\x50\x1e\xe1\xa7\x99\x7e\xfd\x18\x63\x74\xf4\xa3\x0d\xf2"
+        );
+
+        let mut out = [0u8; 50];
+        kbkdf_feedback_hmac::<Sha512>(b"secret key", b"", b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x6a\x10\x4b\x33\xc1\xbc\x68\xbe\x56\x72\x9d\xad\x2e\xae\xa1\x4c\
+               \x62\xcf\x29\xdb\x4a\xd7\x82\x0b\xf0\xbc\x32\xb9\x98\x12\xe6\xef\
+               \xc2\x24\x86\x28\x86\x77\x24\xc5\xab\x92\x54\x75\x8c\xa6\x2a\x97\
+               \x2d\xe5"
+        );
+    }
+
+    #[test]
+    fn length_is_bound() {
+        // nb. `L` covers the whole output, so a shorter output is
+        // not a prefix of a longer one.
+        let mut short = [0u8; 16];
+        let mut long = [0u8; 32];
+        kbkdf_counter_hmac::<Sha256>(b"key", b"label", b"context", &mut short).unwrap();
+        kbkdf_counter_hmac::<Sha256>(b"key", b"label", b"context", &mut long).unwrap();
+        assert_ne!(short, long[..16]);
+    }
+}
@@ -3,13 +3,19 @@
 
 #![deny(unsafe_code)]
 
+pub(super) mod argon2;
 pub(super) mod asn1;
+pub(super) mod concat_kdf;
 pub(super) mod curve;
 pub(super) mod ecdsa;
+pub(super) mod ecies;
 pub(super) mod ed25519;
 pub(super) mod hash;
 pub mod hmac;
 pub(super) mod hmac_drbg;
+pub(super) mod kbkdf;
+pub(super) mod pbkdf2;
 pub(super) mod pkcs1;
 pub(super) mod pkcs8;
 pub(super) mod rsa;
+pub(super) mod scrypt;
@@ -0,0 +1,213 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! PBKDF2 (Password-Based Key Derivation Function 2).
+//!
+//! PBKDF2 is standardized in [RFC8018](https://datatracker.ietf.org/doc/html/rfc8018#section-5.2).
+//! This implementation only supports HMAC as the PRF.
+
+use super::hash::Hash;
+use super::hmac::Hmac;
+use crate::Error;
+use crate::low::zeroise;
+
+/// Derive key material from `password` and `salt`, writing it to `out`.
+///
+/// This is PBKDF2 using HMAC with hash function `H` as the PRF.
+/// `iterations` is the PBKDF2 iteration count, and must be non-zero.
+/// The length of `out` determines the amount of key material
+/// produced.
+///
+/// `Error::OutOfRange` is returned if `iterations` is zero, or `out`
+/// is longer than PBKDF2 allows.
+///
+/// ```
+/// use graviola::hashing::Sha256;
+/// use graviola::kdf::pbkdf2;
+///
+/// let mut key = [0u8; 32];
+/// pbkdf2::<Sha256>(b"password", b"salt", 100_000, &mut key).unwrap();
+/// ```
+pub fn pbkdf2<H: Hash>(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    out: &mut [u8],
+) -> Result<(), Error> {
+    if iterations == 0 {
+        return Err(Error::OutOfRange);
+    }
+
+    // The key is only processed once: `Hmac` holds the
+    // inner and outer hash states after absorbing the padded key,
+    // and cloning it is far cheaper than repeating that work.
+    let prf = Hmac::<H>::new(password);
+    let h_len = H::zeroed_output().as_ref().len();
+
+    // 1-based block indices, which may not exceed 2**32 - 1
+    if out.len().div_ceil(h_len) > u32::MAX as usize {
+        return Err(Error::OutOfRange);
+    }
+
+    // Output blocks are independent, so several are computed
+    // together where the hash function supports that.
+    let mut buf = [0u8; LANES * MAX_OUTPUT_LEN];
+
+    for (group, out) in out.chunks_mut(LANES * h_len).enumerate() {
+        let buf = &mut buf[..out.len().next_multiple_of(h_len)];
+
+        // U_1 = PRF (P, S || INT (i))
+        for (j, u) in buf.chunks_exact_mut(h_len).enumerate() {
+            let block_index = (group * LANES + j + 1) as u32;
+            let mut ctx = prf.clone();
+            ctx.update(salt);
+            ctx.update(block_index.to_be_bytes());
+            let mut u_1 = ctx.finish();
+            u.copy_from_slice(u_1.as_ref());
+            zeroise(u_1.as_mut());
+        }
+
+        let (inner, outer) = prf.keyed_contexts();
+        if !H::pbkdf2_iterate(inner, outer, iterations, buf) {
+            for t in buf.chunks_exact_mut(h_len) {
+                iterate(&prf, iterations, t);
+            }
+        }
+
+        out.copy_from_slice(&buf[..out.len()]);
+    }
+
+    zeroise(&mut buf);
+    Ok(())
+}
+
+/// On entry `t` is U_1; on exit it is T.
+fn iterate<H: Hash>(prf: &Hmac<H>, iterations: u32, t: &mut [u8]) {
+    let mut u = H::zeroed_output();
+    u.as_mut().copy_from_slice(t);
+
+    // U_c = PRF (P, U_{c-1}), T_i = U_1 \xor U_2 \xor ... \xor U_c
+    for _ in 1..iterations {
+        let mut ctx = prf.clone();
+        ctx.update(u.as_ref());
+        u = ctx.finish();
+
+        for (t, u) in t.iter_mut().zip(u.as_ref()) {
+            *t ^= *u;
+        }
+    }
+
+    zeroise(u.as_mut());
+}
+
+const LANES: usize = 8;
+const MAX_OUTPUT_LEN: usize = 64;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::high::hash::{Sha256, Sha384, Sha512};
+
+    #[test]
+    fn rfc7914_vectors() {
+        let mut out = [0u8; 64];
+        pbkdf2::<Sha256>(b"passwd", b"salt", 1, &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x55\xac\x04\x6e\x56\xe3\x08\x9f\xec\x16\x91\xc2\x25\x44\xb6\x05\
+               \xf9\x41\x85\x21\x6d\xde\x04\x65\xe6\x8b\x9d\x57\xc2\x0d\xac\xbc\
+               \x49\xca\x9c\xcc\xf1\x79\xb6\x45\x99\x16\x64\xb3\x9d\x77\xef\x31\
+               \x7c\x71\xb8\x45\xb1\xe3\x0b\xd5\x09\x11\x20\x41\xd3\xa1\x97\x83"
+        );
+
+        pbkdf2::<Sha256>(b"Password", b"NaCl", 80000, &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x4d\xdc\xd8\xf6\x0b\x98\xbe\x21\x83\x0c\xee\x5e\xf2\x27\x01\xf9\
+               \x64\x1a\x44\x18\xd0\x4c\x04\x14\xae\xff\x08\x87\x6b\x34\xab\x56\
+               \xa1\xd4\x25\xa1\x22\x58\x33\x54\x9a\xdb\x84\x1b\x51\xc9\xb3\x17\
+               \x6a\x27\x2b\xde\xbb\xa1\xd0\x78\x47\x8f\x62\xb3\x97\xf3\x3c\x8d"
+        );
+    }
+
+    #[test]
+    fn sha384_and_sha512() {
+        let mut out = [0u8; 50];
+        pbkdf2::<Sha384>(b"password", b"salt", 4096, &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x55\x97\x26\xbe\x38\xdb\x12\x5b\xc8\x5e\xd7\x89\x5f\x6e\x3c\xf5\
+               \x74\xc7\xa0\x1c\x08\x0c\x34\x47\xdb\x1e\x8a\x76\x76\x4d\xeb\x3c\
+               \x30\x7b\x94\x85\x3f\xbe\x42\x4f\x64\x88\xc5\xf4\xf1\x28\x96\x26\
+               \x1d\x1e"
+        );
+
+        let mut out = [0u8; 64];
+        pbkdf2::<Sha512>(
+            b"passwordPASSWORDpassword",
+            b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+            4096,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            *b"\x8c\x05\x11\xf4\xc6\xe5\x97\xc6\xac\x63\x15\xd8\xf0\x36\x2e\x22\
+               \x5f\x3c\x50\x14\x95\xba\x23\xb8\x68\xc0\x05\x17\x4d\xc4\xee\x71\
+               \x11\x5b\x59\xf9\xe6\x0c\xd9\x53\x2f\xa3\x3e\x0f\x75\xae\xfe\x30\
+               \x22\x5c\x58\x3a\x18\x6c\xd8\x2b\xd4\xda\xea\x97\x24\xa3\xd3\xb8"
+        );
+    }
+
+    #[test]
+    fn output_is_prefix_consistent() {
+        let mut long = [0u8; 100];
+        pbkdf2::<Sha256>(b"p", b"s", 3, &mut long).unwrap();
+
+        for len in 0..long.len() {
+            let mut short = vec![0u8; len];
+            pbkdf2::<Sha256>(b"p", b"s", 3, &mut short).unwrap();
+            assert_eq!(&long[..len], &short[..]);
+        }
+    }
+
+    #[test]
+    fn lanes_match_definition() {
+        fn check<H: Hash>() {
+            let h_len = H::zeroed_output().as_ref().len();
+            // more than one group of lanes, and a partial block
+            let mut out = vec![0u8; h_len * 17 + 5];
+            pbkdf2::<H>(b"password", b"salt", 5, &mut out).unwrap();
+
+            for (i, chunk) in out.chunks(h_len).enumerate() {
+                let mut ctx = Hmac::<H>::new(b"password");
+                ctx.update(b"salt");
+                ctx.update((i as u32 + 1).to_be_bytes());
+                let mut u = ctx.finish();
+                let mut t = u.clone();
+                for _ in 1..5 {
+                    let mut ctx = Hmac::<H>::new(b"password");
+                    ctx.update(u.as_ref());
+                    u = ctx.finish();
+                    for (t, u) in t.as_mut().iter_mut().zip(u.as_ref()) {
+                        *t ^= *u;
+                    }
+                }
+                assert_eq!(chunk, &t.as_ref()[..chunk.len()]);
+            }
+        }
+
+        check::<Sha256>();
+        check::<Sha384>();
+        check::<Sha512>();
+    }
+
+    #[test]
+    fn zero_iterations() {
+        assert_eq!(
+            pbkdf2::<Sha256>(b"p", b"s", 0, &mut [0u8; 32]),
+            Err(Error::OutOfRange)
+        );
+    }
+}
@@ -56,6 +56,16 @@ pub(crate) fn encode_pkcs1_sig(out: &mut [u8], digest_info: &[u8], hash: &[u8])
     hash_out.copy_from_slice(hash);
 }
 
+#[cfg(feature = "legacy-sha1")]
+pub(crate) static DIGESTINFO_SHA1: &[u8] = &[
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+
+pub(crate) static DIGESTINFO_SHA224: &[u8] = &[
+    0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04, 0x05,
+    0x00, 0x04, 0x1c,
+];
+
 pub(crate) static DIGESTINFO_SHA256: &[u8] = &[
     0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
     0x00, 0x04, 0x20,
@@ -71,6 +81,36 @@ pub(crate) static DIGESTINFO_SHA512: &[u8] = &[
     0x00, 0x04, 0x40,
 ];
 
+pub(crate) static DIGESTINFO_SHA512_224: &[u8] = &[
+    0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x05, 0x05,
+    0x00, 0x04, 0x1c,
+];
+
+pub(crate) static DIGESTINFO_SHA512_256: &[u8] = &[
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x06, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+pub(crate) static DIGESTINFO_SHA3_224: &[u8] = &[
+    0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x07, 0x05,
+    0x00, 0x04, 0x1c,
+];
+
+pub(crate) static DIGESTINFO_SHA3_256: &[u8] = &[
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x08, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+pub(crate) static DIGESTINFO_SHA3_384: &[u8] = &[
+    0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x09, 0x05,
+    0x00, 0x04, 0x30,
+];
+
+pub(crate) static DIGESTINFO_SHA3_512: &[u8] = &[
+    0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0a, 0x05,
+    0x00, 0x04, 0x40,
+];
+
 /// This is EMSA-PSS-ENCODE.
 ///
 /// `sLen` is fixed as `hLen`.
@@ -251,6 +291,59 @@ mod tests {
     use crate::high::hash;
     use crate::mid::rng::SliceRandomSource;
 
+    #[cfg(feature = "legacy-sha1")]
+    #[test]
+    fn digestinfo_sha1_is_correct() {
+        let hash = [0xaa; 20];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha1.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA1);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
+    #[test]
+    fn digestinfo_sha224_is_correct() {
+        let hash = [0xaa; 28];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha224.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA224);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
     #[test]
     fn digestinfo_sha256_is_correct() {
         let hash = [0xaa; 32];
@@ -329,6 +422,162 @@ mod tests {
         assert_eq!(actual, correct);
     }
 
+    #[test]
+    fn digestinfo_sha512_224_is_correct() {
+        let hash = [0xaa; 28];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha512_224.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA512_224);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
+    #[test]
+    fn digestinfo_sha512_256_is_correct() {
+        let hash = [0xaa; 32];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha512_256.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA512_256);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
+    #[test]
+    fn digestinfo_sha3_224_is_correct() {
+        let hash = [0xaa; 28];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha3_224.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA3_224);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
+    #[test]
+    fn digestinfo_sha3_256_is_correct() {
+        let hash = [0xaa; 32];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha3_256.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA3_256);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
+    #[test]
+    fn digestinfo_sha3_384_is_correct() {
+        let hash = [0xaa; 48];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha3_384.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA3_384);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
+    #[test]
+    fn digestinfo_sha3_512_is_correct() {
+        let hash = [0xaa; 64];
+
+        let di = pkix::DigestInfo {
+            digestAlgorithm: pkix::AlgorithmIdentifier {
+                algorithm: oid::id_sha3_512.clone(),
+                parameters: Some(asn1::Any::Null(asn1::Null)),
+            },
+            digest: asn1::OctetString::new(&hash),
+        };
+
+        let mut correct = vec![0; di.encoded_len()];
+        di.encode(&mut asn1::Encoder::new(&mut correct)).unwrap();
+
+        let actual = {
+            let mut v = Vec::new();
+            v.extend_from_slice(DIGESTINFO_SHA3_512);
+            v.extend_from_slice(&hash);
+            v
+        };
+
+        println!("correct: {correct:#04x?}");
+        assert_eq!(actual, correct);
+    }
+
     #[test]
     fn pss_encode_test() {
         // from first wycheproof test in rsa_pss_2048_sha256_mgf1_32_test.json
@@ -97,6 +97,18 @@ impl VerifyingKey {
         Ok(&output[..used])
     }
 
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA-224.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-224 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha224(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha224::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA224, hash.as_ref())
+    }
+
     /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA-256.
     ///
     /// `message` is the (unhashed) signed message.  It is hashed
@@ -133,6 +145,95 @@ impl VerifyingKey {
         self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA512, hash.as_ref())
     }
 
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA-512/224.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-512/224 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha512_224(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha512_224::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA512_224, hash.as_ref())
+    }
+
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA-512/256.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-512/256 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha512_256(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha512_256::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA512_256, hash.as_ref())
+    }
+
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA3-224.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-224 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha3_224(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha3_224::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_224, hash.as_ref())
+    }
+
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA3-256.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-256 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha3_256(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha3_256::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_256, hash.as_ref())
+    }
+
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA3-384.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-384 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha3_384(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha3_384::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_384, hash.as_ref())
+    }
+
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA3-512.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-512 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    pub fn verify_pkcs1_sha3_512(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha3_512::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_512, hash.as_ref())
+    }
+
+    /// Verifies `signature`, using RSASSA-PKCS1-v1_5 with SHA-1.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-1 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid,
+    /// or if `message` contains a SHA-1 collision attack.  See
+    /// [`crate::hashing::sha1`].
+    ///
+    /// SHA-1 is broken; this is only for verifying legacy signatures.
+    #[cfg(feature = "legacy-sha1")]
+    pub fn verify_pkcs1_sha1(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        let hash = hash::Sha1::hash(message);
+        self._verify_pkcs1(signature, pkcs1::DIGESTINFO_SHA1, hash.as_ref())
+    }
+
     fn _verify_pkcs1(
         &self,
         signature: &[u8],
@@ -155,6 +256,24 @@ impl VerifyingKey {
         }
     }
 
+    /// Verifies `signature`, using RSASSA-PSS with SHA-224.
+    ///
+    /// `saltLength` is fixed as 28 bytes; this is the most common
+    /// option when used with SHA-224.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-224 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha224(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha224>(signature, message)
+    }
+
     /// Verifies `signature`, using RSASSA-PSS with SHA-256.
     ///
     /// `saltLength` is fixed as 32 bytes; this is the most common
@@ -209,6 +328,114 @@ impl VerifyingKey {
         self._verify_pss::<hash::Sha512>(signature, message)
     }
 
+    /// Verifies `signature`, using RSASSA-PSS with SHA-512/224.
+    ///
+    /// `saltLength` is fixed as 28 bytes; this is the most common
+    /// option when used with SHA-512/224.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-512/224 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha512_224(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha512_224>(signature, message)
+    }
+
+    /// Verifies `signature`, using RSASSA-PSS with SHA-512/256.
+    ///
+    /// `saltLength` is fixed as 32 bytes; this is the most common
+    /// option when used with SHA-512/256.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA-512/256 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha512_256(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha512_256>(signature, message)
+    }
+
+    /// Verifies `signature`, using RSASSA-PSS with SHA3-224.
+    ///
+    /// `saltLength` is fixed as 28 bytes; this is the most common
+    /// option when used with SHA3-224.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-224 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha3_224(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha3_224>(signature, message)
+    }
+
+    /// Verifies `signature`, using RSASSA-PSS with SHA3-256.
+    ///
+    /// `saltLength` is fixed as 32 bytes; this is the most common
+    /// option when used with SHA3-256.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-256 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha3_256(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha3_256>(signature, message)
+    }
+
+    /// Verifies `signature`, using RSASSA-PSS with SHA3-384.
+    ///
+    /// `saltLength` is fixed as 48 bytes; this is the most common
+    /// option when used with SHA3-384.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-384 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha3_384(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha3_384>(signature, message)
+    }
+
+    /// Verifies `signature`, using RSASSA-PSS with SHA3-512.
+    ///
+    /// `saltLength` is fixed as 64 bytes; this is the most common
+    /// option when used with SHA3-512.
+    ///
+    /// `message` is the (unhashed) signed message.  It is hashed
+    /// using SHA3-512 by this function.
+    ///
+    /// [`Error::BadSignature`] is returned if the signature is invalid.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn verify_pss_sha3_512(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_public();
+        self._verify_pss::<hash::Sha3_512>(signature, message)
+    }
+
     fn _verify_pss<H: Hash>(&self, signature: &[u8], message: &[u8]) -> Result<(), Error> {
         let hash = H::hash(message);
 
@@ -386,6 +613,25 @@ impl SigningKey {
         self.0.public_key().modulus_len_bytes()
     }
 
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA-224.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha224<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha224::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA224, hash.as_ref())
+    }
+
     /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA-256.
     ///
     /// The signature is written to the front of `signature`, is
@@ -443,6 +689,141 @@ impl SigningKey {
         self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA512, hash.as_ref())
     }
 
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA-512/224.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha512_224<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha512_224::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA512_224, hash.as_ref())
+    }
+
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA-512/256.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha512_256<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha512_256::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA512_256, hash.as_ref())
+    }
+
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA3-224.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha3_224<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha3_224::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_224, hash.as_ref())
+    }
+
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA3-256.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha3_256<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha3_256::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_256, hash.as_ref())
+    }
+
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA3-384.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha3_384<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha3_384::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_384, hash.as_ref())
+    }
+
+    /// Signs `message`, using RSASSA-PKCS1-v1_5 with SHA3-512.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PKCS1-v1_5 is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.2)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pkcs1_sha3_512<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let hash = hash::Sha3_512::hash(message);
+        self._sign_pkcs1(signature, pkcs1::DIGESTINFO_SHA3_512, hash.as_ref())
+    }
+
+    /// Signs `message`, using RSASSA-PSS with SHA-224.
+    ///
+    /// `saltLength` is fixed as 28 bytes; this is the most common
+    /// option when used with SHA-224.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha224<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha224>(signature, message)
+    }
+
     /// Signs `message`, using RSASSA-PSS with SHA-256.
     ///
     /// `saltLength` is fixed as 32 bytes; this is the most common
@@ -506,6 +887,132 @@ impl SigningKey {
         self._sign_pss::<hash::Sha512>(signature, message)
     }
 
+    /// Signs `message`, using RSASSA-PSS with SHA-512/224.
+    ///
+    /// `saltLength` is fixed as 28 bytes; this is the most common
+    /// option when used with SHA-512/224.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha512_224<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha512_224>(signature, message)
+    }
+
+    /// Signs `message`, using RSASSA-PSS with SHA-512/256.
+    ///
+    /// `saltLength` is fixed as 32 bytes; this is the most common
+    /// option when used with SHA-512/256.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha512_256<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha512_256>(signature, message)
+    }
+
+    /// Signs `message`, using RSASSA-PSS with SHA3-224.
+    ///
+    /// `saltLength` is fixed as 28 bytes; this is the most common
+    /// option when used with SHA3-224.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha3_224<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha3_224>(signature, message)
+    }
+
+    /// Signs `message`, using RSASSA-PSS with SHA3-256.
+    ///
+    /// `saltLength` is fixed as 32 bytes; this is the most common
+    /// option when used with SHA3-256.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha3_256<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha3_256>(signature, message)
+    }
+
+    /// Signs `message`, using RSASSA-PSS with SHA3-384.
+    ///
+    /// `saltLength` is fixed as 48 bytes; this is the most common
+    /// option when used with SHA3-384.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha3_384<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha3_384>(signature, message)
+    }
+
+    /// Signs `message`, using RSASSA-PSS with SHA3-512.
+    ///
+    /// `saltLength` is fixed as 64 bytes; this is the most common
+    /// option when used with SHA3-512.
+    ///
+    /// The signature is written to the front of `signature`, is
+    /// precisely [`Self::modulus_len_bytes()`] in length, and
+    /// then the written-to slice is returned.
+    ///
+    /// RSASSA-PSS is described in
+    /// [RFC8017](https://datatracker.ietf.org/doc/html/rfc8017#section-8.1)
+    /// (and earlier standards, including the original PKCS#1 standard).
+    pub fn sign_pss_sha3_512<'a>(
+        &self,
+        signature: &'a mut [u8],
+        message: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        self._sign_pss::<hash::Sha3_512>(signature, message)
+    }
+
     fn _sign_pkcs1<'a>(
         &self,
         signature: &'a mut [u8],
@@ -530,6 +1037,10 @@ impl SigningKey {
         signature: &'a mut [u8],
         message: &[u8],
     ) -> Result<&'a [u8], Error> {
+        if !H::ALLOWED_FOR_SIGNING {
+            return Err(Error::WeakHash);
+        }
+
         if signature.len() < self.0.modulus_len_bytes() {
             return Err(Error::OutOfRange);
         }
@@ -552,6 +1063,9 @@ mod tests {
     use super::*;
 
     fn check_all_algs(buf: &mut [u8], private: &SigningKey, public: &VerifyingKey) {
+        let sig = private.sign_pkcs1_sha224(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha224(sig, b"hello").unwrap();
+
         let sig = private.sign_pkcs1_sha256(buf, b"hello").unwrap();
         public.verify_pkcs1_sha256(sig, b"hello").unwrap();
 
@@ -561,6 +1075,27 @@ mod tests {
         let sig = private.sign_pkcs1_sha512(buf, b"hello").unwrap();
         public.verify_pkcs1_sha512(sig, b"hello").unwrap();
 
+        let sig = private.sign_pkcs1_sha512_224(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha512_224(sig, b"hello").unwrap();
+
+        let sig = private.sign_pkcs1_sha512_256(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha512_256(sig, b"hello").unwrap();
+
+        let sig = private.sign_pkcs1_sha3_224(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha3_224(sig, b"hello").unwrap();
+
+        let sig = private.sign_pkcs1_sha3_256(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha3_256(sig, b"hello").unwrap();
+
+        let sig = private.sign_pkcs1_sha3_384(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha3_384(sig, b"hello").unwrap();
+
+        let sig = private.sign_pkcs1_sha3_512(buf, b"hello").unwrap();
+        public.verify_pkcs1_sha3_512(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha224(buf, b"hello").unwrap();
+        public.verify_pss_sha224(sig, b"hello").unwrap();
+
         let sig = private.sign_pss_sha256(buf, b"hello").unwrap();
         public.verify_pss_sha256(sig, b"hello").unwrap();
 
@@ -569,6 +1104,66 @@ mod tests {
 
         let sig = private.sign_pss_sha512(buf, b"hello").unwrap();
         public.verify_pss_sha512(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha512_224(buf, b"hello").unwrap();
+        public.verify_pss_sha512_224(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha512_256(buf, b"hello").unwrap();
+        public.verify_pss_sha512_256(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha3_224(buf, b"hello").unwrap();
+        public.verify_pss_sha3_224(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha3_256(buf, b"hello").unwrap();
+        public.verify_pss_sha3_256(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha3_384(buf, b"hello").unwrap();
+        public.verify_pss_sha3_384(sig, b"hello").unwrap();
+
+        let sig = private.sign_pss_sha3_512(buf, b"hello").unwrap();
+        public.verify_pss_sha3_512(sig, b"hello").unwrap();
+    }
+
+    #[cfg(feature = "legacy-sha1")]
+    #[test]
+    fn verify_pkcs1_sha1() {
+        let private = SigningKey::from_pkcs1_der(include_bytes!("rsa/rsa2048.der")).unwrap();
+        let public = private.public_key();
+        let mut buf = [0u8; 256];
+
+        let hash = hash::Sha1::hash(b"hello");
+        let sig = private
+            ._sign_pkcs1(&mut buf, pkcs1::DIGESTINFO_SHA1, hash.as_ref())
+            .unwrap();
+        public.verify_pkcs1_sha1(sig, b"hello").unwrap();
+        assert_eq!(
+            public.verify_pkcs1_sha1(sig, b"hellp"),
+            Err(Error::BadSignature)
+        );
+
+        // a signature over the (real) SHA-1 of one side of the
+        // SHAttered collision does not verify either side.
+        let sig = private
+            ._sign_pkcs1(
+                &mut buf,
+                pkcs1::DIGESTINFO_SHA1,
+                b"\xf9\x2d\x74\xe3\x87\x45\x87\xaa\xf4\x43\xd1\xdb\x96\x1d\x4e\x26\xdd\xe1\x3e\x9c",
+            )
+            .unwrap();
+        assert_eq!(
+            public.verify_pkcs1_sha1(
+                sig,
+                include_bytes!("../mid/testdata/shattered-1-prefix.bin")
+            ),
+            Err(Error::BadSignature)
+        );
+        assert_eq!(
+            public.verify_pkcs1_sha1(
+                sig,
+                include_bytes!("../mid/testdata/shattered-2-prefix.bin")
+            ),
+            Err(Error::BadSignature)
+        );
     }
 
     #[test]
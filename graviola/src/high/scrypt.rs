@@ -0,0 +1,371 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! The scrypt password-based key derivation function.
+//!
+//! scrypt is standardized in [RFC7914](https://datatracker.ietf.org/doc/html/rfc7914).
+
+use super::hash::Sha256;
+use super::hmac::Hmac;
+use crate::Error;
+use crate::low::{Entry, salsa20_8_block_mix, zeroise};
+
+/// Cost parameters for scrypt.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScryptParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+impl ScryptParams {
+    /// Make and validate a new set of scrypt parameters.
+    ///
+    /// `log_n` is the base-2 logarithm of the CPU/memory cost parameter `N`.
+    /// `r` is the block size parameter, and `p` is the parallelization parameter.
+    ///
+    /// This returns `Error::OutOfRange` if:
+    ///
+    /// - `log_n` is zero (`N` must be larger than 1),
+    /// - `N` is not less than 2<sup>16r</sup>,
+    /// - `r` or `p` are zero,
+    /// - `r * p` is not less than 2<sup>30</sup>, or
+    /// - the memory required cannot be addressed on this platform.
+    pub fn new(log_n: u8, r: u32, p: u32) -> Result<Self, Error> {
+        if log_n == 0
+            || r == 0
+            || p == 0
+            || u64::from(log_n) >= u64::from(r) * 16
+            || u64::from(r) * u64::from(p) >= 1 << 30
+        {
+            return Err(Error::OutOfRange);
+        }
+
+        let params = Self { log_n, r, p };
+        params.blocks().ok_or(Error::OutOfRange)?;
+        Ok(params)
+    }
+
+    /// The number of bytes of memory used by [`scrypt()`] with these parameters.
+    ///
+    /// This is dominated by `128 * r * N`.
+    pub fn memory_required(&self) -> usize {
+        // unwrap: checked in `new()`
+        self.blocks().unwrap() * BLOCK_LEN
+    }
+
+    /// Number of Salsa20 blocks needed for `V`, `X` and `Y`.
+    ///
+    /// `p` does not affect this; see [`scrypt()`] for why.
+    fn blocks(&self) -> Option<usize> {
+        let n = 1usize.checked_shl(u32::from(self.log_n))?;
+        let r = usize::try_from(self.r).ok()?;
+        n.checked_add(2)?
+            .checked_mul(r)?
+            .checked_mul(2)
+            .filter(|blocks| blocks.checked_mul(BLOCK_LEN).is_some())
+    }
+}
+
+/// Derive key material from `password` and `salt`, writing it to `out`.
+///
+/// This is scrypt with the costs given in `params`.  The length of
+/// `out` determines the amount of key material produced.
+///
+/// Memory is allocated for the duration of this call; see
+/// [`ScryptParams::memory_required()`].  That memory is proportional
+/// to `r` and `N`, but not `p`: each of the `p` lanes is mixed in turn
+/// and immediately absorbed into the final PBKDF2 step.
+///
+/// This returns `Error::OutOfRange` if `out` is longer than scrypt allows,
+/// or the required memory cannot be allocated.
+///
+/// ```
+/// use graviola::kdf::{ScryptParams, scrypt};
+///
+/// let params = ScryptParams::new(14, 8, 1).unwrap();
+/// let mut key = [0u8; 32];
+/// scrypt(b"password", b"salt", &params, &mut key).unwrap();
+/// ```
+pub fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    params: &ScryptParams,
+    out: &mut [u8],
+) -> Result<(), Error> {
+    let _entry = Entry::new_secret();
+
+    if out.len() as u64 > MAX_OUTPUT_LEN {
+        return Err(Error::OutOfRange);
+    }
+
+    let blocks = params.blocks().ok_or(Error::OutOfRange)?;
+    let mut memory = Vec::new();
+    memory
+        .try_reserve_exact(blocks)
+        .map_err(|_| Error::OutOfRange)?;
+    memory.resize(blocks, [0u32; 16]);
+
+    let r2 = params.r as usize * 2;
+    let (v, xy) = memory.split_at_mut(blocks - 2 * r2);
+    let (x, y) = xy.split_at_mut(r2);
+
+    let prf = Hmac::<Sha256>::new(password);
+
+    // B = PBKDF2-HMAC-SHA256 (P, S, 1, p * 128 * r) is never
+    // materialised in full.  Instead, the PBKDF2 blocks which form
+    // B[i] are computed just before they are needed.
+    let mut salted = prf.clone();
+    salted.update(salt);
+
+    // The final PBKDF2-HMAC-SHA256 (P, B', 1, dkLen) uses B' as the
+    // salt, so it is absorbed as B'[i] become available.
+    let mut result = prf;
+
+    let mut pbkdf2_index = 1u32;
+    let mut block_bytes = [0u8; BLOCK_LEN];
+
+    for _ in 0..params.p {
+        for block in x.iter_mut() {
+            for half in block_bytes.chunks_exact_mut(32) {
+                let mut ctx = salted.clone();
+                ctx.update(pbkdf2_index.to_be_bytes());
+                half.copy_from_slice(ctx.finish().as_ref());
+                // cannot overflow: r * p < 2**30
+                pbkdf2_index += 1;
+            }
+            to_simd_order(&block_bytes, block);
+        }
+
+        ro_mix(x, y, v);
+
+        for block in x.iter() {
+            from_simd_order(block, &mut block_bytes);
+            result.update(block_bytes);
+        }
+    }
+
+    for (i, chunk) in out.chunks_mut(32).enumerate() {
+        let mut ctx = result.clone();
+        ctx.update((i as u32 + 1).to_be_bytes());
+        chunk.copy_from_slice(&ctx.finish().as_ref()[..chunk.len()]);
+    }
+
+    zeroise(&mut block_bytes);
+    zeroise(memory.as_flattened_mut());
+    Ok(())
+}
+
+/// scryptROMix from RFC7914, on `x` in-place.
+///
+/// `y` is scratch space the same size as `x`.  `v` is scratch
+/// space for the `N` copies of `x`.
+fn ro_mix(x: &mut [[u32; 16]], y: &mut [[u32; 16]], v: &mut [[u32; 16]]) {
+    let r2 = x.len();
+    let n = v.len() / r2;
+
+    // 2. for i = 0 to N - 1 do
+    //      V[i] = X
+    //      X = scryptBlockMix (X)
+    //    end for
+    v[..r2].copy_from_slice(x);
+    for i in 1..n {
+        let (prev, next) = v.split_at_mut(i * r2);
+        salsa20_8_block_mix(&prev[(i - 1) * r2..], &mut next[..r2]);
+    }
+    salsa20_8_block_mix(&v[(n - 1) * r2..], x);
+
+    // 3. for i = 0 to N - 1 do
+    //      j = Integerify (X) mod N
+    //      T = X xor V[j]
+    //      X = scryptBlockMix (T)
+    //    end for
+    for _ in 0..n {
+        let j = integerify(x) & (n - 1);
+        for (x, v) in x.iter_mut().zip(&v[j * r2..(j + 1) * r2]) {
+            for (x, v) in x.iter_mut().zip(v) {
+                *x ^= *v;
+            }
+        }
+        salsa20_8_block_mix(x, y);
+        x.copy_from_slice(y);
+    }
+}
+
+/// Integerify(X) from RFC7914, truncated to `usize`.
+///
+/// This is the low 64 bits of the final block, interpreted
+/// as a little-endian integer.
+fn integerify(x: &[[u32; 16]]) -> usize {
+    let last = x.last().unwrap();
+    // nb. in SIMD order, word 0 is at position 0, and word 1 at position 13.
+    (u64::from(last[0]) | (u64::from(last[13]) << 32)) as usize
+}
+
+fn to_simd_order(bytes: &[u8; BLOCK_LEN], block: &mut [u32; 16]) {
+    for (i, word) in block.iter_mut().enumerate() {
+        let j = (i * 5) % 16;
+        *word = u32::from_le_bytes(bytes[j * 4..(j + 1) * 4].try_into().unwrap());
+    }
+}
+
+fn from_simd_order(block: &[u32; 16], bytes: &mut [u8; BLOCK_LEN]) {
+    for (i, word) in block.iter().enumerate() {
+        let j = (i * 5) % 16;
+        bytes[j * 4..(j + 1) * 4].copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// Size of a Salsa20 block.
+const BLOCK_LEN: usize = 64;
+
+/// dkLen is limited to (2^32 - 1) * hLen, where hLen is 32 for SHA256.
+const MAX_OUTPUT_LEN: u64 = 0xffff_ffff * 32;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc7914_vectors() {
+        let mut out = [0u8; 64];
+        scrypt(b"", b"", &ScryptParams::new(4, 1, 1).unwrap(), &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x77\xd6\x57\x62\x38\x65\x7b\x20\x3b\x19\xca\x42\xc1\x8a\x04\x97\
+               \xf1\x6b\x48\x44\xe3\x07\x4a\xe8\xdf\xdf\xfa\x3f\xed\xe2\x14\x42\
+               \xfc\xd0\x06\x9d\xed\x09\x48\xf8\x32\x6a\x75\x3a\x0f\xc8\x1f\x17\
+               \xe8\xd3\xe0\xfb\x2e\x0d\x36\x28\xcf\x35\xe2\x0c\x38\xd1\x89\x06"
+        );
+
+        scrypt(
+            b"password",
+            b"NaCl",
+            &ScryptParams::new(10, 8, 16).unwrap(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            *b"\xfd\xba\xbe\x1c\x9d\x34\x72\x00\x78\x56\xe7\x19\x0d\x01\xe9\xfe\
+               \x7c\x6a\xd7\xcb\xc8\x23\x78\x30\xe7\x73\x76\x63\x4b\x37\x31\x62\
+               \x2e\xaf\x30\xd9\x2e\x22\xa3\x88\x6f\xf1\x09\x27\x9d\x98\x30\xda\
+               \xc7\x27\xaf\xb9\x4a\x83\xee\x6d\x83\x60\xcb\xdf\xa2\xcc\x06\x40"
+        );
+
+        scrypt(
+            b"pleaseletmein",
+            b"SodiumChloride",
+            &ScryptParams::new(14, 8, 1).unwrap(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            *b"\x70\x23\xbd\xcb\x3a\xfd\x73\x48\x46\x1c\x06\xcd\x81\xfd\x38\xeb\
+               \xfd\xa8\xfb\xba\x90\x4f\x8e\x3e\xa9\xb5\x43\xf6\x54\x5d\xa1\xf2\
+               \xd5\x43\x29\x55\x61\x3f\x0f\xcf\x62\xd4\x97\x05\x24\x2a\x9a\xf9\
+               \xe6\x1e\x85\xdc\x0d\x65\x1e\x40\xdf\xcf\x01\x7b\x45\x57\x58\x87"
+        );
+    }
+
+    #[test]
+    fn rfc7914_slow_vector() {
+        if std::env::var_os("SLOW_TESTS").is_none() {
+            return;
+        }
+
+        let mut out = [0u8; 64];
+        scrypt(
+            b"pleaseletmein",
+            b"SodiumChloride",
+            &ScryptParams::new(20, 8, 1).unwrap(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            *b"\x21\x01\xcb\x9b\x6a\x51\x1a\xae\xad\xdb\xbe\x09\xcf\x70\xf8\x81\
+               \xec\x56\x8d\x57\x4a\x2f\xfd\x4d\xab\xe5\xee\x98\x20\xad\xaa\x47\
+               \x8e\x56\xfd\x8f\x4b\xa5\xd0\x9f\xfa\x1c\x6d\x92\x7c\x40\xf4\xc3\
+               \x37\x30\x40\x49\xe8\xa9\x52\xfb\xcb\xf4\x5c\x6f\xa7\x7a\x41\xa4"
+        );
+    }
+
+    #[test]
+    fn odd_parameters() {
+        // output length is not a multiple of 32, r is odd, p > 1
+        let mut out = [0u8; 100];
+        scrypt(b"p", b"s", &ScryptParams::new(1, 3, 5).unwrap(), &mut out).unwrap();
+        assert_eq!(
+            out,
+            *b"\x3a\xf9\x6b\xf5\xfd\x55\x63\xcf\x96\x25\x20\xe3\xb0\xf0\x08\x83\
+               \xe8\xd6\x0f\x7e\x46\x0e\xf3\x8c\x9c\x40\x39\x50\x03\x5e\x92\x4b\
+               \x59\x20\x66\x5a\xfe\x94\x59\x79\x8e\xbc\x5a\x75\xc5\x28\xc3\x08\
+               \x8e\xb3\xf8\xf8\xb1\xbb\x99\x8f\x1a\x15\x97\xd2\xee\xe3\x65\xd5\
+               \x71\x3a\x65\x4a\xe9\xdd\x5a\x24\xd8\x7b\xe0\x71\xca\xdc\x9a\xfa\
+               \x3b\xc2\x53\xa3\x94\x08\x9e\xf7\x15\x41\x7c\x3a\x79\x9a\x6a\x0e\
+               \x4d\x47\xfe\x5c"
+        );
+    }
+
+    #[test]
+    fn block_mix() {
+        // RFC7914 section 9
+        let input = b"\xf7\xce\x0b\x65\x3d\x2d\x72\xa4\x10\x8c\xf5\xab\xe9\x12\xff\xdd\
+                      \x77\x76\x16\xdb\xbb\x27\xa7\x0e\x82\x04\xf3\xae\x2d\x0f\x6f\xad\
+                      \x89\xf6\x8f\x48\x11\xd1\xe8\x7b\xcc\x3b\xd7\x40\x0a\x9f\xfd\x29\
+                      \x09\x4f\x01\x84\x63\x95\x74\xf3\x9a\xe5\xa1\x31\x52\x17\xbc\xd7\
+                      \x89\x49\x91\x44\x72\x13\xbb\x22\x6c\x25\xb5\x4d\xa8\x63\x70\xfb\
+                      \xcd\x98\x43\x80\x37\x46\x66\xbb\x8f\xfc\xb5\xbf\x40\xc2\x54\xb0\
+                      \x67\xd2\x7c\x51\xce\x4a\xd5\xfe\xd8\x29\xc9\x0b\x50\x5a\x57\x1b\
+                      \x7f\x4d\x1c\xad\x6a\x52\x3c\xda\x77\x0e\x67\xbc\xea\xaf\x7e\x89";
+        let expected = b"\xa4\x1f\x85\x9c\x66\x08\xcc\x99\x3b\x81\xca\xcb\x02\x0c\xef\x05\
+                         \x04\x4b\x21\x81\xa2\xfd\x33\x7d\xfd\x7b\x1c\x63\x96\x68\x2f\x29\
+                         \xb4\x39\x31\x68\xe3\xc9\xe6\xbc\xfe\x6b\xc5\xb7\xa0\x6d\x96\xba\
+                         \xe4\x24\xcc\x10\x2c\x91\x74\x5c\x24\xad\x67\x3d\xc7\x61\x8f\x81\
+                         \x20\xed\xc9\x75\x32\x38\x81\xa8\x05\x40\xf6\x4c\x16\x2d\xcd\x3c\
+                         \x21\x07\x7c\xfe\x5f\x8d\x5f\xe2\xb1\xa4\x16\x8f\x95\x36\x78\xb7\
+                         \x7d\x3b\x3d\x80\x3b\x60\xe4\xab\x92\x09\x96\xe5\x9b\x4d\x53\xb6\
+                         \x5d\x2a\x22\x58\x77\xd5\xed\xf5\x84\x2c\xb9\xf1\x4e\xef\xe4\x25";
+
+        let mut blocks = [[0u32; 16]; 2];
+        for (block, bytes) in blocks.iter_mut().zip(input.chunks_exact(BLOCK_LEN)) {
+            to_simd_order(bytes.try_into().unwrap(), block);
+        }
+
+        let mut output = [[0u32; 16]; 2];
+        salsa20_8_block_mix(&blocks, &mut output);
+
+        let mut bytes = [0u8; BLOCK_LEN * 2];
+        for (block, bytes) in output.iter().zip(bytes.chunks_exact_mut(BLOCK_LEN)) {
+            from_simd_order(block, bytes.try_into().unwrap());
+        }
+        assert_eq!(&bytes, expected);
+    }
+
+    #[test]
+    fn invalid_params() {
+        assert_eq!(ScryptParams::new(0, 8, 1), Err(Error::OutOfRange));
+        assert_eq!(ScryptParams::new(14, 0, 1), Err(Error::OutOfRange));
+        assert_eq!(ScryptParams::new(14, 8, 0), Err(Error::OutOfRange));
+        assert_eq!(ScryptParams::new(16, 1, 1), Err(Error::OutOfRange));
+        assert!(ScryptParams::new(15, 1, 1).is_ok());
+        assert_eq!(
+            ScryptParams::new(1, 1 << 15, 1 << 15),
+            Err(Error::OutOfRange)
+        );
+        assert!(ScryptParams::new(1, (1 << 15) - 1, 1 << 15).is_ok());
+        assert_eq!(ScryptParams::new(64, 8, 1), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn memory_required() {
+        let params = ScryptParams::new(14, 8, 1).unwrap();
+        assert_eq!(params.memory_required(), ((1 << 14) + 2) * 128 * 8);
+        // unaffected by `p`
+        let params = ScryptParams::new(14, 8, 16).unwrap();
+        assert_eq!(params.memory_required(), ((1 << 14) + 2) * 128 * 8);
+    }
+}
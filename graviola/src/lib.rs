@@ -166,19 +166,94 @@ pub mod signing {
 
 /// Cryptographic hash functions.
 pub mod hashing {
-    pub use super::high::hash::{Hash, HashContext, HashOutput, Sha256, Sha384, Sha512};
+    #[cfg(feature = "legacy-sha1")]
+    pub use super::high::hash::Sha1;
+    pub use super::high::hash::{
+        Blake2b512, Blake2s256, Blake3, Hash, HashContext, HashOutput, Sha3_224, Sha3_256,
+        Sha3_384, Sha3_512, Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256,
+    };
     pub use super::high::hmac;
+    pub use super::mid::ascon;
+    pub use super::mid::blake2;
+    pub use super::mid::blake3;
+    pub use super::mid::k12;
+    #[cfg(feature = "legacy-sha1")]
+    pub use super::mid::sha1;
     pub use super::mid::sha2;
     pub use super::mid::sha3;
+    pub use super::mid::sp800_185;
+}
+
+/// Key derivation functions.
+pub mod kdf {
+    pub use super::high::argon2::{
+        Argon2Params, argon2id, argon2id_hash_password, argon2id_keyed, argon2id_verify_password,
+    };
+    pub use super::high::concat_kdf::{concat_kdf, concat_kdf_hmac, x963_kdf};
+    pub use super::high::kbkdf::{kbkdf_counter_hmac, kbkdf_feedback_hmac};
+    pub use super::high::pbkdf2::pbkdf2;
+    pub use super::high::scrypt::{ScryptParams, scrypt};
 }
 
 /// Authenticated encryption.
 pub mod aead {
+    pub use super::mid::aegis::{Aegis128L, Aegis128X, Aegis128X2, Aegis128X4, Aegis256};
+    pub use super::mid::aes_ccm::AesCcm;
     pub use super::mid::aes_gcm::AesGcm;
+    pub use super::mid::aes_gcm_siv::AesGcmSiv;
+    pub use super::mid::aes_siv::AesSiv;
+    pub use super::mid::ascon_aead::AsconAead128;
     pub use super::mid::chacha20poly1305::ChaCha20Poly1305;
     pub use super::mid::xchacha20poly1305::XChaCha20Poly1305;
 }
 
+/// Unauthenticated ciphers.
+///
+/// These do not protect the integrity of messages, so are
+/// hazardous.  Prefer [`aead`].
+pub mod cipher {
+    pub use super::mid::aes;
+}
+
+/// Key wrapping.
+///
+/// This encrypts and authenticates keys (or other small, high-entropy
+/// values) under a key-encryption key, without needing a nonce.
+pub mod key_wrap {
+    pub use super::mid::aes_kw::AesKw;
+}
+
+/// Message authentication codes.
+///
+/// See also [`hashing::hmac`].
+pub mod mac {
+    pub use super::mid::aes_cmac::AesCmac;
+    pub use super::mid::aes_gcm::Gmac;
+}
+
+/// Elliptic curve integrated encryption scheme (ECIES).
+///
+/// This encrypts a message to a recipient's P-256, P-384 or X25519
+/// public key, using an ephemeral key pair, the ANSI X9.63 KDF, and
+/// an AEAD.  See [SEC1](https://www.secg.org/sec1-v2.pdf) section 5.1.
+///
+/// The wire format is `enc || ciphertext || tag`, where `enc` is the
+/// encoded ephemeral public key.  The X9.63 KDF's `SharedInfo` is `enc`,
+/// and its output is the AEAD key followed by its nonce: a 16-byte IV for
+/// AES-GCM, or a 12-byte nonce for ChaCha20-Poly1305.
+///
+/// With P-256 and AES-128-GCM, or P-384 and AES-256-GCM, and empty
+/// associated data, this is the "variable IV" X9.63 ECIES used by Apple's
+/// `SecKeyCreateEncryptedData` (`eciesEncryptionStandardVariableIVX963SHA256AESGCM`
+/// and `eciesEncryptionStandardVariableIVX963SHA384AESGCM`).  There is no
+/// external standard for the X25519 or ChaCha20-Poly1305 combinations:
+/// those are specific to this crate.
+pub mod ecies {
+    pub use super::high::ecies::{
+        Cipher, Group, P256, P384, X25519, ciphertext_len, decrypt, encrypt,
+    };
+}
+
 /// Cryptographic-quality random source
 pub mod random {
     /// Fills the entirety of `out` with cryptographic-quality random bytes.
@@ -0,0 +1,164 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::aarch64::*;
+
+use crate::low::generic::aegis::{self as generic, AesBlocks};
+
+/// Encrypts `cipher_inout` with AEGIS-128L (`D` = 1) or AEGIS-128X<i>D</i>.
+pub(crate) fn aegis128x_encrypt<const D: usize>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _aegis128x::<D, true>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+/// Decrypts `cipher_inout` with AEGIS-128L (`D` = 1) or AEGIS-128X<i>D</i>,
+/// writing the expected tag to `tag_out`.
+pub(crate) fn aegis128x_decrypt<const D: usize>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _aegis128x::<D, false>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+/// Encrypts `cipher_inout` with AEGIS-256.
+pub(crate) fn aegis256_encrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _aegis256::<true>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+/// Decrypts `cipher_inout` with AEGIS-256, writing the expected tag
+/// to `tag_out`.
+pub(crate) fn aegis256_decrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _aegis256::<false>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+#[target_feature(enable = "aes,neon")]
+fn _aegis128x<const D: usize, const ENC: bool>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    if ENC {
+        generic::aegis128x_encrypt::<Lanes<D>>(key, nonce, aad, cipher_inout, tag_out)
+    } else {
+        generic::aegis128x_decrypt::<Lanes<D>>(key, nonce, aad, cipher_inout, tag_out)
+    }
+}
+
+#[target_feature(enable = "aes,neon")]
+fn _aegis256<const ENC: bool>(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    if ENC {
+        generic::aegis256_encrypt::<Lanes<1>>(key, nonce, aad, cipher_inout, tag_out)
+    } else {
+        generic::aegis256_decrypt::<Lanes<1>>(key, nonce, aad, cipher_inout, tag_out)
+    }
+}
+
+/// `D` lanes, each in a separate 128-bit register.
+#[derive(Clone, Copy)]
+struct Lanes<const D: usize>([uint8x16_t; D]);
+
+impl<const D: usize> AesBlocks for Lanes<D> {
+    const LANES: usize = D;
+
+    #[inline(always)]
+    fn load(bytes: &[u8]) -> Self {
+        let bytes = &bytes[..16 * D];
+        // SAFETY: `bytes` is `16 * D` bytes and readable
+        Self(core::array::from_fn(|i| unsafe {
+            vld1q_u8(bytes.as_ptr().add(16 * i))
+        }))
+    }
+
+    #[inline(always)]
+    fn store(self, bytes: &mut [u8]) {
+        let bytes = &mut bytes[..16 * D];
+        for (i, v) in self.0.into_iter().enumerate() {
+            // SAFETY: `bytes` is `16 * D` bytes and writable
+            unsafe { vst1q_u8(bytes.as_mut_ptr().add(16 * i), v) };
+        }
+    }
+
+    #[inline(always)]
+    fn splat(block: &[u8; 16]) -> Self {
+        // SAFETY: `block` is 16 bytes and readable
+        Self([unsafe { vld1q_u8(block.as_ptr()) }; D])
+    }
+
+    #[inline(always)]
+    fn aes_round(self, round_key: Self) -> Self {
+        // SAFETY: this crate requires the `aes` & `neon` cpu features
+        Self(core::array::from_fn(|i| unsafe {
+            aes_round(self.0[i], round_key.0[i])
+        }))
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        // SAFETY: this crate requires the `neon` cpu feature
+        Self(core::array::from_fn(|i| unsafe {
+            veorq_u8(self.0[i], other.0[i])
+        }))
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        // SAFETY: this crate requires the `neon` cpu feature
+        Self(core::array::from_fn(|i| unsafe {
+            vandq_u8(self.0[i], other.0[i])
+        }))
+    }
+
+    #[inline(always)]
+    fn fold(self) -> [u8; 16] {
+        // SAFETY: this crate requires the `neon` cpu feature
+        let v = self
+            .0
+            .into_iter()
+            .reduce(|a, b| unsafe { veorq_u8(a, b) })
+            .unwrap();
+        let mut out = [0u8; 16];
+        // SAFETY: `out` is 16 bytes and writable
+        unsafe { vst1q_u8(out.as_mut_ptr(), v) };
+        out
+    }
+}
+
+#[target_feature(enable = "aes,neon")]
+#[inline]
+fn aes_round(block: uint8x16_t, round_key: uint8x16_t) -> uint8x16_t {
+    // `vaeseq_u8` does AddRoundKey first, so use a zero key there
+    // and add the real one after MixColumns.
+    veorq_u8(vaesmcq_u8(vaeseq_u8(block, vdupq_n_u8(0))), round_key)
+}
@@ -14,25 +14,36 @@ use crate::low;
 
 pub(crate) enum AesKey {
     Aes128(AesKey128),
+    Aes192(AesKey192),
     Aes256(AesKey256),
 }
 
 impl AesKey {
     /// Creates an AesKey.
     ///
-    /// `key` must be 16 or 32 bytes in length (AES-192 not supported).
+    /// `key` must be 16, 24 or 32 bytes in length.
     pub(crate) fn new(key: &[u8]) -> Self {
         match key.len() {
             16 => Self::Aes128(AesKey128::new(key.try_into().unwrap())),
+            24 => Self::Aes192(AesKey192::new(key.try_into().unwrap())),
             32 => Self::Aes256(AesKey256::new(key.try_into().unwrap())),
-            24 => panic!("aes-192 not supported"),
             _ => panic!("invalid aes key size"),
         }
     }
 
+    /// The length of the key, in bytes.
+    pub(crate) fn key_len(&self) -> usize {
+        match self {
+            Self::Aes128(_) => 16,
+            Self::Aes192(_) => 24,
+            Self::Aes256(_) => 32,
+        }
+    }
+
     pub(crate) fn encrypt_block(&self, inout: &mut [u8; 16]) {
         match self {
             Self::Aes128(a128) => a128.encrypt_block(inout),
+            Self::Aes192(a192) => a192.encrypt_block(inout),
             Self::Aes256(a256) => a256.encrypt_block(inout),
         }
     }
@@ -46,6 +57,56 @@ impl Drop for AesKey {
 
 impl low::generic::zeroise::Zeroable for AesKey {}
 
+/// Round keys for decryption.
+///
+/// These are for the "equivalent inverse cipher" of FIPS-197 section 5.3.5,
+/// which is what `aesd`/`aesimc` implement.
+pub(crate) enum AesDecryptKey {
+    Aes128([uint8x16_t; 10 + 1]),
+    Aes192([uint8x16_t; 12 + 1]),
+    Aes256([uint8x16_t; 14 + 1]),
+}
+
+impl AesDecryptKey {
+    pub(crate) fn new(key: &AesKey) -> Self {
+        // SAFETY: this crate requires the `aes` cpu feature
+        unsafe {
+            match key {
+                AesKey::Aes128(a128) => Self::Aes128(inverse_round_keys(&a128.round_keys)),
+                AesKey::Aes192(a192) => Self::Aes192(inverse_round_keys(&a192.round_keys)),
+                AesKey::Aes256(a256) => Self::Aes256(inverse_round_keys(&a256.round_keys)),
+            }
+        }
+    }
+
+    pub(crate) fn decrypt_block(&self, inout: &mut [u8; 16]) {
+        // SAFETY: this crate requires the `aes` cpu feature
+        unsafe {
+            match self {
+                Self::Aes128(rk) => decrypt_block(rk, inout),
+                Self::Aes192(rk) => decrypt_block(rk, inout),
+                Self::Aes256(rk) => decrypt_block(rk, inout),
+            }
+        }
+    }
+
+    pub(crate) fn round_keys(&self) -> &[uint8x16_t] {
+        match self {
+            Self::Aes128(rk) => rk,
+            Self::Aes192(rk) => rk,
+            Self::Aes256(rk) => rk,
+        }
+    }
+}
+
+impl Drop for AesDecryptKey {
+    fn drop(&mut self) {
+        low::zeroise_value(self);
+    }
+}
+
+impl low::generic::zeroise::Zeroable for AesDecryptKey {}
+
 pub(crate) struct AesKey128 {
     round_keys: [uint8x16_t; 10 + 1],
 }
@@ -87,6 +148,46 @@ impl AesKey128 {
     }
 }
 
+pub(crate) struct AesKey192 {
+    round_keys: [uint8x16_t; 12 + 1],
+}
+
+impl AesKey192 {
+    pub(crate) fn new(key: &[u8; 24]) -> Self {
+        let mut rk32 = [0; (12 + 1) * 4];
+        for (w, k) in rk32.iter_mut().zip(key.chunks_exact(4)) {
+            *w = u32::from_be_bytes(k.try_into().unwrap());
+        }
+
+        // the key is six words, so rounds do not align with the
+        // expansion: work word-by-word instead.
+        for i in 6..rk32.len() {
+            let mut t = rk32[i - 1];
+            if i % 6 == 0 {
+                t = sub_word(t.rotate_left(8)) ^ (RCON[i / 6 - 1] << 24);
+            }
+            rk32[i] = rk32[i - 6] ^ t;
+        }
+
+        let mut round_keys = [zero(); 12 + 1];
+        for (i, rk) in rk32.chunks(4).enumerate() {
+            // SAFETY: `rk` is 128-bit in size; `vld1q_u8` has no alignment req.
+            round_keys[i] = unsafe { vrev32q_u8(vld1q_u8(rk.as_ptr().cast())) };
+        }
+
+        Self { round_keys }
+    }
+
+    pub(crate) fn encrypt_block(&self, inout: &mut [u8; 16]) {
+        // SAFETY: this crate requires the `aes` cpu feature
+        unsafe { aes192_block(&self.round_keys, inout) }
+    }
+
+    pub(crate) fn round_keys(&self) -> &[uint8x16_t; 13] {
+        &self.round_keys
+    }
+}
+
 pub(crate) struct AesKey256 {
     round_keys: [uint8x16_t; 14 + 1],
 }
@@ -273,6 +374,98 @@ pub(crate) fn _aes128_8_blocks(
     )
 }
 
+#[target_feature(enable = "aes")]
+fn aes192_block(round_keys: &[uint8x16_t; 13], block_inout: &mut [u8; 16]) {
+    // SAFETY: `block_inout` is 16 bytes and readable
+    let block = unsafe { vld1q_u8(block_inout.as_ptr().cast()) };
+    let block = _aes192_block(round_keys, block);
+    // SAFETY: `block_inout` is 16 bytes and writable
+    unsafe { vst1q_u8(block_inout.as_mut_ptr().cast(), block) };
+}
+
+#[target_feature(enable = "aes")]
+#[inline]
+pub(crate) fn _aes192_block(round_keys: &[uint8x16_t; 13], block: uint8x16_t) -> uint8x16_t {
+    let block = vaeseq_u8(block, round_keys[0]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[1]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[2]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[3]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[4]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[5]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[6]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[7]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[8]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[9]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[10]);
+    let block = vaesmcq_u8(block);
+    let block = vaeseq_u8(block, round_keys[11]);
+    veorq_u8(block, round_keys[12])
+}
+
+#[target_feature(enable = "aes")]
+#[inline]
+pub(crate) fn _aes192_8_blocks(
+    round_keys: &[uint8x16_t; 13],
+    mut b0: uint8x16_t,
+    mut b1: uint8x16_t,
+    mut b2: uint8x16_t,
+    mut b3: uint8x16_t,
+    mut b4: uint8x16_t,
+    mut b5: uint8x16_t,
+    mut b6: uint8x16_t,
+    mut b7: uint8x16_t,
+) -> (
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+) {
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[0]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[1]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[2]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[3]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[4]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[5]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[6]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[7]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[8]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[9]);
+    round_8!(b0, b1, b2, b3, b4, b5, b6, b7, round_keys[10]);
+
+    let b0 = vaeseq_u8(b0, round_keys[11]);
+    let b1 = vaeseq_u8(b1, round_keys[11]);
+    let b2 = vaeseq_u8(b2, round_keys[11]);
+    let b3 = vaeseq_u8(b3, round_keys[11]);
+    let b4 = vaeseq_u8(b4, round_keys[11]);
+    let b5 = vaeseq_u8(b5, round_keys[11]);
+    let b6 = vaeseq_u8(b6, round_keys[11]);
+    let b7 = vaeseq_u8(b7, round_keys[11]);
+    (
+        veorq_u8(b0, round_keys[12]),
+        veorq_u8(b1, round_keys[12]),
+        veorq_u8(b2, round_keys[12]),
+        veorq_u8(b3, round_keys[12]),
+        veorq_u8(b4, round_keys[12]),
+        veorq_u8(b5, round_keys[12]),
+        veorq_u8(b6, round_keys[12]),
+        veorq_u8(b7, round_keys[12]),
+    )
+}
+
 #[target_feature(enable = "aes")]
 fn aes256_block(round_keys: &[uint8x16_t; 15], block_inout: &mut [u8; 16]) {
     // SAFETY: `block_inout` is 16 bytes and readable
@@ -371,6 +564,112 @@ pub(crate) fn _aes256_8_blocks(
     )
 }
 
+#[target_feature(enable = "aes")]
+fn inverse_round_keys<const N: usize>(round_keys: &[uint8x16_t; N]) -> [uint8x16_t; N] {
+    // reverse the order, and apply InvMixColumns to all but the
+    // first and last
+    let mut r = *round_keys;
+    r.reverse();
+    for rk in r[1..N - 1].iter_mut() {
+        *rk = vaesimcq_u8(*rk);
+    }
+    r
+}
+
+#[target_feature(enable = "aes")]
+fn decrypt_block(round_keys: &[uint8x16_t], block_inout: &mut [u8; 16]) {
+    // SAFETY: `block_inout` is 16 bytes and readable
+    let block = unsafe { vld1q_u8(block_inout.as_ptr().cast()) };
+    let block = _decrypt_block(round_keys, block);
+    // SAFETY: `block_inout` is 16 bytes and writable
+    unsafe { vst1q_u8(block_inout.as_mut_ptr().cast(), block) };
+}
+
+#[target_feature(enable = "aes")]
+#[inline]
+pub(crate) fn _decrypt_block(round_keys: &[uint8x16_t], mut block: uint8x16_t) -> uint8x16_t {
+    let (last, rest) = round_keys.split_last().unwrap();
+    let (penultimate, rest) = rest.split_last().unwrap();
+
+    for rk in rest {
+        block = vaesdq_u8(block, *rk);
+        block = vaesimcq_u8(block);
+    }
+    block = vaesdq_u8(block, *penultimate);
+    veorq_u8(block, *last)
+}
+
+macro_rules! inv_round_8 {
+    ($b0:ident, $b1:ident, $b2:ident, $b3:ident, $b4:ident, $b5:ident, $b6:ident, $b7:ident, $rk:expr) => {
+        let rk = $rk;
+        $b0 = vaesdq_u8($b0, rk);
+        $b0 = vaesimcq_u8($b0);
+        $b1 = vaesdq_u8($b1, rk);
+        $b1 = vaesimcq_u8($b1);
+        $b2 = vaesdq_u8($b2, rk);
+        $b2 = vaesimcq_u8($b2);
+        $b3 = vaesdq_u8($b3, rk);
+        $b3 = vaesimcq_u8($b3);
+        $b4 = vaesdq_u8($b4, rk);
+        $b4 = vaesimcq_u8($b4);
+        $b5 = vaesdq_u8($b5, rk);
+        $b5 = vaesimcq_u8($b5);
+        $b6 = vaesdq_u8($b6, rk);
+        $b6 = vaesimcq_u8($b6);
+        $b7 = vaesdq_u8($b7, rk);
+        $b7 = vaesimcq_u8($b7);
+    };
+}
+
+#[target_feature(enable = "aes")]
+#[inline]
+pub(crate) fn _decrypt_8_blocks(
+    round_keys: &[uint8x16_t],
+    mut b0: uint8x16_t,
+    mut b1: uint8x16_t,
+    mut b2: uint8x16_t,
+    mut b3: uint8x16_t,
+    mut b4: uint8x16_t,
+    mut b5: uint8x16_t,
+    mut b6: uint8x16_t,
+    mut b7: uint8x16_t,
+) -> (
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+) {
+    let (last, rest) = round_keys.split_last().unwrap();
+    let (penultimate, rest) = rest.split_last().unwrap();
+
+    for rk in rest {
+        inv_round_8!(b0, b1, b2, b3, b4, b5, b6, b7, *rk);
+    }
+
+    let b0 = vaesdq_u8(b0, *penultimate);
+    let b1 = vaesdq_u8(b1, *penultimate);
+    let b2 = vaesdq_u8(b2, *penultimate);
+    let b3 = vaesdq_u8(b3, *penultimate);
+    let b4 = vaesdq_u8(b4, *penultimate);
+    let b5 = vaesdq_u8(b5, *penultimate);
+    let b6 = vaesdq_u8(b6, *penultimate);
+    let b7 = vaesdq_u8(b7, *penultimate);
+    (
+        veorq_u8(b0, *last),
+        veorq_u8(b1, *last),
+        veorq_u8(b2, *last),
+        veorq_u8(b3, *last),
+        veorq_u8(b4, *last),
+        veorq_u8(b5, *last),
+        veorq_u8(b6, *last),
+        veorq_u8(b7, *last),
+    )
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -394,7 +693,7 @@ mod tests {
     #[cfg(panic = "unwind")]
     fn test_construct() {
         // The generic AesKey::new wrapper should create the proper subtype of key when called
-        // with a 128- or 256-bit value and should fail with any other argument length.
+        // with a 128-, 192- or 256-bit value and should fail with any other argument length.
         let input: [u8; 33] = [
             0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
             0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b,
@@ -405,10 +704,12 @@ mod tests {
             match panic::catch_unwind(|| AesKey::new(&input[..len])) {
                 Ok(key) => match key {
                     AesKey::Aes128(_) => assert_eq!(num_bits, 128),
+                    AesKey::Aes192(_) => assert_eq!(num_bits, 192),
                     AesKey::Aes256(_) => assert_eq!(num_bits, 256),
                 },
                 Err(_) => {
                     assert_ne!(num_bits, 128);
+                    assert_ne!(num_bits, 192);
                     assert_ne!(num_bits, 256);
                 }
             }
@@ -441,6 +742,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_key_expansion_192() {
+        let context = AesKey192::new(&[
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90,
+            0x79, 0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ]);
+
+        let expected = [
+            0x8e73b0f7_da0e6452_c810f32b_809079e5,
+            0x62f8ead2_522c6b7b_fe0c91f7_2402f5a5,
+            0xec12068e_6c827f6b_0e7a95b9_5c56fec2,
+            0x4db7b4bd_69b54118_85a74796_e92538fd,
+            0xe75fad44_bb095386_485af057_21efb14f,
+            0xa448f6d9_4d6dce24_aa326360_113b30e6,
+            0xa25e7ed5_83b1cf9a_27f93943_6a94f767,
+            0xc0a69407_d19da4e1_ec1786eb_6fa64971,
+            0x485f7032_22cb8755_e26d1352_33f0b7b3,
+            0x40beeb28_2f18a259_6747d26b_458c553e,
+            0xa7e1466c_9411f1df_821f750a_ad07d753,
+            0xca400538_8fcc5006_282d166a_bc3ce7b5,
+            0xe98ba06f_448c773c_8ecc7204_01002202,
+        ];
+
+        for (i, expect) in expected.into_iter().enumerate() {
+            assert_eq!(to_u128(context.round_keys[i]), expect);
+        }
+    }
+
     #[test]
     fn test_key_expansion_256() {
         let context = AesKey256::new(&[
@@ -492,6 +821,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_block_192() {
+        let context = AesKey192::new(&[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        ]);
+        let mut block = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        context.encrypt_block(&mut block);
+        assert_eq!(
+            block,
+            [
+                0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d,
+                0x71, 0x91
+            ]
+        );
+    }
+
     #[test]
     fn test_block_256() {
         let context = AesKey256::new(&[
@@ -512,4 +861,44 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_decrypt_block() {
+        // FIPS-197 appendix C.1 - C.3, inverted
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        for (key_len, ciphertext) in [
+            (
+                16,
+                [
+                    0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70,
+                    0xb4, 0xc5, 0x5a,
+                ],
+            ),
+            (
+                24,
+                [
+                    0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec,
+                    0x0d, 0x71, 0x91,
+                ],
+            ),
+            (
+                32,
+                [
+                    0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b,
+                    0x49, 0x60, 0x89,
+                ],
+            ),
+        ] {
+            let context = AesDecryptKey::new(&AesKey::new(&key[..key_len]));
+            let mut block = ciphertext;
+            context.decrypt_block(&mut block);
+            assert_eq!(
+                block,
+                [
+                    0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc,
+                    0xdd, 0xee, 0xff
+                ]
+            );
+        }
+    }
 }
@@ -0,0 +1,66 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::aarch64::*;
+
+use crate::low::AesKey;
+use crate::low::aarch64::aes::{_aes128_8_blocks, _aes192_8_blocks, _aes256_8_blocks};
+
+/// XORs the encryption of each of `counters` into the corresponding
+/// block of `inout`.
+///
+/// The counter blocks are formed by the caller, so this serves any CTR
+/// mode regardless of how its counter is incremented.
+pub(crate) fn apply_keystream_8(key: &AesKey, counters: &[[u8; 16]; 8], inout: &mut [u8; 128]) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _apply_keystream_8(key, counters, inout) }
+}
+
+#[target_feature(enable = "aes,neon")]
+fn _apply_keystream_8(key: &AesKey, counters: &[[u8; 16]; 8], inout: &mut [u8; 128]) {
+    // SAFETY: each of `counters` is 16 bytes and readable
+    let (b0, b1, b2, b3, b4, b5, b6, b7) = unsafe {
+        (
+            vld1q_u8(counters[0].as_ptr()),
+            vld1q_u8(counters[1].as_ptr()),
+            vld1q_u8(counters[2].as_ptr()),
+            vld1q_u8(counters[3].as_ptr()),
+            vld1q_u8(counters[4].as_ptr()),
+            vld1q_u8(counters[5].as_ptr()),
+            vld1q_u8(counters[6].as_ptr()),
+            vld1q_u8(counters[7].as_ptr()),
+        )
+    };
+
+    let (b0, b1, b2, b3, b4, b5, b6, b7) = match key {
+        AesKey::Aes128(a128) => _aes128_8_blocks(a128.round_keys(), b0, b1, b2, b3, b4, b5, b6, b7),
+        AesKey::Aes192(a192) => _aes192_8_blocks(a192.round_keys(), b0, b1, b2, b3, b4, b5, b6, b7),
+        AesKey::Aes256(a256) => _aes256_8_blocks(a256.round_keys(), b0, b1, b2, b3, b4, b5, b6, b7),
+    };
+
+    // SAFETY: `inout` is 128 bytes and readable
+    let (p0, p1, p2, p3, p4, p5, p6, p7) = unsafe {
+        (
+            vld1q_u8(inout.as_ptr().add(0)),
+            vld1q_u8(inout.as_ptr().add(16)),
+            vld1q_u8(inout.as_ptr().add(32)),
+            vld1q_u8(inout.as_ptr().add(48)),
+            vld1q_u8(inout.as_ptr().add(64)),
+            vld1q_u8(inout.as_ptr().add(80)),
+            vld1q_u8(inout.as_ptr().add(96)),
+            vld1q_u8(inout.as_ptr().add(112)),
+        )
+    };
+
+    // SAFETY: `inout` is 128 bytes and writable
+    unsafe {
+        vst1q_u8(inout.as_mut_ptr().add(0), veorq_u8(p0, b0));
+        vst1q_u8(inout.as_mut_ptr().add(16), veorq_u8(p1, b1));
+        vst1q_u8(inout.as_mut_ptr().add(32), veorq_u8(p2, b2));
+        vst1q_u8(inout.as_mut_ptr().add(48), veorq_u8(p3, b3));
+        vst1q_u8(inout.as_mut_ptr().add(64), veorq_u8(p4, b4));
+        vst1q_u8(inout.as_mut_ptr().add(80), veorq_u8(p5, b5));
+        vst1q_u8(inout.as_mut_ptr().add(96), veorq_u8(p6, b6));
+        vst1q_u8(inout.as_mut_ptr().add(112), veorq_u8(p7, b7));
+    }
+}
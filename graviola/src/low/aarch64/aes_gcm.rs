@@ -81,6 +81,17 @@ fn _cipher<const ENC: bool>(
                 b6,
                 b7,
             ),
+            AesKey::Aes192(a192) => crate::low::aarch64::aes::_aes192_8_blocks(
+                a192.round_keys(),
+                b0,
+                b1,
+                b2,
+                b3,
+                b4,
+                b5,
+                b6,
+                b7,
+            ),
             AesKey::Aes256(a256) => crate::low::aarch64::aes::_aes256_8_blocks(
                 a256.round_keys(),
                 b0,
@@ -148,6 +159,9 @@ fn _cipher<const ENC: bool>(
             AesKey::Aes128(a128) => {
                 crate::low::aarch64::aes::_aes128_block(a128.round_keys(), block)
             }
+            AesKey::Aes192(a192) => {
+                crate::low::aarch64::aes::_aes192_block(a192.round_keys(), block)
+            }
             AesKey::Aes256(a256) => {
                 crate::low::aarch64::aes::_aes256_block(a256.round_keys(), block)
             }
@@ -181,6 +195,9 @@ fn _cipher<const ENC: bool>(
                 AesKey::Aes128(a128) => {
                     crate::low::aarch64::aes::_aes128_block(a128.round_keys(), block)
                 }
+                AesKey::Aes192(a192) => {
+                    crate::low::aarch64::aes::_aes192_block(a192.round_keys(), block)
+                }
                 AesKey::Aes256(a256) => {
                     crate::low::aarch64::aes::_aes256_block(a256.round_keys(), block)
                 }
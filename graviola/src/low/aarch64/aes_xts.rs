@@ -0,0 +1,194 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::aarch64::*;
+
+use crate::low::aarch64::aes::{
+    _aes128_8_blocks, _aes128_block, _aes192_8_blocks, _aes192_block, _aes256_8_blocks,
+    _aes256_block, _decrypt_8_blocks, _decrypt_block,
+};
+use crate::low::aarch64::cpu;
+use crate::low::{AesDecryptKey, AesKey};
+
+/// Encrypts whole blocks of `inout` in XTS mode.
+///
+/// `tweak` is the encrypted tweak for the first block, and on
+/// exit is the tweak for the block after the last.
+pub(crate) fn encrypt(key: &AesKey, tweak: &mut [u8; 16], inout: &mut [u8]) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _encrypt(key, tweak, inout) }
+}
+
+/// Decrypts whole blocks of `inout` in XTS mode.
+///
+/// `tweak` is as for [`encrypt`].
+pub(crate) fn decrypt(key: &AesDecryptKey, tweak: &mut [u8; 16], inout: &mut [u8]) {
+    // SAFETY: this crate requires the `aes` & `neon` cpu features
+    unsafe { _decrypt(key.round_keys(), tweak, inout) }
+}
+
+#[target_feature(enable = "aes,neon")]
+fn _encrypt(key: &AesKey, tweak: &mut [u8; 16], inout: &mut [u8]) {
+    debug_assert!(inout.len().is_multiple_of(16));
+
+    // SAFETY: `tweak` is 16 bytes and readable
+    let mut t = unsafe { vld1q_u8(tweak.as_ptr().cast()) };
+    let mut by8 = inout.chunks_exact_mut(128);
+
+    for blocks in by8.by_ref() {
+        cpu::prefetch_rw(blocks.as_ptr());
+        let (t0, t1, t2, t3, t4, t5, t6, t7);
+        (t0, t1, t2, t3, t4, t5, t6, t7, t) = tweaks_8(t);
+
+        // SAFETY: `blocks` is 128 bytes long, via `chunks_exact_mut`
+        let (b0, b1, b2, b3, b4, b5, b6, b7) = unsafe {
+            (
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(0).cast()), t0),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(16).cast()), t1),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(32).cast()), t2),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(48).cast()), t3),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(64).cast()), t4),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(80).cast()), t5),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(96).cast()), t6),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(112).cast()), t7),
+            )
+        };
+
+        let (b0, b1, b2, b3, b4, b5, b6, b7) = match key {
+            AesKey::Aes128(a128) => {
+                _aes128_8_blocks(a128.round_keys(), b0, b1, b2, b3, b4, b5, b6, b7)
+            }
+            AesKey::Aes192(a192) => {
+                _aes192_8_blocks(a192.round_keys(), b0, b1, b2, b3, b4, b5, b6, b7)
+            }
+            AesKey::Aes256(a256) => {
+                _aes256_8_blocks(a256.round_keys(), b0, b1, b2, b3, b4, b5, b6, b7)
+            }
+        };
+
+        // SAFETY: `blocks` is 128 bytes long, via `chunks_exact_mut`
+        unsafe {
+            vst1q_u8(blocks.as_mut_ptr().add(0).cast(), veorq_u8(b0, t0));
+            vst1q_u8(blocks.as_mut_ptr().add(16).cast(), veorq_u8(b1, t1));
+            vst1q_u8(blocks.as_mut_ptr().add(32).cast(), veorq_u8(b2, t2));
+            vst1q_u8(blocks.as_mut_ptr().add(48).cast(), veorq_u8(b3, t3));
+            vst1q_u8(blocks.as_mut_ptr().add(64).cast(), veorq_u8(b4, t4));
+            vst1q_u8(blocks.as_mut_ptr().add(80).cast(), veorq_u8(b5, t5));
+            vst1q_u8(blocks.as_mut_ptr().add(96).cast(), veorq_u8(b6, t6));
+            vst1q_u8(blocks.as_mut_ptr().add(112).cast(), veorq_u8(b7, t7));
+        }
+    }
+
+    for block in by8.into_remainder().chunks_exact_mut(16) {
+        // SAFETY: `block` is 16 bytes long, via `chunks_exact_mut`
+        let b = unsafe { veorq_u8(vld1q_u8(block.as_ptr().cast()), t) };
+        let b = match key {
+            AesKey::Aes128(a128) => _aes128_block(a128.round_keys(), b),
+            AesKey::Aes192(a192) => _aes192_block(a192.round_keys(), b),
+            AesKey::Aes256(a256) => _aes256_block(a256.round_keys(), b),
+        };
+        // SAFETY: `block` is 16 bytes long, via `chunks_exact_mut`
+        unsafe { vst1q_u8(block.as_mut_ptr().cast(), veorq_u8(b, t)) };
+        t = mul_alpha(t);
+    }
+
+    // SAFETY: `tweak` is 16 bytes and writable
+    unsafe { vst1q_u8(tweak.as_mut_ptr().cast(), t) };
+}
+
+#[target_feature(enable = "aes,neon")]
+fn _decrypt(round_keys: &[uint8x16_t], tweak: &mut [u8; 16], inout: &mut [u8]) {
+    debug_assert!(inout.len().is_multiple_of(16));
+
+    // SAFETY: `tweak` is 16 bytes and readable
+    let mut t = unsafe { vld1q_u8(tweak.as_ptr().cast()) };
+    let mut by8 = inout.chunks_exact_mut(128);
+
+    for blocks in by8.by_ref() {
+        cpu::prefetch_rw(blocks.as_ptr());
+        let (t0, t1, t2, t3, t4, t5, t6, t7);
+        (t0, t1, t2, t3, t4, t5, t6, t7, t) = tweaks_8(t);
+
+        // SAFETY: `blocks` is 128 bytes long, via `chunks_exact_mut`
+        let (b0, b1, b2, b3, b4, b5, b6, b7) = unsafe {
+            (
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(0).cast()), t0),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(16).cast()), t1),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(32).cast()), t2),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(48).cast()), t3),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(64).cast()), t4),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(80).cast()), t5),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(96).cast()), t6),
+                veorq_u8(vld1q_u8(blocks.as_ptr().add(112).cast()), t7),
+            )
+        };
+
+        let (b0, b1, b2, b3, b4, b5, b6, b7) =
+            _decrypt_8_blocks(round_keys, b0, b1, b2, b3, b4, b5, b6, b7);
+
+        // SAFETY: `blocks` is 128 bytes long, via `chunks_exact_mut`
+        unsafe {
+            vst1q_u8(blocks.as_mut_ptr().add(0).cast(), veorq_u8(b0, t0));
+            vst1q_u8(blocks.as_mut_ptr().add(16).cast(), veorq_u8(b1, t1));
+            vst1q_u8(blocks.as_mut_ptr().add(32).cast(), veorq_u8(b2, t2));
+            vst1q_u8(blocks.as_mut_ptr().add(48).cast(), veorq_u8(b3, t3));
+            vst1q_u8(blocks.as_mut_ptr().add(64).cast(), veorq_u8(b4, t4));
+            vst1q_u8(blocks.as_mut_ptr().add(80).cast(), veorq_u8(b5, t5));
+            vst1q_u8(blocks.as_mut_ptr().add(96).cast(), veorq_u8(b6, t6));
+            vst1q_u8(blocks.as_mut_ptr().add(112).cast(), veorq_u8(b7, t7));
+        }
+    }
+
+    for block in by8.into_remainder().chunks_exact_mut(16) {
+        // SAFETY: `block` is 16 bytes long, via `chunks_exact_mut`
+        let b = unsafe { veorq_u8(vld1q_u8(block.as_ptr().cast()), t) };
+        let b = _decrypt_block(round_keys, b);
+        // SAFETY: `block` is 16 bytes long, via `chunks_exact_mut`
+        unsafe { vst1q_u8(block.as_mut_ptr().cast(), veorq_u8(b, t)) };
+        t = mul_alpha(t);
+    }
+
+    // SAFETY: `tweak` is 16 bytes and writable
+    unsafe { vst1q_u8(tweak.as_mut_ptr().cast(), t) };
+}
+
+/// Returns the eight tweaks starting with `t`, and the one after those.
+#[target_feature(enable = "neon")]
+#[inline]
+fn tweaks_8(
+    t0: uint8x16_t,
+) -> (
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+    uint8x16_t,
+) {
+    let t1 = mul_alpha(t0);
+    let t2 = mul_alpha(t1);
+    let t3 = mul_alpha(t2);
+    let t4 = mul_alpha(t3);
+    let t5 = mul_alpha(t4);
+    let t6 = mul_alpha(t5);
+    let t7 = mul_alpha(t6);
+    (t0, t1, t2, t3, t4, t5, t6, t7, mul_alpha(t7))
+}
+
+/// Multiplies the tweak by `x`, as a little-endian element of GF(2<sup>128</sup>).
+#[target_feature(enable = "neon")]
+#[inline]
+fn mul_alpha(t: uint8x16_t) -> uint8x16_t {
+    let t = vreinterpretq_u64_u8(t);
+
+    // each 64-bit half is shifted left by one.  the top bit of the low
+    // half carries into the high half, and the top bit of the high half
+    // is reduced into the low half.
+    let carries = vreinterpretq_u64_s64(vshrq_n_s64(vreinterpretq_s64_u64(t), 63));
+    let carries = vextq_u64(carries, carries, 1);
+    let carries = vandq_u64(carries, vcombine_u64(vcreate_u64(0x87), vcreate_u64(1)));
+    vreinterpretq_u8_u64(veorq_u64(vshlq_n_u64(t, 1), carries))
+}
@@ -0,0 +1,185 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// BLAKE2b compression, using NEON.
+//
+// cf. the x86_64 version, on which this one is based.  A row
+// of the state is 256 bits, so here it is held in two vectors.
+
+use core::arch::aarch64::*;
+
+/// The BLAKE2b compression function F, as defined in RFC7693.
+///
+/// `counter` is the total number of bytes processed, including
+/// those in `block`.  `last` is set for the final block.
+pub(crate) fn blake2b_compress(h: &mut [u64; 8], block: &[u8; 128], counter: u128, last: bool) {
+    // SAFETY: this crate requires the `neon` cpu feature
+    unsafe { compress(h, block, counter, last) }
+}
+
+/// One row of the state: words 0 and 1, then words 2 and 3.
+type Row = [uint64x2_t; 2];
+
+#[target_feature(enable = "neon")]
+fn compress(h: &mut [u64; 8], block: &[u8; 128], counter: u128, last: bool) {
+    let mut m = [0u64; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *m = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let h0 = load(&h[0..4]);
+    let h1 = load(&h[4..8]);
+
+    let mut a = h0;
+    let mut b = h1;
+    let mut c = load(&IV[0..4]);
+    let mut d = load(&[
+        IV[4] ^ counter as u64,
+        IV[5] ^ (counter >> 64) as u64,
+        IV[6] ^ if last { !0 } else { 0 },
+        IV[7],
+    ]);
+
+    for s in SIGMA.iter().cycle().take(ROUNDS) {
+        let x = gather(&m, s[0], s[2], s[4], s[6]);
+        let y = gather(&m, s[1], s[3], s[5], s[7]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // diagonalize: rotate rows 1, 2 and 3 by one, two and three words
+        b = [vextq_u64(b[0], b[1], 1), vextq_u64(b[1], b[0], 1)];
+        c = [c[1], c[0]];
+        d = [vextq_u64(d[1], d[0], 1), vextq_u64(d[0], d[1], 1)];
+
+        let x = gather(&m, s[8], s[10], s[12], s[14]);
+        let y = gather(&m, s[9], s[11], s[13], s[15]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // undiagonalize
+        b = [vextq_u64(b[1], b[0], 1), vextq_u64(b[0], b[1], 1)];
+        c = [c[1], c[0]];
+        d = [vextq_u64(d[0], d[1], 1), vextq_u64(d[1], d[0], 1)];
+    }
+
+    store(&mut h[0..4], xor(h0, xor(a, c)));
+    store(&mut h[4..8], xor(h1, xor(b, d)));
+}
+
+/// BLAKE2b's G, on four columns at once.
+#[target_feature(enable = "neon")]
+#[inline]
+fn g(a: &mut Row, b: &mut Row, c: &mut Row, d: &mut Row, x: Row, y: Row) {
+    for i in 0..2 {
+        a[i] = vaddq_u64(vaddq_u64(a[i], b[i]), x[i]);
+        d[i] = rotr32(veorq_u64(d[i], a[i]));
+        c[i] = vaddq_u64(c[i], d[i]);
+        b[i] = rotr24(veorq_u64(b[i], c[i]));
+        a[i] = vaddq_u64(vaddq_u64(a[i], b[i]), y[i]);
+        d[i] = rotr16(veorq_u64(d[i], a[i]));
+        c[i] = vaddq_u64(c[i], d[i]);
+        b[i] = rotr63(veorq_u64(b[i], c[i]));
+    }
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn xor(x: Row, y: Row) -> Row {
+    [veorq_u64(x[0], y[0]), veorq_u64(x[1], y[1])]
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr32(x: uint64x2_t) -> uint64x2_t {
+    vreinterpretq_u64_u32(vrev64q_u32(vreinterpretq_u32_u64(x)))
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr24(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64(vshlq_n_u64(x, 40), x, 24)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr16(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64(vshlq_n_u64(x, 48), x, 16)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr63(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64(vshlq_n_u64(x, 1), x, 63)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn gather(m: &[u64; 16], i0: usize, i1: usize, i2: usize, i3: usize) -> Row {
+    load(&[m[i0], m[i1], m[i2], m[i3]])
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn load(x: &[u64]) -> Row {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 32 bytes and readable; `vld1q_u64` has no alignment req.
+    unsafe { [vld1q_u64(x[0..2].as_ptr()), vld1q_u64(x[2..4].as_ptr())] }
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn store(x: &mut [u64], v: Row) {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 32 bytes and writable; `vst1q_u64` has no alignment req.
+    unsafe {
+        vst1q_u64(x[0..2].as_mut_ptr(), v[0]);
+        vst1q_u64(x[2..4].as_mut_ptr(), v[1]);
+    }
+}
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const ROUNDS: usize = 12;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut block = [0u8; 128];
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+
+        for (counter, last) in [(128, false), (200, true), (u128::MAX - 5, true)] {
+            let mut h = [0x0123456789abcdef; 8];
+            let mut h_generic = h;
+            blake2b_compress(&mut h, &block, counter, last);
+            generic::blake2b::blake2b_compress(&mut h_generic, &block, counter, last);
+            assert_eq!(h, h_generic);
+        }
+    }
+}
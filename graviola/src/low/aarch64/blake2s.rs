@@ -0,0 +1,168 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// BLAKE2s compression, using NEON.
+//
+// cf. the x86_64 version, on which this one is based.
+
+use core::arch::aarch64::*;
+
+/// The BLAKE2s compression function F, as defined in RFC7693.
+///
+/// `counter` is the total number of bytes processed, including
+/// those in `block`.  `last` is set for the final block.
+pub(crate) fn blake2s_compress(h: &mut [u32; 8], block: &[u8; 64], counter: u64, last: bool) {
+    // SAFETY: this crate requires the `neon` cpu feature
+    unsafe { compress(h, block, counter, last) }
+}
+
+#[target_feature(enable = "neon")]
+fn compress(h: &mut [u32; 8], block: &[u8; 64], counter: u64, last: bool) {
+    let mut m = [0u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let h0 = load(&h[0..4]);
+    let h1 = load(&h[4..8]);
+
+    let mut a = h0;
+    let mut b = h1;
+    let mut c = load(&IV[0..4]);
+    let mut d = load(&[
+        IV[4] ^ counter as u32,
+        IV[5] ^ (counter >> 32) as u32,
+        IV[6] ^ if last { !0 } else { 0 },
+        IV[7],
+    ]);
+
+    for s in SIGMA.iter() {
+        let x = gather(&m, s[0], s[2], s[4], s[6]);
+        let y = gather(&m, s[1], s[3], s[5], s[7]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // diagonalize
+        b = vextq_u32(b, b, 1);
+        c = vextq_u32(c, c, 2);
+        d = vextq_u32(d, d, 3);
+
+        let x = gather(&m, s[8], s[10], s[12], s[14]);
+        let y = gather(&m, s[9], s[11], s[13], s[15]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // undiagonalize
+        b = vextq_u32(b, b, 3);
+        c = vextq_u32(c, c, 2);
+        d = vextq_u32(d, d, 1);
+    }
+
+    store(&mut h[0..4], veorq_u32(h0, veorq_u32(a, c)));
+    store(&mut h[4..8], veorq_u32(h1, veorq_u32(b, d)));
+}
+
+/// BLAKE2s's G, on four columns at once.
+#[target_feature(enable = "neon")]
+#[inline]
+fn g(
+    a: &mut uint32x4_t,
+    b: &mut uint32x4_t,
+    c: &mut uint32x4_t,
+    d: &mut uint32x4_t,
+    x: uint32x4_t,
+    y: uint32x4_t,
+) {
+    *a = vaddq_u32(vaddq_u32(*a, *b), x);
+    *d = rotr16(veorq_u32(*d, *a));
+    *c = vaddq_u32(*c, *d);
+    *b = rotr12(veorq_u32(*b, *c));
+    *a = vaddq_u32(vaddq_u32(*a, *b), y);
+    *d = rotr8(veorq_u32(*d, *a));
+    *c = vaddq_u32(*c, *d);
+    *b = rotr7(veorq_u32(*b, *c));
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr16(x: uint32x4_t) -> uint32x4_t {
+    vreinterpretq_u32_u16(vrev32q_u16(vreinterpretq_u16_u32(x)))
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr12(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32(vshlq_n_u32(x, 20), x, 12)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr8(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32(vshlq_n_u32(x, 24), x, 8)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr7(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32(vshlq_n_u32(x, 25), x, 7)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn gather(m: &[u32; 16], i0: usize, i1: usize, i2: usize, i3: usize) -> uint32x4_t {
+    load(&[m[i0], m[i1], m[i2], m[i3]])
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn load(x: &[u32]) -> uint32x4_t {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 16 bytes and readable; `vld1q_u32` has no alignment req.
+    unsafe { vld1q_u32(x.as_ptr()) }
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn store(x: &mut [u32], v: uint32x4_t) {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 16 bytes and writable; `vst1q_u32` has no alignment req.
+    unsafe { vst1q_u32(x.as_mut_ptr(), v) }
+}
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut block = [0u8; 64];
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+
+        for (counter, last) in [(64, false), (100, true), (u64::MAX - 5, true)] {
+            let mut h = [0x01234567; 8];
+            let mut h_generic = h;
+            blake2s_compress(&mut h, &block, counter, last);
+            generic::blake2s::blake2s_compress(&mut h_generic, &block, counter, last);
+            assert_eq!(h, h_generic);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// BLAKE3 compression, using NEON.
+//
+// cf. the x86_64 version, on which this one is based.  Here whole
+// chunks are hashed four at a time.
+
+use core::arch::aarch64::*;
+
+/// The BLAKE3 compression function.
+///
+/// This returns the whole 16-word output; the first 8 words are the
+/// new chaining value.
+pub(crate) fn blake3_compress(
+    cv: &[u32; 8],
+    block: &[u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    // SAFETY: this crate requires the `neon` cpu feature
+    unsafe { compress(cv, block, counter, block_len, flags) }
+}
+
+/// Compute the chaining values of whole chunks.
+///
+/// `chunks[i]` has chunk counter `counter + i`, and its chaining
+/// value is written to `cvs[i]`.  `flags` are the domain flags
+/// (eg. `KEYED_HASH`): `CHUNK_START` and `CHUNK_END` are added here.
+pub(crate) fn blake3_hash_chunks(
+    key: &[u32; 8],
+    chunks: &[[u8; 1024]],
+    counter: u64,
+    flags: u32,
+    cvs: &mut [[u32; 8]],
+) {
+    assert_eq!(chunks.len(), cvs.len());
+    let (chunks4, chunks) = chunks.as_chunks::<4>();
+    let (cvs4, cvs) = cvs.as_chunks_mut::<4>();
+
+    let mut counter = counter;
+    for (chunks, cvs) in chunks4.iter().zip(cvs4.iter_mut()) {
+        // SAFETY: this crate requires the `neon` cpu feature
+        unsafe { hash4(key, chunks, counter, flags, cvs) };
+        counter += 4;
+    }
+
+    for (chunk, cv) in chunks.iter().zip(cvs.iter_mut()) {
+        *cv = *key;
+        let (blocks, _) = chunk.as_chunks::<64>();
+        for (j, block) in blocks.iter().enumerate() {
+            let out = blake3_compress(cv, block, counter, 64, flags | block_flags(j));
+            cv.copy_from_slice(&out[..8]);
+        }
+        counter += 1;
+    }
+}
+
+#[target_feature(enable = "neon")]
+fn compress(
+    cv: &[u32; 8],
+    block: &[u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut m = [0u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let cv0 = load(&cv[0..4]);
+    let cv1 = load(&cv[4..8]);
+
+    let mut a = cv0;
+    let mut b = cv1;
+    let mut c = load(&IV[0..4]);
+    let mut d = load(&[counter as u32, (counter >> 32) as u32, block_len, flags]);
+
+    for s in SCHEDULE.iter() {
+        let x = gather(&m, s[0], s[2], s[4], s[6]);
+        let y = gather(&m, s[1], s[3], s[5], s[7]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // diagonalize
+        b = vextq_u32(b, b, 1);
+        c = vextq_u32(c, c, 2);
+        d = vextq_u32(d, d, 3);
+
+        let x = gather(&m, s[8], s[10], s[12], s[14]);
+        let y = gather(&m, s[9], s[11], s[13], s[15]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // undiagonalize
+        b = vextq_u32(b, b, 3);
+        c = vextq_u32(c, c, 2);
+        d = vextq_u32(d, d, 1);
+    }
+
+    let mut out = [0u32; 16];
+    store(&mut out[0..4], veorq_u32(a, c));
+    store(&mut out[4..8], veorq_u32(b, d));
+    store(&mut out[8..12], veorq_u32(c, cv0));
+    store(&mut out[12..16], veorq_u32(d, cv1));
+    out
+}
+
+/// BLAKE3's G, on four columns at once.
+#[target_feature(enable = "neon")]
+#[inline]
+fn g(
+    a: &mut uint32x4_t,
+    b: &mut uint32x4_t,
+    c: &mut uint32x4_t,
+    d: &mut uint32x4_t,
+    x: uint32x4_t,
+    y: uint32x4_t,
+) {
+    *a = vaddq_u32(vaddq_u32(*a, *b), x);
+    *d = rotr16(veorq_u32(*d, *a));
+    *c = vaddq_u32(*c, *d);
+    *b = rotr12(veorq_u32(*b, *c));
+    *a = vaddq_u32(vaddq_u32(*a, *b), y);
+    *d = rotr8(veorq_u32(*d, *a));
+    *c = vaddq_u32(*c, *d);
+    *b = rotr7(veorq_u32(*b, *c));
+}
+
+/// Hash four whole chunks in parallel.
+#[target_feature(enable = "neon")]
+fn hash4(
+    key: &[u32; 8],
+    chunks: &[[u8; 1024]; 4],
+    counter: u64,
+    flags: u32,
+    cvs: &mut [[u32; 8]; 4],
+) {
+    let mut h = [vdupq_n_u32(0); 8];
+    for (h, key) in h.iter_mut().zip(key) {
+        *h = vdupq_n_u32(*key);
+    }
+
+    let mut counter_lo = [0u32; 4];
+    let mut counter_hi = [0u32; 4];
+    for (i, (lo, hi)) in counter_lo.iter_mut().zip(counter_hi.iter_mut()).enumerate() {
+        let counter = counter + i as u64;
+        *lo = counter as u32;
+        *hi = (counter >> 32) as u32;
+    }
+    let counter_lo = load(&counter_lo);
+    let counter_hi = load(&counter_hi);
+
+    for block in 0..16 {
+        let m = transpose_block(chunks, block);
+
+        let mut v = [vdupq_n_u32(0); 16];
+        v[..8].copy_from_slice(&h);
+        v[8] = vdupq_n_u32(IV[0]);
+        v[9] = vdupq_n_u32(IV[1]);
+        v[10] = vdupq_n_u32(IV[2]);
+        v[11] = vdupq_n_u32(IV[3]);
+        v[12] = counter_lo;
+        v[13] = counter_hi;
+        v[14] = vdupq_n_u32(64);
+        v[15] = vdupq_n_u32(flags | block_flags(block));
+
+        for s in SCHEDULE.iter() {
+            g4(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            g4(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            g4(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            g4(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            g4(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            g4(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            g4(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            g4(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+            h[i] = veorq_u32(v[i], v[i + 8]);
+        }
+    }
+
+    let lo = transpose4x4([h[0], h[1], h[2], h[3]]);
+    let hi = transpose4x4([h[4], h[5], h[6], h[7]]);
+    for (cv, (lo, hi)) in cvs.iter_mut().zip(lo.into_iter().zip(hi)) {
+        store(&mut cv[0..4], lo);
+        store(&mut cv[4..8], hi);
+    }
+}
+
+/// BLAKE3's G, on one word of four states.
+#[target_feature(enable = "neon")]
+#[inline]
+fn g4(
+    v: &mut [uint32x4_t; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    x: uint32x4_t,
+    y: uint32x4_t,
+) {
+    v[a] = vaddq_u32(vaddq_u32(v[a], v[b]), x);
+    v[d] = rotr16(veorq_u32(v[d], v[a]));
+    v[c] = vaddq_u32(v[c], v[d]);
+    v[b] = rotr12(veorq_u32(v[b], v[c]));
+    v[a] = vaddq_u32(vaddq_u32(v[a], v[b]), y);
+    v[d] = rotr8(veorq_u32(v[d], v[a]));
+    v[c] = vaddq_u32(v[c], v[d]);
+    v[b] = rotr7(veorq_u32(v[b], v[c]));
+}
+
+/// Load message block `block` of each chunk, so that vector `i` of
+/// the result holds word `i` of each.
+#[target_feature(enable = "neon")]
+#[inline]
+fn transpose_block(chunks: &[[u8; 1024]; 4], block: usize) -> [uint32x4_t; 16] {
+    let mut m = [vdupq_n_u32(0); 16];
+    for quarter in 0..4 {
+        let mut rows = [vdupq_n_u32(0); 4];
+        for (row, chunk) in rows.iter_mut().zip(chunks) {
+            let offset = block * 64 + quarter * 16;
+            // SAFETY: `chunk[offset..offset + 16]` is in bounds and readable;
+            // `vld1q_u8` has no alignment req.
+            *row = vreinterpretq_u32_u8(unsafe { vld1q_u8(chunk[offset..offset + 16].as_ptr()) });
+        }
+        m[quarter * 4..quarter * 4 + 4].copy_from_slice(&transpose4x4(rows));
+    }
+    m
+}
+
+/// Transpose a 4x4 matrix of words.
+#[target_feature(enable = "neon")]
+#[inline]
+fn transpose4x4(x: [uint32x4_t; 4]) -> [uint32x4_t; 4] {
+    // ab.0 is a0 b0 a2 b2, ab.1 is a1 b1 a3 b3
+    let ab = vtrnq_u32(x[0], x[1]);
+    let cd = vtrnq_u32(x[2], x[3]);
+    [
+        vcombine_u32(vget_low_u32(ab.0), vget_low_u32(cd.0)),
+        vcombine_u32(vget_low_u32(ab.1), vget_low_u32(cd.1)),
+        vcombine_u32(vget_high_u32(ab.0), vget_high_u32(cd.0)),
+        vcombine_u32(vget_high_u32(ab.1), vget_high_u32(cd.1)),
+    ]
+}
+
+/// `CHUNK_START` for the first block of a chunk, and `CHUNK_END` for the last.
+fn block_flags(block: usize) -> u32 {
+    match block {
+        0 => CHUNK_START,
+        15 => CHUNK_END,
+        _ => 0,
+    }
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr16(x: uint32x4_t) -> uint32x4_t {
+    vreinterpretq_u32_u16(vrev32q_u16(vreinterpretq_u16_u32(x)))
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr12(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32(vshlq_n_u32(x, 20), x, 12)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr8(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32(vshlq_n_u32(x, 24), x, 8)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn rotr7(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32(vshlq_n_u32(x, 25), x, 7)
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn gather(m: &[u32; 16], i0: usize, i1: usize, i2: usize, i3: usize) -> uint32x4_t {
+    load(&[m[i0], m[i1], m[i2], m[i3]])
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn load(x: &[u32]) -> uint32x4_t {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 16 bytes and readable; `vld1q_u32` has no alignment req.
+    unsafe { vld1q_u32(x.as_ptr()) }
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn store(x: &mut [u32], v: uint32x4_t) {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 16 bytes and writable; `vst1q_u32` has no alignment req.
+    unsafe { vst1q_u32(x.as_mut_ptr(), v) }
+}
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SCHEDULE: [[usize; 16]; 7] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut block = [0u8; 64];
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let cv = [0x01234567, 0x89abcdef, 1, 2, 3, 4, 5, 6];
+
+        for (counter, block_len, flags) in [(0, 64, 0b1011), (5, 13, 0b10), (u64::MAX - 5, 1, 0)] {
+            assert_eq!(
+                blake3_compress(&cv, &block, counter, block_len, flags),
+                generic::blake3::blake3_compress(&cv, &block, counter, block_len, flags)
+            );
+        }
+    }
+
+    #[test]
+    fn hash_chunks_matches_generic() {
+        let mut chunks = vec![[0u8; 1024]; 11];
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            for (j, b) in chunk.iter_mut().enumerate() {
+                *b = (i * 7 + j) as u8;
+            }
+        }
+        let key = [0x01234567, 0x89abcdef, 1, 2, 3, 4, 5, 6];
+
+        // nb. the counter carries into the top word part-way through
+        for (counter, flags) in [(0, 0), (0xffff_fffe, 1 << 4)] {
+            let mut cvs = vec![[0u32; 8]; 11];
+            let mut cvs_generic = cvs.clone();
+            blake3_hash_chunks(&key, &chunks, counter, flags, &mut cvs);
+            generic::blake3::blake3_hash_chunks(&key, &chunks, counter, flags, &mut cvs_generic);
+            assert_eq!(cvs, cvs_generic);
+        }
+    }
+}
@@ -1,8 +1,11 @@
 // Written for Graviola by Joe Birr-Pixton, 2024.
 // SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
 
+pub(crate) mod aegis;
 pub(crate) mod aes;
+pub(crate) mod aes_ctr;
 pub(crate) mod aes_gcm;
+pub(crate) mod aes_xts;
 pub(crate) mod bignum_add;
 pub(crate) mod bignum_add_p256;
 pub(crate) mod bignum_add_p384;
@@ -54,6 +57,9 @@ pub(crate) mod bignum_point_select_p384;
 pub(crate) mod bignum_shr_small;
 pub(crate) mod bignum_tomont_p256;
 pub(crate) mod bignum_tomont_p384;
+pub(crate) mod blake2b;
+pub(crate) mod blake2s;
+pub(crate) mod blake3;
 pub(crate) mod cpu;
 pub(crate) mod curve25519_x25519;
 pub(crate) mod curve25519_x25519base;
@@ -75,10 +81,14 @@ pub(crate) mod p256_montjdouble;
 pub(crate) mod p256_montjmixadd;
 pub(crate) mod p384_montjadd;
 pub(crate) mod p384_montjdouble;
+pub(crate) mod salsa20;
 pub(crate) mod sha256;
 pub(crate) mod sha3_keccak2_f1600;
+pub(crate) mod sha3_keccak2_p1600_12;
 pub(crate) mod sha3_keccak2of4_f1600;
 pub(crate) mod sha3_keccak4_f1600_mux;
 pub(crate) mod sha3_keccak_f1600;
 pub(crate) mod sha3_keccak_f1600_alt;
 pub(crate) mod sha3_keccak_f1600_mux;
+pub(crate) mod sha3_keccak_p1600_12;
+pub(crate) mod sha3_keccak_p1600_12_alt;
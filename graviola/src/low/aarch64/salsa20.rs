@@ -0,0 +1,118 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// Salsa20/8 and scrypt's BlockMix.
+//
+// cf. the x86_64 version, on which this one is based.
+
+use core::arch::aarch64::*;
+
+/// BlockMix<sub>Salsa20/8, r</sub>, as defined in RFC7914.
+///
+/// `input` and `output` are `2 * r` blocks.
+///
+/// Blocks are in "SIMD order": position `i` of a block contains
+/// word `5 * i % 16` of the block in standard order.  This
+/// means the diagonals of the Salsa20 state are in the same
+/// lane across the four vectors.
+pub(crate) fn salsa20_8_block_mix(input: &[[u32; 16]], output: &mut [[u32; 16]]) {
+    // SAFETY: this crate requires the `neon` cpu feature
+    unsafe { block_mix(input, output) }
+}
+
+#[target_feature(enable = "neon")]
+fn block_mix(input: &[[u32; 16]], output: &mut [[u32; 16]]) {
+    debug_assert_eq!(input.len(), output.len());
+    debug_assert!(input.len().is_multiple_of(2));
+    let r = input.len() / 2;
+
+    // 1. X = B[2 * r - 1]
+    let mut x = load(&input[2 * r - 1]);
+
+    // 2. for i = 0 to 2 * r - 1 do
+    //      T = X xor B[i]
+    //      X = Salsa (T)
+    //      Y[i] = X
+    //    end for
+    // 3. B' = (Y[0], Y[2], ..., Y[2 * r - 2],
+    //          Y[1], Y[3], ..., Y[2 * r - 1])
+    for (i, block) in input.iter().enumerate() {
+        let b = load(block);
+        x = salsa20_8([
+            veorq_u32(x[0], b[0]),
+            veorq_u32(x[1], b[1]),
+            veorq_u32(x[2], b[2]),
+            veorq_u32(x[3], b[3]),
+        ]);
+        store(&mut output[(i / 2) + (i & 1) * r], x);
+    }
+}
+
+macro_rules! quarter {
+    ($t:ident, $a:ident, $b:ident, $c:ident, $rot:literal) => {
+        let $t = vaddq_u32($b, $c);
+        let $t = vsriq_n_u32(vshlq_n_u32($t, $rot), $t, 32 - $rot);
+        $a = veorq_u32($a, $t);
+    };
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn salsa20_8(b: [uint32x4_t; 4]) -> [uint32x4_t; 4] {
+    let [mut x0, mut x1, mut x2, mut x3] = b;
+
+    for _ in 0..4 {
+        // columns
+        quarter!(t, x1, x0, x3, 7);
+        quarter!(t, x2, x1, x0, 9);
+        quarter!(t, x3, x2, x1, 13);
+        quarter!(t, x0, x3, x2, 18);
+
+        x1 = vextq_u32(x1, x1, 3);
+        x2 = vextq_u32(x2, x2, 2);
+        x3 = vextq_u32(x3, x3, 1);
+
+        // rows
+        quarter!(t, x3, x0, x1, 7);
+        quarter!(t, x2, x3, x0, 9);
+        quarter!(t, x1, x2, x3, 13);
+        quarter!(t, x0, x1, x2, 18);
+
+        x1 = vextq_u32(x1, x1, 1);
+        x2 = vextq_u32(x2, x2, 2);
+        x3 = vextq_u32(x3, x3, 3);
+    }
+
+    [
+        vaddq_u32(b[0], x0),
+        vaddq_u32(b[1], x1),
+        vaddq_u32(b[2], x2),
+        vaddq_u32(b[3], x3),
+    ]
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn load(block: &[u32; 16]) -> [uint32x4_t; 4] {
+    // SAFETY: `block` is 64 bytes and readable; `vld1q_u32` has no alignment req.
+    unsafe {
+        [
+            vld1q_u32(block[0..4].as_ptr()),
+            vld1q_u32(block[4..8].as_ptr()),
+            vld1q_u32(block[8..12].as_ptr()),
+            vld1q_u32(block[12..16].as_ptr()),
+        ]
+    }
+}
+
+#[target_feature(enable = "neon")]
+#[inline]
+fn store(block: &mut [u32; 16], x: [uint32x4_t; 4]) {
+    // SAFETY: `block` is 64 bytes and writable; `vst1q_u32` has no alignment req.
+    unsafe {
+        vst1q_u32(block[0..4].as_mut_ptr(), x[0]);
+        vst1q_u32(block[4..8].as_mut_ptr(), x[1]);
+        vst1q_u32(block[8..12].as_mut_ptr(), x[2]);
+        vst1q_u32(block[12..16].as_mut_ptr(), x[3]);
+    }
+}
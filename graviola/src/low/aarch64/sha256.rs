@@ -11,6 +11,26 @@ pub(crate) fn sha256_compress_blocks(state: &mut [u32; 8], blocks: &[u8]) {
     unsafe { sha256(state, blocks) }
 }
 
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+///
+/// These are done four at a time.  Each SHA256 instruction depends on
+/// the previous one, so interleaving independent computations keeps
+/// more of them in flight.
+pub(crate) fn sha256_compress_lanes(states: &mut [[u32; 8]], blocks: &[&[u8; 64]]) {
+    assert_eq!(states.len(), blocks.len());
+    let (states4, states) = states.as_chunks_mut::<4>();
+    let (blocks4, blocks) = blocks.as_chunks::<4>();
+
+    for (states, blocks) in states4.iter_mut().zip(blocks4) {
+        // SAFETY: crate requires the `neon` and `sha2` cpu features
+        unsafe { sha256_x4(states, blocks) }
+    }
+
+    for (state, block) in states.iter_mut().zip(blocks) {
+        sha256_compress_blocks(state, *block);
+    }
+}
+
 macro_rules! k {
     ($k:literal) => {
         // SAFETY: values for `$k` are compile-time constants
@@ -118,6 +138,63 @@ fn sha256(state: &mut [u32; 8], blocks: &[u8]) {
     }
 }
 
+#[target_feature(enable = "neon,sha2")]
+fn sha256_x4(states: &mut [[u32; 8]; 4], blocks: &[&[u8; 64]; 4]) {
+    let mut state0 = [vdupq_n_u32(0); 4];
+    let mut state1 = [vdupq_n_u32(0); 4];
+    let mut msg = [[vdupq_n_u32(0); 4]; 4];
+
+    for lane in 0..4 {
+        // SAFETY: `states[lane]` is readable, and 8 words in length
+        unsafe {
+            state0[lane] = vld1q_u32(states[lane][0..4].as_ptr());
+            state1[lane] = vld1q_u32(states[lane][4..8].as_ptr());
+        }
+
+        for (i, msg) in msg[lane].iter_mut().enumerate() {
+            // SAFETY: `blocks[lane][i * 16..]` is 16 bytes long and readable
+            let words = unsafe { vld1q_u8(blocks[lane][i * 16..].as_ptr()) };
+            *msg = vreinterpretq_u32_u8(vrev32q_u8(words));
+        }
+    }
+
+    let state0_prev = state0;
+    let state1_prev = state1;
+
+    // as `sha256`, but the `round!` steps of each lane are interleaved.
+    for r in 0..16 {
+        // SAFETY: `r * 4 + 4` is at most 64, the length of `K`
+        let k = unsafe { vld1q_u32(K.0[r * 4..].as_ptr()) };
+
+        for lane in 0..4 {
+            let msg = &mut msg[lane];
+            let t0 = vaddq_u32(msg[r % 4], k);
+            let t1 = state0[lane];
+            state0[lane] = vsha256hq_u32(state0[lane], state1[lane], t0);
+            state1[lane] = vsha256h2q_u32(state1[lane], t1, t0);
+
+            if r < 12 {
+                let m = vsha256su0q_u32(msg[r % 4], msg[(r + 1) % 4]);
+                msg[r % 4] = vsha256su1q_u32(m, msg[(r + 2) % 4], msg[(r + 3) % 4]);
+            }
+        }
+    }
+
+    for lane in 0..4 {
+        // SAFETY: `states[lane]` is writable, and 8 words in length
+        unsafe {
+            vst1q_u32(
+                states[lane][0..4].as_mut_ptr(),
+                vaddq_u32(state0[lane], state0_prev[lane]),
+            );
+            vst1q_u32(
+                states[lane][4..8].as_mut_ptr(),
+                vaddq_u32(state1[lane], state1_prev[lane]),
+            );
+        }
+    }
+}
+
 #[repr(align(16))]
 struct Aligned([u32; 64]);
 
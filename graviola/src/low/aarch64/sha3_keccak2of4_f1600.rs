@@ -19,3 +19,22 @@ pub(crate) fn sha3_keccak2of4_f1600(a: &mut [[u64; 25]; 4], rc: &[u64; 24]) {
         }
     }
 }
+
+/// Compute Keccak-p[1600,12] on the first two states in `a`.
+#[inline]
+pub(crate) fn sha3_keccak2of4_p1600_12(a: &mut [[u64; 25]; 4], rc: &[u64; 12]) {
+    let (a, _) = a.split_at_mut(2);
+    let a = a.try_into().unwrap();
+
+    match super::cpu::HaveSha3::check() {
+        // SAFETY: This branch is only called if the CPU has the SHA3 feature.
+        Some(_) => unsafe {
+            super::sha3_keccak2_p1600_12::sha3_keccak2_p1600_12(a, rc);
+        },
+        None => {
+            for a in a.iter_mut() {
+                super::sha3_keccak_p1600_12::sha3_keccak_p1600_12(a, rc);
+            }
+        }
+    }
+}
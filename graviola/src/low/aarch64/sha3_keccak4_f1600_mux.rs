@@ -19,3 +19,20 @@ pub(crate) fn sha3_keccak4_f1600(a: &mut [[u64; 25]; 4], rc: &[u64; 24]) {
         }
     }
 }
+
+#[inline]
+pub(crate) fn sha3_keccak4_p1600_12(a: &mut [[u64; 25]; 4], rc: &[u64; 12]) {
+    match super::cpu::HaveSha3::check() {
+        // SAFETY: This branch is only called if the CPU has the SHA3 feature.
+        Some(_) => unsafe {
+            for a in a.as_chunks_mut().0.iter_mut() {
+                super::sha3_keccak2_p1600_12::sha3_keccak2_p1600_12(a, rc);
+            }
+        },
+        None => {
+            for a in a.iter_mut() {
+                super::sha3_keccak_p1600_12::sha3_keccak_p1600_12(a, rc);
+            }
+        }
+    }
+}
@@ -9,3 +9,12 @@ pub(crate) fn sha3_keccak_f1600(a: &mut [u64; 25], rc: &[u64; 24]) {
         None => super::sha3_keccak_f1600::sha3_keccak_f1600(a, rc),
     }
 }
+
+#[inline]
+pub(crate) fn sha3_keccak_p1600_12(a: &mut [u64; 25], rc: &[u64; 12]) {
+    match super::cpu::HaveSha3::check() {
+        // SAFETY: This branch is only called if the CPU has the SHA3 feature.
+        Some(_) => unsafe { super::sha3_keccak_p1600_12_alt::sha3_keccak_p1600_12(a, rc) },
+        None => super::sha3_keccak_p1600_12::sha3_keccak_p1600_12(a, rc),
+    }
+}
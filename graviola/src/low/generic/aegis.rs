@@ -0,0 +1,453 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// The AEGIS family of AEADs.
+//
+// Ref. <https://datatracker.ietf.org/doc/draft-irtf-cfrg-aegis-aead/>
+//
+// This is written in terms of `AesBlocks`, which is implemented by
+// each architecture.  The functions here are all `#[inline(always)]`
+// so that they are compiled into the caller's `#[target_feature]`
+// context.
+
+/// A vector of one or more independent 128-bit lanes.
+///
+/// AEGIS-128L and AEGIS-256 use one lane, AEGIS-128X<i>n</i> uses <i>n</i>.
+pub(crate) trait AesBlocks: Copy {
+    /// The number of lanes.
+    const LANES: usize;
+
+    /// Loads from `bytes`, which is `16 * LANES` bytes.
+    fn load(bytes: &[u8]) -> Self;
+
+    /// Stores into `bytes`, which is `16 * LANES` bytes.
+    fn store(self, bytes: &mut [u8]);
+
+    /// Returns `block` in every lane.
+    fn splat(block: &[u8; 16]) -> Self;
+
+    /// One AES encryption round (SubBytes, ShiftRows, MixColumns then
+    /// AddRoundKey with `round_key`) in each lane.
+    fn aes_round(self, round_key: Self) -> Self;
+
+    fn xor(self, other: Self) -> Self;
+
+    fn and(self, other: Self) -> Self;
+
+    /// XORs together all the lanes.
+    fn fold(self) -> [u8; 16];
+}
+
+/// The most lanes supported.
+const MAX_LANES: usize = 4;
+
+const C0: [u8; 16] = [
+    0x00, 0x01, 0x01, 0x02, 0x03, 0x05, 0x08, 0x0d, 0x15, 0x22, 0x37, 0x59, 0x90, 0xe9, 0x79, 0x62,
+];
+
+const C1: [u8; 16] = [
+    0xdb, 0x3d, 0x18, 0x55, 0x6d, 0xc2, 0x2f, 0xf1, 0x20, 0x11, 0x31, 0x42, 0x73, 0xb5, 0x28, 0xdd,
+];
+
+/// Encrypts `cipher_inout` with AEGIS-128L or AEGIS-128X, and writes
+/// the 16- or 32-byte tag to `tag_out`.
+#[inline(always)]
+pub(crate) fn aegis128x_encrypt<B: AesBlocks>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    let mut state = Aegis128X::<B>::new(key, nonce);
+    state.absorb(aad);
+    state.encrypt(cipher_inout);
+    state.finalize(aad.len(), cipher_inout.len(), tag_out);
+}
+
+/// Decrypts `cipher_inout` with AEGIS-128L or AEGIS-128X, and writes
+/// the 16- or 32-byte expected tag to `tag_out`.
+#[inline(always)]
+pub(crate) fn aegis128x_decrypt<B: AesBlocks>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    let mut state = Aegis128X::<B>::new(key, nonce);
+    state.absorb(aad);
+    state.decrypt(cipher_inout);
+    state.finalize(aad.len(), cipher_inout.len(), tag_out);
+}
+
+/// Encrypts `cipher_inout` with AEGIS-256, and writes the 16- or
+/// 32-byte tag to `tag_out`.
+#[inline(always)]
+pub(crate) fn aegis256_encrypt<B: AesBlocks>(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    let mut state = Aegis256::<B>::new(key, nonce);
+    state.absorb(aad);
+    state.encrypt(cipher_inout);
+    state.finalize(aad.len(), cipher_inout.len(), tag_out);
+}
+
+/// Decrypts `cipher_inout` with AEGIS-256, and writes the 16- or
+/// 32-byte expected tag to `tag_out`.
+#[inline(always)]
+pub(crate) fn aegis256_decrypt<B: AesBlocks>(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    let mut state = Aegis256::<B>::new(key, nonce);
+    state.absorb(aad);
+    state.decrypt(cipher_inout);
+    state.finalize(aad.len(), cipher_inout.len(), tag_out);
+}
+
+/// The per-lane context, `ZeroPad(LE8(i) || LE8(LANES - 1))`,
+/// used to separate the lanes during initialization.
+#[inline(always)]
+fn lane_context<B: AesBlocks>() -> B {
+    let mut ctx = [0u8; 16 * MAX_LANES];
+    for (i, lane) in ctx.chunks_exact_mut(16).take(B::LANES).enumerate() {
+        lane[0] = i as u8;
+        lane[1] = (B::LANES - 1) as u8;
+    }
+    B::load(&ctx[..16 * B::LANES])
+}
+
+/// The final block for `Finalize`, in every lane.
+#[inline(always)]
+fn lengths<B: AesBlocks>(ad_len: usize, msg_len: usize) -> B {
+    let mut block = [0u8; 16];
+    block[..8].copy_from_slice(&((ad_len as u64) * 8).to_le_bytes());
+    block[8..].copy_from_slice(&((msg_len as u64) * 8).to_le_bytes());
+    B::splat(&block)
+}
+
+struct Aegis128X<B: AesBlocks> {
+    s: [B; 8],
+}
+
+impl<B: AesBlocks> Aegis128X<B> {
+    /// Bytes absorbed or encrypted per update.
+    const RATE: usize = 32 * B::LANES;
+
+    #[inline(always)]
+    fn new(key: &[u8; 16], nonce: &[u8; 16]) -> Self {
+        let key = B::splat(key);
+        let nonce = B::splat(nonce);
+        let c0 = B::splat(&C0);
+        let c1 = B::splat(&C1);
+        let ctx = lane_context::<B>();
+
+        let mut state = Self {
+            s: [
+                key.xor(nonce),
+                c1,
+                c0,
+                c1,
+                key.xor(nonce),
+                key.xor(c0),
+                key.xor(c1),
+                key.xor(c0),
+            ],
+        };
+
+        for _ in 0..10 {
+            state.s[3] = state.s[3].xor(ctx);
+            state.s[7] = state.s[7].xor(ctx);
+            state.update(nonce, key);
+        }
+        state
+    }
+
+    #[inline(always)]
+    fn update(&mut self, m0: B, m1: B) {
+        let s = &self.s;
+        self.s = [
+            s[7].aes_round(s[0].xor(m0)),
+            s[0].aes_round(s[1]),
+            s[1].aes_round(s[2]),
+            s[2].aes_round(s[3]),
+            s[3].aes_round(s[4].xor(m1)),
+            s[4].aes_round(s[5]),
+            s[5].aes_round(s[6]),
+            s[6].aes_round(s[7]),
+        ];
+    }
+
+    #[inline(always)]
+    fn keystream(&self) -> (B, B) {
+        let s = &self.s;
+        (
+            s[1].xor(s[6]).xor(s[2].and(s[3])),
+            s[2].xor(s[5]).xor(s[6].and(s[7])),
+        )
+    }
+
+    #[inline(always)]
+    fn split(block: &[u8]) -> (B, B) {
+        let (t0, t1) = block.split_at(Self::RATE / 2);
+        (B::load(t0), B::load(t1))
+    }
+
+    #[inline(always)]
+    fn absorb(&mut self, aad: &[u8]) {
+        let mut blocks = aad.chunks_exact(Self::RATE);
+        for block in blocks.by_ref() {
+            let (t0, t1) = Self::split(block);
+            self.update(t0, t1);
+        }
+
+        let last = blocks.remainder();
+        if !last.is_empty() {
+            let mut block = [0u8; 32 * MAX_LANES];
+            block[..last.len()].copy_from_slice(last);
+            let (t0, t1) = Self::split(&block[..Self::RATE]);
+            self.update(t0, t1);
+        }
+    }
+
+    #[inline(always)]
+    fn encrypt(&mut self, inout: &mut [u8]) {
+        let mut blocks = inout.chunks_exact_mut(Self::RATE);
+        for block in blocks.by_ref() {
+            let (z0, z1) = self.keystream();
+            let (t0, t1) = Self::split(block);
+
+            let (out0, out1) = block.split_at_mut(Self::RATE / 2);
+            t0.xor(z0).store(out0);
+            t1.xor(z1).store(out1);
+            self.update(t0, t1);
+        }
+
+        let last = blocks.into_remainder();
+        if !last.is_empty() {
+            let mut block = [0u8; 32 * MAX_LANES];
+            let block = &mut block[..Self::RATE];
+            block[..last.len()].copy_from_slice(last);
+
+            let (z0, z1) = self.keystream();
+            let (t0, t1) = Self::split(block);
+            self.update(t0, t1);
+
+            let (out0, out1) = block.split_at_mut(Self::RATE / 2);
+            t0.xor(z0).store(out0);
+            t1.xor(z1).store(out1);
+            last.copy_from_slice(&block[..last.len()]);
+        }
+    }
+
+    #[inline(always)]
+    fn decrypt(&mut self, inout: &mut [u8]) {
+        let mut blocks = inout.chunks_exact_mut(Self::RATE);
+        for block in blocks.by_ref() {
+            let (z0, z1) = self.keystream();
+            let (t0, t1) = Self::split(block);
+            let (out0, out1) = (t0.xor(z0), t1.xor(z1));
+            self.update(out0, out1);
+
+            let (p0, p1) = block.split_at_mut(Self::RATE / 2);
+            out0.store(p0);
+            out1.store(p1);
+        }
+
+        let last = blocks.into_remainder();
+        if !last.is_empty() {
+            let mut block = [0u8; 32 * MAX_LANES];
+            let block = &mut block[..Self::RATE];
+            block[..last.len()].copy_from_slice(last);
+
+            let (z0, z1) = self.keystream();
+            let (t0, t1) = Self::split(block);
+            let (p0, p1) = block.split_at_mut(Self::RATE / 2);
+            t0.xor(z0).store(p0);
+            t1.xor(z1).store(p1);
+
+            // the plaintext is zero-padded before updating the state
+            last.copy_from_slice(&block[..last.len()]);
+            block[last.len()..].fill(0);
+            let (v0, v1) = Self::split(block);
+            self.update(v0, v1);
+        }
+    }
+
+    #[inline(always)]
+    fn finalize(mut self, ad_len: usize, msg_len: usize, tag_out: &mut [u8]) {
+        let t = self.s[2].xor(lengths(ad_len, msg_len));
+        for _ in 0..7 {
+            self.update(t, t);
+        }
+
+        let s = &self.s;
+        match tag_out.len() {
+            16 => tag_out.copy_from_slice(
+                &s[0]
+                    .xor(s[1])
+                    .xor(s[2])
+                    .xor(s[3])
+                    .xor(s[4])
+                    .xor(s[5])
+                    .xor(s[6])
+                    .fold(),
+            ),
+            32 => {
+                let (t0, t1) = tag_out.split_at_mut(16);
+                t0.copy_from_slice(&s[0].xor(s[1]).xor(s[2]).xor(s[3]).fold());
+                t1.copy_from_slice(&s[4].xor(s[5]).xor(s[6]).xor(s[7]).fold());
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+struct Aegis256<B: AesBlocks> {
+    s: [B; 6],
+}
+
+impl<B: AesBlocks> Aegis256<B> {
+    /// Bytes absorbed or encrypted per update.
+    const RATE: usize = 16 * B::LANES;
+
+    #[inline(always)]
+    fn new(key: &[u8; 32], nonce: &[u8; 32]) -> Self {
+        let k0 = B::splat(key[..16].try_into().unwrap());
+        let k1 = B::splat(key[16..].try_into().unwrap());
+        let n0 = B::splat(nonce[..16].try_into().unwrap());
+        let n1 = B::splat(nonce[16..].try_into().unwrap());
+        let c0 = B::splat(&C0);
+        let c1 = B::splat(&C1);
+        let ctx = lane_context::<B>();
+
+        let mut state = Self {
+            s: [k0.xor(n0), k1.xor(n1), c1, c0, k0.xor(c0), k1.xor(c1)],
+        };
+
+        for _ in 0..4 {
+            for m in [k0, k1, k0.xor(n0), k1.xor(n1)] {
+                state.s[3] = state.s[3].xor(ctx);
+                state.s[5] = state.s[5].xor(ctx);
+                state.update(m);
+            }
+        }
+        state
+    }
+
+    #[inline(always)]
+    fn update(&mut self, m: B) {
+        let s = &self.s;
+        self.s = [
+            s[5].aes_round(s[0].xor(m)),
+            s[0].aes_round(s[1]),
+            s[1].aes_round(s[2]),
+            s[2].aes_round(s[3]),
+            s[3].aes_round(s[4]),
+            s[4].aes_round(s[5]),
+        ];
+    }
+
+    #[inline(always)]
+    fn keystream(&self) -> B {
+        let s = &self.s;
+        s[1].xor(s[4]).xor(s[5]).xor(s[2].and(s[3]))
+    }
+
+    #[inline(always)]
+    fn absorb(&mut self, aad: &[u8]) {
+        let mut blocks = aad.chunks_exact(Self::RATE);
+        for block in blocks.by_ref() {
+            self.update(B::load(block));
+        }
+
+        let last = blocks.remainder();
+        if !last.is_empty() {
+            let mut block = [0u8; 16 * MAX_LANES];
+            block[..last.len()].copy_from_slice(last);
+            self.update(B::load(&block[..Self::RATE]));
+        }
+    }
+
+    #[inline(always)]
+    fn encrypt(&mut self, inout: &mut [u8]) {
+        let mut blocks = inout.chunks_exact_mut(Self::RATE);
+        for block in blocks.by_ref() {
+            let z = self.keystream();
+            let t = B::load(block);
+            self.update(t);
+            t.xor(z).store(block);
+        }
+
+        let last = blocks.into_remainder();
+        if !last.is_empty() {
+            let mut block = [0u8; 16 * MAX_LANES];
+            let block = &mut block[..Self::RATE];
+            block[..last.len()].copy_from_slice(last);
+
+            let z = self.keystream();
+            let t = B::load(block);
+            self.update(t);
+            t.xor(z).store(block);
+            last.copy_from_slice(&block[..last.len()]);
+        }
+    }
+
+    #[inline(always)]
+    fn decrypt(&mut self, inout: &mut [u8]) {
+        let mut blocks = inout.chunks_exact_mut(Self::RATE);
+        for block in blocks.by_ref() {
+            let out = B::load(block).xor(self.keystream());
+            self.update(out);
+            out.store(block);
+        }
+
+        let last = blocks.into_remainder();
+        if !last.is_empty() {
+            let mut block = [0u8; 16 * MAX_LANES];
+            let block = &mut block[..Self::RATE];
+            block[..last.len()].copy_from_slice(last);
+            B::load(block).xor(self.keystream()).store(block);
+
+            // the plaintext is zero-padded before updating the state
+            last.copy_from_slice(&block[..last.len()]);
+            block[last.len()..].fill(0);
+            self.update(B::load(block));
+        }
+    }
+
+    #[inline(always)]
+    fn finalize(mut self, ad_len: usize, msg_len: usize, tag_out: &mut [u8]) {
+        let t = self.s[3].xor(lengths(ad_len, msg_len));
+        for _ in 0..7 {
+            self.update(t);
+        }
+
+        let s = &self.s;
+        match tag_out.len() {
+            16 => tag_out.copy_from_slice(
+                &s[0]
+                    .xor(s[1])
+                    .xor(s[2])
+                    .xor(s[3])
+                    .xor(s[4])
+                    .xor(s[5])
+                    .fold(),
+            ),
+            32 => {
+                let (t0, t1) = tag_out.split_at_mut(16);
+                t0.copy_from_slice(&s[0].xor(s[1]).xor(s[2]).fold());
+                t1.copy_from_slice(&s[3].xor(s[4]).xor(s[5]).fold());
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+/// The Argon2 compression function G, as defined in RFC9106.
+///
+/// This computes `G(prev, reference)` and writes it to `next`, or
+/// when `with_xor` is set, XORs it into the existing contents of `next`
+/// (as required for passes after the first).
+pub(crate) fn argon2_fill_block(
+    prev: &[u64; 128],
+    reference: &[u64; 128],
+    next: &mut [u64; 128],
+    with_xor: bool,
+) {
+    // R = X xor Y
+    let mut r = [0u64; 128];
+    for (r, (x, y)) in r.iter_mut().zip(prev.iter().zip(reference)) {
+        *r = x ^ y;
+    }
+
+    // Q = P(R_0, ..., R_7) applied to the rows
+    let mut z = r;
+    for row in z.chunks_exact_mut(16) {
+        permute(row, |i| i);
+    }
+
+    // Z = P(Q_0, ..., Q_7) applied to the columns
+    for col in 0..8 {
+        permute(&mut z, |i| 2 * col + (i & 1) + (i >> 1) * 16);
+    }
+
+    // G(X, Y) = Z xor R
+    for (next, (z, r)) in next.iter_mut().zip(z.iter().zip(&r)) {
+        *next = if with_xor { *next ^ z ^ r } else { z ^ r };
+    }
+}
+
+/// The permutation P, on the 16 words given by `index`.
+#[inline]
+fn permute(v: &mut [u64], index: impl Fn(usize) -> usize) {
+    let w: [usize; 16] = core::array::from_fn(index);
+    gb(v, w[0], w[4], w[8], w[12]);
+    gb(v, w[1], w[5], w[9], w[13]);
+    gb(v, w[2], w[6], w[10], w[14]);
+    gb(v, w[3], w[7], w[11], w[15]);
+    gb(v, w[0], w[5], w[10], w[15]);
+    gb(v, w[1], w[6], w[11], w[12]);
+    gb(v, w[2], w[7], w[8], w[13]);
+    gb(v, w[3], w[4], w[9], w[14]);
+}
+
+/// BLAKE2b's G, with additions replaced by BlaMka.
+#[inline]
+fn gb(v: &mut [u64], a: usize, b: usize, c: usize, d: usize) {
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// `x + y + 2 * trunc(x) * trunc(y)`, where `trunc` takes the low 32 bits.
+#[inline]
+fn blamka(x: u64, y: u64) -> u64 {
+    let xy = (x & 0xffff_ffff).wrapping_mul(y & 0xffff_ffff);
+    x.wrapping_add(y).wrapping_add(xy.wrapping_mul(2))
+}
@@ -0,0 +1,101 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// The Ascon permutation.
+//
+// Ref. <https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-232.pdf>
+//
+// The state is five 64-bit words.  The 5-bit S-box is applied
+// bit-sliced: word `i` holds bit `i` of each of the 64 S-box inputs,
+// so one pass over the words evaluates all of them at once, in
+// constant time.
+
+/// Applies the last `ROUNDS` rounds of the Ascon permutation to `s`.
+///
+/// Ascon uses 12 rounds (`Ascon-p[12]`) or 8 rounds (`Ascon-p[8]`).
+pub(crate) fn ascon_permute<const ROUNDS: usize>(s: &mut [u64; 5]) {
+    const { assert!(ROUNDS <= 12) };
+
+    let mut x = *s;
+    for c in &ROUND_CONSTANTS[12 - ROUNDS..] {
+        x[2] ^= c;
+        x = substitution(x);
+        x = linear(x);
+    }
+    *s = x;
+}
+
+/// The substitution layer, `p_S`.
+#[inline]
+fn substitution([mut x0, mut x1, mut x2, mut x3, mut x4]: [u64; 5]) -> [u64; 5] {
+    x0 ^= x4;
+    x4 ^= x3;
+    x2 ^= x1;
+
+    let t0 = !x0 & x1;
+    let t1 = !x1 & x2;
+    let t2 = !x2 & x3;
+    let t3 = !x3 & x4;
+    let t4 = !x4 & x0;
+
+    x0 ^= t1;
+    x1 ^= t2;
+    x2 ^= t3;
+    x3 ^= t4;
+    x4 ^= t0;
+
+    x1 ^= x0;
+    x0 ^= x4;
+    x3 ^= x2;
+    x2 = !x2;
+
+    [x0, x1, x2, x3, x4]
+}
+
+/// The linear diffusion layer, `p_L`.
+#[inline]
+fn linear([x0, x1, x2, x3, x4]: [u64; 5]) -> [u64; 5] {
+    [
+        x0 ^ x0.rotate_right(19) ^ x0.rotate_right(28),
+        x1 ^ x1.rotate_right(61) ^ x1.rotate_right(39),
+        x2 ^ x2.rotate_right(1) ^ x2.rotate_right(6),
+        x3 ^ x3.rotate_right(10) ^ x3.rotate_right(17),
+        x4 ^ x4.rotate_right(7) ^ x4.rotate_right(41),
+    ]
+}
+
+const ROUND_CONSTANTS: [u64; 12] = [
+    0xf0, 0xe1, 0xd2, 0xc3, 0xb4, 0xa5, 0x96, 0x87, 0x78, 0x69, 0x5a, 0x4b,
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sbox() {
+        // SP800-232 table 5, where `x0` is the most significant bit
+        const SBOX: [u8; 32] = [
+            0x04, 0x0b, 0x1f, 0x14, 0x1a, 0x15, 0x09, 0x02, 0x1b, 0x05, 0x08, 0x12, 0x1d, 0x03,
+            0x06, 0x1c, 0x1e, 0x13, 0x07, 0x0e, 0x00, 0x0d, 0x11, 0x18, 0x10, 0x0c, 0x01, 0x19,
+            0x16, 0x0a, 0x0f, 0x17,
+        ];
+
+        // put input `i` into bit `i` of the words
+        let mut x = [0u64; 5];
+        for i in 0..32 {
+            for (j, word) in x.iter_mut().enumerate() {
+                *word |= (((i >> (4 - j)) & 1) as u64) << i;
+            }
+        }
+
+        let y = substitution(x);
+        for (i, expected) in SBOX.iter().enumerate() {
+            let actual = y
+                .iter()
+                .fold(0u8, |acc, word| (acc << 1) | ((word >> i) & 1) as u8);
+            assert_eq!(actual, *expected, "sbox({i})");
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! POLYVAL, from [RFC8452](https://datatracker.ietf.org/doc/html/rfc8452).
+//!
+//! POLYVAL is GHASH with its bytes and bits in the opposite order.
+//! Rather than having separate implementations, this uses the
+//! identity from RFC8452 appendix A:
+//!
+//! ```text
+//! POLYVAL(H, X_1, ..., X_n) =
+//!     ByteReverse(GHASH(mulX_GHASH(ByteReverse(H)), ByteReverse(X_1), ..., ByteReverse(X_n)))
+//! ```
+//!
+//! so POLYVAL uses the platform's fast GHASH implementation.
+
+use crate::low::ghash::{Ghash, GhashTable};
+use crate::low::zeroise;
+
+pub(crate) struct PolyvalTable(GhashTable);
+
+impl PolyvalTable {
+    pub(crate) fn new(h: &[u8; 16]) -> Self {
+        Self(GhashTable::new(mul_x(u128::from_le_bytes(*h))))
+    }
+}
+
+pub(crate) struct Polyval<'a>(Ghash<'a>);
+
+impl<'a> Polyval<'a> {
+    pub(crate) fn new(table: &'a PolyvalTable) -> Self {
+        Self(Ghash::new(&table.0))
+    }
+
+    /// Input `bytes` to the computation.
+    ///
+    /// `bytes` is zero-padded, if required.
+    pub(crate) fn add(&mut self, bytes: &[u8]) {
+        // reverse several blocks at a time, so GHASH can use its wide paths.
+        let mut batch = [0u8; 256];
+
+        for chunk in bytes.chunks(batch.len()) {
+            let batch = &mut batch[..chunk.len().next_multiple_of(16)];
+            let (data, padding) = batch.split_at_mut(chunk.len());
+            data.copy_from_slice(chunk);
+            padding.fill(0);
+
+            for block in batch.chunks_exact_mut(16) {
+                block.reverse();
+            }
+            self.0.add(batch);
+        }
+
+        zeroise(&mut batch);
+    }
+
+    pub(crate) fn into_bytes(self) -> [u8; 16] {
+        let mut r = self.0.into_bytes();
+        r.reverse();
+        r
+    }
+}
+
+/// Multiply `a` by x, in GHASH's representation.
+fn mul_x(a: u128) -> u128 {
+    let mask = (a & 1).wrapping_neg();
+    (a >> 1) ^ (mask & 0xe1000000_00000000_00000000_00000000)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc8452_appendix_a() {
+        let table =
+            PolyvalTable::new(b"\x25\x62\x93\x47\x58\x92\x42\x76\x1d\x31\xf8\x26\xba\x4b\x75\x7b");
+        let mut p = Polyval::new(&table);
+        p.add(b"\x4f\x4f\x95\x66\x8c\x83\xdf\xb6\x40\x17\x62\xbb\x2d\x01\xa2\x62");
+        p.add(b"\xd1\xa2\x4d\xdd\x27\x21\xd0\x06\xbb\xe4\x5f\x20\xd3\xc9\xf3\x62");
+        assert_eq!(
+            &p.into_bytes(),
+            b"\xf7\xa3\xb4\x7b\x84\x61\x19\xfa\xe5\xb7\x86\x6c\xf5\xe5\xb7\x7e"
+        );
+    }
+
+    #[test]
+    fn long_input_matches_blockwise() {
+        let table = PolyvalTable::new(&[0x5a; 16]);
+        let input: [u8; 1000] = core::array::from_fn(|i| i as u8);
+
+        let mut whole = Polyval::new(&table);
+        whole.add(&input);
+
+        let mut blockwise = Polyval::new(&table);
+        for block in input.chunks(16) {
+            blockwise.add(block);
+        }
+
+        assert_eq!(whole.into_bytes(), blockwise.into_bytes());
+    }
+}
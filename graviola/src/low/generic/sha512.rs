@@ -104,7 +104,16 @@ pub(crate) fn sha512_compress_blocks(state: &mut [u64; 8], blocks: &[u8]) {
     }
 }
 
-static K: [u64; 80] = [
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+#[cfg(target_arch = "aarch64")]
+pub(crate) fn sha512_compress_lanes(states: &mut [[u64; 8]], blocks: &[&[u8; 128]]) {
+    assert_eq!(states.len(), blocks.len());
+    for (state, block) in states.iter_mut().zip(blocks) {
+        sha512_compress_block(state, *block);
+    }
+}
+
+pub(in crate::low) static K: [u64; 80] = [
     0x428a2f98d728ae22,
     0x7137449123ef65cd,
     0xb5c0fbcfec4d3b2f,
@@ -10,6 +10,16 @@ pub(crate) mod ct;
 pub mod inline_assembly_safety;
 
 mod generic {
+    pub(crate) mod aegis;
+    #[cfg(any(test, target_arch = "aarch64"))]
+    pub(crate) mod argon2;
+    pub(crate) mod ascon;
+    #[cfg(test)]
+    pub(crate) mod blake2b;
+    #[cfg(test)]
+    pub(crate) mod blake2s;
+    #[cfg(test)]
+    pub(crate) mod blake3;
     pub(super) mod blockwise;
     #[cfg(target_arch = "aarch64")]
     pub(crate) mod chacha20;
@@ -20,6 +30,9 @@ mod generic {
     #[cfg(target_arch = "aarch64")]
     pub(crate) mod mlkem;
     pub(crate) mod poly1305;
+    pub(crate) mod polyval;
+    #[cfg(feature = "legacy-sha1")]
+    pub(super) mod sha1;
     #[cfg(target_arch = "x86_64")]
     pub(super) mod sha256;
     pub(super) mod sha512;
@@ -30,11 +43,13 @@ mod entry;
 mod posint;
 
 pub(crate) use entry::Entry;
+pub(crate) use generic::ascon::ascon_permute;
 pub(crate) use generic::blockwise::Blockwise;
 pub(crate) use generic::ct_copy::{ct_copy, ct_select_i16};
 pub(crate) use generic::ct_equal::ct_equal;
 pub(crate) use generic::poly1305;
-pub(crate) use generic::zeroise::{zeroise, zeroise_value};
+pub(crate) use generic::polyval::{Polyval, PolyvalTable};
+pub(crate) use generic::zeroise::{Zeroable, zeroise, zeroise_value};
 pub(crate) use posint::{PosInt, SecretPosInt};
 
 #[cfg(test)]
@@ -47,8 +62,14 @@ cfg_if::cfg_if! {
 
         pub(in crate::low) use x86_64::cpu::{enter_cpu_state, zero_bytes, ct_compare_bytes, leave_cpu_state, verify_cpu_features};
         pub(crate) use x86_64::chacha20;
-        pub(crate) use x86_64::aes::AesKey;
+        pub(crate) use x86_64::aes::{AesDecryptKey, AesKey};
+        pub(crate) use x86_64::blake2b::{argon2_fill_block, blake2b_compress};
+        pub(crate) use x86_64::blake2s::blake2s_compress;
+        pub(crate) use x86_64::blake3::{blake3_compress, blake3_hash_chunks};
         pub(crate) use x86_64::aes_gcm;
+        pub(crate) use x86_64::aegis;
+        pub(crate) use x86_64::aes_ctr;
+        pub(crate) use x86_64::aes_xts;
         pub(crate) use x86_64::bignum_add::bignum_add;
         pub(crate) use x86_64::bignum_add_p256::bignum_add_p256;
         pub(crate) use x86_64::bignum_add_p384::bignum_add_p384;
@@ -119,17 +140,29 @@ cfg_if::cfg_if! {
         pub(crate) use x86_64::p256_montjmixadd::p256_montjmixadd;
         pub(crate) use x86_64::p384_montjadd::p384_montjadd;
         pub(crate) use x86_64::p384_montjdouble::p384_montjdouble;
-        pub(crate) use x86_64::sha256_mux::sha256_compress_blocks;
-        pub(crate) use x86_64::sha512_mux::sha512_compress_blocks;
+        pub(crate) use x86_64::salsa20::salsa20_8_block_mix;
+        #[cfg(feature = "legacy-sha1")]
+        pub(crate) use x86_64::sha1_mux::sha1_compress_blocks;
+        pub(crate) use x86_64::sha256_mux::{sha256_compress_blocks, sha256_compress_lanes};
+        pub(crate) use x86_64::sha512_mux::{sha512_compress_blocks, sha512_compress_lanes};
         pub(crate) use x86_64::sha3_keccak_f1600::sha3_keccak_f1600;
         pub(crate) use x86_64::sha3_keccak4_f1600_shim::sha3_keccak4_f1600;
         pub(crate) use x86_64::sha3_keccak2of4_f1600::sha3_keccak2of4_f1600;
+        pub(crate) use x86_64::sha3_keccak_p1600_12::sha3_keccak_p1600_12;
+        pub(crate) use x86_64::sha3_keccak4_f1600_shim::sha3_keccak4_p1600_12;
+        pub(crate) use x86_64::sha3_keccak2of4_f1600::sha3_keccak2of4_p1600_12;
     } else if #[cfg(target_arch = "aarch64")] {
         mod aarch64;
 
         pub(in crate::low) use aarch64::cpu::{enter_cpu_state, zero_bytes, ct_compare_bytes, leave_cpu_state, verify_cpu_features};
-        pub(crate) use aarch64::aes::AesKey;
+        pub(crate) use aarch64::aes::{AesDecryptKey, AesKey};
         pub(crate) use aarch64::aes_gcm;
+        pub(crate) use aarch64::aegis;
+        pub(crate) use aarch64::aes_ctr;
+        pub(crate) use aarch64::aes_xts;
+        pub(crate) use aarch64::blake2b::blake2b_compress;
+        pub(crate) use aarch64::blake2s::blake2s_compress;
+        pub(crate) use aarch64::blake3::{blake3_compress, blake3_hash_chunks};
         pub(crate) use aarch64::bignum_add::bignum_add;
         pub(crate) use aarch64::bignum_add_p256::bignum_add_p256;
         pub(crate) use aarch64::bignum_add_p384::bignum_add_p384;
@@ -198,13 +231,20 @@ cfg_if::cfg_if! {
         pub(crate) use aarch64::p256_montjmixadd::p256_montjmixadd;
         pub(crate) use aarch64::p384_montjadd::p384_montjadd;
         pub(crate) use aarch64::p384_montjdouble::p384_montjdouble;
-        pub(crate) use aarch64::sha256::sha256_compress_blocks;
+        pub(crate) use aarch64::salsa20::salsa20_8_block_mix;
+        pub(crate) use aarch64::sha256::{sha256_compress_blocks, sha256_compress_lanes};
         pub(crate) use aarch64::sha3_keccak_f1600_mux::sha3_keccak_f1600;
         pub(crate) use aarch64::sha3_keccak4_f1600_mux::sha3_keccak4_f1600;
         pub(crate) use aarch64::sha3_keccak2of4_f1600::sha3_keccak2of4_f1600;
+        pub(crate) use aarch64::sha3_keccak_f1600_mux::sha3_keccak_p1600_12;
+        pub(crate) use aarch64::sha3_keccak4_f1600_mux::sha3_keccak4_p1600_12;
+        pub(crate) use aarch64::sha3_keccak2of4_f1600::sha3_keccak2of4_p1600_12;
 
+        pub(crate) use generic::argon2::argon2_fill_block;
         pub(crate) use generic::chacha20;
-        pub(crate) use generic::sha512::sha512_compress_blocks;
+        #[cfg(feature = "legacy-sha1")]
+        pub(crate) use generic::sha1::sha1_compress_blocks;
+        pub(crate) use generic::sha512::{sha512_compress_blocks, sha512_compress_lanes};
         pub(crate) use generic::mlkem::{mlkem_frombytes, mlkem_unpack};
     } else {
         compile_error!("This crate only supports x86_64 or aarch64");
@@ -49,6 +49,32 @@ fn ct_equal_equiv(a: &[u8], b: &[u8]) {
     assert_eq!(a == b, super::ct_equal(a, b));
 }
 
+#[test]
+fn aes_ctr_apply_keystream_8() {
+    for key_len in [16, 24, 32] {
+        let key = super::AesKey::new(&[0x4b; 32][..key_len]);
+        let counters: [[u8; 16]; 8] =
+            core::array::from_fn(|i| core::array::from_fn(|j| (i * 16 + j) as u8));
+
+        let mut inout = [0u8; 128];
+        for (i, b) in inout.iter_mut().enumerate() {
+            *b = (i * 7) as u8;
+        }
+        let mut expect = inout;
+
+        super::aes_ctr::apply_keystream_8(&key, &counters, &mut inout);
+
+        for (block, counter) in expect.chunks_exact_mut(16).zip(counters) {
+            let mut keystream = counter;
+            key.encrypt_block(&mut keystream);
+            for (b, k) in block.iter_mut().zip(keystream) {
+                *b ^= k;
+            }
+        }
+        assert_eq!(inout, expect);
+    }
+}
+
 mod model {
     pub(super) fn bignum_mux(p: u64, z: &mut [u64], x_if_p: &[u64], y_if_not_p: &[u64]) {
         if p > 0 {
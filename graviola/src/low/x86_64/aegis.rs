@@ -0,0 +1,350 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::x86_64::*;
+
+use super::cpu::{HaveAvx512Vaes, HaveVaes};
+use crate::low::generic::aegis::{self as generic, AesBlocks};
+
+/// Encrypts `cipher_inout` with AEGIS-128L (`D` = 1) or AEGIS-128X<i>D</i>.
+pub(crate) fn aegis128x_encrypt<const D: usize>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    aegis128x::<D, true>(key, nonce, aad, cipher_inout, tag_out)
+}
+
+/// Decrypts `cipher_inout` with AEGIS-128L (`D` = 1) or AEGIS-128X<i>D</i>,
+/// writing the expected tag to `tag_out`.
+pub(crate) fn aegis128x_decrypt<const D: usize>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    aegis128x::<D, false>(key, nonce, aad, cipher_inout, tag_out)
+}
+
+/// Encrypts `cipher_inout` with AEGIS-256.
+pub(crate) fn aegis256_encrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    // SAFETY: this crate requires the `aes` & `avx` cpu features
+    unsafe { _aegis256::<true>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+/// Decrypts `cipher_inout` with AEGIS-256, writing the expected tag
+/// to `tag_out`.
+pub(crate) fn aegis256_decrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    // SAFETY: this crate requires the `aes` & `avx` cpu features
+    unsafe { _aegis256::<false>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+fn aegis128x<const D: usize, const ENC: bool>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    match D {
+        2 => {
+            if let Some(token) = HaveVaes::check() {
+                // SAFETY: the `vaes` cpu feature is checked by `HaveVaes`
+                return unsafe {
+                    _aegis128x2_vaes::<ENC>(key, nonce, aad, cipher_inout, tag_out, token)
+                };
+            }
+        }
+        4 => {
+            if let Some(token) = HaveAvx512Vaes::check() {
+                // SAFETY: the `avx512f` & `vaes` cpu features are checked by `HaveAvx512Vaes`
+                return unsafe {
+                    _aegis128x4_vaes::<ENC>(key, nonce, aad, cipher_inout, tag_out, token)
+                };
+            }
+        }
+        _ => {}
+    }
+
+    // SAFETY: this crate requires the `aes` & `avx` cpu features
+    unsafe { _aegis128x::<Lanes<D>, ENC>(key, nonce, aad, cipher_inout, tag_out) }
+}
+
+#[target_feature(enable = "aes,avx")]
+fn _aegis128x<B: AesBlocks, const ENC: bool>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    if ENC {
+        generic::aegis128x_encrypt::<B>(key, nonce, aad, cipher_inout, tag_out)
+    } else {
+        generic::aegis128x_decrypt::<B>(key, nonce, aad, cipher_inout, tag_out)
+    }
+}
+
+#[target_feature(enable = "aes,avx,avx2,vaes")]
+fn _aegis128x2_vaes<const ENC: bool>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+    _token: HaveVaes,
+) {
+    if ENC {
+        generic::aegis128x_encrypt::<Vaes2>(key, nonce, aad, cipher_inout, tag_out)
+    } else {
+        generic::aegis128x_decrypt::<Vaes2>(key, nonce, aad, cipher_inout, tag_out)
+    }
+}
+
+#[target_feature(enable = "aes,avx,avx2,avx512f,vaes")]
+fn _aegis128x4_vaes<const ENC: bool>(
+    key: &[u8; 16],
+    nonce: &[u8; 16],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+    _token: HaveAvx512Vaes,
+) {
+    if ENC {
+        generic::aegis128x_encrypt::<Vaes4>(key, nonce, aad, cipher_inout, tag_out)
+    } else {
+        generic::aegis128x_decrypt::<Vaes4>(key, nonce, aad, cipher_inout, tag_out)
+    }
+}
+
+#[target_feature(enable = "aes,avx")]
+fn _aegis256<const ENC: bool>(
+    key: &[u8; 32],
+    nonce: &[u8; 32],
+    aad: &[u8],
+    cipher_inout: &mut [u8],
+    tag_out: &mut [u8],
+) {
+    if ENC {
+        generic::aegis256_encrypt::<Lanes<1>>(key, nonce, aad, cipher_inout, tag_out)
+    } else {
+        generic::aegis256_decrypt::<Lanes<1>>(key, nonce, aad, cipher_inout, tag_out)
+    }
+}
+
+/// `D` lanes, each in a separate 128-bit register.
+#[derive(Clone, Copy)]
+struct Lanes<const D: usize>([__m128i; D]);
+
+impl<const D: usize> AesBlocks for Lanes<D> {
+    const LANES: usize = D;
+
+    #[inline(always)]
+    fn load(bytes: &[u8]) -> Self {
+        let bytes = &bytes[..16 * D];
+        // SAFETY: `bytes` is `16 * D` bytes and readable
+        Self(core::array::from_fn(|i| unsafe {
+            _mm_loadu_si128(bytes.as_ptr().add(16 * i).cast())
+        }))
+    }
+
+    #[inline(always)]
+    fn store(self, bytes: &mut [u8]) {
+        let bytes = &mut bytes[..16 * D];
+        for (i, v) in self.0.into_iter().enumerate() {
+            // SAFETY: `bytes` is `16 * D` bytes and writable
+            unsafe { _mm_storeu_si128(bytes.as_mut_ptr().add(16 * i).cast(), v) };
+        }
+    }
+
+    #[inline(always)]
+    fn splat(block: &[u8; 16]) -> Self {
+        // SAFETY: `block` is 16 bytes and readable
+        Self([unsafe { _mm_loadu_si128(block.as_ptr().cast()) }; D])
+    }
+
+    #[inline(always)]
+    fn aes_round(self, round_key: Self) -> Self {
+        // SAFETY: this crate requires the `aes` & `avx` cpu features
+        Self(core::array::from_fn(|i| unsafe {
+            _mm_aesenc_si128(self.0[i], round_key.0[i])
+        }))
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        // SAFETY: this crate requires the `avx` cpu feature
+        Self(core::array::from_fn(|i| unsafe {
+            _mm_xor_si128(self.0[i], other.0[i])
+        }))
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        // SAFETY: this crate requires the `avx` cpu feature
+        Self(core::array::from_fn(|i| unsafe {
+            _mm_and_si128(self.0[i], other.0[i])
+        }))
+    }
+
+    #[inline(always)]
+    fn fold(self) -> [u8; 16] {
+        // SAFETY: this crate requires the `avx` cpu feature
+        let v = self
+            .0
+            .into_iter()
+            .reduce(|a, b| unsafe { _mm_xor_si128(a, b) })
+            .unwrap();
+        let mut out = [0u8; 16];
+        // SAFETY: `out` is 16 bytes and writable
+        unsafe { _mm_storeu_si128(out.as_mut_ptr().cast(), v) };
+        out
+    }
+}
+
+/// Two lanes in a 256-bit register.
+///
+/// These are only used inside `_aegis128x2_vaes`, so the `vaes`
+/// cpu feature is available.
+#[derive(Clone, Copy)]
+struct Vaes2(__m256i);
+
+impl AesBlocks for Vaes2 {
+    const LANES: usize = 2;
+
+    #[inline(always)]
+    fn load(bytes: &[u8]) -> Self {
+        let bytes = &bytes[..32];
+        // SAFETY: `bytes` is 32 bytes and readable
+        Self(unsafe { _mm256_loadu_si256(bytes.as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn store(self, bytes: &mut [u8]) {
+        let bytes = &mut bytes[..32];
+        // SAFETY: `bytes` is 32 bytes and writable
+        unsafe { _mm256_storeu_si256(bytes.as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn splat(block: &[u8; 16]) -> Self {
+        // SAFETY: `block` is 16 bytes and readable, and `avx2` is available
+        Self(unsafe { _mm256_broadcastsi128_si256(_mm_loadu_si128(block.as_ptr().cast())) })
+    }
+
+    #[inline(always)]
+    fn aes_round(self, round_key: Self) -> Self {
+        // SAFETY: `vaes` is available
+        Self(unsafe { _mm256_aesenc_epi128(self.0, round_key.0) })
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        // SAFETY: `avx2` is available
+        Self(unsafe { _mm256_xor_si256(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        // SAFETY: `avx2` is available
+        Self(unsafe { _mm256_and_si256(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn fold(self) -> [u8; 16] {
+        let mut out = [0u8; 16];
+        // SAFETY: `out` is 16 bytes and writable, and `avx2` is available
+        unsafe {
+            let v = _mm_xor_si128(
+                _mm256_castsi256_si128(self.0),
+                _mm256_extracti128_si256::<1>(self.0),
+            );
+            _mm_storeu_si128(out.as_mut_ptr().cast(), v);
+        }
+        out
+    }
+}
+
+/// Four lanes in a 512-bit register.
+///
+/// These are only used inside `_aegis128x4_vaes`, so the `avx512f`
+/// and `vaes` cpu features are available.
+#[derive(Clone, Copy)]
+struct Vaes4(__m512i);
+
+impl AesBlocks for Vaes4 {
+    const LANES: usize = 4;
+
+    #[inline(always)]
+    fn load(bytes: &[u8]) -> Self {
+        let bytes = &bytes[..64];
+        // SAFETY: `bytes` is 64 bytes and readable
+        Self(unsafe { _mm512_loadu_si512(bytes.as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn store(self, bytes: &mut [u8]) {
+        let bytes = &mut bytes[..64];
+        // SAFETY: `bytes` is 64 bytes and writable
+        unsafe { _mm512_storeu_si512(bytes.as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn splat(block: &[u8; 16]) -> Self {
+        // SAFETY: `block` is 16 bytes and readable, and `avx512f` is available
+        Self(unsafe { _mm512_broadcast_i32x4(_mm_loadu_si128(block.as_ptr().cast())) })
+    }
+
+    #[inline(always)]
+    fn aes_round(self, round_key: Self) -> Self {
+        // SAFETY: `avx512f` & `vaes` are available
+        Self(unsafe { _mm512_aesenc_epi128(self.0, round_key.0) })
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        // SAFETY: `avx512f` is available
+        Self(unsafe { _mm512_xor_si512(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        // SAFETY: `avx512f` is available
+        Self(unsafe { _mm512_and_si512(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn fold(self) -> [u8; 16] {
+        let mut out = [0u8; 16];
+        // SAFETY: `out` is 16 bytes and writable, and `avx512f` is available
+        unsafe {
+            let v = _mm_xor_si128(
+                _mm_xor_si128(
+                    _mm512_extracti32x4_epi32::<0>(self.0),
+                    _mm512_extracti32x4_epi32::<1>(self.0),
+                ),
+                _mm_xor_si128(
+                    _mm512_extracti32x4_epi32::<2>(self.0),
+                    _mm512_extracti32x4_epi32::<3>(self.0),
+                ),
+            );
+            _mm_storeu_si128(out.as_mut_ptr().cast(), v);
+        }
+        out
+    }
+}
@@ -11,25 +11,36 @@ use crate::low;
 
 pub(crate) enum AesKey {
     Aes128(AesKey128),
+    Aes192(AesKey192),
     Aes256(AesKey256),
 }
 
 impl AesKey {
     /// Creates an AesKey.
     ///
-    /// `key` must be 16 or 32 bytes in length (AES-192 not supported).
+    /// `key` must be 16, 24 or 32 bytes in length.
     pub(crate) fn new(key: &[u8]) -> Self {
         match key.len() {
             16 => Self::Aes128(AesKey128::new(key.try_into().unwrap())),
+            24 => Self::Aes192(AesKey192::new(key.try_into().unwrap())),
             32 => Self::Aes256(AesKey256::new(key.try_into().unwrap())),
-            24 => panic!("aes-192 not supported"),
             _ => panic!("invalid aes key size"),
         }
     }
 
+    /// The length of the key, in bytes.
+    pub(crate) fn key_len(&self) -> usize {
+        match self {
+            Self::Aes128(_) => 16,
+            Self::Aes192(_) => 24,
+            Self::Aes256(_) => 32,
+        }
+    }
+
     pub(crate) fn encrypt_block(&self, inout: &mut [u8; 16]) {
         match self {
             Self::Aes128(a128) => a128.encrypt_block(inout),
+            Self::Aes192(a192) => a192.encrypt_block(inout),
             Self::Aes256(a256) => a256.encrypt_block(inout),
         }
     }
@@ -41,6 +52,11 @@ impl AesKey {
                 &a128.round_keys[1..10],
                 a128.round_keys[10],
             ),
+            Self::Aes192(a192) => (
+                a192.round_keys[0],
+                &a192.round_keys[1..12],
+                a192.round_keys[12],
+            ),
             Self::Aes256(a256) => (
                 a256.round_keys[0],
                 &a256.round_keys[1..14],
@@ -67,6 +83,23 @@ impl AesKey {
                 _mm512_broadcast_i32x4(*j),
                 _mm512_broadcast_i32x4(*k),
             ]),
+            Self::Aes192(AesKey192 {
+                round_keys: [a, b, c, d, e, f, g, h, i, j, k, l, m],
+            }) => RoundKeys512::Aes192([
+                _mm512_broadcast_i32x4(*a),
+                _mm512_broadcast_i32x4(*b),
+                _mm512_broadcast_i32x4(*c),
+                _mm512_broadcast_i32x4(*d),
+                _mm512_broadcast_i32x4(*e),
+                _mm512_broadcast_i32x4(*f),
+                _mm512_broadcast_i32x4(*g),
+                _mm512_broadcast_i32x4(*h),
+                _mm512_broadcast_i32x4(*i),
+                _mm512_broadcast_i32x4(*j),
+                _mm512_broadcast_i32x4(*k),
+                _mm512_broadcast_i32x4(*l),
+                _mm512_broadcast_i32x4(*m),
+            ]),
             Self::Aes256(AesKey256 {
                 round_keys: [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o],
             }) => RoundKeys512::Aes256([
@@ -98,9 +131,59 @@ impl Drop for AesKey {
 
 impl low::generic::zeroise::Zeroable for AesKey {}
 
-#[expect(clippy::large_enum_variant)] // 200 bytes is a bad heuristic for large enums
+/// Round keys for decryption.
+///
+/// These are for the "equivalent inverse cipher" of FIPS-197 section 5.3.5,
+/// which is what `aesdec` implements.
+pub(crate) enum AesDecryptKey {
+    Aes128([__m128i; 10 + 1]),
+    Aes192([__m128i; 12 + 1]),
+    Aes256([__m128i; 14 + 1]),
+}
+
+impl AesDecryptKey {
+    pub(crate) fn new(key: &AesKey) -> Self {
+        // SAFETY: this crate requires the `aes` & `avx` cpu features
+        unsafe {
+            match key {
+                AesKey::Aes128(a128) => Self::Aes128(inverse_round_keys(&a128.round_keys)),
+                AesKey::Aes192(a192) => Self::Aes192(inverse_round_keys(&a192.round_keys)),
+                AesKey::Aes256(a256) => Self::Aes256(inverse_round_keys(&a256.round_keys)),
+            }
+        }
+    }
+
+    pub(crate) fn decrypt_block(&self, inout: &mut [u8; 16]) {
+        // SAFETY: this crate requires the `aes` & `avx` cpu features
+        unsafe {
+            match self {
+                Self::Aes128(rk) => decrypt_block(rk, inout),
+                Self::Aes192(rk) => decrypt_block(rk, inout),
+                Self::Aes256(rk) => decrypt_block(rk, inout),
+            }
+        }
+    }
+
+    pub(crate) fn round_keys(&self) -> (__m128i, &[__m128i], __m128i) {
+        match self {
+            Self::Aes128(rk) => (rk[0], &rk[1..10], rk[10]),
+            Self::Aes192(rk) => (rk[0], &rk[1..12], rk[12]),
+            Self::Aes256(rk) => (rk[0], &rk[1..14], rk[14]),
+        }
+    }
+}
+
+impl Drop for AesDecryptKey {
+    fn drop(&mut self) {
+        low::zeroise_value(self);
+    }
+}
+
+impl low::generic::zeroise::Zeroable for AesDecryptKey {}
+
 pub(crate) enum RoundKeys512 {
     Aes128([__m512i; 11]),
+    Aes192([__m512i; 13]),
     Aes256([__m512i; 15]),
 }
 
@@ -108,6 +191,7 @@ impl RoundKeys512 {
     pub(crate) fn split(&self) -> (__m512i, &[__m512i], __m512i) {
         match self {
             Self::Aes128(rks) => (rks[0], &rks[1..10], rks[10]),
+            Self::Aes192(rks) => (rks[0], &rks[1..12], rks[12]),
             Self::Aes256(rks) => (rks[0], &rks[1..14], rks[14]),
         }
     }
@@ -140,6 +224,28 @@ fn zero() -> __m128i {
     unsafe { _mm_setzero_si128() }
 }
 
+pub(crate) struct AesKey192 {
+    round_keys: [__m128i; 12 + 1],
+}
+
+impl AesKey192 {
+    pub(crate) fn new(key: &[u8; 24]) -> Self {
+        let mut round_keys = [zero(); 12 + 1];
+
+        // SAFETY: this crate requires the `aes` & `avx` cpu features
+        unsafe {
+            aes192_expand(key, &mut round_keys);
+        }
+
+        Self { round_keys }
+    }
+
+    pub(crate) fn encrypt_block(&self, inout: &mut [u8; 16]) {
+        // SAFETY: this crate requires the `aes` & `avx` cpu features
+        unsafe { aes192_block(&self.round_keys, inout) }
+    }
+}
+
 pub(crate) struct AesKey256 {
     round_keys: [__m128i; 14 + 1],
 }
@@ -205,6 +311,86 @@ fn aes128_expand(key: &[u8; 16], out: &mut [__m128i; 11]) {
     expand_128!(0x36, t1, out[10]);
 }
 
+macro_rules! expand_192 {
+    (Last, $rcon:literal, $t1:ident, $t3:ident) => {
+        // t1 holds the previous four words, and the low half of t3
+        // the two after that.  this produces the next four words.
+        let t2 = _mm_aeskeygenassist_si128($t3, $rcon);
+        let t2 = _mm_shuffle_epi32(t2, 0b01_01_01_01); // choose RotWord(SubWord(X1)) term
+
+        let t4 = _mm_slli_si128($t1, 0x4);
+        $t1 = _mm_xor_si128($t1, t4);
+
+        let t4 = _mm_slli_si128(t4, 0x4);
+        $t1 = _mm_xor_si128($t1, t4);
+
+        let t4 = _mm_slli_si128(t4, 0x4);
+        $t1 = _mm_xor_si128($t1, t4);
+        $t1 = _mm_xor_si128($t1, t2);
+    };
+    ($rcon:literal, $t1:ident, $t3:ident) => {
+        // as above, then the two words after that.
+        expand_192!(Last, $rcon, $t1, $t3);
+
+        let t2 = _mm_shuffle_epi32($t1, 0b11_11_11_11);
+        let t4 = _mm_slli_si128($t3, 0x4);
+        $t3 = _mm_xor_si128($t3, t4);
+        $t3 = _mm_xor_si128($t3, t2);
+    };
+}
+
+/// `_mm_shuffle_pd` on integer vectors: the result's low half is the low
+/// (`$imm` bit 0 clear) or high (bit 0 set) half of `$a`, and its high half
+/// is likewise chosen from `$b` by bit 1.
+macro_rules! shuffle_halves {
+    ($a:expr, $b:expr, $imm:literal) => {
+        _mm_castpd_si128(_mm_shuffle_pd(
+            _mm_castsi128_pd($a),
+            _mm_castsi128_pd($b),
+            $imm,
+        ))
+    };
+}
+
+#[target_feature(enable = "aes,avx")]
+fn aes192_expand(key: &[u8; 24], out: &mut [__m128i; 13]) {
+    // SAFETY: `key` is 24 bytes and readable
+    let (mut t1, mut t3) = unsafe {
+        (
+            _mm_lddqu_si128(key.as_ptr().cast()),
+            _mm_loadl_epi64(key[16..].as_ptr().cast()),
+        )
+    };
+
+    // each expansion makes 1.5 round keys, so alternate between
+    // storing whole and split results.
+    out[0] = t1;
+    out[1] = t3;
+    expand_192!(0x01, t1, t3);
+    out[1] = shuffle_halves!(out[1], t1, 0b00);
+    out[2] = shuffle_halves!(t1, t3, 0b01);
+    expand_192!(0x02, t1, t3);
+    out[3] = t1;
+    out[4] = t3;
+    expand_192!(0x04, t1, t3);
+    out[4] = shuffle_halves!(out[4], t1, 0b00);
+    out[5] = shuffle_halves!(t1, t3, 0b01);
+    expand_192!(0x08, t1, t3);
+    out[6] = t1;
+    out[7] = t3;
+    expand_192!(0x10, t1, t3);
+    out[7] = shuffle_halves!(out[7], t1, 0b00);
+    out[8] = shuffle_halves!(t1, t3, 0b01);
+    expand_192!(0x20, t1, t3);
+    out[9] = t1;
+    out[10] = t3;
+    expand_192!(0x40, t1, t3);
+    out[10] = shuffle_halves!(out[10], t1, 0b00);
+    out[11] = shuffle_halves!(t1, t3, 0b01);
+    expand_192!(Last, 0x80, t1, t3);
+    out[12] = t1;
+}
+
 macro_rules! expand_256 {
     (Odd, $rcon:literal, $t1:ident, $t3:ident, $out:expr) => {
         let t2 = _mm_aeskeygenassist_si128($t3, $rcon);
@@ -289,6 +475,27 @@ fn aes128_block(round_keys: &[__m128i; 11], block_inout: &mut [u8; 16]) {
     unsafe { _mm_storeu_si128(block_inout.as_mut_ptr().cast(), block) };
 }
 
+#[target_feature(enable = "aes,avx")]
+fn aes192_block(round_keys: &[__m128i; 13], block_inout: &mut [u8; 16]) {
+    // SAFETY: `block_inout` is 16 bytes and readable
+    let block = unsafe { _mm_lddqu_si128(block_inout.as_ptr().cast()) };
+    let block = _mm_xor_si128(block, round_keys[0]);
+    let block = _mm_aesenc_si128(block, round_keys[1]);
+    let block = _mm_aesenc_si128(block, round_keys[2]);
+    let block = _mm_aesenc_si128(block, round_keys[3]);
+    let block = _mm_aesenc_si128(block, round_keys[4]);
+    let block = _mm_aesenc_si128(block, round_keys[5]);
+    let block = _mm_aesenc_si128(block, round_keys[6]);
+    let block = _mm_aesenc_si128(block, round_keys[7]);
+    let block = _mm_aesenc_si128(block, round_keys[8]);
+    let block = _mm_aesenc_si128(block, round_keys[9]);
+    let block = _mm_aesenc_si128(block, round_keys[10]);
+    let block = _mm_aesenc_si128(block, round_keys[11]);
+    let block = _mm_aesenclast_si128(block, round_keys[12]);
+    // SAFETY: `block_inout` is 16 bytes and writeable
+    unsafe { _mm_storeu_si128(block_inout.as_mut_ptr().cast(), block) };
+}
+
 #[target_feature(enable = "aes,avx")]
 fn aes256_block(round_keys: &[__m128i; 15], block_inout: &mut [u8; 16]) {
     // SAFETY: `block_inout` is 16 bytes and readable
@@ -312,6 +519,34 @@ fn aes256_block(round_keys: &[__m128i; 15], block_inout: &mut [u8; 16]) {
     unsafe { _mm_storeu_si128(block_inout.as_mut_ptr().cast(), block) };
 }
 
+#[target_feature(enable = "aes,avx")]
+fn inverse_round_keys<const N: usize>(round_keys: &[__m128i; N]) -> [__m128i; N] {
+    // reverse the order, and apply InvMixColumns to all but the
+    // first and last
+    let mut r = *round_keys;
+    r.reverse();
+    for rk in r[1..N - 1].iter_mut() {
+        *rk = _mm_aesimc_si128(*rk);
+    }
+    r
+}
+
+#[target_feature(enable = "aes,avx")]
+fn decrypt_block(round_keys: &[__m128i], block_inout: &mut [u8; 16]) {
+    let (first, rest) = round_keys.split_first().unwrap();
+    let (last, middle) = rest.split_last().unwrap();
+
+    // SAFETY: `block_inout` is 16 bytes and readable
+    let mut block = unsafe { _mm_lddqu_si128(block_inout.as_ptr().cast()) };
+    block = _mm_xor_si128(block, *first);
+    for rk in middle {
+        block = _mm_aesdec_si128(block, *rk);
+    }
+    block = _mm_aesdeclast_si128(block, *last);
+    // SAFETY: `block_inout` is 16 bytes and writeable
+    unsafe { _mm_storeu_si128(block_inout.as_mut_ptr().cast(), block) };
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -335,7 +570,7 @@ mod tests {
     #[cfg(panic = "unwind")]
     fn test_construct() {
         // The generic AesKey::new wrapper should create the proper subtype of key when called
-        // with a 128- or 256-bit value and should fail with any other argument length.
+        // with a 128-, 192- or 256-bit value and should fail with any other argument length.
         let input: [u8; 33] = [
             0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
             0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b,
@@ -346,10 +581,12 @@ mod tests {
             match panic::catch_unwind(|| AesKey::new(&input[..len])) {
                 Ok(key) => match key {
                     AesKey::Aes128(_) => assert_eq!(num_bits, 128),
+                    AesKey::Aes192(_) => assert_eq!(num_bits, 192),
                     AesKey::Aes256(_) => assert_eq!(num_bits, 256),
                 },
                 Err(_) => {
                     assert_ne!(num_bits, 128);
+                    assert_ne!(num_bits, 192);
                     assert_ne!(num_bits, 256);
                 }
             }
@@ -382,6 +619,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_key_expansion_192() {
+        let context = AesKey192::new(&[
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90,
+            0x79, 0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ]);
+
+        let expected = [
+            0x8e73b0f7_da0e6452_c810f32b_809079e5,
+            0x62f8ead2_522c6b7b_fe0c91f7_2402f5a5,
+            0xec12068e_6c827f6b_0e7a95b9_5c56fec2,
+            0x4db7b4bd_69b54118_85a74796_e92538fd,
+            0xe75fad44_bb095386_485af057_21efb14f,
+            0xa448f6d9_4d6dce24_aa326360_113b30e6,
+            0xa25e7ed5_83b1cf9a_27f93943_6a94f767,
+            0xc0a69407_d19da4e1_ec1786eb_6fa64971,
+            0x485f7032_22cb8755_e26d1352_33f0b7b3,
+            0x40beeb28_2f18a259_6747d26b_458c553e,
+            0xa7e1466c_9411f1df_821f750a_ad07d753,
+            0xca400538_8fcc5006_282d166a_bc3ce7b5,
+            0xe98ba06f_448c773c_8ecc7204_01002202,
+        ];
+
+        for (i, expect) in expected.into_iter().enumerate() {
+            assert_eq!(to_u128(context.round_keys[i]).swap_bytes(), expect);
+        }
+    }
+
     #[test]
     fn test_key_expansion_256() {
         let context = AesKey256::new(&[
@@ -433,6 +698,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_block_192() {
+        let context = AesKey192::new(&[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        ]);
+        let mut block = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        context.encrypt_block(&mut block);
+        assert_eq!(
+            block,
+            [
+                0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d,
+                0x71, 0x91
+            ]
+        );
+    }
+
     #[test]
     fn test_block_256() {
         let context = AesKey256::new(&[
@@ -453,4 +738,44 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_decrypt_block() {
+        // FIPS-197 appendix C.1 - C.3, inverted
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        for (key_len, ciphertext) in [
+            (
+                16,
+                [
+                    0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70,
+                    0xb4, 0xc5, 0x5a,
+                ],
+            ),
+            (
+                24,
+                [
+                    0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec,
+                    0x0d, 0x71, 0x91,
+                ],
+            ),
+            (
+                32,
+                [
+                    0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b,
+                    0x49, 0x60, 0x89,
+                ],
+            ),
+        ] {
+            let context = AesDecryptKey::new(&AesKey::new(&key[..key_len]));
+            let mut block = ciphertext;
+            context.decrypt_block(&mut block);
+            assert_eq!(
+                block,
+                [
+                    0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc,
+                    0xdd, 0xee, 0xff
+                ]
+            );
+        }
+    }
 }
@@ -0,0 +1,86 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::x86_64::*;
+
+use super::aes::AesKey;
+
+/// XORs the encryption of each of `counters` into the corresponding
+/// block of `inout`.
+///
+/// The counter blocks are formed by the caller, so this serves any CTR
+/// mode regardless of how its counter is incremented.
+pub(crate) fn apply_keystream_8(key: &AesKey, counters: &[[u8; 16]; 8], inout: &mut [u8; 128]) {
+    let (rk_first, rks, rk_last) = key.round_keys();
+    // SAFETY: this crate requires the `aes` & `avx` cpu features
+    unsafe { _apply_keystream_8(rk_first, rks, rk_last, counters, inout) }
+}
+
+#[target_feature(enable = "aes,avx")]
+fn _apply_keystream_8(
+    rk_first: __m128i,
+    rks: &[__m128i],
+    rk_last: __m128i,
+    counters: &[[u8; 16]; 8],
+    inout: &mut [u8; 128],
+) {
+    // SAFETY: each of `counters` is 16 bytes and readable
+    let (mut b1, mut b2, mut b3, mut b4, mut b5, mut b6, mut b7, mut b8) = unsafe {
+        (
+            _mm_xor_si128(_mm_loadu_si128(counters[0].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[1].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[2].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[3].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[4].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[5].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[6].as_ptr().cast()), rk_first),
+            _mm_xor_si128(_mm_loadu_si128(counters[7].as_ptr().cast()), rk_first),
+        )
+    };
+
+    for rk in rks {
+        b1 = _mm_aesenc_si128(b1, *rk);
+        b2 = _mm_aesenc_si128(b2, *rk);
+        b3 = _mm_aesenc_si128(b3, *rk);
+        b4 = _mm_aesenc_si128(b4, *rk);
+        b5 = _mm_aesenc_si128(b5, *rk);
+        b6 = _mm_aesenc_si128(b6, *rk);
+        b7 = _mm_aesenc_si128(b7, *rk);
+        b8 = _mm_aesenc_si128(b8, *rk);
+    }
+
+    let b1 = _mm_aesenclast_si128(b1, rk_last);
+    let b2 = _mm_aesenclast_si128(b2, rk_last);
+    let b3 = _mm_aesenclast_si128(b3, rk_last);
+    let b4 = _mm_aesenclast_si128(b4, rk_last);
+    let b5 = _mm_aesenclast_si128(b5, rk_last);
+    let b6 = _mm_aesenclast_si128(b6, rk_last);
+    let b7 = _mm_aesenclast_si128(b7, rk_last);
+    let b8 = _mm_aesenclast_si128(b8, rk_last);
+
+    // SAFETY: `inout` is 128 bytes and readable
+    let (p1, p2, p3, p4, p5, p6, p7, p8) = unsafe {
+        (
+            _mm_loadu_si128(inout.as_ptr().add(0).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(16).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(32).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(48).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(64).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(80).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(96).cast()),
+            _mm_loadu_si128(inout.as_ptr().add(112).cast()),
+        )
+    };
+
+    // SAFETY: `inout` is 128 bytes and writable
+    unsafe {
+        _mm_storeu_si128(inout.as_mut_ptr().add(0).cast(), _mm_xor_si128(p1, b1));
+        _mm_storeu_si128(inout.as_mut_ptr().add(16).cast(), _mm_xor_si128(p2, b2));
+        _mm_storeu_si128(inout.as_mut_ptr().add(32).cast(), _mm_xor_si128(p3, b3));
+        _mm_storeu_si128(inout.as_mut_ptr().add(48).cast(), _mm_xor_si128(p4, b4));
+        _mm_storeu_si128(inout.as_mut_ptr().add(64).cast(), _mm_xor_si128(p5, b5));
+        _mm_storeu_si128(inout.as_mut_ptr().add(80).cast(), _mm_xor_si128(p6, b6));
+        _mm_storeu_si128(inout.as_mut_ptr().add(96).cast(), _mm_xor_si128(p7, b7));
+        _mm_storeu_si128(inout.as_mut_ptr().add(112).cast(), _mm_xor_si128(p8, b8));
+    }
+}
@@ -419,7 +419,7 @@ impl Counter512 {
     #[inline]
     fn next4(&mut self) -> __m512i {
         let r = _mm512_shuffle_epi8(self.0, BYTESWAP_512_EPI64);
-        self.0 = _mm512_add_epi64(self.0, COUNTER_512_4);
+        self.0 = _mm512_add_epi32(self.0, COUNTER_512_4);
         r
     }
 }
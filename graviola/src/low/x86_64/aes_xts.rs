@@ -0,0 +1,188 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::x86_64::*;
+
+use super::aes::{AesDecryptKey, AesKey};
+
+/// Encrypts whole blocks of `inout` in XTS mode.
+///
+/// `tweak` is the encrypted tweak for the first block, and on
+/// exit is the tweak for the block after the last.
+pub(crate) fn encrypt(key: &AesKey, tweak: &mut [u8; 16], inout: &mut [u8]) {
+    let (rk_first, rks, rk_last) = key.round_keys();
+    // SAFETY: this crate requires the `aes` & `avx` cpu features
+    unsafe { _xts::<true>(rk_first, rks, rk_last, tweak, inout) }
+}
+
+/// Decrypts whole blocks of `inout` in XTS mode.
+///
+/// `tweak` is as for [`encrypt`].
+pub(crate) fn decrypt(key: &AesDecryptKey, tweak: &mut [u8; 16], inout: &mut [u8]) {
+    let (rk_first, rks, rk_last) = key.round_keys();
+    // SAFETY: this crate requires the `aes` & `avx` cpu features
+    unsafe { _xts::<false>(rk_first, rks, rk_last, tweak, inout) }
+}
+
+#[target_feature(enable = "aes,avx")]
+fn _xts<const ENC: bool>(
+    rk_first: __m128i,
+    rks: &[__m128i],
+    rk_last: __m128i,
+    tweak: &mut [u8; 16],
+    inout: &mut [u8],
+) {
+    debug_assert!(inout.len().is_multiple_of(16));
+
+    // SAFETY: `tweak` is 16 bytes and readable
+    let mut t = unsafe { _mm_lddqu_si128(tweak.as_ptr().cast()) };
+    let mut by8_iter = inout.chunks_exact_mut(128);
+
+    for blocks in by8_iter.by_ref() {
+        // prefetch to avoid any stall later
+        super::cpu::prefetch(blocks, 64);
+
+        let t1 = t;
+        let t2 = mul_alpha(t1);
+        let t3 = mul_alpha(t2);
+        let t4 = mul_alpha(t3);
+        let t5 = mul_alpha(t4);
+        let t6 = mul_alpha(t5);
+        let t7 = mul_alpha(t6);
+        let t8 = mul_alpha(t7);
+        t = mul_alpha(t8);
+
+        // SAFETY: `blocks` is 128 bytes and readable
+        let (p1, p2, p3, p4, p5, p6, p7, p8) = unsafe {
+            (
+                _mm_loadu_si128(blocks.as_ptr().add(0).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(16).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(32).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(48).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(64).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(80).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(96).cast()),
+                _mm_loadu_si128(blocks.as_ptr().add(112).cast()),
+            )
+        };
+
+        let mut b1 = _mm_xor_si128(_mm_xor_si128(p1, t1), rk_first);
+        let mut b2 = _mm_xor_si128(_mm_xor_si128(p2, t2), rk_first);
+        let mut b3 = _mm_xor_si128(_mm_xor_si128(p3, t3), rk_first);
+        let mut b4 = _mm_xor_si128(_mm_xor_si128(p4, t4), rk_first);
+        let mut b5 = _mm_xor_si128(_mm_xor_si128(p5, t5), rk_first);
+        let mut b6 = _mm_xor_si128(_mm_xor_si128(p6, t6), rk_first);
+        let mut b7 = _mm_xor_si128(_mm_xor_si128(p7, t7), rk_first);
+        let mut b8 = _mm_xor_si128(_mm_xor_si128(p8, t8), rk_first);
+
+        for rk in rks {
+            b1 = round::<ENC>(b1, *rk);
+            b2 = round::<ENC>(b2, *rk);
+            b3 = round::<ENC>(b3, *rk);
+            b4 = round::<ENC>(b4, *rk);
+            b5 = round::<ENC>(b5, *rk);
+            b6 = round::<ENC>(b6, *rk);
+            b7 = round::<ENC>(b7, *rk);
+            b8 = round::<ENC>(b8, *rk);
+        }
+
+        let b1 = _mm_xor_si128(last_round::<ENC>(b1, rk_last), t1);
+        let b2 = _mm_xor_si128(last_round::<ENC>(b2, rk_last), t2);
+        let b3 = _mm_xor_si128(last_round::<ENC>(b3, rk_last), t3);
+        let b4 = _mm_xor_si128(last_round::<ENC>(b4, rk_last), t4);
+        let b5 = _mm_xor_si128(last_round::<ENC>(b5, rk_last), t5);
+        let b6 = _mm_xor_si128(last_round::<ENC>(b6, rk_last), t6);
+        let b7 = _mm_xor_si128(last_round::<ENC>(b7, rk_last), t7);
+        let b8 = _mm_xor_si128(last_round::<ENC>(b8, rk_last), t8);
+
+        // SAFETY: `blocks` is 128 bytes and writable, due to `chunks_exact_mut`
+        unsafe {
+            _mm_storeu_si128(blocks.as_mut_ptr().add(0).cast(), b1);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(16).cast(), b2);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(32).cast(), b3);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(48).cast(), b4);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(64).cast(), b5);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(80).cast(), b6);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(96).cast(), b7);
+            _mm_storeu_si128(blocks.as_mut_ptr().add(112).cast(), b8);
+        }
+    }
+
+    for block in by8_iter.into_remainder().chunks_exact_mut(16) {
+        // SAFETY: `block` is 16 bytes and readable
+        let p = unsafe { _mm_loadu_si128(block.as_ptr().cast()) };
+
+        let mut b = _mm_xor_si128(_mm_xor_si128(p, t), rk_first);
+        for rk in rks {
+            b = round::<ENC>(b, *rk);
+        }
+        let b = _mm_xor_si128(last_round::<ENC>(b, rk_last), t);
+
+        // SAFETY: `block` is 16 bytes and writable, due to `chunks_exact_mut`
+        unsafe { _mm_storeu_si128(block.as_mut_ptr().cast(), b) };
+        t = mul_alpha(t);
+    }
+
+    // SAFETY: `tweak` is 16 bytes and writable
+    unsafe { _mm_storeu_si128(tweak.as_mut_ptr().cast(), t) };
+}
+
+#[target_feature(enable = "aes,avx")]
+#[inline]
+fn round<const ENC: bool>(block: __m128i, rk: __m128i) -> __m128i {
+    if ENC {
+        _mm_aesenc_si128(block, rk)
+    } else {
+        _mm_aesdec_si128(block, rk)
+    }
+}
+
+#[target_feature(enable = "aes,avx")]
+#[inline]
+fn last_round<const ENC: bool>(block: __m128i, rk: __m128i) -> __m128i {
+    if ENC {
+        _mm_aesenclast_si128(block, rk)
+    } else {
+        _mm_aesdeclast_si128(block, rk)
+    }
+}
+
+/// Multiplies the tweak by `x`, as a little-endian element of GF(2<sup>128</sup>).
+#[target_feature(enable = "avx")]
+#[inline]
+fn mul_alpha(t: __m128i) -> __m128i {
+    // each 64-bit half is shifted left by one.  the top bit of the low
+    // half carries into the high half, and the top bit of the high half
+    // is reduced into the low half.
+    let carries = _mm_shuffle_epi32(_mm_srai_epi32(t, 31), 0b00_01_00_11);
+    let carries = _mm_and_si128(carries, _mm_set_epi32(0, 1, 0, 0x87));
+    _mm_xor_si128(_mm_add_epi64(t, t), carries)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_alpha() {
+        for t in [
+            0u128,
+            1,
+            1 << 63,
+            1 << 64,
+            1 << 127,
+            u128::MAX,
+            0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+        ] {
+            let expected = (t << 1) ^ if t >> 127 == 1 { 0x87 } else { 0 };
+            let mut actual = [0u8; 16];
+            // SAFETY: this crate requires the `avx` cpu feature
+            unsafe {
+                let v = _mm_lddqu_si128(t.to_le_bytes().as_ptr().cast());
+                _mm_storeu_si128(actual.as_mut_ptr().cast(), mul_alpha(v));
+            }
+            assert_eq!(u128::from_le_bytes(actual), expected);
+        }
+    }
+}
@@ -0,0 +1,336 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// BLAKE2b compression, and the BLAKE2b-derived Argon2 compression
+// function, using AVX2.
+//
+// The 16-word state is held as four rows of four words, so the
+// column step of a round operates on whole vectors.  The diagonal
+// step is done by rotating the lanes of rows 1, 2 and 3.
+//
+// refs:
+// - https://datatracker.ietf.org/doc/html/rfc7693
+// - https://datatracker.ietf.org/doc/html/rfc9106
+
+use core::arch::x86_64::*;
+
+/// The BLAKE2b compression function F, as defined in RFC7693.
+///
+/// `counter` is the total number of bytes processed, including
+/// those in `block`.  `last` is set for the final block.
+pub(crate) fn blake2b_compress(h: &mut [u64; 8], block: &[u8; 128], counter: u128, last: bool) {
+    // SAFETY: this crate requires the `avx2` cpu feature
+    unsafe { compress(h, block, counter, last) }
+}
+
+/// The Argon2 compression function G, as defined in RFC9106.
+///
+/// This computes `G(prev, reference)` and writes it to `next`, or
+/// when `with_xor` is set, XORs it into the existing contents of `next`
+/// (as required for passes after the first).
+pub(crate) fn argon2_fill_block(
+    prev: &[u64; 128],
+    reference: &[u64; 128],
+    next: &mut [u64; 128],
+    with_xor: bool,
+) {
+    // SAFETY: this crate requires the `avx2` cpu feature
+    unsafe { fill_block(prev, reference, next, with_xor) }
+}
+
+#[target_feature(enable = "avx2")]
+fn compress(h: &mut [u64; 8], block: &[u8; 128], counter: u128, last: bool) {
+    let mut m = [0u64; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *m = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let h0 = load(&h[0..4]);
+    let h1 = load(&h[4..8]);
+
+    let mut a = h0;
+    let mut b = h1;
+    let mut c = _mm256_setr_epi64x(IV[0] as i64, IV[1] as i64, IV[2] as i64, IV[3] as i64);
+    let mut d = _mm256_setr_epi64x(
+        (IV[4] ^ counter as u64) as i64,
+        (IV[5] ^ (counter >> 64) as u64) as i64,
+        (IV[6] ^ if last { !0 } else { 0 }) as i64,
+        IV[7] as i64,
+    );
+
+    for s in SIGMA.iter().cycle().take(ROUNDS) {
+        let x = gather(&m, s[0], s[2], s[4], s[6]);
+        let y = gather(&m, s[1], s[3], s[5], s[7]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+        diagonalize(&mut b, &mut c, &mut d);
+
+        let x = gather(&m, s[8], s[10], s[12], s[14]);
+        let y = gather(&m, s[9], s[11], s[13], s[15]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+        undiagonalize(&mut b, &mut c, &mut d);
+    }
+
+    store(&mut h[0..4], _mm256_xor_si256(h0, _mm256_xor_si256(a, c)));
+    store(&mut h[4..8], _mm256_xor_si256(h1, _mm256_xor_si256(b, d)));
+}
+
+#[target_feature(enable = "avx2")]
+fn fill_block(prev: &[u64; 128], reference: &[u64; 128], next: &mut [u64; 128], with_xor: bool) {
+    // R = X xor Y
+    let mut r = [0u64; 128];
+    for i in (0..128).step_by(4) {
+        store(
+            &mut r[i..i + 4],
+            _mm256_xor_si256(load(&prev[i..i + 4]), load(&reference[i..i + 4])),
+        );
+    }
+
+    // Q = P(R_0, ..., R_7) applied to the rows.  Each row is 16
+    // contiguous words, so this is four whole vectors.
+    let mut z = r;
+    for row in z.chunks_exact_mut(16) {
+        let mut a = load(&row[0..4]);
+        let mut b = load(&row[4..8]);
+        let mut c = load(&row[8..12]);
+        let mut d = load(&row[12..16]);
+        permute(&mut a, &mut b, &mut c, &mut d);
+        store(&mut row[0..4], a);
+        store(&mut row[4..8], b);
+        store(&mut row[8..12], c);
+        store(&mut row[12..16], d);
+    }
+
+    // Z = P(Q_0, ..., Q_7) applied to the columns.  Each column
+    // is eight pairs of words, spaced 16 words apart.
+    for col in (0..16).step_by(2) {
+        let mut a = load_pairs(&z, col, col + 16);
+        let mut b = load_pairs(&z, col + 32, col + 48);
+        let mut c = load_pairs(&z, col + 64, col + 80);
+        let mut d = load_pairs(&z, col + 96, col + 112);
+        permute(&mut a, &mut b, &mut c, &mut d);
+        store_pairs(&mut z, col, col + 16, a);
+        store_pairs(&mut z, col + 32, col + 48, b);
+        store_pairs(&mut z, col + 64, col + 80, c);
+        store_pairs(&mut z, col + 96, col + 112, d);
+    }
+
+    // G(X, Y) = Z xor R
+    for i in (0..128).step_by(4) {
+        let mut out = _mm256_xor_si256(load(&z[i..i + 4]), load(&r[i..i + 4]));
+        if with_xor {
+            out = _mm256_xor_si256(out, load(&next[i..i + 4]));
+        }
+        store(&mut next[i..i + 4], out);
+    }
+}
+
+/// BLAKE2b's G, on four columns at once.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn g(a: &mut __m256i, b: &mut __m256i, c: &mut __m256i, d: &mut __m256i, x: __m256i, y: __m256i) {
+    *a = _mm256_add_epi64(_mm256_add_epi64(*a, *b), x);
+    *d = rotr32(_mm256_xor_si256(*d, *a));
+    *c = _mm256_add_epi64(*c, *d);
+    *b = rotr24(_mm256_xor_si256(*b, *c));
+    *a = _mm256_add_epi64(_mm256_add_epi64(*a, *b), y);
+    *d = rotr16(_mm256_xor_si256(*d, *a));
+    *c = _mm256_add_epi64(*c, *d);
+    *b = rotr63(_mm256_xor_si256(*b, *c));
+}
+
+/// The Argon2 permutation P: a BLAKE2b round with no message
+/// words, and additions replaced by BlaMka.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn permute(a: &mut __m256i, b: &mut __m256i, c: &mut __m256i, d: &mut __m256i) {
+    gb(a, b, c, d);
+    diagonalize(b, c, d);
+    gb(a, b, c, d);
+    undiagonalize(b, c, d);
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn gb(a: &mut __m256i, b: &mut __m256i, c: &mut __m256i, d: &mut __m256i) {
+    *a = blamka(*a, *b);
+    *d = rotr32(_mm256_xor_si256(*d, *a));
+    *c = blamka(*c, *d);
+    *b = rotr24(_mm256_xor_si256(*b, *c));
+    *a = blamka(*a, *b);
+    *d = rotr16(_mm256_xor_si256(*d, *a));
+    *c = blamka(*c, *d);
+    *b = rotr63(_mm256_xor_si256(*b, *c));
+}
+
+/// `x + y + 2 * trunc(x) * trunc(y)`, where `trunc` takes the low 32 bits.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn blamka(x: __m256i, y: __m256i) -> __m256i {
+    let xy = _mm256_mul_epu32(x, y);
+    _mm256_add_epi64(_mm256_add_epi64(x, y), _mm256_add_epi64(xy, xy))
+}
+
+/// Rotate rows so the diagonals lie in columns.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn diagonalize(b: &mut __m256i, c: &mut __m256i, d: &mut __m256i) {
+    *b = _mm256_permute4x64_epi64(*b, 0b00_11_10_01);
+    *c = _mm256_permute4x64_epi64(*c, 0b01_00_11_10);
+    *d = _mm256_permute4x64_epi64(*d, 0b10_01_00_11);
+}
+
+/// Inverse of `diagonalize()`.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn undiagonalize(b: &mut __m256i, c: &mut __m256i, d: &mut __m256i) {
+    *b = _mm256_permute4x64_epi64(*b, 0b10_01_00_11);
+    *c = _mm256_permute4x64_epi64(*c, 0b01_00_11_10);
+    *d = _mm256_permute4x64_epi64(*d, 0b00_11_10_01);
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr32(x: __m256i) -> __m256i {
+    _mm256_shuffle_epi32(x, 0b10_11_00_01)
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr24(x: __m256i) -> __m256i {
+    // this is a byte shuffle rightwards, except little-endian
+    _mm256_shuffle_epi8(
+        x,
+        _mm256_setr_epi8(
+            3, 4, 5, 6, 7, 0, 1, 2, 11, 12, 13, 14, 15, 8, 9, 10, 3, 4, 5, 6, 7, 0, 1, 2, 11, 12,
+            13, 14, 15, 8, 9, 10,
+        ),
+    )
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr16(x: __m256i) -> __m256i {
+    _mm256_shuffle_epi8(
+        x,
+        _mm256_setr_epi8(
+            2, 3, 4, 5, 6, 7, 0, 1, 10, 11, 12, 13, 14, 15, 8, 9, 2, 3, 4, 5, 6, 7, 0, 1, 10, 11,
+            12, 13, 14, 15, 8, 9,
+        ),
+    )
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr63(x: __m256i) -> __m256i {
+    _mm256_or_si256(_mm256_srli_epi64(x, 63), _mm256_add_epi64(x, x))
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn gather(m: &[u64; 16], i0: usize, i1: usize, i2: usize, i3: usize) -> __m256i {
+    _mm256_setr_epi64x(m[i0] as i64, m[i1] as i64, m[i2] as i64, m[i3] as i64)
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn load(x: &[u64]) -> __m256i {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 32 bytes and readable
+    unsafe { _mm256_loadu_si256(x.as_ptr().cast()) }
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn store(x: &mut [u64], v: __m256i) {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 32 bytes and writable
+    unsafe { _mm256_storeu_si256(x.as_mut_ptr().cast(), v) }
+}
+
+/// Load words `lo..lo + 2` and `hi..hi + 2` of `x` into one vector.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn load_pairs(x: &[u64; 128], lo: usize, hi: usize) -> __m256i {
+    let lo = &x[lo..lo + 2];
+    let hi = &x[hi..hi + 2];
+    // SAFETY: `lo` and `hi` are each 16 bytes and readable
+    unsafe { _mm256_loadu2_m128i(hi.as_ptr().cast(), lo.as_ptr().cast()) }
+}
+
+/// Inverse of `load_pairs()`.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn store_pairs(x: &mut [u64; 128], lo: usize, hi: usize, v: __m256i) {
+    let (first, second) = x.split_at_mut(hi);
+    let lo = &mut first[lo..lo + 2];
+    let hi = &mut second[..2];
+    // SAFETY: `lo` and `hi` are each 16 bytes and writable
+    unsafe { _mm256_storeu2_m128i(hi.as_mut_ptr().cast(), lo.as_mut_ptr().cast(), v) }
+}
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const ROUNDS: usize = 12;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut block = [0u8; 128];
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+
+        for (counter, last) in [(128, false), (200, true), (u128::MAX - 5, true)] {
+            let mut h = [0x0123456789abcdef; 8];
+            let mut h_generic = h;
+            blake2b_compress(&mut h, &block, counter, last);
+            generic::blake2b::blake2b_compress(&mut h_generic, &block, counter, last);
+            assert_eq!(h, h_generic);
+        }
+    }
+
+    #[test]
+    fn fill_block_matches_generic() {
+        let mut prev = [0u64; 128];
+        let mut reference = [0u64; 128];
+        for i in 0..128 {
+            prev[i] = (i as u64).wrapping_mul(0x9e3779b97f4a7c15);
+            reference[i] = !(i as u64).wrapping_mul(0xc2b2ae3d27d4eb4f);
+        }
+
+        for with_xor in [false, true] {
+            let mut next = [0x5555_5555_aaaa_aaaa; 128];
+            let mut next_generic = next;
+            argon2_fill_block(&prev, &reference, &mut next, with_xor);
+            generic::argon2::argon2_fill_block(&prev, &reference, &mut next_generic, with_xor);
+            assert_eq!(next, next_generic);
+        }
+    }
+}
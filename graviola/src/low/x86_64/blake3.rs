@@ -0,0 +1,392 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// BLAKE3 compression, using AVX2.
+//
+// Single compressions hold the state as four rows of four words, as
+// for BLAKE2s.  Whole chunks are hashed eight at a time, with each
+// vector holding one state word from each of eight chunks: there
+// the message words are transposed on loading.
+//
+// refs:
+// - https://github.com/BLAKE3-team/BLAKE3-specs/blob/master/blake3.pdf
+
+use core::arch::x86_64::*;
+
+/// The BLAKE3 compression function.
+///
+/// This returns the whole 16-word output; the first 8 words are the
+/// new chaining value.
+pub(crate) fn blake3_compress(
+    cv: &[u32; 8],
+    block: &[u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    // SAFETY: this crate requires the `avx2` cpu feature
+    unsafe { compress(cv, block, counter, block_len, flags) }
+}
+
+/// Compute the chaining values of whole chunks.
+///
+/// `chunks[i]` has chunk counter `counter + i`, and its chaining
+/// value is written to `cvs[i]`.  `flags` are the domain flags
+/// (eg. `KEYED_HASH`): `CHUNK_START` and `CHUNK_END` are added here.
+pub(crate) fn blake3_hash_chunks(
+    key: &[u32; 8],
+    chunks: &[[u8; 1024]],
+    counter: u64,
+    flags: u32,
+    cvs: &mut [[u32; 8]],
+) {
+    assert_eq!(chunks.len(), cvs.len());
+    let (chunks8, chunks) = chunks.as_chunks::<8>();
+    let (cvs8, cvs) = cvs.as_chunks_mut::<8>();
+
+    let mut counter = counter;
+    for (chunks, cvs) in chunks8.iter().zip(cvs8.iter_mut()) {
+        // SAFETY: this crate requires the `avx2` cpu feature
+        unsafe { hash8(key, chunks, counter, flags, cvs) };
+        counter += 8;
+    }
+
+    for (chunk, cv) in chunks.iter().zip(cvs.iter_mut()) {
+        *cv = *key;
+        let (blocks, _) = chunk.as_chunks::<64>();
+        for (j, block) in blocks.iter().enumerate() {
+            let out = blake3_compress(cv, block, counter, 64, flags | block_flags(j));
+            cv.copy_from_slice(&out[..8]);
+        }
+        counter += 1;
+    }
+}
+
+#[target_feature(enable = "avx2")]
+fn compress(
+    cv: &[u32; 8],
+    block: &[u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut m = [0u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let cv0 = load(&cv[0..4]);
+    let cv1 = load(&cv[4..8]);
+
+    let mut a = cv0;
+    let mut b = cv1;
+    let mut c = load(&IV[0..4]);
+    let mut d = _mm_setr_epi32(
+        counter as i32,
+        (counter >> 32) as i32,
+        block_len as i32,
+        flags as i32,
+    );
+
+    for s in SCHEDULE.iter() {
+        let x = gather(&m, s[0], s[2], s[4], s[6]);
+        let y = gather(&m, s[1], s[3], s[5], s[7]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // diagonalize
+        b = _mm_shuffle_epi32(b, 0b00_11_10_01);
+        c = _mm_shuffle_epi32(c, 0b01_00_11_10);
+        d = _mm_shuffle_epi32(d, 0b10_01_00_11);
+
+        let x = gather(&m, s[8], s[10], s[12], s[14]);
+        let y = gather(&m, s[9], s[11], s[13], s[15]);
+        g(&mut a, &mut b, &mut c, &mut d, x, y);
+
+        // undiagonalize
+        b = _mm_shuffle_epi32(b, 0b10_01_00_11);
+        c = _mm_shuffle_epi32(c, 0b01_00_11_10);
+        d = _mm_shuffle_epi32(d, 0b00_11_10_01);
+    }
+
+    let mut out = [0u32; 16];
+    store(&mut out[0..4], _mm_xor_si128(a, c));
+    store(&mut out[4..8], _mm_xor_si128(b, d));
+    store(&mut out[8..12], _mm_xor_si128(c, cv0));
+    store(&mut out[12..16], _mm_xor_si128(d, cv1));
+    out
+}
+
+/// BLAKE3's G, on four columns at once.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn g(a: &mut __m128i, b: &mut __m128i, c: &mut __m128i, d: &mut __m128i, x: __m128i, y: __m128i) {
+    *a = _mm_add_epi32(_mm_add_epi32(*a, *b), x);
+    *d = _mm_shuffle_epi8(_mm_xor_si128(*d, *a), rotr16_128());
+    *c = _mm_add_epi32(*c, *d);
+    *b = _mm_xor_si128(*b, *c);
+    *b = _mm_or_si128(_mm_srli_epi32(*b, 12), _mm_slli_epi32(*b, 20));
+    *a = _mm_add_epi32(_mm_add_epi32(*a, *b), y);
+    *d = _mm_shuffle_epi8(_mm_xor_si128(*d, *a), rotr8_128());
+    *c = _mm_add_epi32(*c, *d);
+    *b = _mm_xor_si128(*b, *c);
+    *b = _mm_or_si128(_mm_srli_epi32(*b, 7), _mm_slli_epi32(*b, 25));
+}
+
+/// Hash eight whole chunks in parallel.
+#[target_feature(enable = "avx2")]
+fn hash8(
+    key: &[u32; 8],
+    chunks: &[[u8; 1024]; 8],
+    counter: u64,
+    flags: u32,
+    cvs: &mut [[u32; 8]; 8],
+) {
+    let mut h = [_mm256_setzero_si256(); 8];
+    for (h, key) in h.iter_mut().zip(key) {
+        *h = _mm256_set1_epi32(*key as i32);
+    }
+
+    let mut counter_lo = [0u32; 8];
+    let mut counter_hi = [0u32; 8];
+    for (i, (lo, hi)) in counter_lo.iter_mut().zip(counter_hi.iter_mut()).enumerate() {
+        let counter = counter + i as u64;
+        *lo = counter as u32;
+        *hi = (counter >> 32) as u32;
+    }
+    let counter_lo = load8(&counter_lo);
+    let counter_hi = load8(&counter_hi);
+
+    for block in 0..16 {
+        let m = transpose_block(chunks, block);
+
+        let mut v = [_mm256_setzero_si256(); 16];
+        v[..8].copy_from_slice(&h);
+        v[8] = _mm256_set1_epi32(IV[0] as i32);
+        v[9] = _mm256_set1_epi32(IV[1] as i32);
+        v[10] = _mm256_set1_epi32(IV[2] as i32);
+        v[11] = _mm256_set1_epi32(IV[3] as i32);
+        v[12] = counter_lo;
+        v[13] = counter_hi;
+        v[14] = _mm256_set1_epi32(64);
+        v[15] = _mm256_set1_epi32((flags | block_flags(block)) as i32);
+
+        for s in SCHEDULE.iter() {
+            g8(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            g8(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            g8(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            g8(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            g8(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            g8(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            g8(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            g8(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+            h[i] = _mm256_xor_si256(v[i], v[i + 8]);
+        }
+    }
+
+    for (cv, h) in cvs.iter_mut().zip(transpose8x8(h)) {
+        store8(cv, h);
+    }
+}
+
+/// BLAKE3's G, on one word of eight states.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn g8(v: &mut [__m256i; 16], a: usize, b: usize, c: usize, d: usize, x: __m256i, y: __m256i) {
+    v[a] = _mm256_add_epi32(_mm256_add_epi32(v[a], v[b]), x);
+    v[d] = _mm256_shuffle_epi8(_mm256_xor_si256(v[d], v[a]), rotr16_256());
+    v[c] = _mm256_add_epi32(v[c], v[d]);
+    v[b] = _mm256_xor_si256(v[b], v[c]);
+    v[b] = _mm256_or_si256(_mm256_srli_epi32(v[b], 12), _mm256_slli_epi32(v[b], 20));
+    v[a] = _mm256_add_epi32(_mm256_add_epi32(v[a], v[b]), y);
+    v[d] = _mm256_shuffle_epi8(_mm256_xor_si256(v[d], v[a]), rotr8_256());
+    v[c] = _mm256_add_epi32(v[c], v[d]);
+    v[b] = _mm256_xor_si256(v[b], v[c]);
+    v[b] = _mm256_or_si256(_mm256_srli_epi32(v[b], 7), _mm256_slli_epi32(v[b], 25));
+}
+
+/// Load message block `block` of each chunk, so that vector `i` of
+/// the result holds word `i` of each.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn transpose_block(chunks: &[[u8; 1024]; 8], block: usize) -> [__m256i; 16] {
+    let mut m = [_mm256_setzero_si256(); 16];
+    for half in 0..2 {
+        let mut rows = [_mm256_setzero_si256(); 8];
+        for (row, chunk) in rows.iter_mut().zip(chunks) {
+            let offset = block * 64 + half * 32;
+            // SAFETY: `chunk[offset..offset + 32]` is in bounds and readable
+            *row = unsafe { _mm256_loadu_si256(chunk[offset..offset + 32].as_ptr().cast()) };
+        }
+        m[half * 8..half * 8 + 8].copy_from_slice(&transpose8x8(rows));
+    }
+    m
+}
+
+/// Transpose an 8x8 matrix of words.
+#[target_feature(enable = "avx2")]
+#[inline]
+pub(super) fn transpose8x8(x: [__m256i; 8]) -> [__m256i; 8] {
+    // interleave pairs of rows: ab_0145 is a0 b0 a1 b1 | a4 b4 a5 b5
+    let ab_0145 = _mm256_unpacklo_epi32(x[0], x[1]);
+    let ab_2367 = _mm256_unpackhi_epi32(x[0], x[1]);
+    let cd_0145 = _mm256_unpacklo_epi32(x[2], x[3]);
+    let cd_2367 = _mm256_unpackhi_epi32(x[2], x[3]);
+    let ef_0145 = _mm256_unpacklo_epi32(x[4], x[5]);
+    let ef_2367 = _mm256_unpackhi_epi32(x[4], x[5]);
+    let gh_0145 = _mm256_unpacklo_epi32(x[6], x[7]);
+    let gh_2367 = _mm256_unpackhi_epi32(x[6], x[7]);
+
+    // then pairs of pairs: abcd_04 is a0 b0 c0 d0 | a4 b4 c4 d4
+    let abcd_04 = _mm256_unpacklo_epi64(ab_0145, cd_0145);
+    let abcd_15 = _mm256_unpackhi_epi64(ab_0145, cd_0145);
+    let abcd_26 = _mm256_unpacklo_epi64(ab_2367, cd_2367);
+    let abcd_37 = _mm256_unpackhi_epi64(ab_2367, cd_2367);
+    let efgh_04 = _mm256_unpacklo_epi64(ef_0145, gh_0145);
+    let efgh_15 = _mm256_unpackhi_epi64(ef_0145, gh_0145);
+    let efgh_26 = _mm256_unpacklo_epi64(ef_2367, gh_2367);
+    let efgh_37 = _mm256_unpackhi_epi64(ef_2367, gh_2367);
+
+    // then swap 128-bit halves
+    [
+        _mm256_permute2x128_si256(abcd_04, efgh_04, 0x20),
+        _mm256_permute2x128_si256(abcd_15, efgh_15, 0x20),
+        _mm256_permute2x128_si256(abcd_26, efgh_26, 0x20),
+        _mm256_permute2x128_si256(abcd_37, efgh_37, 0x20),
+        _mm256_permute2x128_si256(abcd_04, efgh_04, 0x31),
+        _mm256_permute2x128_si256(abcd_15, efgh_15, 0x31),
+        _mm256_permute2x128_si256(abcd_26, efgh_26, 0x31),
+        _mm256_permute2x128_si256(abcd_37, efgh_37, 0x31),
+    ]
+}
+
+/// `CHUNK_START` for the first block of a chunk, and `CHUNK_END` for the last.
+fn block_flags(block: usize) -> u32 {
+    match block {
+        0 => CHUNK_START,
+        15 => CHUNK_END,
+        _ => 0,
+    }
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr16_128() -> __m128i {
+    _mm_setr_epi8(2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13)
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr8_128() -> __m128i {
+    _mm_setr_epi8(1, 2, 3, 0, 5, 6, 7, 4, 9, 10, 11, 8, 13, 14, 15, 12)
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr16_256() -> __m256i {
+    _mm256_broadcastsi128_si256(rotr16_128())
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn rotr8_256() -> __m256i {
+    _mm256_broadcastsi128_si256(rotr8_128())
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn gather(m: &[u32; 16], i0: usize, i1: usize, i2: usize, i3: usize) -> __m128i {
+    _mm_setr_epi32(m[i0] as i32, m[i1] as i32, m[i2] as i32, m[i3] as i32)
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn load(x: &[u32]) -> __m128i {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 16 bytes and readable
+    unsafe { _mm_loadu_si128(x.as_ptr().cast()) }
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn store(x: &mut [u32], v: __m128i) {
+    assert_eq!(x.len(), 4);
+    // SAFETY: `x` is 16 bytes and writable
+    unsafe { _mm_storeu_si128(x.as_mut_ptr().cast(), v) }
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn load8(x: &[u32; 8]) -> __m256i {
+    // SAFETY: `x` is 32 bytes and readable
+    unsafe { _mm256_loadu_si256(x.as_ptr().cast()) }
+}
+
+#[target_feature(enable = "avx2")]
+#[inline]
+fn store8(x: &mut [u32; 8], v: __m256i) {
+    // SAFETY: `x` is 32 bytes and writable
+    unsafe { _mm256_storeu_si256(x.as_mut_ptr().cast(), v) }
+}
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SCHEDULE: [[usize; 16]; 7] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut block = [0u8; 64];
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let cv = [0x01234567, 0x89abcdef, 1, 2, 3, 4, 5, 6];
+
+        for (counter, block_len, flags) in [(0, 64, 0b1011), (5, 13, 0b10), (u64::MAX - 5, 1, 0)] {
+            assert_eq!(
+                blake3_compress(&cv, &block, counter, block_len, flags),
+                generic::blake3::blake3_compress(&cv, &block, counter, block_len, flags)
+            );
+        }
+    }
+
+    #[test]
+    fn hash_chunks_matches_generic() {
+        let mut chunks = vec![[0u8; 1024]; 19];
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            for (j, b) in chunk.iter_mut().enumerate() {
+                *b = (i * 7 + j) as u8;
+            }
+        }
+        let key = [0x01234567, 0x89abcdef, 1, 2, 3, 4, 5, 6];
+
+        // nb. the counter carries into the top word part-way through
+        for (counter, flags) in [(0, 0), (0xffff_fffc, 1 << 4)] {
+            let mut cvs = vec![[0u32; 8]; 19];
+            let mut cvs_generic = cvs.clone();
+            blake3_hash_chunks(&key, &chunks, counter, flags, &mut cvs);
+            generic::blake3::blake3_hash_chunks(&key, &chunks, counter, flags, &mut cvs_generic);
+            assert_eq!(cvs, cvs_generic);
+        }
+    }
+}
@@ -179,13 +179,43 @@ impl HaveAvx512ForAesGcm {
     }
 }
 
-/// Token type reflecting the check for CPU features needed for SHA256 using SHA-NI
+/// Token type reflecting the check for CPU features needed for 256-bit VAES
 ///
 /// A value of this type is proof that the CPU dynamic feature check has happened.
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct HaveSha256(());
+pub(crate) struct HaveVaes(());
 
-impl HaveSha256 {
+impl HaveVaes {
+    pub(crate) fn check() -> Option<Self> {
+        match have_cpu_feature!("vaes") {
+            true => Some(Self(())),
+            false => None,
+        }
+    }
+}
+
+/// Token type reflecting the check for CPU features needed for 512-bit VAES
+///
+/// A value of this type is proof that the CPU dynamic feature check has happened.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HaveAvx512Vaes(());
+
+impl HaveAvx512Vaes {
+    pub(crate) fn check() -> Option<Self> {
+        match have_cpu_feature!("avx512f") && have_cpu_feature!("vaes") {
+            true => Some(Self(())),
+            false => None,
+        }
+    }
+}
+
+/// Token type reflecting the check for CPU features needed for SHA1 and SHA256 using SHA-NI
+///
+/// A value of this type is proof that the CPU dynamic feature check has happened.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HaveShaNi(());
+
+impl HaveShaNi {
     pub(crate) fn check() -> Option<Self> {
         match have_cpu_feature!("sha") {
             true => Some(Self(())),
@@ -0,0 +1,121 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// Salsa20/8 and scrypt's BlockMix, using the SIMD arrangement from
+// Colin Percival's SSE2 scrypt implementation.
+//
+// refs:
+// - https://datatracker.ietf.org/doc/html/rfc7914
+// - https://github.com/Tarsnap/scrypt/blob/master/libcperciva/crypto/crypto_scrypt_smix_sse2.c
+
+use core::arch::x86_64::*;
+
+/// BlockMix<sub>Salsa20/8, r</sub>, as defined in RFC7914.
+///
+/// `input` and `output` are `2 * r` blocks.
+///
+/// Blocks are in "SIMD order": position `i` of a block contains
+/// word `5 * i % 16` of the block in standard order.  This
+/// means the diagonals of the Salsa20 state are in the same
+/// lane across the four vectors.
+pub(crate) fn salsa20_8_block_mix(input: &[[u32; 16]], output: &mut [[u32; 16]]) {
+    // SAFETY: this crate requires the `avx` cpu feature
+    unsafe { block_mix(input, output) }
+}
+
+#[target_feature(enable = "avx")]
+fn block_mix(input: &[[u32; 16]], output: &mut [[u32; 16]]) {
+    debug_assert_eq!(input.len(), output.len());
+    debug_assert!(input.len().is_multiple_of(2));
+    let r = input.len() / 2;
+
+    // 1. X = B[2 * r - 1]
+    let mut x = load(&input[2 * r - 1]);
+
+    // 2. for i = 0 to 2 * r - 1 do
+    //      T = X xor B[i]
+    //      X = Salsa (T)
+    //      Y[i] = X
+    //    end for
+    // 3. B' = (Y[0], Y[2], ..., Y[2 * r - 2],
+    //          Y[1], Y[3], ..., Y[2 * r - 1])
+    for (i, block) in input.iter().enumerate() {
+        let b = load(block);
+        x = salsa20_8([
+            _mm_xor_si128(x[0], b[0]),
+            _mm_xor_si128(x[1], b[1]),
+            _mm_xor_si128(x[2], b[2]),
+            _mm_xor_si128(x[3], b[3]),
+        ]);
+        store(&mut output[(i / 2) + (i & 1) * r], x);
+    }
+}
+
+macro_rules! quarter {
+    ($t:ident, $a:ident, $b:ident, $c:ident, $rot:literal) => {
+        let $t = _mm_add_epi32($b, $c);
+        $a = _mm_xor_si128($a, _mm_slli_epi32($t, $rot));
+        $a = _mm_xor_si128($a, _mm_srli_epi32($t, 32 - $rot));
+    };
+}
+
+#[target_feature(enable = "avx")]
+#[inline]
+fn salsa20_8(b: [__m128i; 4]) -> [__m128i; 4] {
+    let [mut x0, mut x1, mut x2, mut x3] = b;
+
+    for _ in 0..4 {
+        // columns
+        quarter!(t, x1, x0, x3, 7);
+        quarter!(t, x2, x1, x0, 9);
+        quarter!(t, x3, x2, x1, 13);
+        quarter!(t, x0, x3, x2, 18);
+
+        x1 = _mm_shuffle_epi32(x1, 0b10_01_00_11);
+        x2 = _mm_shuffle_epi32(x2, 0b01_00_11_10);
+        x3 = _mm_shuffle_epi32(x3, 0b00_11_10_01);
+
+        // rows
+        quarter!(t, x3, x0, x1, 7);
+        quarter!(t, x2, x3, x0, 9);
+        quarter!(t, x1, x2, x3, 13);
+        quarter!(t, x0, x1, x2, 18);
+
+        x1 = _mm_shuffle_epi32(x1, 0b00_11_10_01);
+        x2 = _mm_shuffle_epi32(x2, 0b01_00_11_10);
+        x3 = _mm_shuffle_epi32(x3, 0b10_01_00_11);
+    }
+
+    [
+        _mm_add_epi32(b[0], x0),
+        _mm_add_epi32(b[1], x1),
+        _mm_add_epi32(b[2], x2),
+        _mm_add_epi32(b[3], x3),
+    ]
+}
+
+#[target_feature(enable = "avx")]
+#[inline]
+fn load(block: &[u32; 16]) -> [__m128i; 4] {
+    // SAFETY: `block` is 64 bytes and readable
+    unsafe {
+        [
+            _mm_loadu_si128(block[0..4].as_ptr().cast()),
+            _mm_loadu_si128(block[4..8].as_ptr().cast()),
+            _mm_loadu_si128(block[8..12].as_ptr().cast()),
+            _mm_loadu_si128(block[12..16].as_ptr().cast()),
+        ]
+    }
+}
+
+#[target_feature(enable = "avx")]
+#[inline]
+fn store(block: &mut [u32; 16], x: [__m128i; 4]) {
+    // SAFETY: `block` is 64 bytes and writable
+    unsafe {
+        _mm_storeu_si128(block[0..4].as_mut_ptr().cast(), x[0]);
+        _mm_storeu_si128(block[4..8].as_mut_ptr().cast(), x[1]);
+        _mm_storeu_si128(block[8..12].as_mut_ptr().cast(), x[2]);
+        _mm_storeu_si128(block[12..16].as_mut_ptr().cast(), x[3]);
+    }
+}
@@ -0,0 +1,104 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use core::arch::x86_64::*;
+
+pub(in crate::low) fn sha1_compress_blocks_shaext(
+    state: &mut [u32; 5],
+    blocks: &[u8],
+    _token: super::cpu::HaveShaNi,
+) {
+    debug_assert!(blocks.len().is_multiple_of(64));
+    // SAFETY: `_token` proves the caller checked the `sha` feature;
+    // this crate requires the `sse4.1` and `ssse3` features
+    unsafe { sha1(state, blocks) }
+}
+
+#[target_feature(enable = "sha,sse4.1,ssse3")]
+fn sha1(state: &mut [u32; 5], blocks: &[u8]) {
+    let big_endian_shuffle = _mm_set_epi64x(0x0001020304050607, 0x08090a0b0c0d0e0f);
+
+    // SAFETY: `state` is 5 32-bit words and readable
+    let abcd = unsafe { _mm_loadu_si128(state[0..4].as_ptr().cast()) };
+    let mut abcd = _mm_shuffle_epi32(abcd, 0b00_01_10_11);
+    let mut e0 = _mm_set_epi32(state[4] as i32, 0, 0, 0);
+
+    for block in blocks.chunks_exact(64) {
+        let abcd_save = abcd;
+        let e0_save = e0;
+
+        // SAFETY: `block` is 64 bytes and readable
+        let mut msg = unsafe {
+            [
+                _mm_loadu_si128(block[0..16].as_ptr().cast()),
+                _mm_loadu_si128(block[16..32].as_ptr().cast()),
+                _mm_loadu_si128(block[32..48].as_ptr().cast()),
+                _mm_loadu_si128(block[48..64].as_ptr().cast()),
+            ]
+        };
+        for m in msg.iter_mut() {
+            *m = _mm_shuffle_epi8(*m, big_endian_shuffle);
+        }
+
+        // each iteration is four rounds, using `msg[i % 4]`, which
+        // holds W[4i..4i+4].
+        let mut e = _mm_add_epi32(e0, msg[0]);
+        let mut abcd_prev = abcd;
+        for i in 0..20 {
+            if i >= 4 {
+                msg[i % 4] = _mm_sha1msg2_epu32(
+                    _mm_xor_si128(
+                        _mm_sha1msg1_epu32(msg[i % 4], msg[(i + 1) % 4]),
+                        msg[(i + 2) % 4],
+                    ),
+                    msg[(i + 3) % 4],
+                );
+            }
+            if i > 0 {
+                e = _mm_sha1nexte_epu32(abcd_prev, msg[i % 4]);
+            }
+            abcd_prev = abcd;
+            abcd = match i / 5 {
+                0 => _mm_sha1rnds4_epu32(abcd, e, 0),
+                1 => _mm_sha1rnds4_epu32(abcd, e, 1),
+                2 => _mm_sha1rnds4_epu32(abcd, e, 2),
+                _ => _mm_sha1rnds4_epu32(abcd, e, 3),
+            };
+        }
+
+        e0 = _mm_sha1nexte_epu32(abcd_prev, e0_save);
+        abcd = _mm_add_epi32(abcd, abcd_save);
+    }
+
+    let abcd = _mm_shuffle_epi32(abcd, 0b00_01_10_11);
+    // SAFETY: `state` is 5 32-bit words and writable
+    unsafe { _mm_storeu_si128(state[0..4].as_mut_ptr().cast(), abcd) };
+    state[4] = _mm_extract_epi32(e0, 3) as u32;
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let Some(token) = super::super::cpu::HaveShaNi::check() else {
+            return;
+        };
+
+        let mut blocks = [0u8; 64 * 5];
+        for (i, b) in blocks.iter_mut().enumerate() {
+            *b = (i * 7 + 3) as u8;
+        }
+
+        for n in 0..=5 {
+            let mut expect = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+            let mut got = expect;
+            generic::sha1::sha1_compress_blocks(&mut expect, &blocks[..n * 64]);
+            sha1_compress_blocks_shaext(&mut got, &blocks[..n * 64], token);
+            assert_eq!(expect, got);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::low::generic;
+use crate::low::x86_64;
+
+pub(crate) fn sha1_compress_blocks(state: &mut [u32; 5], blocks: &[u8]) {
+    if let Some(token) = x86_64::cpu::HaveShaNi::check() {
+        x86_64::sha1::sha1_compress_blocks_shaext(state, blocks, token)
+    } else {
+        generic::sha1::sha1_compress_blocks(state, blocks)
+    }
+}
@@ -6,7 +6,7 @@ use core::arch::x86_64::*;
 pub(in crate::low) fn sha256_compress_blocks_shaext(
     state: &mut [u32; 8],
     blocks: &[u8],
-    _token: super::cpu::HaveSha256,
+    _token: super::cpu::HaveShaNi,
 ) {
     debug_assert!(blocks.len().is_multiple_of(64));
     // SAFETY: `_token` proves the caller checked the `sha` feature;
@@ -14,6 +14,21 @@ pub(in crate::low) fn sha256_compress_blocks_shaext(
     unsafe { sha256(state, blocks) }
 }
 
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+///
+/// Each SHA-NI instruction depends on the previous one, so
+/// interleaving two independent computations keeps more of them
+/// in flight.
+pub(in crate::low) fn sha256_compress_x2_shaext(
+    states: &mut [[u32; 8]; 2],
+    blocks: &[&[u8; 64]; 2],
+    _token: super::cpu::HaveShaNi,
+) {
+    // SAFETY: `_token` proves the caller checked the `sha` feature;
+    // this crate requires the `sse4.1` and `ssse3` features
+    unsafe { sha256_xn(states, blocks) }
+}
+
 macro_rules! k {
     ($k:literal) => {
         // SAFETY: `$k` is a compile-time constant and results in a valid index
@@ -136,6 +151,83 @@ fn sha256(state: &mut [u32; 8], blocks: &[u8]) {
     }
 }
 
+#[target_feature(enable = "sha,sse4.1,ssse3")]
+fn sha256_xn<const N: usize>(states: &mut [[u32; 8]; N], blocks: &[&[u8; 64]; N]) {
+    let little_endian_shuffle = _mm_set_epi64x(0x0c0d0e0f08090a0b, 0x0405060700010203);
+
+    let mut state0 = [_mm_setzero_si128(); N];
+    let mut state1 = [_mm_setzero_si128(); N];
+    let mut msg = [[_mm_setzero_si128(); 4]; N];
+
+    for lane in 0..N {
+        // SAFETY: `states[lane]` is 8 32-bit words and readable
+        let (s0, s1) = unsafe {
+            (
+                _mm_loadu_si128(states[lane][0..4].as_ptr().cast()),
+                _mm_loadu_si128(states[lane][4..8].as_ptr().cast()),
+            )
+        };
+        let tmp = _mm_shuffle_epi32(s0, 0b10_11_00_01);
+        let s1 = _mm_shuffle_epi32(s1, 0b00_01_10_11);
+        state0[lane] = _mm_alignr_epi8(tmp, s1, 8);
+        state1[lane] = _mm_blend_epi16(s1, tmp, 0xf0);
+
+        for (i, msg) in msg[lane].iter_mut().enumerate() {
+            // SAFETY: `blocks[lane][i * 16..]` is 16 bytes and readable
+            let words = unsafe { _mm_loadu_si128(blocks[lane][i * 16..].as_ptr().cast()) };
+            *msg = _mm_shuffle_epi8(words, little_endian_shuffle);
+        }
+    }
+
+    let state0_prev = state0;
+    let state1_prev = state1;
+
+    // as `sha256`, but the `round!` steps of each lane are interleaved.
+    macro_rules! rounds {
+        ($($r:literal)*) => {$(
+            let r: usize = $r;
+            // SAFETY: `r * 4 + 4` is at most 64, the length of `K`.  `K` has
+            // proper alignment for this aligned load.
+            let k = unsafe { _mm_load_si128(K.0[r * 4..].as_ptr().cast()) };
+
+            for lane in 0..N {
+                let msg = &mut msg[lane];
+                let cur = msg[r % 4];
+
+                let m = _mm_add_epi32(cur, k);
+                state1[lane] = _mm_sha256rnds2_epu32(state1[lane], state0[lane], m);
+                if (3..15).contains(&r) {
+                    let tmp = _mm_alignr_epi8(cur, msg[(r + 3) % 4], 4);
+                    let next = _mm_add_epi32(msg[(r + 1) % 4], tmp);
+                    msg[(r + 1) % 4] = _mm_sha256msg2_epu32(next, cur);
+                }
+                let m = _mm_shuffle_epi32(m, 0b00_00_11_10);
+                state0[lane] = _mm_sha256rnds2_epu32(state0[lane], state1[lane], m);
+                if (1..15).contains(&r) {
+                    msg[(r + 3) % 4] = _mm_sha256msg1_epu32(msg[(r + 3) % 4], cur);
+                }
+            }
+        )*};
+    }
+    rounds!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15);
+
+    for lane in 0..N {
+        let s0 = _mm_add_epi32(state0[lane], state0_prev[lane]);
+        let s1 = _mm_add_epi32(state1[lane], state1_prev[lane]);
+
+        let tmp = _mm_shuffle_epi32(s0, 0b00_01_10_11);
+        let s1 = _mm_shuffle_epi32(s1, 0b10_11_00_01);
+        let s0 = _mm_blend_epi16(tmp, s1, 0xf0);
+        let s1 = _mm_alignr_epi8(s1, tmp, 8);
+
+        // SAFETY: `states[lane]` is 8 32-bit words and writable
+        unsafe {
+            _mm_storeu_si128(states[lane][0..4].as_mut_ptr().cast(), s0);
+            _mm_storeu_si128(states[lane][4..8].as_mut_ptr().cast(), s1);
+        }
+    }
+}
+
 #[repr(align(16))]
 struct Aligned([u32; 64]);
 
@@ -149,3 +241,27 @@ static K: Aligned = Aligned([
     0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
     0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
 ]);
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_x2_matches_generic() {
+        let Some(token) = super::super::cpu::HaveShaNi::check() else {
+            return;
+        };
+
+        let blocks = [[0x11u8; 64], [0xeeu8; 64]];
+        let mut states = [[0x01234567; 8], [0x89abcdef; 8]];
+        let mut expect = states;
+        for (state, block) in expect.iter_mut().zip(blocks.iter()) {
+            generic::sha256::sha256_compress_blocks(state, block);
+        }
+
+        sha256_compress_x2_shaext(&mut states, &[&blocks[0], &blocks[1]], token);
+        assert_eq!(states, expect);
+    }
+}
@@ -5,9 +5,35 @@ use crate::low::generic;
 use crate::low::x86_64;
 
 pub(crate) fn sha256_compress_blocks(state: &mut [u32; 8], blocks: &[u8]) {
-    if let Some(token) = x86_64::cpu::HaveSha256::check() {
+    if let Some(token) = x86_64::cpu::HaveShaNi::check() {
         x86_64::sha256::sha256_compress_blocks_shaext(state, blocks, token)
     } else {
         generic::sha256::sha256_compress_blocks(state, blocks)
     }
 }
+
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+pub(crate) fn sha256_compress_lanes(states: &mut [[u32; 8]], blocks: &[&[u8; 64]]) {
+    assert_eq!(states.len(), blocks.len());
+
+    // SHA-NI is faster than eight lanes of AVX2, if we have it.
+    let (states, blocks) = if let Some(token) = x86_64::cpu::HaveShaNi::check() {
+        let (states2, states) = states.as_chunks_mut::<2>();
+        let (blocks2, blocks) = blocks.as_chunks::<2>();
+        for (states, blocks) in states2.iter_mut().zip(blocks2) {
+            x86_64::sha256::sha256_compress_x2_shaext(states, blocks, token);
+        }
+        (states, blocks)
+    } else {
+        let (states8, states) = states.as_chunks_mut::<8>();
+        let (blocks8, blocks) = blocks.as_chunks::<8>();
+        for (states, blocks) in states8.iter_mut().zip(blocks8) {
+            x86_64::sha256_x8::sha256_compress_x8(states, blocks);
+        }
+        (states, blocks)
+    };
+
+    for (state, block) in states.iter_mut().zip(blocks) {
+        sha256_compress_blocks(state, *block);
+    }
+}
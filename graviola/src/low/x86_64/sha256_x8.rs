@@ -0,0 +1,158 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// SHA256 compression of eight independent blocks, using AVX2.
+//
+// Each vector holds one word from each of the eight computations,
+// so the states and message blocks are transposed on the way in,
+// and the states transposed back on the way out.
+
+use core::arch::x86_64::*;
+
+use crate::low::generic::sha256::K;
+use crate::low::x86_64::blake3::transpose8x8;
+
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+pub(in crate::low) fn sha256_compress_x8(states: &mut [[u32; 8]; 8], blocks: &[&[u8; 64]; 8]) {
+    // SAFETY: this crate requires the `avx2` cpu feature
+    unsafe { sha256_x8(states, blocks) }
+}
+
+macro_rules! rotr {
+    ($x:expr, $n:literal) => {
+        _mm256_or_si256(_mm256_srli_epi32($x, $n), _mm256_slli_epi32($x, 32 - $n))
+    };
+}
+
+// one round: the state words are renamed rather than moved, so
+// `$d` and `$h` become the new `e` and `a`.
+macro_rules! round {
+    ($a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident, $w:expr, $k:expr) => {
+        let bsig1 = _mm256_xor_si256(_mm256_xor_si256(rotr!($e, 6), rotr!($e, 11)), rotr!($e, 25));
+        let ch = _mm256_xor_si256(_mm256_and_si256($e, $f), _mm256_andnot_si256($e, $g));
+        let t1 = _mm256_add_epi32(
+            _mm256_add_epi32(_mm256_add_epi32($h, bsig1), _mm256_add_epi32(ch, $w)),
+            _mm256_set1_epi32($k as i32),
+        );
+
+        let bsig0 = _mm256_xor_si256(_mm256_xor_si256(rotr!($a, 2), rotr!($a, 13)), rotr!($a, 22));
+        let maj = _mm256_or_si256(
+            _mm256_and_si256($a, $b),
+            _mm256_and_si256($c, _mm256_or_si256($a, $b)),
+        );
+
+        $d = _mm256_add_epi32($d, t1);
+        $h = _mm256_add_epi32(t1, _mm256_add_epi32(bsig0, maj));
+    };
+}
+
+#[target_feature(enable = "avx2")]
+fn sha256_x8(states: &mut [[u32; 8]; 8], blocks: &[&[u8; 64]; 8]) {
+    let mut rows = [_mm256_setzero_si256(); 8];
+    for (row, state) in rows.iter_mut().zip(states.iter()) {
+        // SAFETY: `state` is 32 bytes and readable
+        *row = unsafe { _mm256_loadu_si256(state.as_ptr().cast()) };
+    }
+    let prev = transpose8x8(rows);
+
+    let mut w = [_mm256_setzero_si256(); 16];
+    for half in 0..2 {
+        for (row, block) in rows.iter_mut().zip(blocks) {
+            let offset = half * 32;
+            // SAFETY: `block[offset..offset + 32]` is in bounds and readable
+            let words = unsafe { _mm256_loadu_si256(block[offset..].as_ptr().cast()) };
+            *row = _mm256_shuffle_epi8(words, bswap32());
+        }
+        w[half * 8..half * 8 + 8].copy_from_slice(&transpose8x8(rows));
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = prev;
+
+    for (i, k) in K.as_chunks::<16>().0.iter().enumerate() {
+        if i > 0 {
+            for t in 0..16 {
+                // w[t] = ssig1(w[t - 2]) + w[t - 7] + ssig0(w[t - 15]) + w[t - 16]
+                let w2 = w[(t + 14) % 16];
+                let w15 = w[(t + 1) % 16];
+                let ssig0 = _mm256_xor_si256(
+                    _mm256_xor_si256(rotr!(w15, 7), rotr!(w15, 18)),
+                    _mm256_srli_epi32(w15, 3),
+                );
+                let ssig1 = _mm256_xor_si256(
+                    _mm256_xor_si256(rotr!(w2, 17), rotr!(w2, 19)),
+                    _mm256_srli_epi32(w2, 10),
+                );
+                w[t] = _mm256_add_epi32(
+                    _mm256_add_epi32(w[t], w[(t + 9) % 16]),
+                    _mm256_add_epi32(ssig0, ssig1),
+                );
+            }
+        }
+
+        round!(a, b, c, d, e, f, g, h, w[0], k[0]);
+        round!(h, a, b, c, d, e, f, g, w[1], k[1]);
+        round!(g, h, a, b, c, d, e, f, w[2], k[2]);
+        round!(f, g, h, a, b, c, d, e, w[3], k[3]);
+        round!(e, f, g, h, a, b, c, d, w[4], k[4]);
+        round!(d, e, f, g, h, a, b, c, w[5], k[5]);
+        round!(c, d, e, f, g, h, a, b, w[6], k[6]);
+        round!(b, c, d, e, f, g, h, a, w[7], k[7]);
+        round!(a, b, c, d, e, f, g, h, w[8], k[8]);
+        round!(h, a, b, c, d, e, f, g, w[9], k[9]);
+        round!(g, h, a, b, c, d, e, f, w[10], k[10]);
+        round!(f, g, h, a, b, c, d, e, w[11], k[11]);
+        round!(e, f, g, h, a, b, c, d, w[12], k[12]);
+        round!(d, e, f, g, h, a, b, c, w[13], k[13]);
+        round!(c, d, e, f, g, h, a, b, w[14], k[14]);
+        round!(b, c, d, e, f, g, h, a, w[15], k[15]);
+    }
+
+    let mut next = [a, b, c, d, e, f, g, h];
+    for (next, prev) in next.iter_mut().zip(prev) {
+        *next = _mm256_add_epi32(*next, prev);
+    }
+
+    for (state, row) in states.iter_mut().zip(transpose8x8(next)) {
+        // SAFETY: `state` is 32 bytes and writable
+        unsafe { _mm256_storeu_si256(state.as_mut_ptr().cast(), row) };
+    }
+}
+
+/// Byte-swap each 32-bit word, with `_mm256_shuffle_epi8`.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn bswap32() -> __m256i {
+    _mm256_broadcastsi128_si256(_mm_setr_epi8(
+        3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12,
+    ))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut blocks = [[0u8; 64]; 8];
+        let mut states = [[0u32; 8]; 8];
+        for (i, (block, state)) in blocks.iter_mut().zip(states.iter_mut()).enumerate() {
+            for (j, b) in block.iter_mut().enumerate() {
+                *b = (i * 31 + j * 7) as u8;
+            }
+            for (j, s) in state.iter_mut().enumerate() {
+                *s = (i as u32 + 1).wrapping_mul(0x9e3779b9) ^ j as u32;
+            }
+        }
+
+        let mut expect = states;
+        for (state, block) in expect.iter_mut().zip(blocks.iter()) {
+            generic::sha256::sha256_compress_blocks(state, block);
+        }
+
+        let blocks = core::array::from_fn(|i| &blocks[i]);
+        sha256_compress_x8(&mut states, &blocks);
+        assert_eq!(states, expect);
+    }
+}
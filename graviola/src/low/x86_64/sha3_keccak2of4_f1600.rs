@@ -6,3 +6,9 @@
 pub(crate) fn sha3_keccak2of4_f1600(a: &mut [[u64; 25]; 4], rc: &[u64; 24]) {
     super::sha3_keccak4_f1600_shim::sha3_keccak4_f1600(a, rc);
 }
+
+/// Compute Keccak-p[1600,12] on the first two states in `a`.
+#[inline]
+pub(crate) fn sha3_keccak2of4_p1600_12(a: &mut [[u64; 25]; 4], rc: &[u64; 12]) {
+    super::sha3_keccak4_f1600_shim::sha3_keccak4_p1600_12(a, rc);
+}
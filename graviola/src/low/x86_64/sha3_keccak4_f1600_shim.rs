@@ -6,6 +6,11 @@ pub(crate) fn sha3_keccak4_f1600(a: &mut [[u64; 25]; 4], rc: &[u64; 24]) {
     super::sha3_keccak4_f1600_alt::sha3_keccak4_f1600(a, rc, &RHO8, &RHO56);
 }
 
+#[inline]
+pub(crate) fn sha3_keccak4_p1600_12(a: &mut [[u64; 25]; 4], rc: &[u64; 12]) {
+    super::sha3_keccak4_p1600_12_alt::sha3_keccak4_p1600_12(a, rc, &RHO8, &RHO56);
+}
+
 static RHO8: [u64; 4] = [
     0x0605040302010007,
     0x0E0D0C0B0A09080F,
@@ -12,3 +12,18 @@ pub(crate) fn sha512_compress_blocks(state: &mut [u64; 8], blocks: &[u8]) {
         generic::sha512::sha512_compress_blocks(state, blocks)
     }
 }
+
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+pub(crate) fn sha512_compress_lanes(states: &mut [[u64; 8]], blocks: &[&[u8; 128]]) {
+    assert_eq!(states.len(), blocks.len());
+    let (states4, states) = states.as_chunks_mut::<4>();
+    let (blocks4, blocks) = blocks.as_chunks::<4>();
+
+    for (states, blocks) in states4.iter_mut().zip(blocks4) {
+        x86_64::sha512_x4::sha512_compress_x4(states, blocks);
+    }
+
+    for (state, block) in states.iter_mut().zip(blocks) {
+        sha512_compress_blocks(state, *block);
+    }
+}
@@ -0,0 +1,164 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+//
+// SHA512 compression of four independent blocks, using AVX2.
+//
+// As for `sha256_x8`, each vector holds one word from each of the
+// four computations.
+
+use core::arch::x86_64::*;
+
+use crate::low::generic::sha512::K;
+
+/// Compress `blocks[i]` into `states[i]`, for each `i`.
+pub(in crate::low) fn sha512_compress_x4(states: &mut [[u64; 8]; 4], blocks: &[&[u8; 128]; 4]) {
+    // SAFETY: this crate requires the `avx2` cpu feature
+    unsafe { sha512_x4(states, blocks) }
+}
+
+macro_rules! rotr {
+    ($x:expr, $n:literal) => {
+        _mm256_or_si256(_mm256_srli_epi64($x, $n), _mm256_slli_epi64($x, 64 - $n))
+    };
+}
+
+#[target_feature(enable = "avx2")]
+fn sha512_x4(states: &mut [[u64; 8]; 4], blocks: &[&[u8; 128]; 4]) {
+    let mut prev = [_mm256_setzero_si256(); 8];
+    for half in 0..2 {
+        let mut rows = [_mm256_setzero_si256(); 4];
+        for (row, state) in rows.iter_mut().zip(states.iter()) {
+            // SAFETY: `state[half * 4..]` is 32 bytes and readable
+            *row = unsafe { _mm256_loadu_si256(state[half * 4..].as_ptr().cast()) };
+        }
+        prev[half * 4..half * 4 + 4].copy_from_slice(&transpose4x4(rows));
+    }
+
+    let mut w = [_mm256_setzero_si256(); 16];
+    for quarter in 0..4 {
+        let mut rows = [_mm256_setzero_si256(); 4];
+        for (row, block) in rows.iter_mut().zip(blocks) {
+            let offset = quarter * 32;
+            // SAFETY: `block[offset..offset + 32]` is in bounds and readable
+            let words = unsafe { _mm256_loadu_si256(block[offset..].as_ptr().cast()) };
+            *row = _mm256_shuffle_epi8(words, bswap64());
+        }
+        w[quarter * 4..quarter * 4 + 4].copy_from_slice(&transpose4x4(rows));
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = prev;
+
+    for (t, k) in K.iter().enumerate() {
+        if t >= 16 {
+            // w[t] = ssig1(w[t - 2]) + w[t - 7] + ssig0(w[t - 15]) + w[t - 16]
+            let w2 = w[(t - 2) % 16];
+            let w15 = w[(t - 15) % 16];
+            let ssig0 = _mm256_xor_si256(
+                _mm256_xor_si256(rotr!(w15, 1), rotr!(w15, 8)),
+                _mm256_srli_epi64(w15, 7),
+            );
+            let ssig1 = _mm256_xor_si256(
+                _mm256_xor_si256(rotr!(w2, 19), rotr!(w2, 61)),
+                _mm256_srli_epi64(w2, 6),
+            );
+            w[t % 16] = _mm256_add_epi64(
+                _mm256_add_epi64(w[t % 16], w[(t - 7) % 16]),
+                _mm256_add_epi64(ssig0, ssig1),
+            );
+        }
+
+        let bsig1 = _mm256_xor_si256(_mm256_xor_si256(rotr!(e, 14), rotr!(e, 18)), rotr!(e, 41));
+        let ch = _mm256_xor_si256(_mm256_and_si256(e, f), _mm256_andnot_si256(e, g));
+        let t1 = _mm256_add_epi64(
+            _mm256_add_epi64(_mm256_add_epi64(h, bsig1), _mm256_add_epi64(ch, w[t % 16])),
+            _mm256_set1_epi64x(*k as i64),
+        );
+
+        let bsig0 = _mm256_xor_si256(_mm256_xor_si256(rotr!(a, 28), rotr!(a, 34)), rotr!(a, 39));
+        let maj = _mm256_or_si256(
+            _mm256_and_si256(a, b),
+            _mm256_and_si256(c, _mm256_or_si256(a, b)),
+        );
+        let t2 = _mm256_add_epi64(bsig0, maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = _mm256_add_epi64(d, t1);
+        d = c;
+        c = b;
+        b = a;
+        a = _mm256_add_epi64(t1, t2);
+    }
+
+    let mut next = [a, b, c, d, e, f, g, h];
+    for (next, prev) in next.iter_mut().zip(prev) {
+        *next = _mm256_add_epi64(*next, prev);
+    }
+
+    for half in 0..2 {
+        let rows: [__m256i; 4] = next[half * 4..half * 4 + 4].try_into().unwrap();
+        for (state, row) in states.iter_mut().zip(transpose4x4(rows)) {
+            // SAFETY: `state[half * 4..]` is 32 bytes and writable
+            unsafe { _mm256_storeu_si256(state[half * 4..].as_mut_ptr().cast(), row) };
+        }
+    }
+}
+
+/// Transpose a 4x4 matrix of 64-bit words.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn transpose4x4(x: [__m256i; 4]) -> [__m256i; 4] {
+    // interleave pairs of rows: ab_02 is a0 b0 | a2 b2
+    let ab_02 = _mm256_unpacklo_epi64(x[0], x[1]);
+    let ab_13 = _mm256_unpackhi_epi64(x[0], x[1]);
+    let cd_02 = _mm256_unpacklo_epi64(x[2], x[3]);
+    let cd_13 = _mm256_unpackhi_epi64(x[2], x[3]);
+
+    // then swap 128-bit halves
+    [
+        _mm256_permute2x128_si256(ab_02, cd_02, 0x20),
+        _mm256_permute2x128_si256(ab_13, cd_13, 0x20),
+        _mm256_permute2x128_si256(ab_02, cd_02, 0x31),
+        _mm256_permute2x128_si256(ab_13, cd_13, 0x31),
+    ]
+}
+
+/// Byte-swap each 64-bit word, with `_mm256_shuffle_epi8`.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn bswap64() -> __m256i {
+    _mm256_broadcastsi128_si256(_mm_setr_epi8(
+        7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8,
+    ))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::low::generic;
+
+    #[test]
+    fn compress_matches_generic() {
+        let mut blocks = [[0u8; 128]; 4];
+        let mut states = [[0u64; 8]; 4];
+        for (i, (block, state)) in blocks.iter_mut().zip(states.iter_mut()).enumerate() {
+            for (j, b) in block.iter_mut().enumerate() {
+                *b = (i * 31 + j * 7) as u8;
+            }
+            for (j, s) in state.iter_mut().enumerate() {
+                *s = (i as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15) ^ j as u64;
+            }
+        }
+
+        let mut expect = states;
+        for (state, block) in expect.iter_mut().zip(blocks.iter()) {
+            generic::sha512::sha512_compress_blocks(state, block);
+        }
+
+        let blocks = core::array::from_fn(|i| &blocks[i]);
+        sha512_compress_x4(&mut states, &blocks);
+        assert_eq!(states, expect);
+    }
+}
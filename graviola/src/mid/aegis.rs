@@ -0,0 +1,504 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::Error;
+use crate::low::{Entry, aegis, ct_equal, zeroise};
+
+/// An AEGIS-128L or AEGIS-128X key.
+///
+/// See [draft-irtf-cfrg-aegis-aead](https://datatracker.ietf.org/doc/draft-irtf-cfrg-aegis-aead/).
+///
+/// `LANES` is the degree of parallelism, and must be 1, 2 or 4.
+/// AEGIS-128L is the one lane version; see [`Aegis128L`],
+/// [`Aegis128X2`] and [`Aegis128X4`].  The variants are not
+/// compatible with each other.  AEGIS-128X2 and AEGIS-128X4 are
+/// faster on CPUs that support wider AES instructions (VAES).
+///
+/// Nonces are 16 bytes.  Since AEGIS-128L has a 128-bit key and
+/// nonce, randomly-generated nonces are acceptable, but prefer
+/// [`Aegis256`] if very many messages are encrypted under one key.
+pub struct Aegis128X<const LANES: usize> {
+    key: [u8; 16],
+}
+
+/// AEGIS-128L.
+pub type Aegis128L = Aegis128X<1>;
+
+/// AEGIS-128X2.
+pub type Aegis128X2 = Aegis128X<2>;
+
+/// AEGIS-128X4.
+pub type Aegis128X4 = Aegis128X<4>;
+
+impl<const LANES: usize> Aegis128X<LANES> {
+    /// Create a new [`Aegis128X`] from 16 bytes of key material.
+    pub fn new(key: [u8; 16]) -> Self {
+        const { assert!(matches!(LANES, 1 | 2 | 4), "LANES must be 1, 2 or 4") };
+        Self { key }
+    }
+
+    /// Encrypt the given message.
+    ///
+    /// On entry, `cipher_inout` contains the plaintext of the message.
+    /// `nonce` contains the nonce, which must be unique for a given key.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    ///
+    /// On exit, `cipher_inout` contains the ciphertext of the message,
+    /// and `tag_out` contains the 128-bit authentication tag.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 16],
+    ) {
+        let _entry = Entry::new_secret();
+        aegis::aegis128x_encrypt::<LANES>(&self.key, nonce, aad, cipher_inout, tag_out);
+    }
+
+    /// Encrypt the given message, with a 256-bit authentication tag.
+    ///
+    /// This is otherwise the same as [`Self::encrypt()`].
+    pub fn encrypt_with_long_tag(
+        &self,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 32],
+    ) {
+        let _entry = Entry::new_secret();
+        aegis::aegis128x_encrypt::<LANES>(&self.key, nonce, aad, cipher_inout, tag_out);
+    }
+
+    /// Decrypts and verifies the given message.
+    ///
+    /// On entry, `cipher_inout` contains the ciphertext of the message.
+    /// `nonce` contains the nonce, which must match what was supplied
+    /// when encrypting this message.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    /// `tag` is the purported 128-bit authentication tag.
+    ///
+    /// On success, `cipher_inout` contains the plaintext of the message,
+    /// and `Ok(())` is returned.
+    /// Otherwise, `Err(Error::DecryptFailed)` is returned and `cipher_inout`
+    /// is cleared.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let mut actual_tag = [0u8; 16];
+        aegis::aegis128x_decrypt::<LANES>(&self.key, nonce, aad, cipher_inout, &mut actual_tag);
+        verify(&actual_tag, tag, cipher_inout)
+    }
+
+    /// Decrypts and verifies the given message, with a 256-bit
+    /// authentication tag.
+    ///
+    /// This is otherwise the same as [`Self::decrypt()`].
+    pub fn decrypt_with_long_tag(
+        &self,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let mut actual_tag = [0u8; 32];
+        aegis::aegis128x_decrypt::<LANES>(&self.key, nonce, aad, cipher_inout, &mut actual_tag);
+        verify(&actual_tag, tag, cipher_inout)
+    }
+}
+
+impl<const LANES: usize> Drop for Aegis128X<LANES> {
+    fn drop(&mut self) {
+        zeroise(&mut self.key);
+    }
+}
+
+/// An AEGIS-256 key.
+///
+/// See [draft-irtf-cfrg-aegis-aead](https://datatracker.ietf.org/doc/draft-irtf-cfrg-aegis-aead/).
+///
+/// Nonces are 32 bytes, so may be generated randomly.
+pub struct Aegis256 {
+    key: [u8; 32],
+}
+
+impl Aegis256 {
+    /// Create a new [`Aegis256`] from 32 bytes of key material.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    /// Encrypt the given message.
+    ///
+    /// On entry, `cipher_inout` contains the plaintext of the message.
+    /// `nonce` contains the nonce, which must be unique for a given key.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    ///
+    /// On exit, `cipher_inout` contains the ciphertext of the message,
+    /// and `tag_out` contains the 128-bit authentication tag.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8; 32],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 16],
+    ) {
+        let _entry = Entry::new_secret();
+        aegis::aegis256_encrypt(&self.key, nonce, aad, cipher_inout, tag_out);
+    }
+
+    /// Encrypt the given message, with a 256-bit authentication tag.
+    ///
+    /// This is otherwise the same as [`Self::encrypt()`].
+    pub fn encrypt_with_long_tag(
+        &self,
+        nonce: &[u8; 32],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 32],
+    ) {
+        let _entry = Entry::new_secret();
+        aegis::aegis256_encrypt(&self.key, nonce, aad, cipher_inout, tag_out);
+    }
+
+    /// Decrypts and verifies the given message.
+    ///
+    /// On entry, `cipher_inout` contains the ciphertext of the message.
+    /// `nonce` contains the nonce, which must match what was supplied
+    /// when encrypting this message.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    /// `tag` is the purported 128-bit authentication tag.
+    ///
+    /// On success, `cipher_inout` contains the plaintext of the message,
+    /// and `Ok(())` is returned.
+    /// Otherwise, `Err(Error::DecryptFailed)` is returned and `cipher_inout`
+    /// is cleared.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; 32],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let mut actual_tag = [0u8; 16];
+        aegis::aegis256_decrypt(&self.key, nonce, aad, cipher_inout, &mut actual_tag);
+        verify(&actual_tag, tag, cipher_inout)
+    }
+
+    /// Decrypts and verifies the given message, with a 256-bit
+    /// authentication tag.
+    ///
+    /// This is otherwise the same as [`Self::decrypt()`].
+    pub fn decrypt_with_long_tag(
+        &self,
+        nonce: &[u8; 32],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let mut actual_tag = [0u8; 32];
+        aegis::aegis256_decrypt(&self.key, nonce, aad, cipher_inout, &mut actual_tag);
+        verify(&actual_tag, tag, cipher_inout)
+    }
+}
+
+impl Drop for Aegis256 {
+    fn drop(&mut self) {
+        zeroise(&mut self.key);
+    }
+}
+
+fn verify(actual_tag: &[u8], tag: &[u8], cipher_inout: &mut [u8]) -> Result<(), Error> {
+    if ct_equal(actual_tag, tag) {
+        Ok(())
+    } else {
+        // avoid unauthenticated plaintext leak
+        cipher_inout.fill(0x00);
+        Err(Error::DecryptFailed)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use hex::FromHex;
+
+    #[test]
+    fn aegis128l_draft() {
+        let k = Aegis128L::new(FromHex::from_hex("10010000000000000000000000000000").unwrap());
+        let nonce = FromHex::from_hex("10000200000000000000000000000000").unwrap();
+
+        check_128x(
+            &k,
+            &nonce,
+            b"",
+            &[0u8; 16],
+            "c1c0e58bd913006feba00f4b3cc3594e",
+            "abe0ece80c24868a226a35d16bdae37a",
+            "25835bfbb21632176cf03840687cb968cace4617af1bd0f7d064c639a5c79ee4",
+        );
+        check_128x(
+            &k,
+            &nonce,
+            b"",
+            b"",
+            "",
+            "c2b879a67def9d74e6c14f708bbcc9b4",
+            "1360dc9db8ae42455f6e5b6a9d488ea4f2184c4e12120249335c4ee84bafe25d",
+        );
+        check_128x(
+            &k,
+            &nonce,
+            &hex::decode("0001020304050607").unwrap(),
+            &hex::decode("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+                .unwrap(),
+            "79d94593d8c2119d7e8fd9b8fc77845c5c077a05b2528b6ac54b563aed8efe84",
+            "cc6f3372f6aa1bb82388d695c3962d9a",
+            "022cb796fe7e0ae1197525ff67e309484cfbab6528ddef89f17d74ef8ecd82b3",
+        );
+        check_128x(
+            &k,
+            &nonce,
+            &hex::decode("0001020304050607").unwrap(),
+            &hex::decode("000102030405060708090a0b0c0d").unwrap(),
+            "79d94593d8c2119d7e8fd9b8fc77",
+            "5c04b3dba849b2701effbe32c7f0fab7",
+            "86f1b80bfb463aba711d15405d094baf4a55a15dbfec81a76f35ed0b9c8b04ac",
+        );
+    }
+
+    #[test]
+    fn aegis128x2_draft() {
+        let k = Aegis128X2::new(FromHex::from_hex("000102030405060708090a0b0c0d0e0f").unwrap());
+        let nonce = FromHex::from_hex("101112131415161718191a1b1c1d1e1f").unwrap();
+        check_128x(
+            &k,
+            &nonce,
+            b"",
+            b"",
+            "",
+            "63117dc57756e402819a82e13eca8379",
+            "b92c71fdbd358b8a4de70b27631ace90cffd9b9cfba82028412bac41b4f53759",
+        );
+        check_128x(
+            &k,
+            &nonce,
+            &hex::decode("0102030401020304").unwrap(),
+            &repeated(&[4, 5, 6, 7], 120),
+            "5795544301997f93621b278809d6331b3bfa6f18e90db12c4aa35965b5e98c5f\
+             c6fb4e54bcb6111842c20637252eff747cb3a8f85b37de80919a589fe0f24872\
+             bc926360696739e05520647e390989e1eb5fd42f99678a0276a498f8c454761c\
+             9d6aacb647ad56be62b29c22cd4b5761b38f43d5a5ee062f",
+            "1aebc200804f405cab637f2adebb6d77",
+            "c471876f9b4978c44f2ae1ce770cdb11a094ee3feca64e7afcd48bfe52c60eca",
+        );
+    }
+
+    #[test]
+    fn aegis128x4_draft() {
+        let k = Aegis128X4::new(FromHex::from_hex("000102030405060708090a0b0c0d0e0f").unwrap());
+        let nonce = FromHex::from_hex("101112131415161718191a1b1c1d1e1f").unwrap();
+        check_128x(
+            &k,
+            &nonce,
+            b"",
+            b"",
+            "",
+            "5bef762d0947c00455b97bb3af30dfa3",
+            "a4b25437f4be93cfa856a2f27e4416b42cac79fd4698f2cdbe6af25673e10a68",
+        );
+        check_128x(
+            &k,
+            &nonce,
+            &hex::decode("0102030401020304").unwrap(),
+            &repeated(&[4, 5, 6, 7], 120),
+            "e836118562f4479c9d35c17356a833114c21f9aa39e4dda5e5c87f4152a00fce\
+             9a7c38f832eafe8b1c12f8a7cf12a81a1ad8a9c24ba9dedfbdaa586ffea67ddc\
+             801ea97d9ab4a872f42d0e352e2713dacd609f9442c17517c5a29daf3e2a3fac\
+             4ff6b1380c4e46df7b086af6ce6bc1ed594b8dd64aed2a7e",
+            "0e56ab94e2e85db80f9d54010caabfb4",
+            "69abf0f64a137dd6e122478d777e98bc422823006cf57f5ee822dd78397230b2",
+        );
+    }
+
+    #[test]
+    fn aegis256_draft() {
+        let k = Aegis256::new(FromHex::from_hex(format!("1001{}", "00".repeat(30))).unwrap());
+        let nonce = FromHex::from_hex(format!("100002{}", "00".repeat(29))).unwrap();
+        check_256(
+            &k,
+            &nonce,
+            b"",
+            &[0u8; 16],
+            "754fc3d8c973246dcc6d741412a4b236",
+            "3fe91994768b332ed7f570a19ec5896e",
+            "1181a1d18091082bf0266f66297d167d2e68b845f61a3b0527d31fc7b7b89f13",
+        );
+        check_256(
+            &k,
+            &nonce,
+            &hex::decode("0001020304050607").unwrap(),
+            &hex::decode("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+                .unwrap(),
+            "f373079ed84b2709faee373584585d60accd191db310ef5d8b11833df9dec711",
+            "8d86f91ee606e9ff26a01b64ccbdd91d",
+            "b7d28d0c3c0ebd409fd22b44160503073a547412da0854bfb9723020dab8da1a",
+        );
+        check_256(
+            &k,
+            &nonce,
+            &hex::decode("0001020304050607").unwrap(),
+            &hex::decode("000102030405060708090a0b0c0d").unwrap(),
+            "f373079ed84b2709faee37358458",
+            "c60b9c2d33ceb058f96e6dd03c215652",
+            "8c1cc703c81281bee3f6d9966e14948b4a175b2efbdc31e61a98b4465235c2d9",
+        );
+    }
+
+    #[test]
+    fn decrypt_failures() {
+        let k = Aegis128X4::new([0x11; 16]);
+        let nonce = [0x22; 16];
+        let mut tag = [0u8; 16];
+        let mut long_tag = [0u8; 32];
+        let mut ct = *b"hello world";
+        k.encrypt(&nonce, b"aad", &mut ct, &mut tag);
+
+        let mut buf = ct;
+        assert_eq!(
+            k.decrypt(&nonce, b"aaD", &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+
+        let mut buf = ct;
+        assert_eq!(
+            k.decrypt(&nonce, b"aad", &mut buf, &tag[..15]),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+
+        let mut buf = ct;
+        buf[10] ^= 1;
+        assert_eq!(
+            k.decrypt(&nonce, b"aad", &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+
+        // tags of one length are not accepted as the other
+        let mut buf = ct;
+        k.encrypt_with_long_tag(&nonce, b"aad", &mut buf, &mut long_tag);
+        assert_eq!(
+            k.decrypt_with_long_tag(&nonce, b"aad", &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        let mut buf = ct;
+        assert_eq!(
+            k.decrypt(&nonce, b"aad", &mut buf, &long_tag),
+            Err(Error::DecryptFailed)
+        );
+
+        let k = Aegis256::new([0x11; 32]);
+        let nonce = [0x22; 32];
+        let mut ct = *b"hello world";
+        k.encrypt(&nonce, b"aad", &mut ct, &mut tag);
+        let mut buf = ct;
+        tag[0] ^= 1;
+        assert_eq!(
+            k.decrypt(&nonce, b"aad", &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+    }
+
+    #[test]
+    fn all_lengths_round_trip() {
+        fn round_trip<const LANES: usize>() {
+            let k = Aegis128X::<LANES>::new([0x11; 16]);
+            let nonce = [0x22; 16];
+            let plain = counting(0, 300);
+            for len in 0..plain.len() {
+                let mut tag = [0u8; 16];
+                let mut buf = plain[..len].to_vec();
+                k.encrypt(&nonce, &plain[..len / 2], &mut buf, &mut tag);
+                k.decrypt(&nonce, &plain[..len / 2], &mut buf, &tag)
+                    .unwrap();
+                assert_eq!(&buf, &plain[..len]);
+            }
+        }
+
+        round_trip::<1>();
+        round_trip::<2>();
+        round_trip::<4>();
+    }
+
+    fn check_128x<const LANES: usize>(
+        k: &Aegis128X<LANES>,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        plain: &[u8],
+        cipher: &str,
+        tag: &str,
+        long_tag: &str,
+    ) {
+        let mut buf = plain.to_vec();
+        let mut actual_tag = [0u8; 16];
+        k.encrypt(nonce, aad, &mut buf, &mut actual_tag);
+        assert_eq!(buf, hex::decode(cipher).unwrap());
+        assert_eq!(actual_tag.to_vec(), hex::decode(tag).unwrap());
+        k.decrypt(nonce, aad, &mut buf, &actual_tag).unwrap();
+        assert_eq!(buf, plain);
+
+        let mut actual_tag = [0u8; 32];
+        k.encrypt_with_long_tag(nonce, aad, &mut buf, &mut actual_tag);
+        assert_eq!(buf, hex::decode(cipher).unwrap());
+        assert_eq!(actual_tag.to_vec(), hex::decode(long_tag).unwrap());
+        k.decrypt_with_long_tag(nonce, aad, &mut buf, &actual_tag)
+            .unwrap();
+        assert_eq!(buf, plain);
+    }
+
+    fn check_256(
+        k: &Aegis256,
+        nonce: &[u8; 32],
+        aad: &[u8],
+        plain: &[u8],
+        cipher: &str,
+        tag: &str,
+        long_tag: &str,
+    ) {
+        let mut buf = plain.to_vec();
+        let mut actual_tag = [0u8; 16];
+        k.encrypt(nonce, aad, &mut buf, &mut actual_tag);
+        assert_eq!(buf, hex::decode(cipher).unwrap());
+        assert_eq!(actual_tag.to_vec(), hex::decode(tag).unwrap());
+        k.decrypt(nonce, aad, &mut buf, &actual_tag).unwrap();
+        assert_eq!(buf, plain);
+
+        let mut actual_tag = [0u8; 32];
+        k.encrypt_with_long_tag(nonce, aad, &mut buf, &mut actual_tag);
+        assert_eq!(buf, hex::decode(cipher).unwrap());
+        assert_eq!(actual_tag.to_vec(), hex::decode(long_tag).unwrap());
+        k.decrypt_with_long_tag(nonce, aad, &mut buf, &actual_tag)
+            .unwrap();
+        assert_eq!(buf, plain);
+    }
+
+    fn repeated(pattern: &[u8], len: usize) -> Vec<u8> {
+        pattern.iter().cycle().take(len).copied().collect()
+    }
+
+    fn counting(start: u8, len: usize) -> Vec<u8> {
+        (0..len).map(|i| start.wrapping_add(i as u8)).collect()
+    }
+}
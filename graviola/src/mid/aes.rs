@@ -0,0 +1,906 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! Unauthenticated AES block cipher modes.
+//!
+//! These are CTR, CBC and ECB, as described in
+//! [SP800-38A](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf),
+//! and XTS for storage encryption.
+//!
+//! **These are hazardous.**  They provide confidentiality only: an
+//! attacker who can modify a ciphertext can make controlled changes to
+//! the plaintext, and CBC with padding is liable to padding oracle attacks
+//! if decryption failures are observable.  They exist for reading and
+//! writing existing formats; new designs should use an AEAD from
+//! [`crate::aead`] instead.
+//!
+//! Apart from [`Xts`], the types here take 16, 24 or 32 byte keys,
+//! corresponding to AES-128, AES-192 or AES-256.  Their constructors
+//! panic otherwise.
+
+use crate::Error;
+use crate::low::{AesDecryptKey, AesKey, Entry, aes_ctr, aes_xts, ct_equal, zeroise};
+
+/// The AES block length, in bytes.
+pub const BLOCK_LEN: usize = 16;
+
+/// AES in electronic codebook (ECB) mode: the bare block cipher.
+///
+/// Each block is processed independently, so identical plaintext
+/// blocks produce identical ciphertext blocks.  This is only
+/// suitable as a building block for other constructions.
+pub struct Ecb {
+    enc: AesKey,
+    dec: AesDecryptKey,
+}
+
+impl Ecb {
+    /// Create a new `Ecb` object.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        let enc = AesKey::new(key);
+        let dec = AesDecryptKey::new(&enc);
+        Self { enc, dec }
+    }
+
+    /// Encrypts one block, in place.
+    pub fn encrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        let _entry = Entry::new_secret();
+        self.enc.encrypt_block(block);
+    }
+
+    /// Decrypts one block, in place.
+    pub fn decrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        let _entry = Entry::new_secret();
+        self.dec.decrypt_block(block);
+    }
+}
+
+/// AES in cipher block chaining (CBC) mode.
+///
+/// The IV must be unpredictable (for example, random) for each
+/// message encrypted under a given key.
+pub struct Cbc {
+    enc: AesKey,
+    dec: AesDecryptKey,
+}
+
+impl Cbc {
+    /// Create a new `Cbc` object.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        let enc = AesKey::new(key);
+        let dec = AesDecryptKey::new(&enc);
+        Self { enc, dec }
+    }
+
+    /// Encrypts `inout` in place, without padding.
+    ///
+    /// `inout` must be a whole number of blocks, otherwise
+    /// `Error::WrongLength` is returned.
+    pub fn encrypt(&self, iv: &[u8; BLOCK_LEN], inout: &mut [u8]) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        if !inout.len().is_multiple_of(BLOCK_LEN) {
+            return Err(Error::WrongLength);
+        }
+
+        let mut chain = *iv;
+        for block in inout.chunks_exact_mut(BLOCK_LEN) {
+            xor(&mut chain, block);
+            self.enc.encrypt_block(&mut chain);
+            block.copy_from_slice(&chain);
+        }
+        Ok(())
+    }
+
+    /// Decrypts `inout` in place, without removing padding.
+    ///
+    /// `inout` must be a whole number of blocks, otherwise
+    /// `Error::WrongLength` is returned.
+    pub fn decrypt(&self, iv: &[u8; BLOCK_LEN], inout: &mut [u8]) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        if !inout.len().is_multiple_of(BLOCK_LEN) {
+            return Err(Error::WrongLength);
+        }
+
+        let mut chain = *iv;
+        for block in inout.chunks_exact_mut(BLOCK_LEN) {
+            let cipher: [u8; BLOCK_LEN] = (*block).try_into().unwrap();
+            let mut plain = cipher;
+            self.dec.decrypt_block(&mut plain);
+            xor(&mut plain, &chain);
+            block.copy_from_slice(&plain);
+            zeroise(&mut plain);
+            chain = cipher;
+        }
+        Ok(())
+    }
+
+    /// Returns the length of the ciphertext produced by
+    /// [`Cbc::encrypt_padded()`] for a plaintext of `plaintext_len` bytes.
+    pub const fn padded_len(plaintext_len: usize) -> usize {
+        (plaintext_len / BLOCK_LEN + 1) * BLOCK_LEN
+    }
+
+    /// Encrypts `plaintext`, with PKCS#7 padding.
+    ///
+    /// The ciphertext is written to the front of `out`, and that prefix
+    /// is returned.  `out` must be at least [`Cbc::padded_len()`] bytes,
+    /// otherwise `Error::WrongLength` is returned.
+    pub fn encrypt_padded<'a>(
+        &self,
+        iv: &[u8; BLOCK_LEN],
+        plaintext: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let out = out
+            .get_mut(..Self::padded_len(plaintext.len()))
+            .ok_or(Error::WrongLength)?;
+
+        let (data, padding) = out.split_at_mut(plaintext.len());
+        data.copy_from_slice(plaintext);
+        padding.fill(padding.len() as u8);
+
+        self.encrypt(iv, out)?;
+        Ok(out)
+    }
+
+    /// Decrypts `ciphertext`, and removes PKCS#7 padding.
+    ///
+    /// The plaintext is written to the front of `out`, and that prefix
+    /// is returned.  `out` must be at least as long as `ciphertext`,
+    /// otherwise `Error::WrongLength` is returned.
+    ///
+    /// `Error::DecryptFailed` is returned if `ciphertext` is not a
+    /// non-zero whole number of blocks, or the padding is invalid.
+    /// In that case, `out` is cleared.
+    pub fn decrypt_padded<'a>(
+        &self,
+        iv: &[u8; BLOCK_LEN],
+        ciphertext: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let out = out.get_mut(..ciphertext.len()).ok_or(Error::WrongLength)?;
+
+        if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(BLOCK_LEN) {
+            return Err(Error::DecryptFailed);
+        }
+
+        out.copy_from_slice(ciphertext);
+        self.decrypt(iv, out)?;
+
+        let last = out.len() - BLOCK_LEN;
+        match padding_len(out[last..].try_into().unwrap()) {
+            Some(len) => Ok(&out[..out.len() - len]),
+            None => {
+                out.fill(0x00);
+                Err(Error::DecryptFailed)
+            }
+        }
+    }
+}
+
+/// Returns the length of the PKCS#7 padding ending `block`, or `None`
+/// if it is invalid.
+///
+/// This does not branch on the contents of `block`.
+fn padding_len(block: &[u8; BLOCK_LEN]) -> Option<usize> {
+    let pad = block[BLOCK_LEN - 1] as u32;
+
+    // non-zero if `pad` is zero, or more than a block
+    let mut bad = (pad.wrapping_sub(1) | (BLOCK_LEN as u32).wrapping_sub(pad)) >> 31;
+
+    for (i, byte) in block.iter().rev().enumerate() {
+        // all-ones if this byte should be padding
+        let in_padding = ((i as u32).wrapping_sub(pad) >> 31).wrapping_neg();
+        bad |= in_padding & (*byte as u32 ^ pad);
+    }
+
+    match bad {
+        0 => Some(pad as usize),
+        _ => None,
+    }
+}
+
+/// The size of the counter used in [`Ctr`] mode.
+///
+/// The counter is the last (rightmost) part of the counter block,
+/// and is incremented as a big-endian integer modulo 2<sup>bits</sup>.
+/// The remainder of the counter block is unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CounterWidth {
+    /// A 32-bit counter, as used by AES-GCM.
+    Bits32,
+
+    /// A 64-bit counter.
+    Bits64,
+
+    /// The entire counter block is the counter.
+    Bits128,
+}
+
+impl CounterWidth {
+    fn mask(self) -> u128 {
+        match self {
+            Self::Bits32 => u32::MAX as u128,
+            Self::Bits64 => u64::MAX as u128,
+            Self::Bits128 => u128::MAX,
+        }
+    }
+}
+
+/// AES in counter (CTR) mode.
+///
+/// This produces a keystream by encrypting successive counter blocks,
+/// which is XORed with the input.  Encryption and decryption are the
+/// same operation.
+///
+/// A counter block must never be reused with a given key.  Note that the
+/// counter wraps: a message longer than 2<sup>bits</sup> blocks
+/// reuses the keystream.
+pub struct Ctr {
+    key: AesKey,
+    counter: u128,
+    width: CounterWidth,
+    keystream: [u8; BLOCK_LEN],
+    used: usize,
+}
+
+impl Ctr {
+    /// Create a new `Ctr` object.
+    ///
+    /// `initial_counter` is the first counter block.
+    pub fn new(key: &[u8], initial_counter: &[u8; BLOCK_LEN], width: CounterWidth) -> Self {
+        let _entry = Entry::new_secret();
+        Self {
+            key: AesKey::new(key),
+            counter: u128::from_be_bytes(*initial_counter),
+            width,
+            keystream: [0u8; BLOCK_LEN],
+            used: BLOCK_LEN,
+        }
+    }
+
+    /// XOR the next `inout.len()` bytes of keystream into `inout`.
+    ///
+    /// This may be called repeatedly to process a message in pieces
+    /// of any length.
+    pub fn apply_keystream(&mut self, inout: &mut [u8]) {
+        let _entry = Entry::new_secret();
+
+        // use any remaining keystream from last time
+        let take = inout.len().min(BLOCK_LEN - self.used);
+        let (start, inout) = inout.split_at_mut(take);
+        xor(start, &self.keystream[self.used..]);
+        self.used += take;
+
+        let mut by8 = inout.chunks_exact_mut(BLOCK_LEN * 8);
+        for blocks in by8.by_ref() {
+            let counters = core::array::from_fn(|_| self.next_counter());
+            aes_ctr::apply_keystream_8(&self.key, &counters, blocks.try_into().unwrap());
+        }
+
+        let mut blocks = by8.into_remainder().chunks_exact_mut(BLOCK_LEN);
+        for block in blocks.by_ref() {
+            self.next_keystream();
+            xor(block, &self.keystream);
+        }
+
+        let end = blocks.into_remainder();
+        if !end.is_empty() {
+            self.next_keystream();
+            xor(end, &self.keystream);
+            self.used = end.len();
+        }
+    }
+
+    fn next_keystream(&mut self) {
+        self.keystream = self.next_counter();
+        self.key.encrypt_block(&mut self.keystream);
+        self.used = BLOCK_LEN;
+    }
+
+    /// Returns the current counter block, and increments it.
+    fn next_counter(&mut self) -> [u8; BLOCK_LEN] {
+        let block = self.counter.to_be_bytes();
+        let mask = self.width.mask();
+        self.counter = (self.counter & !mask) | (self.counter.wrapping_add(1) & mask);
+        block
+    }
+}
+
+impl Drop for Ctr {
+    fn drop(&mut self) {
+        zeroise(&mut self.keystream);
+    }
+}
+
+/// AES in XTS mode, for encrypting storage.
+///
+/// This is described in [IEEE 1619](https://standards.ieee.org/ieee/1619/4205/)
+/// and [SP800-38E](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38e.pdf).
+/// Each data unit (typically a disk sector) is encrypted under a
+/// 16-byte tweak, so equal plaintexts in different sectors give
+/// unrelated ciphertexts.  Data units need not be a whole number
+/// of blocks: the last partial block is handled with ciphertext stealing.
+///
+/// XTS does not detect modification of the ciphertext, and rewriting
+/// a sector reveals which 16-byte blocks of it changed.
+pub struct Xts {
+    enc: AesKey,
+    dec: AesDecryptKey,
+    tweak: AesKey,
+}
+
+impl Xts {
+    /// Create a new `Xts` object.
+    ///
+    /// `key` must be 32 or 64 bytes, corresponding to AES-128-XTS or
+    /// AES-256-XTS.  The first half is the data key and the second half
+    /// is the tweak key; these must differ.  This function panics otherwise.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        assert!(matches!(key.len(), 32 | 64), "invalid aes-xts key length");
+        let (data, tweak) = key.split_at(key.len() / 2);
+        assert!(!ct_equal(data, tweak), "aes-xts key halves must differ");
+
+        let enc = AesKey::new(data);
+        let dec = AesDecryptKey::new(&enc);
+        Self {
+            enc,
+            dec,
+            tweak: AesKey::new(tweak),
+        }
+    }
+
+    /// Encrypts one data unit `inout` in place, using `tweak`.
+    ///
+    /// `inout` must be between 16 bytes and 2<sup>20</sup> blocks
+    /// long, otherwise `Error::WrongLength` is returned.
+    pub fn encrypt(&self, tweak: &[u8; BLOCK_LEN], inout: &mut [u8]) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        check_data_unit_len(inout.len())?;
+        self.encrypt_unit(tweak, inout);
+        Ok(())
+    }
+
+    /// Decrypts one data unit `inout` in place, using `tweak`.
+    ///
+    /// `inout` must be between 16 bytes and 2<sup>20</sup> blocks
+    /// long, otherwise `Error::WrongLength` is returned.
+    pub fn decrypt(&self, tweak: &[u8; BLOCK_LEN], inout: &mut [u8]) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        check_data_unit_len(inout.len())?;
+        self.decrypt_unit(tweak, inout);
+        Ok(())
+    }
+
+    /// Encrypts consecutive sectors in place.
+    ///
+    /// `inout` is split into sectors of `sector_len` bytes.  The first is
+    /// numbered `first_sector`, and the tweak for each is its number as a
+    /// little-endian 128-bit integer, as in IEEE 1619.
+    ///
+    /// `inout` must be a whole number of sectors, and `sector_len` must be
+    /// between 16 bytes and 2<sup>20</sup> blocks, otherwise
+    /// `Error::WrongLength` is returned.
+    pub fn encrypt_sectors(
+        &self,
+        first_sector: u128,
+        sector_len: usize,
+        inout: &mut [u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        check_sectors_len(sector_len, inout.len())?;
+        for (sector, unit) in (first_sector..).zip(inout.chunks_exact_mut(sector_len)) {
+            self.encrypt_unit(&sector.to_le_bytes(), unit);
+        }
+        Ok(())
+    }
+
+    /// Decrypts consecutive sectors in place.
+    ///
+    /// The arguments are as for [`Xts::encrypt_sectors()`].
+    pub fn decrypt_sectors(
+        &self,
+        first_sector: u128,
+        sector_len: usize,
+        inout: &mut [u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        check_sectors_len(sector_len, inout.len())?;
+        for (sector, unit) in (first_sector..).zip(inout.chunks_exact_mut(sector_len)) {
+            self.decrypt_unit(&sector.to_le_bytes(), unit);
+        }
+        Ok(())
+    }
+
+    fn encrypt_unit(&self, tweak: &[u8; BLOCK_LEN], inout: &mut [u8]) {
+        let mut t = *tweak;
+        self.tweak.encrypt_block(&mut t);
+
+        let partial = inout.len() % BLOCK_LEN;
+        if partial == 0 {
+            aes_xts::encrypt(&self.enc, &mut t, inout);
+        } else {
+            let (head, tail) = inout.split_at_mut(inout.len() - BLOCK_LEN - partial);
+            let (last_full, last_partial) = tail.split_at_mut(BLOCK_LEN);
+            aes_xts::encrypt(&self.enc, &mut t, head);
+            aes_xts::encrypt(&self.enc, &mut t, last_full);
+
+            // ciphertext stealing: the final partial ciphertext block
+            // is the start of the previous one, whose tail pads the
+            // final plaintext block.
+            last_full[..partial].swap_with_slice(last_partial);
+            aes_xts::encrypt(&self.enc, &mut t, last_full);
+        }
+        zeroise(&mut t);
+    }
+
+    fn decrypt_unit(&self, tweak: &[u8; BLOCK_LEN], inout: &mut [u8]) {
+        let mut t = *tweak;
+        self.tweak.encrypt_block(&mut t);
+
+        let partial = inout.len() % BLOCK_LEN;
+        if partial == 0 {
+            aes_xts::decrypt(&self.dec, &mut t, inout);
+        } else {
+            let (head, tail) = inout.split_at_mut(inout.len() - BLOCK_LEN - partial);
+            let (last_full, last_partial) = tail.split_at_mut(BLOCK_LEN);
+            aes_xts::decrypt(&self.dec, &mut t, head);
+
+            // the last full ciphertext block was encrypted with the
+            // tweak after this one.
+            let mut next = mul_alpha(&t);
+            aes_xts::decrypt(&self.dec, &mut next, last_full);
+            last_full[..partial].swap_with_slice(last_partial);
+            aes_xts::decrypt(&self.dec, &mut t, last_full);
+            zeroise(&mut next);
+        }
+        zeroise(&mut t);
+    }
+}
+
+/// IEEE 1619 limits a data unit to 2<sup>20</sup> blocks.
+const MAX_DATA_UNIT_LEN: usize = BLOCK_LEN << 20;
+
+fn check_data_unit_len(len: usize) -> Result<(), Error> {
+    match len {
+        BLOCK_LEN..=MAX_DATA_UNIT_LEN => Ok(()),
+        _ => Err(Error::WrongLength),
+    }
+}
+
+fn check_sectors_len(sector_len: usize, len: usize) -> Result<(), Error> {
+    check_data_unit_len(sector_len)?;
+    match len.is_multiple_of(sector_len) {
+        true => Ok(()),
+        false => Err(Error::WrongLength),
+    }
+}
+
+/// Multiplies an XTS tweak by `x`, as a little-endian element of
+/// GF(2<sup>128</sup>).
+fn mul_alpha(t: &[u8; BLOCK_LEN]) -> [u8; BLOCK_LEN] {
+    let v = u128::from_le_bytes(*t);
+    let carry = (v >> 127).wrapping_neg() & 0x87;
+    ((v << 1) ^ carry).to_le_bytes()
+}
+
+/// `a ^= b`, for the length of the shorter.
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= b;
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    // plaintext, key, IV and counter block from SP800-38A appendix F
+    const PLAIN: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+    const KEY_128: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const KEY_192: &str = "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b";
+    const KEY_256: &str = "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+    const IV: &[u8; 16] = b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f";
+    const COUNTER: &[u8; 16] = b"\xf0\xf1\xf2\xf3\xf4\xf5\xf6\xf7\xf8\xf9\xfa\xfb\xfc\xfd\xfe\xff";
+
+    #[test]
+    fn ecb() {
+        // F.1.5 and F.1.6
+        let ecb = Ecb::new(&hex::decode(KEY_256).unwrap());
+        let plain = hex::decode(PLAIN).unwrap();
+        let expected = hex::decode(
+            "f3eed1bdb5d2a03c064b5a7e3db181f8591ccb10d410ed26dc5ba74a31362870\
+             b6ed21b99ca6f4f9f153e7b1beafed1d23304b7a39f9f3ff067d8d8f9e24ecc7",
+        )
+        .unwrap();
+
+        for (plain, expected) in plain.chunks(16).zip(expected.chunks(16)) {
+            let mut block: [u8; 16] = plain.try_into().unwrap();
+            ecb.encrypt_block(&mut block);
+            assert_eq!(block, expected);
+            ecb.decrypt_block(&mut block);
+            assert_eq!(block, plain);
+        }
+    }
+
+    #[test]
+    fn cbc() {
+        // F.2.1 - F.2.4
+        for (key, expected) in [
+            (
+                KEY_128,
+                "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7",
+            ),
+            (
+                KEY_192,
+                "4f021db243bc633d7178183a9fa071e8b4d9ada9ad7dedf4e5e738763f69145a571b242012fb7ae07fa9baac3df102e008b0e27988598881d920a9e64f5615cd",
+            ),
+        ] {
+            let cbc = Cbc::new(&hex::decode(key).unwrap());
+            let mut buf = hex::decode(PLAIN).unwrap();
+            cbc.encrypt(IV, &mut buf).unwrap();
+            assert_eq!(buf, hex::decode(expected).unwrap());
+            cbc.decrypt(IV, &mut buf).unwrap();
+            assert_eq!(buf, hex::decode(PLAIN).unwrap());
+        }
+
+        let cbc = Cbc::new(&hex::decode(KEY_128).unwrap());
+        assert_eq!(
+            cbc.encrypt(IV, &mut [0u8; 15]).unwrap_err(),
+            Error::WrongLength
+        );
+        assert_eq!(
+            cbc.decrypt(IV, &mut [0u8; 17]).unwrap_err(),
+            Error::WrongLength
+        );
+    }
+
+    #[test]
+    fn cbc_padded() {
+        let cbc = Cbc::new(&hex::decode(KEY_128).unwrap());
+
+        for (len, expected) in [
+            (0, "c84af0b613435d5d9182801a9bd9320b"),
+            (1, "0b0c4bf1f6b1c89c6f368a1356299da1"),
+            (15, "0776d387f4931a5bb9f0a0173c912cc7"),
+            (
+                16,
+                "3459d4ca18c19941b910aea3c349077779c7f186e96b8ec805f78916d69b9175",
+            ),
+            (
+                17,
+                "3459d4ca18c19941b910aea3c3490777f9f50d7b88b1716c6dc1f269975197b7",
+            ),
+        ] {
+            let plain = vec![b'p'; len];
+            let mut out = [0u8; 48];
+            let cipher = cbc.encrypt_padded(IV, &plain, &mut out).unwrap();
+            assert_eq!(cipher, hex::decode(expected).unwrap());
+            assert_eq!(cipher.len(), Cbc::padded_len(len));
+
+            let cipher = cipher.to_vec();
+            let mut out = [0u8; 48];
+            assert_eq!(cbc.decrypt_padded(IV, &cipher, &mut out).unwrap(), plain);
+
+            assert_eq!(
+                cbc.encrypt_padded(IV, &plain, &mut out[..cipher.len() - 1])
+                    .unwrap_err(),
+                Error::WrongLength
+            );
+            assert_eq!(
+                cbc.decrypt_padded(IV, &cipher, &mut out[..cipher.len() - 1])
+                    .unwrap_err(),
+                Error::WrongLength
+            );
+        }
+
+        let mut out = [0u8; 32];
+        assert_eq!(
+            cbc.decrypt_padded(IV, &[], &mut out).unwrap_err(),
+            Error::DecryptFailed
+        );
+        assert_eq!(
+            cbc.decrypt_padded(IV, &[0u8; 17], &mut out).unwrap_err(),
+            Error::DecryptFailed
+        );
+    }
+
+    #[test]
+    fn cbc_bad_padding() {
+        let cbc = Cbc::new(&hex::decode(KEY_128).unwrap());
+
+        for padding in [
+            &b"\x00"[..],
+            b"\x11",
+            b"\xff",
+            b"\x01\x02",
+            b"\x02\x03\x03",
+            b"\x03\x02\x03",
+            b"\x10\x10\x10\x10\x10\x10\x10\x10\x10\x10\x10\x10\x10\x10\x10\x0f",
+        ] {
+            let mut buf = [b'p'; 16];
+            buf[16 - padding.len()..].copy_from_slice(padding);
+            cbc.encrypt(IV, &mut buf).unwrap();
+
+            let mut out = [0xffu8; 16];
+            assert_eq!(
+                cbc.decrypt_padded(IV, &buf, &mut out).unwrap_err(),
+                Error::DecryptFailed
+            );
+            assert_eq!(out, [0u8; 16]);
+        }
+    }
+
+    #[test]
+    fn padding_len_exhaustive() {
+        for pad in 0..=255u8 {
+            for len in 1..=16 {
+                let mut block = [0xaa; 16];
+                block[16 - len..].fill(pad);
+                let valid = pad != 0 && usize::from(pad) <= len;
+                assert_eq!(
+                    padding_len(&block),
+                    valid.then_some(usize::from(pad)),
+                    "pad={pad} len={len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ctr() {
+        // F.5.1
+        let expected = hex::decode(
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff\
+             5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee",
+        )
+        .unwrap();
+
+        let mut buf = hex::decode(PLAIN).unwrap();
+        Ctr::new(
+            &hex::decode(KEY_128).unwrap(),
+            COUNTER,
+            CounterWidth::Bits128,
+        )
+        .apply_keystream(&mut buf);
+        assert_eq!(buf, expected);
+
+        // in pieces of varying lengths
+        for step in 1..=33 {
+            let mut ctr = Ctr::new(
+                &hex::decode(KEY_128).unwrap(),
+                COUNTER,
+                CounterWidth::Bits128,
+            );
+            let mut buf = hex::decode(PLAIN).unwrap();
+            for chunk in buf.chunks_mut(step) {
+                ctr.apply_keystream(chunk);
+            }
+            assert_eq!(buf, expected, "step={step}");
+        }
+    }
+
+    #[test]
+    fn ctr_counter_widths() {
+        let key = hex::decode(KEY_128).unwrap();
+        let ecb = Ecb::new(&key);
+        let initial = [0xffu8; 16];
+
+        for (width, second) in [
+            (
+                CounterWidth::Bits32,
+                b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00\x00\x00",
+            ),
+            (
+                CounterWidth::Bits64,
+                b"\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00\x00\x00\x00\x00\x00\x00",
+            ),
+            (
+                CounterWidth::Bits128,
+                b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+            ),
+        ] {
+            let mut buf = [0u8; 32];
+            Ctr::new(&key, &initial, width).apply_keystream(&mut buf);
+
+            let mut expected = initial;
+            ecb.encrypt_block(&mut expected);
+            assert_eq!(buf[..16], expected);
+
+            let mut expected = *second;
+            ecb.encrypt_block(&mut expected);
+            assert_eq!(buf[16..], expected);
+        }
+    }
+
+    #[test]
+    fn ctr_long() {
+        let key = hex::decode(KEY_128).unwrap();
+        let ecb = Ecb::new(&key);
+        let mut initial = [0xffu8; 16];
+        initial[15] = 0xfc;
+
+        for width in [
+            CounterWidth::Bits32,
+            CounterWidth::Bits64,
+            CounterWidth::Bits128,
+        ] {
+            let mut expected = [b'p'; 4164];
+            let mut counter = u128::from_be_bytes(initial);
+            for chunk in expected.chunks_mut(16) {
+                let mut keystream = counter.to_be_bytes();
+                ecb.encrypt_block(&mut keystream);
+                xor(chunk, &keystream);
+                counter = (counter & !width.mask()) | (counter.wrapping_add(1) & width.mask());
+            }
+
+            for step in [1, 15, 16, 129, 4164] {
+                let mut ctr = Ctr::new(&key, &initial, width);
+                let mut buf = [b'p'; 4164];
+                for chunk in buf.chunks_mut(step) {
+                    ctr.apply_keystream(chunk);
+                }
+                assert_eq!(buf, expected, "width={width:?} step={step}");
+            }
+        }
+    }
+
+    #[test]
+    fn xts_ieee1619() {
+        // vector 2
+        let xts = Xts::new(
+            &hex::decode("1111111111111111111111111111111122222222222222222222222222222222")
+                .unwrap(),
+        );
+        let mut tweak = [0u8; 16];
+        tweak[..5].copy_from_slice(&[0x33; 5]);
+        let mut buf = [0x44u8; 32];
+        xts.encrypt(&tweak, &mut buf).unwrap();
+        assert_eq!(
+            buf.to_vec(),
+            hex::decode("c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0")
+                .unwrap()
+        );
+        xts.decrypt(&tweak, &mut buf).unwrap();
+        assert_eq!(buf, [0x44u8; 32]);
+    }
+
+    #[test]
+    fn xts_ciphertext_stealing() {
+        let xts = Xts::new(
+            &hex::decode("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0")
+                .unwrap(),
+        );
+        let tweak = 0x9a78563412u128.to_le_bytes();
+
+        for expected in [
+            "641610679dcbf92e505c41333fb06c2a95",
+            "223a725cbcd4dc647b9a9826d54c99c895c8",
+            "0d39809a65c1d55501960b671d4b8b6b95c871",
+            "a8ba0048d75084603eb8423a09b7bf7595c871f6",
+        ] {
+            let expected = hex::decode(expected).unwrap();
+            let plain = (0..expected.len() as u8).collect::<Vec<_>>();
+            let mut buf = plain.clone();
+            xts.encrypt(&tweak, &mut buf).unwrap();
+            assert_eq!(buf, expected);
+            xts.decrypt(&tweak, &mut buf).unwrap();
+            assert_eq!(buf, plain);
+        }
+    }
+
+    #[test]
+    fn xts_long() {
+        let mut key = [b'k'; 64];
+        key[32..].fill(b'K');
+        let xts = Xts::new(&key);
+        let tweak = 1234u128.to_le_bytes();
+
+        // not divisible by 128 or 16 to cover by-8, single block
+        // and ciphertext stealing cases
+        let mut buf = [b'p'; 4164];
+        xts.encrypt(&tweak, &mut buf).unwrap();
+        assert_eq!(
+            &buf[..],
+            &include_bytes!("../testdata/aes-xts-ciphertext.bin")[..]
+        );
+        xts.decrypt(&tweak, &mut buf).unwrap();
+        assert_eq!(buf, [b'p'; 4164]);
+
+        // and shorter lengths
+        for len in 16..=300 {
+            let mut one = buf[..len].to_vec();
+            xts.encrypt(&tweak, &mut one).unwrap();
+            xts.decrypt(&tweak, &mut one).unwrap();
+            assert_eq!(one, &buf[..len]);
+        }
+    }
+
+    #[test]
+    fn xts_sectors() {
+        let xts = Xts::new(&(0u8..32).collect::<Vec<_>>());
+        let expected = hex::decode(
+            "8de81e2e5f728507aabd9b2cde7997c787a08dd21d1d4571ab56af7d1ac784d9\
+             72b4b63406c2a11553f6328a3c6f722aaecff9c90a50d5f279a5ff9f199bd286\
+             f85901eb838616ceb5aa9acf6e1f70907dd32176301a0f458347d52c460ef9b5",
+        )
+        .unwrap();
+        let plain = [[0u8; 32], [1u8; 32], [2u8; 32]].concat();
+        let first = u64::MAX as u128;
+
+        let mut buf = plain.clone();
+        xts.encrypt_sectors(first, 32, &mut buf).unwrap();
+        assert_eq!(buf, expected);
+
+        // equivalent to encrypting each sector separately
+        for (i, sector) in plain.chunks(32).enumerate() {
+            let mut one = sector.to_vec();
+            xts.encrypt(&(first + i as u128).to_le_bytes(), &mut one)
+                .unwrap();
+            assert_eq!(one, &expected[i * 32..(i + 1) * 32]);
+        }
+
+        xts.decrypt_sectors(first, 32, &mut buf).unwrap();
+        assert_eq!(buf, plain);
+    }
+
+    #[test]
+    fn xts_lengths() {
+        let xts = Xts::new(&[[1u8; 32], [2u8; 32]].concat());
+        let tweak = [0u8; 16];
+        let mut buf = vec![0u8; MAX_DATA_UNIT_LEN + 1];
+
+        assert_eq!(xts.encrypt(&tweak, &mut buf[..15]), Err(Error::WrongLength));
+        assert_eq!(xts.decrypt(&tweak, &mut buf[..15]), Err(Error::WrongLength));
+        assert_eq!(xts.encrypt(&tweak, &mut buf), Err(Error::WrongLength));
+        assert_eq!(xts.decrypt(&tweak, &mut buf), Err(Error::WrongLength));
+        xts.encrypt(&tweak, &mut buf[..MAX_DATA_UNIT_LEN]).unwrap();
+
+        assert_eq!(
+            xts.encrypt_sectors(0, 0, &mut buf[..512]),
+            Err(Error::WrongLength)
+        );
+        assert_eq!(
+            xts.encrypt_sectors(0, 15, &mut buf[..30]),
+            Err(Error::WrongLength)
+        );
+        assert_eq!(
+            xts.decrypt_sectors(0, 512, &mut buf[..1000]),
+            Err(Error::WrongLength)
+        );
+        xts.encrypt_sectors(0, 512, &mut buf[..0]).unwrap();
+        xts.decrypt_sectors(0, 512, &mut buf[..1024]).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn xts_rejects_equal_key_halves() {
+        Xts::new(&[0x55u8; 32]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn xts_rejects_aes192() {
+        Xts::new(&[0x55u8; 48]);
+    }
+
+    #[test]
+    fn xts_mul_alpha() {
+        let mut t = [0u8; 16];
+        t[15] = 0x80;
+        let mut expected = [0u8; 16];
+        expected[0] = 0x87;
+        assert_eq!(mul_alpha(&t), expected);
+
+        t[15] = 0x40;
+        expected[0] = 0;
+        expected[15] = 0x80;
+        assert_eq!(mul_alpha(&t), expected);
+    }
+}
@@ -0,0 +1,429 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::Error;
+use crate::low::{AesKey, Entry, aes_ctr, ct_equal, zeroise};
+
+/// An AES-CCM key.
+///
+/// CCM is CTR-mode encryption combined with a CBC-MAC.  It is described in
+/// [SP800-38C](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38c.pdf)
+/// and [RFC3610](https://datatracker.ietf.org/doc/html/rfc3610).
+///
+/// The tag length is fixed when the key is created.  For example,
+/// "CCM_8" (as in `TLS_AES_128_CCM_8_SHA256`) uses an 8-byte tag.
+///
+/// The nonce may be between 7 and 13 bytes long.  Shorter nonces
+/// allow longer messages: a message must be shorter than
+/// 2<sup>8 * (15 - nonce length)</sup> bytes.
+pub struct AesCcm {
+    key: AesKey,
+    tag_len: usize,
+}
+
+impl AesCcm {
+    /// Create a new `AesCcm` object.
+    ///
+    /// `key` must be 16, 24 or 32 bytes, corresponding
+    /// to AES-128, AES-192 or AES-256.  `tag_len` must be
+    /// 4, 6, 8, 10, 12, 14 or 16.  This function panics otherwise.
+    pub fn new(key: &[u8], tag_len: usize) -> Self {
+        let _entry = Entry::new_secret();
+        assert!(
+            (4..=16).contains(&tag_len) && tag_len.is_multiple_of(2),
+            "invalid ccm tag length"
+        );
+        Self {
+            key: AesKey::new(key),
+            tag_len,
+        }
+    }
+
+    /// Encrypts the given message.
+    ///
+    /// On entry, `cipher_inout` contains the plaintext of the message.
+    /// `nonce` contains the nonce, which must be unique for a given key.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    ///
+    /// On exit, `cipher_inout` contains the ciphertext of the message,
+    /// and `tag_out` contains the authentication tag.
+    ///
+    /// `Error::WrongLength` is returned if `nonce` is not between
+    /// 7 and 13 bytes, `tag_out` is not the tag length, or the message
+    /// is too long for the nonce length.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        if tag_out.len() != self.tag_len {
+            return Err(Error::WrongLength);
+        }
+        let ctr0 = self.counter_block(nonce, cipher_inout.len())?;
+
+        let mac = self.cbc_mac(&ctr0, aad, cipher_inout);
+        self.ctr(&ctr0, cipher_inout);
+        tag_out.copy_from_slice(&self.encrypt_mac(&ctr0, mac)[..self.tag_len]);
+        Ok(())
+    }
+
+    /// Decrypts and verifies the given message.
+    ///
+    /// On entry, `cipher_inout` contains the ciphertext of the message.
+    /// `nonce` contains the nonce, which must match what was supplied
+    /// when encrypting this message.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    /// `tag` is the purported authentication tag.
+    ///
+    /// On success, `cipher_inout` contains the plaintext of the message,
+    /// and `Ok(())` is returned.
+    /// Otherwise, `Err(Error::DecryptFailed)` is returned and `cipher_inout`
+    /// is cleared.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let ctr0 = match self.counter_block(nonce, cipher_inout.len()) {
+            Ok(ctr0) if tag.len() == self.tag_len => ctr0,
+            _ => {
+                cipher_inout.fill(0x00);
+                return Err(Error::DecryptFailed);
+            }
+        };
+
+        self.ctr(&ctr0, cipher_inout);
+        let mac = self.cbc_mac(&ctr0, aad, cipher_inout);
+        let actual_tag = self.encrypt_mac(&ctr0, mac);
+
+        if ct_equal(&actual_tag[..self.tag_len], tag) {
+            Ok(())
+        } else {
+            // avoid unauthenticated plaintext leak
+            cipher_inout.fill(0x00);
+            Err(Error::DecryptFailed)
+        }
+    }
+
+    /// Formats the first counter block, Ctr<sub>0</sub>.
+    ///
+    /// This is `q - 1 || nonce || 0`, where `q` is the length of the
+    /// counter field.  This also checks that `nonce` is a valid length,
+    /// and the message length fits in the counter field.
+    fn counter_block(&self, nonce: &[u8], msg_len: usize) -> Result<[u8; 16], Error> {
+        if !(7..=13).contains(&nonce.len()) {
+            return Err(Error::WrongLength);
+        }
+
+        let q = 15 - nonce.len();
+        if q < 8 && (msg_len as u64) >> (q * 8) != 0 {
+            return Err(Error::WrongLength);
+        }
+
+        let mut block = [0u8; 16];
+        block[0] = (q - 1) as u8;
+        block[1..1 + nonce.len()].copy_from_slice(nonce);
+        Ok(block)
+    }
+
+    /// Computes the CBC-MAC over the formatted `aad` and `plain`.
+    ///
+    /// See SP800-38C section A.2.
+    fn cbc_mac(&self, ctr0: &[u8; 16], aad: &[u8], plain: &[u8]) -> [u8; 16] {
+        // B_0 is Ctr_0 with the flags adjusted, and the message length
+        // in place of the counter.
+        let q = ctr0[0] as usize + 1;
+        let mut b0 = *ctr0;
+        b0[0] |= (((self.tag_len - 2) / 2) << 3) as u8;
+        if !aad.is_empty() {
+            b0[0] |= 0x40;
+        }
+        b0[16 - q..].copy_from_slice(&(plain.len() as u64).to_be_bytes()[8 - q..]);
+
+        let mut mac = CbcMac::new(&self.key, &b0);
+
+        if !aad.is_empty() {
+            let len = aad.len() as u64;
+            match len {
+                ..0xff00 => mac.add(&len.to_be_bytes()[6..]),
+                0xff00..=0xffff_ffff => {
+                    mac.add(&[0xff, 0xfe]);
+                    mac.add(&len.to_be_bytes()[4..]);
+                }
+                _ => {
+                    mac.add(&[0xff, 0xff]);
+                    mac.add(&len.to_be_bytes());
+                }
+            }
+            mac.add(aad);
+            mac.pad();
+        }
+
+        mac.add(plain);
+        mac.pad();
+        mac.finish()
+    }
+
+    /// Encrypts `inout` in CTR mode, starting with Ctr<sub>1</sub>.
+    fn ctr(&self, ctr0: &[u8; 16], inout: &mut [u8]) {
+        let mut counter = u128::from_be_bytes(*ctr0);
+        // nb. the message length is checked, so this never
+        // carries into the nonce
+        let mut next_counter = || {
+            counter += 1;
+            counter.to_be_bytes()
+        };
+
+        let mut by8 = inout.chunks_exact_mut(128);
+        for blocks in by8.by_ref() {
+            let counters = core::array::from_fn(|_| next_counter());
+            aes_ctr::apply_keystream_8(&self.key, &counters, blocks.try_into().unwrap());
+        }
+
+        for chunk in by8.into_remainder().chunks_mut(16) {
+            let mut keystream = next_counter();
+            self.key.encrypt_block(&mut keystream);
+            for (x, ks) in chunk.iter_mut().zip(keystream.iter()) {
+                *x ^= *ks;
+            }
+            zeroise(&mut keystream);
+        }
+    }
+
+    /// Encrypts the CBC-MAC result with Ctr<sub>0</sub>, to give the tag.
+    fn encrypt_mac(&self, ctr0: &[u8; 16], mut mac: [u8; 16]) -> [u8; 16] {
+        let mut s0 = *ctr0;
+        self.key.encrypt_block(&mut s0);
+        for (m, s) in mac.iter_mut().zip(s0.iter()) {
+            *m ^= *s;
+        }
+        zeroise(&mut s0);
+        mac
+    }
+}
+
+/// Incremental CBC-MAC computation.
+struct CbcMac<'a> {
+    key: &'a AesKey,
+    y: [u8; 16],
+    used: usize,
+}
+
+impl<'a> CbcMac<'a> {
+    fn new(key: &'a AesKey, b0: &[u8; 16]) -> Self {
+        let mut y = *b0;
+        key.encrypt_block(&mut y);
+        Self { key, y, used: 0 }
+    }
+
+    fn add(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let take = bytes.len().min(16 - self.used);
+            let (chunk, rest) = bytes.split_at(take);
+            for (y, b) in self.y[self.used..].iter_mut().zip(chunk) {
+                *y ^= *b;
+            }
+            self.used += take;
+            bytes = rest;
+
+            if self.used == 16 {
+                self.key.encrypt_block(&mut self.y);
+                self.used = 0;
+            }
+        }
+    }
+
+    /// Completes any partial block, as if it were padded with zeroes.
+    fn pad(&mut self) {
+        if self.used != 0 {
+            self.key.encrypt_block(&mut self.y);
+            self.used = 0;
+        }
+    }
+
+    fn finish(self) -> [u8; 16] {
+        self.y
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sp800_38c_examples() {
+        // appendix C.1 - C.3
+        let key = b"\x40\x41\x42\x43\x44\x45\x46\x47\x48\x49\x4a\x4b\x4c\x4d\x4e\x4f";
+        let aad =
+            b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13";
+        let plain = b"\x20\x21\x22\x23\x24\x25\x26\x27\x28\x29\x2a\x2b\x2c\x2d\x2e\x2f\x30\x31\x32\x33\x34\x35\x36\x37";
+        let nonce = b"\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b";
+
+        for (tag_len, nonce_len, aad_len, plain_len, expected) in [
+            (4, 7, 8, 4, &b"\x71\x62\x01\x5b\x4d\xac\x25\x5d"[..]),
+            (
+                6,
+                8,
+                16,
+                16,
+                b"\xd2\xa1\xf0\xe0\x51\xea\x5f\x62\x08\x1a\x77\x92\x07\x3d\x59\x3d\x1f\xc6\x4f\xbf\xac\xcd",
+            ),
+            (
+                8,
+                12,
+                20,
+                24,
+                b"\xe3\xb2\x01\xa9\xf5\xb7\x1a\x7a\x9b\x1c\xea\xec\xcd\x97\xe7\x0b\x61\x76\xaa\xd9\xa4\x42\x8a\xa5\x48\x43\x92\xfb\xc1\xb0\x99\x51",
+            ),
+        ] {
+            let ccm = AesCcm::new(key, tag_len);
+            let (expected_cipher, expected_tag) = expected.split_at(plain_len);
+
+            let mut cipher = plain[..plain_len].to_vec();
+            let mut tag = vec![0u8; tag_len];
+            ccm.encrypt(&nonce[..nonce_len], &aad[..aad_len], &mut cipher, &mut tag)
+                .unwrap();
+            assert_eq!(cipher, expected_cipher);
+            assert_eq!(tag, expected_tag);
+
+            ccm.decrypt(&nonce[..nonce_len], &aad[..aad_len], &mut cipher, &tag)
+                .unwrap();
+            assert_eq!(cipher, &plain[..plain_len]);
+        }
+    }
+
+    #[test]
+    fn long_aad() {
+        let aad: Vec<u8> = (0..0x10000).map(|i| i as u8).collect();
+
+        // 0xff00 and more bytes of AAD use the 6-byte length encoding
+        let ccm = AesCcm::new(&[b'k'; 24], 16);
+        let mut cipher = [b'p'; 100];
+        let mut tag = [0u8; 16];
+        ccm.encrypt(b"noncenonc", &aad, &mut cipher, &mut tag)
+            .unwrap();
+        assert_eq!(
+            &tag,
+            b"\x78\xed\x4d\xd1\x43\xb3\x95\x21\xd5\xa2\x10\xc5\xa8\xd4\x6e\xa7"
+        );
+        assert_eq!(
+            &cipher[..16],
+            b"\xf0\xa6\x0f\xb0\xd0\x44\xc6\x88\xe6\x0a\x1a\xb4\x50\x47\xfa\x4c"
+        );
+        ccm.decrypt(b"noncenonc", &aad, &mut cipher, &tag).unwrap();
+        assert_eq!(cipher, [b'p'; 100]);
+
+        // and less uses 2 bytes
+        let ccm = AesCcm::new(&[b'k'; 32], 16);
+        let mut cipher = [b'p'; 100];
+        ccm.encrypt(b"noncenonc", &aad[..0xfeff], &mut cipher, &mut tag)
+            .unwrap();
+        assert_eq!(
+            &tag,
+            b"\xc9\x08\x4e\xd8\x42\xbd\xb5\xb0\xc9\x83\xb7\x02\x69\x8c\xe8\x2f"
+        );
+    }
+
+    #[test]
+    fn long_encrypt_test() {
+        let ccm = AesCcm::new(&[b'k'; 16], 16);
+        let mut tag = [0u8; 16];
+        // not divisible by 128 or 16, to cover by-8, single block and trailing cases
+        let mut cipher = [b'p'; 4164];
+        ccm.encrypt(b"noncenonc", b"aad", &mut cipher, &mut tag)
+            .unwrap();
+
+        let expected = include_bytes!("../testdata/aes-ccm-ciphertext.bin");
+        let (expected_cipher, expected_tag) = expected.split_at(expected.len() - 16);
+        assert_eq!(expected_cipher, cipher);
+        assert_eq!(expected_tag, tag);
+
+        ccm.decrypt(b"noncenonc", b"aad", &mut cipher, &tag)
+            .unwrap();
+        assert_eq!(cipher, [b'p'; 4164]);
+    }
+
+    #[test]
+    fn length_checks() {
+        let ccm = AesCcm::new(&[b'k'; 16], 8);
+        let mut tag = [0u8; 8];
+
+        for nonce_len in [0, 6, 14, 16] {
+            let nonce = [0u8; 16];
+            assert_eq!(
+                ccm.encrypt(&nonce[..nonce_len], b"", &mut [], &mut tag)
+                    .unwrap_err(),
+                Error::WrongLength
+            );
+            assert_eq!(
+                ccm.decrypt(&nonce[..nonce_len], b"", &mut [], &tag)
+                    .unwrap_err(),
+                Error::DecryptFailed
+            );
+        }
+
+        assert_eq!(
+            ccm.encrypt(&[0u8; 12], b"", &mut [], &mut [0u8; 16])
+                .unwrap_err(),
+            Error::WrongLength
+        );
+
+        // 13-byte nonces allow messages up to 2^16 - 1 bytes
+        let mut msg = vec![0u8; 0x10000];
+        ccm.encrypt(&[0u8; 13], b"", &mut msg[..0xffff], &mut tag)
+            .unwrap();
+        assert_eq!(
+            ccm.encrypt(&[0u8; 13], b"", &mut msg, &mut tag)
+                .unwrap_err(),
+            Error::WrongLength
+        );
+        ccm.encrypt(&[0u8; 12], b"", &mut msg, &mut tag).unwrap();
+    }
+
+    #[test]
+    fn decrypt_failures() {
+        let ccm = AesCcm::new(&[b'k'; 16], 16);
+        let mut tag = [0u8; 16];
+        let mut cipher = *b"hello world";
+        ccm.encrypt(b"noncenonceno", b"aad", &mut cipher, &mut tag)
+            .unwrap();
+
+        let mut bad = cipher;
+        bad[0] ^= 1;
+        assert_eq!(
+            ccm.decrypt(b"noncenonceno", b"aad", &mut bad, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(bad, [0u8; 11]);
+
+        let mut bad = cipher;
+        assert_eq!(
+            ccm.decrypt(b"noncenonceno", b"aae", &mut bad, &tag),
+            Err(Error::DecryptFailed)
+        );
+
+        let mut bad = cipher;
+        assert_eq!(
+            ccm.decrypt(b"noncenonceno", b"aad", &mut bad, &tag[..15]),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(bad, [0u8; 11]);
+
+        ccm.decrypt(b"noncenonceno", b"aad", &mut cipher, &tag)
+            .unwrap();
+        assert_eq!(&cipher, b"hello world");
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_odd_tag_length() {
+        AesCcm::new(&[b'k'; 16], 5);
+    }
+}
@@ -0,0 +1,198 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::Error;
+use crate::low::{AesKey, Entry, ct_equal, zeroise};
+
+/// An AES-CMAC key.
+///
+/// CMAC is described in [SP800-38B](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-38B.pdf)
+/// and [RFC4493](https://datatracker.ietf.org/doc/html/rfc4493).
+pub struct AesCmac {
+    key: AesKey,
+    k1: [u8; 16],
+    k2: [u8; 16],
+}
+
+impl AesCmac {
+    /// Create a new `AesCmac` object.
+    ///
+    /// `key` must be 16, 24 or 32 bytes, corresponding
+    /// to AES-128, AES-192 or AES-256.  This function
+    /// panics otherwise.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        let key = AesKey::new(key);
+
+        let mut l = [0u8; 16];
+        key.encrypt_block(&mut l);
+        let k1 = dbl(&l);
+        let k2 = dbl(&k1);
+        zeroise(&mut l);
+
+        Self { key, k1, k2 }
+    }
+
+    /// Compute the authentication tag for `message`.
+    pub fn sign(&self, message: &[u8]) -> [u8; 16] {
+        let _entry = Entry::new_secret();
+        let mut ctx = self.context();
+        ctx.update(message);
+        ctx.finish()
+    }
+
+    /// Verify that `tag` is the authentication tag for `message`.
+    ///
+    /// Returns `Err(Error::BadSignature)` if it is not.
+    pub fn verify(&self, message: &[u8], tag: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let actual = self.sign(message);
+        if ct_equal(&actual, tag) {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+
+    pub(super) fn context(&self) -> Context<'_> {
+        Context {
+            cmac: self,
+            x: [0u8; 16],
+            used: 0,
+        }
+    }
+}
+
+impl Drop for AesCmac {
+    fn drop(&mut self) {
+        zeroise(&mut self.k1);
+        zeroise(&mut self.k2);
+    }
+}
+
+/// Incremental CMAC computation.
+///
+/// The final block is treated differently, so a full block
+/// is only encrypted once more input arrives.
+pub(super) struct Context<'a> {
+    cmac: &'a AesCmac,
+    x: [u8; 16],
+    used: usize,
+}
+
+impl Context<'_> {
+    pub(super) fn update(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            if self.used == 16 {
+                self.cmac.key.encrypt_block(&mut self.x);
+                self.used = 0;
+            }
+
+            let take = bytes.len().min(16 - self.used);
+            let (chunk, rest) = bytes.split_at(take);
+            xor(&mut self.x[self.used..], chunk);
+            self.used += take;
+            bytes = rest;
+        }
+    }
+
+    pub(super) fn finish(mut self) -> [u8; 16] {
+        if self.used == 16 {
+            xor(&mut self.x, &self.cmac.k1);
+        } else {
+            self.x[self.used] ^= 0x80;
+            xor(&mut self.x, &self.cmac.k2);
+        }
+        self.cmac.key.encrypt_block(&mut self.x);
+        self.x
+    }
+}
+
+/// Multiplication by `x` in GF(2<sup>128</sup>), as used by CMAC and S2V.
+pub(super) fn dbl(block: &[u8; 16]) -> [u8; 16] {
+    let v = u128::from_be_bytes(*block);
+    let carry = (v >> 127).wrapping_neg() & 0x87;
+    ((v << 1) ^ carry).to_be_bytes()
+}
+
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= *b;
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc4493() {
+        let key = hex::decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let message = hex::decode(
+            "6bc1bee22e409f96e93d7e117393172a\
+                           ae2d8a571e03ac9c9eb76fac45af8e51\
+                           30c81c46a35ce411e5fbc1191a0a52ef\
+                           f69f2445df4f9b17ad2b417be66c3710",
+        )
+        .unwrap();
+        let cmac = AesCmac::new(&key);
+
+        assert_eq!(
+            cmac.k1.to_vec(),
+            hex::decode("fbeed618357133667c85e08f7236a8de").unwrap()
+        );
+        assert_eq!(
+            cmac.k2.to_vec(),
+            hex::decode("f7ddac306ae266ccf90bc11ee46d513b").unwrap()
+        );
+
+        for (len, tag) in [
+            (0, "bb1d6929e95937287fa37d129b756746"),
+            (16, "070a16b46b4d4144f79bdd9dd04a287c"),
+            (40, "dfa66747de9ae63030ca32611497c827"),
+            (64, "51f0bebf7e3b9d92fc49741779363cfe"),
+        ] {
+            let tag = hex::decode(tag).unwrap();
+            assert_eq!(cmac.sign(&message[..len]).to_vec(), tag);
+            cmac.verify(&message[..len], &tag).unwrap();
+        }
+    }
+
+    #[test]
+    fn sp800_38b_aes256() {
+        let cmac = AesCmac::new(
+            &hex::decode("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4")
+                .unwrap(),
+        );
+        assert_eq!(
+            cmac.sign(b"").to_vec(),
+            hex::decode("028962f61b7bf89efc6b551f4667d983").unwrap()
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let cmac = AesCmac::new(&[0x11; 16]);
+        let message = [0x5a; 100];
+        for split in 0..message.len() {
+            let (a, b) = message.split_at(split);
+            let mut ctx = cmac.context();
+            ctx.update(a);
+            ctx.update(b);
+            assert_eq!(ctx.finish(), cmac.sign(&message));
+        }
+    }
+
+    #[test]
+    fn verify_failures() {
+        let cmac = AesCmac::new(&[0x11; 32]);
+        let tag = cmac.sign(b"hello");
+        cmac.verify(b"hello", &tag).unwrap();
+        assert_eq!(cmac.verify(b"hellO", &tag), Err(Error::BadSignature));
+        assert_eq!(cmac.verify(b"hello", &tag[..15]), Err(Error::BadSignature));
+        let mut bad = tag;
+        bad[15] ^= 1;
+        assert_eq!(cmac.verify(b"hello", &bad), Err(Error::BadSignature));
+    }
+}
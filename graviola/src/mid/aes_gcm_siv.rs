@@ -0,0 +1,341 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::Error;
+use crate::low::{AesKey, Entry, Polyval, PolyvalTable, aes_ctr, ct_equal, zeroise};
+
+/// An AES-GCM-SIV key.
+///
+/// AES-GCM-SIV is a nonce-misuse-resistant AEAD: if a nonce is
+/// repeated, the only thing revealed is whether the same message
+/// (with the same AAD) was encrypted.  It is described in
+/// [RFC8452](https://datatracker.ietf.org/doc/html/rfc8452).
+///
+/// Repeated nonces remain undesirable, so use unique nonces where
+/// possible.
+///
+/// - AES-192 is not supported, as it is not defined by RFC8452.
+pub struct AesGcmSiv {
+    key: AesKey,
+}
+
+impl AesGcmSiv {
+    /// Create a new `AesGcmSiv` object.
+    ///
+    /// `key` must be 16 or 32 bytes, corresponding
+    /// to AES-128-GCM-SIV or AES-256-GCM-SIV.  This function panics otherwise.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        assert!(key.len() != 24, "aes-192 not supported by AES-GCM-SIV");
+        Self {
+            key: AesKey::new(key),
+        }
+    }
+
+    /// Encrypts the given message.
+    ///
+    /// On entry, `cipher_inout` contains the plaintext of the message.
+    /// `nonce` contains the nonce, which should be unique for a given key.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    ///
+    /// On exit, `cipher_inout` contains the ciphertext of the message,
+    /// and `tag_out` contains the authentication tag.
+    ///
+    /// `aad` and `cipher_inout` must each be at most 2<sup>36</sup> bytes.
+    /// This function panics otherwise.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8; 12],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 16],
+    ) {
+        let _entry = Entry::new_secret();
+        assert!(aad.len() as u64 <= MAX_LEN && cipher_inout.len() as u64 <= MAX_LEN);
+
+        let (auth_key, enc_key) = self.derive_keys(nonce);
+        *tag_out = tag(&auth_key, &enc_key, nonce, aad, cipher_inout);
+        ctr(&enc_key, tag_out, cipher_inout);
+    }
+
+    /// Decrypts and verifies the given message.
+    ///
+    /// On entry, `cipher_inout` contains the ciphertext of the message.
+    /// `nonce` contains the nonce, which must match what was supplied
+    /// when encrypting this message.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    /// `tag` is the purported authentication tag.
+    ///
+    /// On success, `cipher_inout` contains the plaintext of the message,
+    /// and `Ok(())` is returned.
+    /// Otherwise, `Err(Error::DecryptFailed)` is returned and `cipher_inout`
+    /// is cleared.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; 12],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+
+        let tag: &[u8; 16] = match tag.try_into() {
+            Ok(tag) if aad.len() as u64 <= MAX_LEN && cipher_inout.len() as u64 <= MAX_LEN => tag,
+            _ => {
+                cipher_inout.fill(0x00);
+                return Err(Error::DecryptFailed);
+            }
+        };
+
+        let (auth_key, enc_key) = self.derive_keys(nonce);
+        ctr(&enc_key, tag, cipher_inout);
+        let actual_tag = self::tag(&auth_key, &enc_key, nonce, aad, cipher_inout);
+
+        if ct_equal(&actual_tag, tag) {
+            Ok(())
+        } else {
+            // avoid unauthenticated plaintext leak
+            cipher_inout.fill(0x00);
+            Err(Error::DecryptFailed)
+        }
+    }
+
+    /// Derive the per-nonce message-authentication and message-encryption
+    /// keys.
+    ///
+    /// See RFC8452 section 4.
+    fn derive_keys(&self, nonce: &[u8; 12]) -> (PolyvalTable, AesKey) {
+        let mut keys = [0u8; 16 + 32];
+        let keys_len = 16 + self.key.key_len();
+
+        for (i, half) in keys[..keys_len].chunks_exact_mut(8).enumerate() {
+            let mut block = [0u8; 16];
+            block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+            self.key.encrypt_block(&mut block);
+            half.copy_from_slice(&block[..8]);
+            zeroise(&mut block);
+        }
+
+        let (auth_key, enc_key) = keys[..keys_len].split_at(16);
+        let r = (
+            PolyvalTable::new(auth_key.try_into().unwrap()),
+            AesKey::new(enc_key),
+        );
+        zeroise(&mut keys);
+        r
+    }
+}
+
+/// Compute the tag, over `aad` and the plaintext `plain`.
+fn tag(
+    auth_key: &PolyvalTable,
+    enc_key: &AesKey,
+    nonce: &[u8; 12],
+    aad: &[u8],
+    plain: &[u8],
+) -> [u8; 16] {
+    let mut polyval = Polyval::new(auth_key);
+    polyval.add(aad);
+    polyval.add(plain);
+
+    let mut lengths = [0u8; 16];
+    lengths[..8].copy_from_slice(&((aad.len() * 8) as u64).to_le_bytes());
+    lengths[8..].copy_from_slice(&((plain.len() * 8) as u64).to_le_bytes());
+    polyval.add(&lengths);
+
+    let mut s = polyval.into_bytes();
+    for (s, n) in s.iter_mut().zip(nonce.iter()) {
+        *s ^= *n;
+    }
+    s[15] &= 0x7f;
+    enc_key.encrypt_block(&mut s);
+    s
+}
+
+/// Encrypt or decrypt `inout` in counter mode, starting from `tag`.
+///
+/// Unlike GCM, the counter is the first 32 bits of the block,
+/// little-endian.
+fn ctr(enc_key: &AesKey, tag: &[u8; 16], inout: &mut [u8]) {
+    let mut counter_block = *tag;
+    counter_block[15] |= 0x80;
+    let mut counter = u32::from_le_bytes(counter_block[..4].try_into().unwrap());
+    let mut next_counter = || {
+        let mut block = counter_block;
+        block[..4].copy_from_slice(&counter.to_le_bytes());
+        counter = counter.wrapping_add(1);
+        block
+    };
+
+    let mut by8 = inout.chunks_exact_mut(128);
+    for blocks in by8.by_ref() {
+        let counters = core::array::from_fn(|_| next_counter());
+        aes_ctr::apply_keystream_8(enc_key, &counters, blocks.try_into().unwrap());
+    }
+
+    for chunk in by8.into_remainder().chunks_mut(16) {
+        let mut block = next_counter();
+        enc_key.encrypt_block(&mut block);
+
+        for (x, k) in chunk.iter_mut().zip(block.iter()) {
+            *x ^= *k;
+        }
+    }
+}
+
+/// Maximum length of plaintext and AAD, in bytes.
+const MAX_LEN: u64 = 1 << 36;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc8452_vectors() {
+        struct Case {
+            key: &'static [u8],
+            nonce: &'static [u8; 12],
+            aad: &'static [u8],
+            plain: &'static [u8],
+            cipher_and_tag: &'static [u8],
+        }
+
+        for case in [
+            // C.1
+            Case {
+                key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                aad: b"",
+                plain: b"",
+                cipher_and_tag: b"\xdc\x20\xe2\xd8\x3f\x25\x70\x5b\xb4\x9e\x43\x9e\xca\x56\xde\x25",
+            },
+            Case {
+                key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                aad: b"",
+                plain: b"\x01\x00\x00\x00\x00\x00\x00\x00",
+                cipher_and_tag: b"\xb5\xd8\x39\x33\x0a\xc7\xb7\x86\x57\x87\x82\xff\xf6\x01\x3b\x81\x5b\x28\x7c\x22\x49\x3a\x36\x4c",
+            },
+            Case {
+                key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                aad: b"\x01",
+                plain: b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                cipher_and_tag: b"\x62\x00\x48\xef\x3c\x1e\x73\xe5\x7e\x02\xbb\x85\x62\xc4\x16\xa3\x19\xe7\x3e\x4c\xaa\xc8\xe9\x6a\x1e\xcb\x29\x33\x14\x5a\x1d\x71\xe6\xaf\x6a\x7f\x87\x28\x7d\xa0\x59\xa7\x16\x84\xed\x34\x98\xe1",
+            },
+            // C.2
+            Case {
+                key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                aad: b"",
+                plain: b"",
+                cipher_and_tag: b"\x07\xf5\xf4\x16\x9b\xbf\x55\xa8\x40\x0c\xd4\x7e\xa6\xfd\x40\x0f",
+            },
+            Case {
+                key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+                aad: b"",
+                plain: b"\x01\x00\x00\x00\x00\x00\x00\x00",
+                cipher_and_tag: b"\xc2\xef\x32\x8e\x5c\x71\xc8\x3b\x84\x31\x22\x13\x0f\x73\x64\xb7\x61\xe0\xb9\x74\x27\xe3\xdf\x28",
+            },
+            // C.3: counter wraps
+            Case {
+                key: &[0; 32],
+                nonce: &[0; 12],
+                aad: b"",
+                plain: b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x4d\xb9\x23\xdc\x79\x3e\xe6\x49\x7c\x76\xdc\xc0\x3a\x98\xe1\x08",
+                cipher_and_tag: b"\xf3\xf8\x0f\x2c\xf0\xcb\x2d\xd9\xc5\x98\x4f\xcd\xa9\x08\x45\x6c\xc5\x37\x70\x3b\x5b\xa7\x03\x24\xa6\x79\x3a\x7b\xf2\x18\xd3\xea\xff\xff\xff\xff\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+            },
+        ] {
+            let (expected_cipher, expected_tag) =
+                case.cipher_and_tag.split_at(case.cipher_and_tag.len() - 16);
+            let t = AesGcmSiv::new(case.key);
+
+            let mut cipher = case.plain.to_vec();
+            let mut tag = [0u8; 16];
+            t.encrypt(case.nonce, case.aad, &mut cipher, &mut tag);
+            assert_eq!(cipher, expected_cipher);
+            assert_eq!(tag, expected_tag);
+
+            t.decrypt(case.nonce, case.aad, &mut cipher, &tag).unwrap();
+            assert_eq!(cipher, case.plain);
+        }
+    }
+
+    #[test]
+    fn long_encrypt_test() {
+        let t = AesGcmSiv::new(&[b'k'; 32]);
+        let mut tag = [0u8; 16];
+        let mut cipher = [b'p'; 4164];
+        t.encrypt(b"noncenonceno", b"aad", &mut cipher, &mut tag);
+
+        let expected = include_bytes!("../testdata/aes-gcm-siv-ciphertext.bin");
+        let (expected_cipher, expected_tag) = expected.split_at(expected.len() - 16);
+        assert_eq!(expected_cipher, cipher);
+        assert_eq!(expected_tag, tag);
+    }
+
+    #[test]
+    fn counter_wraps_at_32_bits() {
+        let key = AesKey::new(&[b'k'; 16]);
+        let mut tag = [0x5au8; 16];
+        tag[..4].copy_from_slice(&0xffff_fffcu32.to_le_bytes());
+
+        let mut cipher = [b'p'; 16 * 20];
+        ctr(&key, &tag, &mut cipher);
+
+        let mut counter = 0xffff_fffcu32;
+        for block in cipher.chunks_exact(16) {
+            let mut expected = tag;
+            expected[15] |= 0x80;
+            expected[..4].copy_from_slice(&counter.to_le_bytes());
+            key.encrypt_block(&mut expected);
+            for e in expected.iter_mut() {
+                *e ^= b'p';
+            }
+            assert_eq!(block, expected);
+            counter = counter.wrapping_add(1);
+        }
+    }
+
+    #[test]
+    fn decrypt_failures() {
+        let t = AesGcmSiv::new(&[b'k'; 16]);
+        let mut tag = [0u8; 16];
+        let mut cipher = *b"hello world";
+        t.encrypt(b"noncenonceno", b"aad", &mut cipher, &mut tag);
+
+        let mut bad = cipher;
+        bad[0] ^= 1;
+        assert_eq!(
+            t.decrypt(b"noncenonceno", b"aad", &mut bad, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(bad, [0u8; 11]);
+
+        let mut bad = cipher;
+        assert_eq!(
+            t.decrypt(b"noncenonceno", b"aae", &mut bad, &tag),
+            Err(Error::DecryptFailed)
+        );
+
+        let mut bad = cipher;
+        assert_eq!(
+            t.decrypt(b"noncenonceno", b"aad", &mut bad, &tag[..15]),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(bad, [0u8; 11]);
+
+        t.decrypt(b"noncenonceno", b"aad", &mut cipher, &tag)
+            .unwrap();
+        assert_eq!(&cipher, b"hello world");
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_aes192() {
+        AesGcmSiv::new(&[b'k'; 24]);
+    }
+}
@@ -0,0 +1,415 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::Error;
+use crate::low::{AesDecryptKey, AesKey, Entry, ct_equal, zeroise};
+
+/// An AES key-encryption key, for AES Key Wrap.
+///
+/// This supports AES-KW from [RFC3394](https://datatracker.ietf.org/doc/html/rfc3394),
+/// and AES-KWP (Key Wrap with Padding) from
+/// [RFC5649](https://datatracker.ietf.org/doc/html/rfc5649).  These are
+/// also described in [SP800-38F](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-38F.pdf).
+///
+/// Key wrapping is deterministic: it needs no IV or nonce, and wrapping the
+/// same key twice gives the same result.
+pub struct AesKw {
+    enc: AesKey,
+    dec: AesDecryptKey,
+}
+
+impl AesKw {
+    /// Create a new `AesKw` object.
+    ///
+    /// `key` must be 16, 24 or 32 bytes, corresponding
+    /// to AES-128, AES-192 or AES-256.  This function panics otherwise.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        let enc = AesKey::new(key);
+        let dec = AesDecryptKey::new(&enc);
+        Self { enc, dec }
+    }
+
+    /// Wraps `key_data` using AES-KW.
+    ///
+    /// `key_data` must be a multiple of 8 bytes, and at least 16 bytes,
+    /// otherwise `Error::WrongLength` is returned.
+    ///
+    /// The wrapped key is written to the front of `out`, and that prefix
+    /// is returned.  It is 8 bytes longer than `key_data`.  If `out` is
+    /// not long enough, `Error::WrongLength` is returned.
+    pub fn wrap<'a>(&self, key_data: &[u8], out: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        if key_data.len() < 16 || !key_data.len().is_multiple_of(8) {
+            return Err(Error::WrongLength);
+        }
+
+        let out = out
+            .get_mut(..key_data.len() + 8)
+            .ok_or(Error::WrongLength)?;
+        let (a, r) = out.split_at_mut(8);
+        r.copy_from_slice(key_data);
+        a.copy_from_slice(&self.wrap_blocks(KW_IV, r));
+        Ok(out)
+    }
+
+    /// Unwraps `wrapped` using AES-KW.
+    ///
+    /// The key data is written to the front of `out`, and that prefix
+    /// is returned.  It is 8 bytes shorter than `wrapped`.  If `out` is
+    /// not long enough, `Error::WrongLength` is returned.
+    ///
+    /// `Error::DecryptFailed` is returned if `wrapped` is an invalid
+    /// length, or fails the integrity check.  In that case, `out` is cleared.
+    pub fn unwrap<'a>(&self, wrapped: &[u8], out: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let out = out
+            .get_mut(..wrapped.len().saturating_sub(8))
+            .ok_or(Error::WrongLength)?;
+
+        if wrapped.len() < 24 || !wrapped.len().is_multiple_of(8) {
+            return Err(Error::DecryptFailed);
+        }
+
+        let (a, c) = wrapped.split_at(8);
+        out.copy_from_slice(c);
+        let a = self.unwrap_blocks(a.try_into().unwrap(), out);
+
+        if ct_equal(&a, &KW_IV) {
+            Ok(out)
+        } else {
+            out.fill(0x00);
+            Err(Error::DecryptFailed)
+        }
+    }
+
+    /// Returns the length of the output of [`AesKw::wrap_with_padding()`]
+    /// for `key_data_len` bytes of key data.
+    pub const fn padded_len(key_data_len: usize) -> usize {
+        key_data_len.next_multiple_of(8) + 8
+    }
+
+    /// Wraps `key_data` using AES-KWP.
+    ///
+    /// `key_data` may be any length between 1 and 2<sup>32</sup>-1 bytes,
+    /// otherwise `Error::WrongLength` is returned.
+    ///
+    /// The wrapped key is written to the front of `out`, and that prefix
+    /// is returned.  It is [`AesKw::padded_len()`] bytes long.  If `out` is
+    /// not long enough, `Error::WrongLength` is returned.
+    pub fn wrap_with_padding<'a>(
+        &self,
+        key_data: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let mli = match u32::try_from(key_data.len()) {
+            Ok(0) | Err(_) => return Err(Error::WrongLength),
+            Ok(mli) => mli,
+        };
+
+        let out = out
+            .get_mut(..Self::padded_len(key_data.len()))
+            .ok_or(Error::WrongLength)?;
+
+        let mut aiv = [0u8; 8];
+        aiv[..4].copy_from_slice(&KWP_IV_PREFIX);
+        aiv[4..].copy_from_slice(&mli.to_be_bytes());
+
+        let (a, r) = out.split_at_mut(8);
+        let (data, padding) = r.split_at_mut(key_data.len());
+        data.copy_from_slice(key_data);
+        padding.fill(0x00);
+
+        if r.len() == 8 {
+            // a single block is simply encrypted
+            let mut block = [0u8; 16];
+            block[..8].copy_from_slice(&aiv);
+            block[8..].copy_from_slice(r);
+            self.enc.encrypt_block(&mut block);
+            out.copy_from_slice(&block);
+            zeroise(&mut block);
+        } else {
+            a.copy_from_slice(&self.wrap_blocks(aiv, r));
+        }
+        Ok(out)
+    }
+
+    /// Unwraps `wrapped` using AES-KWP.
+    ///
+    /// The key data is written to the front of `out`, and that prefix
+    /// is returned.  `out` must be at least 8 bytes shorter than `wrapped`,
+    /// otherwise `Error::WrongLength` is returned.
+    ///
+    /// `Error::DecryptFailed` is returned if `wrapped` is an invalid
+    /// length, or fails the integrity check.  In that case, `out` is cleared.
+    pub fn unwrap_with_padding<'a>(
+        &self,
+        wrapped: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let _entry = Entry::new_secret();
+        let out = out
+            .get_mut(..wrapped.len().saturating_sub(8))
+            .ok_or(Error::WrongLength)?;
+
+        if wrapped.len() < 16 || !wrapped.len().is_multiple_of(8) {
+            return Err(Error::DecryptFailed);
+        }
+
+        let a = if wrapped.len() == 16 {
+            let mut block: [u8; 16] = wrapped.try_into().unwrap();
+            self.dec.decrypt_block(&mut block);
+            out.copy_from_slice(&block[8..]);
+            let a = block[..8].try_into().unwrap();
+            zeroise(&mut block);
+            a
+        } else {
+            let (a, c) = wrapped.split_at(8);
+            out.copy_from_slice(c);
+            self.unwrap_blocks(a.try_into().unwrap(), out)
+        };
+
+        match check_kwp_iv(&a, out) {
+            Some(len) => Ok(&out[..len]),
+            None => {
+                out.fill(0x00);
+                Err(Error::DecryptFailed)
+            }
+        }
+    }
+
+    /// The wrapping function W, from RFC3394 section 2.2.1.
+    ///
+    /// `r` is wrapped in place, and the final integrity check
+    /// register is returned.
+    fn wrap_blocks(&self, mut a: [u8; 8], r: &mut [u8]) -> [u8; 8] {
+        let n = r.len() / 8;
+        let mut block = [0u8; 16];
+
+        for j in 0..6 {
+            for (i, ri) in r.chunks_exact_mut(8).enumerate() {
+                block[..8].copy_from_slice(&a);
+                block[8..].copy_from_slice(ri);
+                self.enc.encrypt_block(&mut block);
+
+                let t = (n * j + i + 1) as u64;
+                a = (u64::from_be_bytes(block[..8].try_into().unwrap()) ^ t).to_be_bytes();
+                ri.copy_from_slice(&block[8..]);
+            }
+        }
+
+        zeroise(&mut block);
+        a
+    }
+
+    /// The unwrapping function W<sup>-1</sup>, from RFC3394 section 2.2.2.
+    ///
+    /// `r` is unwrapped in place, and the final integrity check
+    /// register is returned.
+    fn unwrap_blocks(&self, mut a: [u8; 8], r: &mut [u8]) -> [u8; 8] {
+        let n = r.len() / 8;
+        let mut block = [0u8; 16];
+
+        for j in (0..6).rev() {
+            for (i, ri) in r.chunks_exact_mut(8).enumerate().rev() {
+                let t = (n * j + i + 1) as u64;
+                block[..8].copy_from_slice(&(u64::from_be_bytes(a) ^ t).to_be_bytes());
+                block[8..].copy_from_slice(ri);
+                self.dec.decrypt_block(&mut block);
+
+                a = block[..8].try_into().unwrap();
+                ri.copy_from_slice(&block[8..]);
+            }
+        }
+
+        zeroise(&mut block);
+        a
+    }
+}
+
+/// Checks the alternative initial value `a`, and the padding in `r`.
+///
+/// Returns the length of the key data, or `None` if the check fails.
+///
+/// This does not branch on `a` or the contents of `r`.
+fn check_kwp_iv(a: &[u8; 8], r: &[u8]) -> Option<usize> {
+    let prefix_ok = ct_equal(&a[..4], &KWP_IV_PREFIX);
+    let mli = u32::from_be_bytes(a[4..].try_into().unwrap()) as u64;
+    let len = r.len() as u64;
+
+    // the padding is between 0 and 7 bytes: top bit set if not.
+    let padding = len.wrapping_sub(mli);
+    let mut bad = (padding | 7u64.wrapping_sub(padding)) >> 63;
+
+    for (i, byte) in r.iter().enumerate() {
+        // all-ones if this byte should be padding
+        let in_padding = (mli.wrapping_sub(i as u64 + 1) >> 63).wrapping_neg();
+        bad |= in_padding & *byte as u64;
+    }
+
+    match (prefix_ok, bad) {
+        (true, 0) => Some(mli as usize),
+        _ => None,
+    }
+}
+
+/// The default initial value, from RFC3394 section 2.2.3.1.
+const KW_IV: [u8; 8] = [0xa6; 8];
+
+/// The first half of the alternative initial value, from RFC5649 section 3.
+const KWP_IV_PREFIX: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3394() {
+        // sections 4.1 and 4.6
+        for (kek, key_data, expected) in [
+            (
+                &b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f"[..],
+                &b"\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff"[..],
+                &b"\x1f\xa6\x8b\x0a\x81\x12\xb4\x47\xae\xf3\x4b\xd8\xfb\x5a\x7b\x82\x9d\x3e\x86\x23\x71\xd2\xcf\xe5"[..],
+            ),
+            (
+                b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b\x1c\x1d\x1e\x1f",
+                b"\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f",
+                b"\x28\xc9\xf4\x04\xc4\xb8\x10\xf4\xcb\xcc\xb3\x5c\xfb\x87\xf8\x26\x3f\x57\x86\xe2\xd8\x0e\xd3\x26\xcb\xc7\xf0\xe7\x1a\x99\xf4\x3b\xfb\x98\x8b\x9b\x7a\x02\xdd\x21",
+            ),
+        ] {
+            let kw = AesKw::new(kek);
+            let mut out = [0u8; 40];
+            assert_eq!(kw.wrap(key_data, &mut out).unwrap(), expected);
+
+            let mut out = [0u8; 40];
+            assert_eq!(kw.unwrap(expected, &mut out).unwrap(), key_data);
+
+            let mut tampered = expected.to_vec();
+            tampered[expected.len() - 1] ^= 1;
+            let mut out = [0xffu8; 40];
+            assert_eq!(
+                kw.unwrap(&tampered, &mut out).unwrap_err(),
+                Error::DecryptFailed
+            );
+            assert_eq!(out[..key_data.len()], vec![0u8; key_data.len()]);
+        }
+    }
+
+    #[test]
+    fn kw_lengths() {
+        let kw = AesKw::new(&[0u8; 16]);
+        let mut out = [0u8; 64];
+
+        for len in [0, 8, 15, 17] {
+            assert_eq!(
+                kw.wrap(&[0u8; 32][..len], &mut out).unwrap_err(),
+                Error::WrongLength
+            );
+        }
+        assert_eq!(
+            kw.wrap(&[0u8; 16], &mut out[..23]).unwrap_err(),
+            Error::WrongLength
+        );
+
+        for len in [0, 8, 16, 23, 25] {
+            assert_eq!(
+                kw.unwrap(&[0u8; 32][..len], &mut out).unwrap_err(),
+                Error::DecryptFailed
+            );
+        }
+        assert_eq!(
+            kw.unwrap(&[0u8; 24], &mut out[..15]).unwrap_err(),
+            Error::WrongLength
+        );
+    }
+
+    #[test]
+    fn rfc5649() {
+        // section 6, plus a single full semiblock
+        let kek = b"\x58\x40\xdf\x6e\x29\xb0\x2a\xf1\xab\x49\x3b\x70\x5b\xf1\x6e\xa1\xae\x83\x38\xf4\xdc\xc1\x76\xa8";
+        let kw = AesKw::new(kek);
+
+        for (key_data, expected) in [
+            (
+                &b"\xc3\x7b\x7e\x64\x92\x58\x43\x40\xbe\xd1\x22\x07\x80\x89\x41\x15\x50\x68\xf7\x38"[..],
+                &b"\x13\x8b\xde\xaa\x9b\x8f\xa7\xfc\x61\xf9\x77\x42\xe7\x22\x48\xee\x5a\xe6\xae\x53\x60\xd1\xae\x6a\x5f\x54\xf3\x73\xfa\x54\x3b\x6a"[..],
+            ),
+            (
+                b"\x46\x6f\x72\x50\x61\x73\x69",
+                b"\xaf\xbe\xb0\xf0\x7d\xfb\xf5\x41\x92\x00\xf2\xcc\xb5\x0b\xb2\x4f",
+            ),
+            (
+                b"\x01\x01\x01\x01\x01\x01\x01\x01",
+                b"\xbf\xb7\xac\xce\x21\x87\x09\x2d\xd4\xf1\xda\xce\x12\x1c\x6f\x99",
+            ),
+        ] {
+            let mut out = [0u8; 32];
+            let wrapped = kw.wrap_with_padding(key_data, &mut out).unwrap();
+            assert_eq!(wrapped, expected);
+            assert_eq!(wrapped.len(), AesKw::padded_len(key_data.len()));
+
+            let mut out = [0u8; 32];
+            assert_eq!(kw.unwrap_with_padding(expected, &mut out).unwrap(), key_data);
+
+            let mut tampered = expected.to_vec();
+            tampered[0] ^= 1;
+            assert_eq!(
+                kw.unwrap_with_padding(&tampered, &mut out).unwrap_err(),
+                Error::DecryptFailed
+            );
+        }
+    }
+
+    #[test]
+    fn kwp_checks_padding_and_length() {
+        let kw = AesKw::new(&[b'k'; 32]);
+
+        // construct wrappings with valid integrity check, but possibly
+        // invalid MLI or padding
+        for (mli, fill, valid) in [
+            (0u32, 0u8, false),
+            (8, 0, false),
+            (9, 0, true),
+            (16, 0, true),
+            (17, 0, false),
+            (9, 1, false),
+            (16, 1, true),
+        ] {
+            let mut aiv = [0u8; 8];
+            aiv[..4].copy_from_slice(&KWP_IV_PREFIX);
+            aiv[4..].copy_from_slice(&mli.to_be_bytes());
+
+            let mut wrapped = [fill; 24];
+            let a = kw.wrap_blocks(aiv, &mut wrapped[8..]);
+            wrapped[..8].copy_from_slice(&a);
+
+            let mut out = [0u8; 16];
+            let result = kw.unwrap_with_padding(&wrapped, &mut out);
+            if valid {
+                assert_eq!(result.unwrap(), &[fill; 16][..mli as usize]);
+            } else {
+                assert_eq!(result.unwrap_err(), Error::DecryptFailed);
+            }
+        }
+
+        let mut out = [0u8; 32];
+        assert_eq!(
+            kw.wrap_with_padding(&[], &mut out).unwrap_err(),
+            Error::WrongLength
+        );
+        assert_eq!(
+            kw.wrap_with_padding(&[0u8; 9], &mut out[..23]).unwrap_err(),
+            Error::WrongLength
+        );
+        for len in [0, 8, 17] {
+            assert_eq!(
+                kw.unwrap_with_padding(&[0u8; 24][..len], &mut out)
+                    .unwrap_err(),
+                Error::DecryptFailed
+            );
+        }
+    }
+}
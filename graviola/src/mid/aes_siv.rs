@@ -0,0 +1,284 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use super::aes_cmac::{AesCmac, dbl};
+use crate::Error;
+use crate::low::{AesKey, Entry, ct_equal, zeroise};
+
+/// An AES-SIV key.
+///
+/// SIV is a deterministic, misuse-resistant AEAD, described in
+/// [RFC5297](https://datatracker.ietf.org/doc/html/rfc5297).
+/// Encrypting the same plaintext with the same associated data
+/// always gives the same ciphertext, so it is suitable for
+/// key wrapping, or where equal plaintexts must be detectable
+/// (for example, an encrypted database column that is searched).
+///
+/// The associated data is a list of separate items.  For
+/// nonce-based use, pass the nonce as the last item.
+pub struct AesSiv {
+    mac: AesCmac,
+    ctr: AesKey,
+}
+
+impl AesSiv {
+    /// Create a new `AesSiv` object.
+    ///
+    /// `key` must be 32, 48 or 64 bytes, corresponding
+    /// to AES-128, AES-192 or AES-256.  This function
+    /// panics otherwise.
+    pub fn new(key: &[u8]) -> Self {
+        let _entry = Entry::new_secret();
+        assert!(
+            matches!(key.len(), 32 | 48 | 64),
+            "invalid aes-siv key length"
+        );
+        let (mac, ctr) = key.split_at(key.len() / 2);
+        Self {
+            mac: AesCmac::new(mac),
+            ctr: AesKey::new(ctr),
+        }
+    }
+
+    /// Encrypts the given message.
+    ///
+    /// On entry, `cipher_inout` contains the plaintext of the message.
+    /// `aad` is the additionally-authenticated data, as a list of items.
+    /// It may be empty.
+    ///
+    /// On exit, `cipher_inout` contains the ciphertext of the message,
+    /// and `tag_out` contains the synthetic IV.  The RFC5297 output
+    /// is `tag_out || cipher_inout`.
+    ///
+    /// `Error::WrongLength` is returned if `aad` has more than
+    /// 126 items.
+    pub fn encrypt(
+        &self,
+        aad: &[&[u8]],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 16],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        if aad.len() > MAX_AAD_ITEMS {
+            return Err(Error::WrongLength);
+        }
+
+        *tag_out = self.s2v(aad, cipher_inout);
+        self.apply_ctr(tag_out, cipher_inout);
+        Ok(())
+    }
+
+    /// Decrypts and verifies the given message.
+    ///
+    /// On entry, `cipher_inout` contains the ciphertext of the message.
+    /// `aad` is the additionally-authenticated data, which must match
+    /// what was supplied when encrypting this message.
+    /// `tag` is the purported synthetic IV.
+    ///
+    /// On success, `cipher_inout` contains the plaintext of the message,
+    /// and `Ok(())` is returned.
+    /// Otherwise, `Err(Error::DecryptFailed)` is returned and `cipher_inout`
+    /// is cleared.
+    pub fn decrypt(&self, aad: &[&[u8]], cipher_inout: &mut [u8], tag: &[u8]) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let iv: &[u8; 16] = match tag.try_into() {
+            Ok(iv) if aad.len() <= MAX_AAD_ITEMS => iv,
+            _ => {
+                cipher_inout.fill(0x00);
+                return Err(Error::DecryptFailed);
+            }
+        };
+
+        self.apply_ctr(iv, cipher_inout);
+        let actual_tag = self.s2v(aad, cipher_inout);
+
+        if ct_equal(&actual_tag, tag) {
+            Ok(())
+        } else {
+            // avoid unauthenticated plaintext leak
+            cipher_inout.fill(0x00);
+            Err(Error::DecryptFailed)
+        }
+    }
+
+    /// The S2V function, over `aad` items followed by `plain`.
+    ///
+    /// See RFC5297 section 2.4.
+    fn s2v(&self, aad: &[&[u8]], plain: &[u8]) -> [u8; 16] {
+        let mut d = self.mac.sign(&[0u8; 16]);
+
+        for item in aad {
+            d = dbl(&d);
+            xor(&mut d, &self.mac.sign(item));
+        }
+
+        let mut ctx = self.mac.context();
+        if plain.len() >= 16 {
+            // T = plain xorend D
+            let (head, tail) = plain.split_at(plain.len() - 16);
+            ctx.update(head);
+            xor(&mut d, tail);
+        } else {
+            // T = dbl(D) xor pad(plain)
+            d = dbl(&d);
+            xor(&mut d, plain);
+            d[plain.len()] ^= 0x80;
+        }
+        ctx.update(&d);
+        zeroise(&mut d);
+        ctx.finish()
+    }
+
+    /// Encrypts (or decrypts) `inout` in CTR mode, starting with
+    /// `iv` with two bits cleared.
+    fn apply_ctr(&self, iv: &[u8; 16], inout: &mut [u8]) {
+        let mut counter = u128::from_be_bytes(*iv) & !(1 << 63 | 1 << 31);
+
+        for chunk in inout.chunks_mut(16) {
+            let mut keystream = counter.to_be_bytes();
+            self.ctr.encrypt_block(&mut keystream);
+            xor(chunk, &keystream);
+            zeroise(&mut keystream);
+            counter = counter.wrapping_add(1);
+        }
+    }
+}
+
+/// S2V supports at most 127 items, including the plaintext.
+const MAX_AAD_ITEMS: usize = 126;
+
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= *b;
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc5297_deterministic() {
+        let siv = AesSiv::new(
+            &hex::decode("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff")
+                .unwrap(),
+        );
+        let aad = hex::decode("101112131415161718191a1b1c1d1e1f2021222324252627").unwrap();
+        check(
+            &siv,
+            &[&aad],
+            &hex::decode("112233445566778899aabbccddee").unwrap(),
+            "85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c",
+        );
+    }
+
+    #[test]
+    fn rfc5297_nonce_based() {
+        let siv = AesSiv::new(
+            &hex::decode("7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f")
+                .unwrap(),
+        );
+        let ad1 = hex::decode(
+            "00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100",
+        )
+        .unwrap();
+        let ad2 = hex::decode("102030405060708090a0").unwrap();
+        let nonce = hex::decode("09f911029d74e35bd84156c5635688c0").unwrap();
+        check(
+            &siv,
+            &[&ad1, &ad2, &nonce],
+            b"this is some plaintext to encrypt using SIV-AES",
+            "7bdb6e3b432667eb06f4d14bff2fbd0f\
+             cb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d",
+        );
+    }
+
+    #[test]
+    fn other_lengths() {
+        let key = (0u8..64).collect::<Vec<_>>();
+        let siv = AesSiv::new(&key);
+        check(&siv, &[], b"", "d4fc53b9c44c2aeea87bfb8c983b136c");
+        check(
+            &siv,
+            &[b"", b"ad"],
+            &[b'x'; 40],
+            "b5c6842563b9f32e4d2668bb63e57ff1\
+             8753d764526bff3a18a6089b683d20e5efb8dfeff8409621f90a4da77b1631f9bc9e00d1d85b4d3e",
+        );
+
+        let siv = AesSiv::new(&key[..48]);
+        check(
+            &siv,
+            &[b"ad"],
+            &[b'y'; 15],
+            "2ede52d401fc224fdbad601cb3eb72c2704d7999e8dba4624d5fc455a3e567",
+        );
+    }
+
+    #[test]
+    fn decrypt_failures() {
+        let siv = AesSiv::new(&[0x11; 32]);
+        let mut tag = [0u8; 16];
+        let mut ct = *b"hello world";
+        siv.encrypt(&[b"aad"], &mut ct, &mut tag).unwrap();
+
+        let mut buf = ct;
+        assert_eq!(
+            siv.decrypt(&[b"aad", b""], &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+
+        let mut buf = ct;
+        assert_eq!(
+            siv.decrypt(&[b"aad"], &mut buf, &tag[..15]),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+
+        let mut buf = ct;
+        buf[0] ^= 1;
+        assert_eq!(
+            siv.decrypt(&[b"aad"], &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 11]);
+
+        let mut buf = ct;
+        siv.decrypt(&[b"aad"], &mut buf, &tag).unwrap();
+        assert_eq!(&buf, b"hello world");
+    }
+
+    #[test]
+    fn aad_item_limit() {
+        let siv = AesSiv::new(&[0x11; 32]);
+        let mut tag = [0u8; 16];
+        let aad = [b"".as_slice(); 127];
+        assert_eq!(
+            siv.encrypt(&aad, &mut [], &mut tag),
+            Err(Error::WrongLength)
+        );
+        siv.encrypt(&aad[..126], &mut [], &mut tag).unwrap();
+        siv.decrypt(&aad[..126], &mut [], &tag).unwrap();
+        assert_eq!(siv.decrypt(&aad, &mut [], &tag), Err(Error::DecryptFailed));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_aes_key_length() {
+        AesSiv::new(&[0u8; 16]);
+    }
+
+    fn check(siv: &AesSiv, aad: &[&[u8]], plain: &[u8], expected: &str) {
+        let expected = hex::decode(expected).unwrap();
+        let mut tag = [0u8; 16];
+        let mut buf = plain.to_vec();
+        siv.encrypt(aad, &mut buf, &mut tag).unwrap();
+        assert_eq!(&expected[..16], &tag);
+        assert_eq!(&expected[16..], &buf);
+
+        siv.decrypt(aad, &mut buf, &tag).unwrap();
+        assert_eq!(plain, &buf);
+    }
+}
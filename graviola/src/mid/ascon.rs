@@ -0,0 +1,411 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! The Ascon hash functions: Ascon-Hash256, Ascon-XOF128 and Ascon-CXOF128.
+//!
+//! See <https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-232.pdf>
+//!
+//! These are lightweight hash functions, intended for constrained
+//! devices.  Elsewhere, prefer SHA-2 or SHA-3.
+//!
+//! The Ascon authenticated encryption scheme is
+//! [`AsconAead128`](crate::aead::AsconAead128).
+
+use crate::Error;
+use crate::low::{Blockwise, ascon_permute};
+
+/// A context for incremental computation of Ascon-Hash256.
+#[derive(Clone)]
+pub struct AsconHash256Context {
+    sponge: Sponge,
+}
+
+impl AsconHash256Context {
+    /// Start a new Ascon-Hash256 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(HASH256_INITIAL),
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Complete the Ascon-Hash256 computation, returning the hash output.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let mut digest = [0u8; Self::OUTPUT_SZ];
+        self.sponge.absorb_final().read(&mut digest);
+        digest
+    }
+
+    /// The internal block size (rate) of Ascon-Hash256.
+    pub const BLOCK_SZ: usize = RATE;
+
+    /// The output size of Ascon-Hash256.
+    pub const OUTPUT_SZ: usize = 32;
+}
+
+impl Default for AsconHash256Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A context for incremental absorption of Ascon-XOF128 input.
+///
+/// Once all input is absorbed, use [`AsconXof128Context::finalize_xof`] to
+/// obtain an [`AsconXof128`] from which output can be read.
+#[derive(Clone)]
+pub struct AsconXof128Context {
+    sponge: Sponge,
+}
+
+impl AsconXof128Context {
+    /// Start a new Ascon-XOF128 computation.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(XOF128_INITIAL),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Complete absorption, returning an [`AsconXof128`] for reading output.
+    pub fn finalize_xof(self) -> AsconXof128 {
+        self.sponge.absorb_final()
+    }
+}
+
+impl Default for AsconXof128Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A context for incremental absorption of Ascon-CXOF128 input.
+///
+/// Once all input is absorbed, use [`AsconCxof128Context::finalize_xof`] to
+/// obtain an [`AsconXof128`] from which output can be read.
+#[derive(Clone)]
+pub struct AsconCxof128Context {
+    sponge: Sponge,
+}
+
+impl AsconCxof128Context {
+    /// Start a new Ascon-CXOF128 computation.
+    ///
+    /// `customization` is a caller-chosen domain separator.  It may
+    /// be empty, but that is distinct from Ascon-XOF128.
+    ///
+    /// Returns [`Error::WrongLength`] if `customization` is longer
+    /// than 256 bytes.
+    pub fn new(customization: &[u8]) -> Result<Self, Error> {
+        if customization.len() > MAX_CUSTOMIZATION_LEN {
+            return Err(Error::WrongLength);
+        }
+
+        let mut sponge = Sponge::new(CXOF128_INITIAL);
+        sponge.absorb(&((customization.len() as u64) * 8).to_le_bytes());
+        sponge.absorb(customization);
+        sponge.absorb_padding();
+        Ok(Self { sponge })
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Complete absorption, returning an [`AsconXof128`] for reading output.
+    pub fn finalize_xof(self) -> AsconXof128 {
+        self.sponge.absorb_final()
+    }
+}
+
+/// Output of Ascon-XOF128 or Ascon-CXOF128.
+///
+/// This has incremental output behaviour.
+#[derive(Clone)]
+pub struct AsconXof128 {
+    s: [u64; 5],
+    buffer: [u8; RATE],
+    buffer_offset: usize,
+}
+
+impl AsconXof128 {
+    /// Read data from this instance into `output`.
+    ///
+    /// This does not fail.  It always fills `output`.
+    pub fn read(&mut self, output: &mut [u8]) {
+        let buffered = Ord::min(RATE - self.buffer_offset, output.len());
+        let (head, output) = output.split_at_mut(buffered);
+        head.copy_from_slice(&self.buffer[self.buffer_offset..self.buffer_offset + buffered]);
+        self.buffer_offset += buffered;
+
+        let (blocks, tail) = output.as_chunks_mut::<RATE>();
+        for block in blocks {
+            *block = self.squeeze_block();
+        }
+
+        if !tail.is_empty() {
+            self.buffer = self.squeeze_block();
+            self.buffer_offset = tail.len();
+            tail.copy_from_slice(&self.buffer[..tail.len()]);
+        }
+    }
+
+    fn squeeze_block(&mut self) -> [u8; RATE] {
+        let block = self.s[0].to_le_bytes();
+        ascon_permute::<12>(&mut self.s);
+        block
+    }
+}
+
+/// The absorbing phase of the Ascon-Hash256 and Ascon-XOF128 sponge.
+#[derive(Clone)]
+struct Sponge {
+    s: [u64; 5],
+    buffer: Blockwise<RATE>,
+}
+
+impl Sponge {
+    const fn new(initial: [u64; 5]) -> Self {
+        Self {
+            s: initial,
+            buffer: Blockwise::new(),
+        }
+    }
+
+    fn absorb(&mut self, bytes: &[u8]) {
+        let bytes = self.buffer.add_leading(bytes);
+
+        if let Some(block) = self.buffer.take() {
+            self.absorb_block(&block);
+        }
+
+        let (blocks, remainder) = bytes.as_chunks();
+        for block in blocks {
+            self.absorb_block(block);
+        }
+
+        self.buffer.add_trailing(remainder);
+    }
+
+    /// Absorb the padded final block of the input so far.
+    fn absorb_padding(&mut self) {
+        let used = self.buffer.peek_remaining().unwrap_or_default();
+        let mut block = [0u8; RATE];
+        block[..used.len()].copy_from_slice(used);
+        block[used.len()] = 0x01;
+        self.absorb_block(&block);
+        self.buffer = Blockwise::new();
+    }
+
+    fn absorb_final(mut self) -> AsconXof128 {
+        self.absorb_padding();
+        AsconXof128 {
+            s: self.s,
+            buffer: [0u8; RATE],
+            buffer_offset: RATE,
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8; RATE]) {
+        self.s[0] ^= u64::from_le_bytes(*block);
+        ascon_permute::<12>(&mut self.s);
+    }
+}
+
+const RATE: usize = 8;
+
+/// SP800-232 limits the customization string to 2048 bits.
+const MAX_CUSTOMIZATION_LEN: usize = 256;
+
+/// `Ascon-p[12]` applied to each function's initial value.
+const HASH256_INITIAL: [u64; 5] = [
+    0x9b1e_5494_e934_d681,
+    0x4bc3_a01e_3337_51d2,
+    0xae65_396c_6b34_b81a,
+    0x3c7f_d4a4_d56a_4db3,
+    0x1a5c_4649_06c5_976d,
+];
+
+const XOF128_INITIAL: [u64; 5] = [
+    0xda82_ce76_8d94_47eb,
+    0xcc7c_e6c7_5f1e_f969,
+    0xe750_8fd7_8008_5631,
+    0x0ee0_ea53_416b_58cc,
+    0xe054_7524_db6f_0bde,
+];
+
+const CXOF128_INITIAL: [u64; 5] = [
+    0x6755_27c2_a0e8_de03,
+    0x43d1_2d7d_c037_7bbc,
+    0xe990_1dec_426e_81b5,
+    0x2ab1_4907_7207_80b6,
+    0x8f3f_1d02_d432_bc46,
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_states() {
+        for (iv, initial) in [
+            (0x0000_0801_00cc_0002, HASH256_INITIAL),
+            (0x0000_0800_00cc_0003, XOF128_INITIAL),
+            (0x0000_0800_00cc_0004, CXOF128_INITIAL),
+        ] {
+            let mut s = [iv, 0, 0, 0, 0];
+            ascon_permute::<12>(&mut s);
+            assert_eq!(s, initial);
+        }
+    }
+
+    #[test]
+    fn hash256() {
+        for (len, expected) in [
+            (
+                0,
+                "0b3be5850f2f6b98caf29f8fdea89b64a1fa70aa249b8f839bd53baa304d92b2",
+            ),
+            (
+                1,
+                "0728621035af3ed2bca03bf6fde900f9456f5330e4b5ee23e7f6a1e70291bc80",
+            ),
+            (
+                7,
+                "3e4d273ba69b3b9c53216107e88b75cdbeedbcbf8faf0219c3928ab62b116577",
+            ),
+            (
+                8,
+                "b88e497ae8e6fb641b87ef622eb8f2fca0ed95383f7ffebe167acf1099ba764f",
+            ),
+            (
+                9,
+                "94269c30e0296e1ec86655041841823efa1927f520fd58c8e9bce6197878c1a6",
+            ),
+            (
+                64,
+                "a6f241bea5d16405812c06019d9f72d60132bd7c089c60549b2e56bb01c64f48",
+            ),
+            (
+                100,
+                "a4bc453c84f824f10092e8e9031799957e984a29bbae5e84345e82f48dd71192",
+            ),
+        ] {
+            let message = counting(0, len);
+            let mut ctx = AsconHash256Context::new();
+            ctx.update(&message);
+            assert_eq!(
+                ctx.finish().to_vec(),
+                hex::decode(expected).unwrap(),
+                "length {len}"
+            );
+
+            // and incrementally
+            for split in 0..len {
+                let mut ctx = AsconHash256Context::new();
+                ctx.update(&message[..split]);
+                ctx.update(&message[split..]);
+                assert_eq!(ctx.finish().to_vec(), hex::decode(expected).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn xof128() {
+        let mut ctx = AsconXof128Context::new();
+        ctx.update(b"");
+        let mut output = [0u8; 64];
+        ctx.finalize_xof().read(&mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex::decode(
+                "473d5e6164f58b39dfd84aacdb8ae42ec2d91fed33388ee0d960d9b3993295c6\
+                 ad77855a5d3b13fe6ad9e6098988373af7d0956d05a8f1665d2c67d1a3ad10ff"
+            )
+            .unwrap()
+        );
+
+        let expected = hex::decode(
+            "816fa0f1ecf91988bad311b02a6b009a44dbc9a70430093d7c3fc47d9c72879b\
+             af008653e573c52a280c8ffcc84b995cbd3bb81bbb902a8dcca7e7f6804b3499\
+             0007ee83b7ecfb6432f01c003112debdedb65a554dcca34b86c3323915ee9fb5\
+             cc33f44f",
+        )
+        .unwrap();
+        let mut ctx = AsconXof128Context::new();
+        ctx.update(&counting(0, 10));
+        let xof = ctx.finalize_xof();
+
+        let mut output = [0u8; 100];
+        xof.clone().read(&mut output);
+        assert_eq!(output.to_vec(), expected);
+
+        // read in irregular pieces
+        for step in 1..20 {
+            let mut xof = xof.clone();
+            let mut output = [0u8; 100];
+            for chunk in output.chunks_mut(step) {
+                xof.read(chunk);
+            }
+            assert_eq!(output.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn cxof128() {
+        let mut ctx = AsconCxof128Context::new(b"").unwrap();
+        ctx.update(b"");
+        let mut output = [0u8; 64];
+        ctx.finalize_xof().read(&mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex::decode(
+                "4f50159ef70bb3dad8807e034eaebd44c4fa2cbbc8cf1f05511ab66cdcc52990\
+                 5ca12083fc186ad899b270b1473dc5f7ec88d1052082dcdfe69fb75d269e7b74"
+            )
+            .unwrap()
+        );
+
+        let mut ctx = AsconCxof128Context::new(&counting(0x10, 10)).unwrap();
+        ctx.update(&counting(0, 17));
+        let mut output = [0u8; 40];
+        ctx.finalize_xof().read(&mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex::decode(
+                "814fda7d925c94cdb05db66f8c07549b0459dc307b2768a7ebcf9b90c2c55ceb\
+                 9e1790c725b0502a"
+            )
+            .unwrap()
+        );
+
+        let mut ctx = AsconCxof128Context::new(&counting(0x10, 256)).unwrap();
+        ctx.update(&counting(0, 8));
+        let mut output = [0u8; 32];
+        ctx.finalize_xof().read(&mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex::decode("002a03e46fc8bb1b6a64dd7a6387269281ac95444228c32cf526528dc0aa4546")
+                .unwrap()
+        );
+
+        assert!(matches!(
+            AsconCxof128Context::new(&[0u8; 257]),
+            Err(Error::WrongLength)
+        ));
+    }
+
+    fn counting(start: u8, len: usize) -> Vec<u8> {
+        (0..len).map(|i| start.wrapping_add(i as u8)).collect()
+    }
+}
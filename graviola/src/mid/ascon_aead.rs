@@ -0,0 +1,304 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+use crate::Error;
+use crate::low::{Entry, ascon_permute, ct_equal, zeroise};
+
+/// An Ascon-AEAD128 key.
+///
+/// See [SP800-232](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-232.pdf).
+///
+/// This is a lightweight AEAD, intended for interoperating with
+/// constrained devices.  Elsewhere, prefer
+/// [`ChaCha20Poly1305`](crate::aead::ChaCha20Poly1305) or
+/// [`AesGcm`](crate::aead::AesGcm).
+pub struct AsconAead128 {
+    key: [u8; 16],
+}
+
+impl AsconAead128 {
+    /// Create a new [`AsconAead128`] from 16 bytes of key material.
+    pub fn new(key: [u8; 16]) -> Self {
+        Self { key }
+    }
+
+    /// Encrypt the given message.
+    ///
+    /// On entry, `cipher_inout` contains the plaintext of the message.
+    /// `nonce` contains the nonce, which must be unique for a given key.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    ///
+    /// On exit, `cipher_inout` contains the ciphertext of the message,
+    /// and `tag_out` contains the authentication tag.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag_out: &mut [u8; 16],
+    ) {
+        let _entry = Entry::new_secret();
+        let mut s = self.start(nonce, aad);
+
+        let (blocks, last) = cipher_inout.as_chunks_mut::<RATE>();
+        for block in blocks {
+            let plain = *block;
+            absorb(&mut s, &plain);
+            *block = squeeze(&s);
+            ascon_permute::<8>(&mut s);
+        }
+
+        let plain = pad(last);
+        absorb(&mut s, &plain);
+        last.copy_from_slice(&squeeze(&s)[..last.len()]);
+
+        *tag_out = self.finish(s);
+    }
+
+    /// Decrypts and verifies the given message.
+    ///
+    /// On entry, `cipher_inout` contains the ciphertext of the message.
+    /// `nonce` contains the nonce, which must match what was supplied
+    /// when encrypting this message.
+    /// `aad` is the additionally-authenticated data.  It may be empty.
+    /// `tag` is the purported authentication tag.
+    ///
+    /// On success, `cipher_inout` contains the plaintext of the message,
+    /// and `Ok(())` is returned.
+    /// Otherwise, `Err(Error::DecryptFailed)` is returned and `cipher_inout`
+    /// is cleared.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; 16],
+        aad: &[u8],
+        cipher_inout: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let _entry = Entry::new_secret();
+        let mut s = self.start(nonce, aad);
+
+        let (blocks, last) = cipher_inout.as_chunks_mut::<RATE>();
+        for block in blocks {
+            let cipher = *block;
+            *block = squeeze(&s);
+            xor(block, &cipher);
+            s[0] = u64::from_le_bytes(cipher[..8].try_into().unwrap());
+            s[1] = u64::from_le_bytes(cipher[8..].try_into().unwrap());
+            ascon_permute::<8>(&mut s);
+        }
+
+        // the padded plaintext is absorbed, which leaves the
+        // ciphertext in the rate (and the padding bit)
+        let mut plain = squeeze(&s);
+        xor(&mut plain[..last.len()], last);
+        last.copy_from_slice(&plain[..last.len()]);
+        let plain = pad(last);
+        absorb(&mut s, &plain);
+
+        let actual_tag = self.finish(s);
+        if ct_equal(&actual_tag, tag) {
+            Ok(())
+        } else {
+            // avoid unauthenticated plaintext leak
+            cipher_inout.fill(0x00);
+            Err(Error::DecryptFailed)
+        }
+    }
+
+    /// Initializes the state, and absorbs `aad`.
+    fn start(&self, nonce: &[u8; 16], aad: &[u8]) -> [u64; 5] {
+        let (k0, k1) = self.key_words();
+        let mut s = [
+            IV,
+            k0,
+            k1,
+            u64::from_le_bytes(nonce[..8].try_into().unwrap()),
+            u64::from_le_bytes(nonce[8..].try_into().unwrap()),
+        ];
+        ascon_permute::<12>(&mut s);
+        s[3] ^= k0;
+        s[4] ^= k1;
+
+        if !aad.is_empty() {
+            let (blocks, last) = aad.as_chunks::<RATE>();
+            for block in blocks {
+                absorb(&mut s, block);
+                ascon_permute::<8>(&mut s);
+            }
+            absorb(&mut s, &pad(last));
+            ascon_permute::<8>(&mut s);
+        }
+
+        // domain separation between `aad` and the message
+        s[4] ^= 1 << 63;
+        s
+    }
+
+    /// Computes the tag, consuming the state.
+    fn finish(&self, mut s: [u64; 5]) -> [u8; 16] {
+        let (k0, k1) = self.key_words();
+        s[2] ^= k0;
+        s[3] ^= k1;
+        ascon_permute::<12>(&mut s);
+
+        let mut tag = [0u8; 16];
+        tag[..8].copy_from_slice(&(s[3] ^ k0).to_le_bytes());
+        tag[8..].copy_from_slice(&(s[4] ^ k1).to_le_bytes());
+        zeroise(&mut s);
+        tag
+    }
+
+    fn key_words(&self) -> (u64, u64) {
+        (
+            u64::from_le_bytes(self.key[..8].try_into().unwrap()),
+            u64::from_le_bytes(self.key[8..].try_into().unwrap()),
+        )
+    }
+}
+
+impl Drop for AsconAead128 {
+    fn drop(&mut self) {
+        zeroise(&mut self.key);
+    }
+}
+
+/// XORs `block` into the rate of `s`.
+fn absorb(s: &mut [u64; 5], block: &[u8; RATE]) {
+    s[0] ^= u64::from_le_bytes(block[..8].try_into().unwrap());
+    s[1] ^= u64::from_le_bytes(block[8..].try_into().unwrap());
+}
+
+/// Returns the rate of `s`.
+fn squeeze(s: &[u64; 5]) -> [u8; RATE] {
+    let mut block = [0u8; RATE];
+    block[..8].copy_from_slice(&s[0].to_le_bytes());
+    block[8..].copy_from_slice(&s[1].to_le_bytes());
+    block
+}
+
+/// Pads a partial block, which may be empty.
+fn pad(partial: &[u8]) -> [u8; RATE] {
+    let mut block = [0u8; RATE];
+    block[..partial.len()].copy_from_slice(partial);
+    block[partial.len()] = 0x01;
+    block
+}
+
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= *b;
+    }
+}
+
+const RATE: usize = 16;
+
+const IV: u64 = 0x0000_1000_808c_0001;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sp800_232_kat() {
+        let k = key();
+        let mut tag = [0u8; 16];
+        k.encrypt(&nonce(), b"", &mut [], &mut tag);
+        assert_eq!(
+            tag.to_vec(),
+            hex::decode("4f9c278211bec9316bf68f46ee8b2ec6").unwrap()
+        );
+        k.decrypt(&nonce(), b"", &mut [], &tag).unwrap();
+    }
+
+    #[test]
+    fn lengths() {
+        for (aad_len, plain_len, expected) in [
+            (1, 0, "da6672b6a11055633125f9173bf76365"),
+            (0, 1, "88268f4425b231192f35875762964ff814"),
+            (
+                16,
+                16,
+                "b77c8c75625bf74160e54bcf9b2e1a7ef8b8985fbe1afb7f4f035b72c5cba55b",
+            ),
+            (
+                17,
+                15,
+                "d70c83c8940ef3e0808eb1f882adea69f12227a2b8053a3e3eab176754b76b",
+            ),
+            (
+                15,
+                17,
+                "c79ab1ac0c387bf9b0819db6a9c21071b331d6014e631dd7a6ae8654996d6ca23a",
+            ),
+            (
+                33,
+                40,
+                "622071b7947b1f1dc8becbca7b8e1f22f9a1a03f11dc2a62c366c2cb678b58a1\
+                 4f72020deae98a205653457f758a33f9ed8a0d993483d06a",
+            ),
+        ] {
+            let k = key();
+            let aad = counting(0x80, aad_len);
+            let plain = counting(0x40, plain_len);
+            let expected = hex::decode(expected).unwrap();
+
+            let mut buf = plain.clone();
+            let mut tag = [0u8; 16];
+            k.encrypt(&nonce(), &aad, &mut buf, &mut tag);
+            assert_eq!(&buf, &expected[..plain_len]);
+            assert_eq!(&tag, &expected[plain_len..]);
+
+            k.decrypt(&nonce(), &aad, &mut buf, &tag).unwrap();
+            assert_eq!(buf, plain);
+        }
+    }
+
+    #[test]
+    fn decrypt_failures() {
+        let k = key();
+        let mut tag = [0u8; 16];
+        let mut ct = *b"hello world, hello world";
+        k.encrypt(&nonce(), b"aad", &mut ct, &mut tag);
+
+        let mut buf = ct;
+        assert_eq!(
+            k.decrypt(&nonce(), b"aaD", &mut buf, &tag),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 24]);
+
+        let mut buf = ct;
+        assert_eq!(
+            k.decrypt(&nonce(), b"aad", &mut buf, &tag[..15]),
+            Err(Error::DecryptFailed)
+        );
+        assert_eq!(buf, [0u8; 24]);
+
+        for i in [0, 16, 23] {
+            let mut buf = ct;
+            buf[i] ^= 1;
+            assert_eq!(
+                k.decrypt(&nonce(), b"aad", &mut buf, &tag),
+                Err(Error::DecryptFailed)
+            );
+            assert_eq!(buf, [0u8; 24]);
+        }
+
+        let mut buf = ct;
+        k.decrypt(&nonce(), b"aad", &mut buf, &tag).unwrap();
+        assert_eq!(&buf, b"hello world, hello world");
+    }
+
+    fn key() -> AsconAead128 {
+        AsconAead128::new(counting(0, 16).try_into().unwrap())
+    }
+
+    fn nonce() -> [u8; 16] {
+        counting(0x10, 16).try_into().unwrap()
+    }
+
+    fn counting(start: u8, len: usize) -> Vec<u8> {
+        (0..len).map(|i| start.wrapping_add(i as u8)).collect()
+    }
+}
@@ -0,0 +1,540 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! The BLAKE2b and BLAKE2s hash functions.
+//!
+//! These are described in [RFC7693](https://datatracker.ietf.org/doc/html/rfc7693).
+//! Keyed hashing, variable output lengths, and the salt and
+//! personalization fields of the parameter block (from the
+//! [BLAKE2 paper](https://www.blake2.net/blake2.pdf)) are supported.
+//! Tree hashing is not.
+
+use crate::Error;
+use crate::low::{blake2b_compress, blake2s_compress, zeroise};
+
+/// A context for incremental computation of BLAKE2b.
+#[derive(Clone)]
+pub struct Blake2bContext {
+    h: [u64; 8],
+    buffer: [u8; Self::BLOCK_SZ],
+    used: usize,
+    counter: u128,
+    output_len: usize,
+}
+
+impl Blake2bContext {
+    /// Start a new unkeyed BLAKE2b hash computation, with an
+    /// output of `output_len` bytes.
+    ///
+    /// `Error::OutOfRange` is returned if `output_len` is not between
+    /// 1 and 64 inclusive.
+    pub fn new(output_len: usize) -> Result<Self, Error> {
+        Self::with_params(&[], &[], &[], output_len)
+    }
+
+    /// Start a new keyed BLAKE2b hash computation, with an
+    /// output of `output_len` bytes.
+    ///
+    /// This is BLAKE2b's MAC mode.  `key` may be up to 64 bytes, and an
+    /// empty `key` is the same as [`Blake2bContext::new()`].
+    ///
+    /// `Error::WrongLength` is returned if `key` is too long, and
+    /// `Error::OutOfRange` if `output_len` is not between 1 and 64
+    /// inclusive.
+    pub fn new_keyed(key: &[u8], output_len: usize) -> Result<Self, Error> {
+        Self::with_params(key, &[], &[], output_len)
+    }
+
+    /// Start a new BLAKE2b hash computation, with a `key` (which may be
+    /// empty), `salt` and `personalization`, and an output of `output_len`
+    /// bytes.
+    ///
+    /// `salt` and `personalization` may be up to 16 bytes; shorter
+    /// values are padded with zero bytes.
+    ///
+    /// `Error::WrongLength` is returned if `key`, `salt` or `personalization`
+    /// is too long, and `Error::OutOfRange` if `output_len` is not between
+    /// 1 and 64 inclusive.
+    pub fn with_params(
+        key: &[u8],
+        salt: &[u8],
+        personalization: &[u8],
+        output_len: usize,
+    ) -> Result<Self, Error> {
+        if key.len() > Self::MAX_KEY_SZ
+            || salt.len() > Self::SALT_SZ
+            || personalization.len() > Self::PERSONALIZATION_SZ
+        {
+            return Err(Error::WrongLength);
+        }
+        if !(1..=Self::MAX_OUTPUT_SZ).contains(&output_len) {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut h = IV_B;
+        // parameter block: digest length, key length, fanout = depth = 1
+        h[0] ^= 0x0101_0000 ^ ((key.len() as u64) << 8) ^ output_len as u64;
+        for (h, word) in h[4..6].iter_mut().zip(words_b(salt)) {
+            *h ^= word;
+        }
+        for (h, word) in h[6..8].iter_mut().zip(words_b(personalization)) {
+            *h ^= word;
+        }
+
+        let mut ctx = Self {
+            h,
+            buffer: [0u8; Self::BLOCK_SZ],
+            used: 0,
+            counter: 0,
+            output_len,
+        };
+
+        // the key is padded to form the first block
+        if !key.is_empty() {
+            let mut block = [0u8; Self::BLOCK_SZ];
+            block[..key.len()].copy_from_slice(key);
+            ctx.update(&block);
+            zeroise(&mut block);
+        }
+
+        Ok(ctx)
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            // the final block is treated differently, so a full
+            // buffer is only compressed once we know more follows.
+            if self.used == Self::BLOCK_SZ {
+                self.counter += Self::BLOCK_SZ as u128;
+                blake2b_compress(&mut self.h, &self.buffer, self.counter, false);
+                self.used = 0;
+            }
+
+            let take = core::cmp::min(bytes.len(), Self::BLOCK_SZ - self.used);
+            let (taken, rest) = bytes.split_at(take);
+            self.buffer[self.used..self.used + take].copy_from_slice(taken);
+            self.used += take;
+            bytes = rest;
+        }
+    }
+
+    /// Complete the BLAKE2b computation, writing the output to `out`.
+    ///
+    /// `out` must be the `output_len` given when this context was
+    /// made.  This function panics otherwise.
+    pub fn finish(mut self, out: &mut [u8]) {
+        assert_eq!(out.len(), self.output_len);
+        self.counter += self.used as u128;
+        self.buffer[self.used..].fill(0);
+        blake2b_compress(&mut self.h, &self.buffer, self.counter, true);
+        zeroise(&mut self.buffer);
+
+        for (out, h) in out.chunks_mut(8).zip(self.h.iter()) {
+            out.copy_from_slice(&h.to_le_bytes()[..out.len()]);
+        }
+    }
+
+    /// The internal block size of BLAKE2b.
+    pub const BLOCK_SZ: usize = 128;
+
+    /// The maximum output size of BLAKE2b.
+    pub const MAX_OUTPUT_SZ: usize = 64;
+
+    /// The maximum key size of BLAKE2b.
+    pub const MAX_KEY_SZ: usize = 64;
+
+    /// The maximum salt size of BLAKE2b.
+    pub const SALT_SZ: usize = 16;
+
+    /// The maximum personalization size of BLAKE2b.
+    pub const PERSONALIZATION_SZ: usize = 16;
+}
+
+/// A context for incremental computation of BLAKE2s.
+#[derive(Clone)]
+pub struct Blake2sContext {
+    h: [u32; 8],
+    buffer: [u8; Self::BLOCK_SZ],
+    used: usize,
+    counter: u64,
+    output_len: usize,
+}
+
+impl Blake2sContext {
+    /// Start a new unkeyed BLAKE2s hash computation, with an
+    /// output of `output_len` bytes.
+    ///
+    /// `Error::OutOfRange` is returned if `output_len` is not between
+    /// 1 and 32 inclusive.
+    pub fn new(output_len: usize) -> Result<Self, Error> {
+        Self::with_params(&[], &[], &[], output_len)
+    }
+
+    /// Start a new keyed BLAKE2s hash computation, with an
+    /// output of `output_len` bytes.
+    ///
+    /// This is BLAKE2s's MAC mode.  `key` may be up to 32 bytes, and an
+    /// empty `key` is the same as [`Blake2sContext::new()`].
+    ///
+    /// `Error::WrongLength` is returned if `key` is too long, and
+    /// `Error::OutOfRange` if `output_len` is not between 1 and 32
+    /// inclusive.
+    pub fn new_keyed(key: &[u8], output_len: usize) -> Result<Self, Error> {
+        Self::with_params(key, &[], &[], output_len)
+    }
+
+    /// Start a new BLAKE2s hash computation, with a `key` (which may be
+    /// empty), `salt` and `personalization`, and an output of `output_len`
+    /// bytes.
+    ///
+    /// `salt` and `personalization` may be up to 8 bytes; shorter
+    /// values are padded with zero bytes.
+    ///
+    /// `Error::WrongLength` is returned if `key`, `salt` or `personalization`
+    /// is too long, and `Error::OutOfRange` if `output_len` is not between
+    /// 1 and 32 inclusive.
+    pub fn with_params(
+        key: &[u8],
+        salt: &[u8],
+        personalization: &[u8],
+        output_len: usize,
+    ) -> Result<Self, Error> {
+        if key.len() > Self::MAX_KEY_SZ
+            || salt.len() > Self::SALT_SZ
+            || personalization.len() > Self::PERSONALIZATION_SZ
+        {
+            return Err(Error::WrongLength);
+        }
+        if !(1..=Self::MAX_OUTPUT_SZ).contains(&output_len) {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut h = IV_S;
+        // parameter block: digest length, key length, fanout = depth = 1
+        h[0] ^= 0x0101_0000 ^ ((key.len() as u32) << 8) ^ output_len as u32;
+        for (h, word) in h[4..6].iter_mut().zip(words_s(salt)) {
+            *h ^= word;
+        }
+        for (h, word) in h[6..8].iter_mut().zip(words_s(personalization)) {
+            *h ^= word;
+        }
+
+        let mut ctx = Self {
+            h,
+            buffer: [0u8; Self::BLOCK_SZ],
+            used: 0,
+            counter: 0,
+            output_len,
+        };
+
+        // the key is padded to form the first block
+        if !key.is_empty() {
+            let mut block = [0u8; Self::BLOCK_SZ];
+            block[..key.len()].copy_from_slice(key);
+            ctx.update(&block);
+            zeroise(&mut block);
+        }
+
+        Ok(ctx)
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            // as for BLAKE2b, a full buffer is only compressed
+            // once we know more follows.
+            if self.used == Self::BLOCK_SZ {
+                self.counter += Self::BLOCK_SZ as u64;
+                blake2s_compress(&mut self.h, &self.buffer, self.counter, false);
+                self.used = 0;
+            }
+
+            let take = core::cmp::min(bytes.len(), Self::BLOCK_SZ - self.used);
+            let (taken, rest) = bytes.split_at(take);
+            self.buffer[self.used..self.used + take].copy_from_slice(taken);
+            self.used += take;
+            bytes = rest;
+        }
+    }
+
+    /// Complete the BLAKE2s computation, writing the output to `out`.
+    ///
+    /// `out` must be the `output_len` given when this context was
+    /// made.  This function panics otherwise.
+    pub fn finish(mut self, out: &mut [u8]) {
+        assert_eq!(out.len(), self.output_len);
+        self.counter += self.used as u64;
+        self.buffer[self.used..].fill(0);
+        blake2s_compress(&mut self.h, &self.buffer, self.counter, true);
+        zeroise(&mut self.buffer);
+
+        for (out, h) in out.chunks_mut(4).zip(self.h.iter()) {
+            out.copy_from_slice(&h.to_le_bytes()[..out.len()]);
+        }
+    }
+
+    /// The internal block size of BLAKE2s.
+    pub const BLOCK_SZ: usize = 64;
+
+    /// The maximum output size of BLAKE2s.
+    pub const MAX_OUTPUT_SZ: usize = 32;
+
+    /// The maximum key size of BLAKE2s.
+    pub const MAX_KEY_SZ: usize = 32;
+
+    /// The maximum salt size of BLAKE2s.
+    pub const SALT_SZ: usize = 8;
+
+    /// The maximum personalization size of BLAKE2s.
+    pub const PERSONALIZATION_SZ: usize = 8;
+}
+
+/// Little-endian words of `bytes`, zero-padded to 16 bytes.
+fn words_b(bytes: &[u8]) -> [u64; 2] {
+    let mut padded = [0u8; 16];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    let (lo, hi) = padded.split_at(8);
+    [
+        u64::from_le_bytes(lo.try_into().unwrap()),
+        u64::from_le_bytes(hi.try_into().unwrap()),
+    ]
+}
+
+/// Little-endian words of `bytes`, zero-padded to 8 bytes.
+fn words_s(bytes: &[u8]) -> [u32; 2] {
+    let mut padded = [0u8; 8];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    let (lo, hi) = padded.split_at(4);
+    [
+        u32::from_le_bytes(lo.try_into().unwrap()),
+        u32::from_le_bytes(hi.try_into().unwrap()),
+    ]
+}
+
+const IV_B: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const IV_S: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc7693_vector() {
+        let mut ctx = Blake2bContext::new(64).unwrap();
+        ctx.update(b"abc");
+        let mut out = [0u8; 64];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xba\x80\xa5\x3f\x98\x1c\x4d\x0d\x6a\x27\x97\xb6\x9f\x12\xf6\xe9\
+               \x4c\x21\x2f\x14\x68\x5a\xc4\xb7\x4b\x12\xbb\x6f\xdb\xff\xa2\xd1\
+               \x7d\x87\xc5\x39\x2a\xab\x79\x2d\xc2\x52\xd5\xde\x45\x33\xcc\x95\
+               \x18\xd3\x8a\xa8\xdb\xf1\x92\x5a\xb9\x23\x86\xed\xd4\x00\x99\x23"
+        );
+    }
+
+    #[test]
+    fn block_boundaries() {
+        // from python's hashlib.blake2b(bytes(range(256)) * 2, digest_size=33)
+        let mut input = [0u8; 512];
+        for (i, b) in input.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let expected = b"\x92\x50\x35\xb7\x2a\x4e\xc4\xb7\xc5\xa0\xa5\xbf\x18\x03\x59\xde\
+                       \xb8\xd0\x9a\xad\x4f\x26\x12\x5c\xd5\xf9\x36\x96\x75\x35\x70\x26\
+                       \x92";
+
+        for split in [0, 1, 127, 128, 129, 256, 511, 512] {
+            let mut ctx = Blake2bContext::new(33).unwrap();
+            ctx.update(&input[..split]);
+            ctx.update(&input[split..]);
+            let mut out = [0u8; 33];
+            ctx.finish(&mut out);
+            assert_eq!(&out, expected);
+        }
+
+        // empty input still has one (all-zero) block
+        let mut out = [0u8; 64];
+        Blake2bContext::new(64).unwrap().finish(&mut out);
+        assert_eq!(&out[..8], b"\x78\x6a\x02\xf7\x42\x01\x59\x03");
+    }
+
+    #[test]
+    fn blake2s_rfc7693_vector() {
+        let mut ctx = Blake2sContext::new(32).unwrap();
+        ctx.update(b"abc");
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x50\x8c\x5e\x8c\x32\x7c\x14\xe2\xe1\xa7\x2b\xa3\x4e\xeb\x45\x2f\
+               \x37\x45\x8b\x20\x9e\xd6\x3a\x29\x4d\x99\x9b\x4c\x86\x67\x59\x82"
+        );
+    }
+
+    #[test]
+    fn blake2s_block_boundaries() {
+        // from python's hashlib.blake2s(bytes(range(256)) * 2, digest_size=17)
+        let mut input = [0u8; 512];
+        for (i, b) in input.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let expected = b"\x20\x11\x61\xa6\x23\xca\x6a\x67\x1e\x15\x4a\x4b\xe1\x8f\x2d\x46\
+                       \x67";
+
+        for split in [0, 1, 63, 64, 65, 128, 511, 512] {
+            let mut ctx = Blake2sContext::new(17).unwrap();
+            ctx.update(&input[..split]);
+            ctx.update(&input[split..]);
+            let mut out = [0u8; 17];
+            ctx.finish(&mut out);
+            assert_eq!(&out, expected);
+        }
+    }
+
+    #[test]
+    fn keyed() {
+        // the final entries of blake2b-kat.txt and blake2s-kat.txt
+        // from the reference implementation
+        let key: [u8; 64] = core::array::from_fn(|i| i as u8);
+        let input: [u8; 255] = core::array::from_fn(|i| i as u8);
+
+        let mut ctx = Blake2bContext::new_keyed(&key, 64).unwrap();
+        ctx.update(&input);
+        let mut out = [0u8; 64];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x14\x27\x09\xd6\x2e\x28\xfc\xcc\xd0\xaf\x97\xfa\xd0\xf8\x46\x5b\
+               \x97\x1e\x82\x20\x1d\xc5\x10\x70\xfa\xa0\x37\x2a\xa4\x3e\x92\x48\
+               \x4b\xe1\xc1\xe7\x3b\xa1\x09\x06\xd5\xd1\x85\x3d\xb6\xa4\x10\x6e\
+               \x0a\x7b\xf9\x80\x0d\x37\x3d\x6d\xee\x2d\x46\xd6\x2e\xf2\xa4\x61"
+        );
+
+        let mut ctx = Blake2sContext::new_keyed(&key[..32], 32).unwrap();
+        ctx.update(&input);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x3f\xb7\x35\x06\x1a\xbc\x51\x9d\xfe\x97\x9e\x54\xc1\xee\x5b\xfa\
+               \xd0\xa9\xd8\x58\xb3\x31\x5b\xad\x34\xbd\xe9\x99\xef\xd7\x24\xdd"
+        );
+
+        // with an empty message, the key block is the final block
+        let mut out = [0u8; 64];
+        Blake2bContext::new_keyed(&key, 64)
+            .unwrap()
+            .finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x10\xeb\xb6\x77\x00\xb1\x86\x8e\xfb\x44\x17\x98\x7a\xcf\x46\x90\
+               \xae\x9d\x97\x2f\xb7\xa5\x90\xc2\xf0\x28\x71\x79\x9a\xaa\x47\x86\
+               \xb5\xe9\x96\xe8\xf0\xf4\xeb\x98\x1f\xc2\x14\xb0\x05\xf4\x2d\x2f\
+               \xf4\x23\x34\x99\x39\x16\x53\xdf\x7a\xef\xcb\xc1\x3f\xc5\x15\x68"
+        );
+
+        let mut out = [0u8; 32];
+        Blake2sContext::new_keyed(&key[..32], 32)
+            .unwrap()
+            .finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x48\xa8\x99\x7d\xa4\x07\x87\x6b\x3d\x79\xc0\xd9\x23\x25\xad\x3b\
+               \x89\xcb\xb7\x54\xd8\x6a\xb7\x1a\xee\x04\x7a\xd3\x45\xfd\x2c\x49"
+        );
+    }
+
+    #[test]
+    fn salt_and_personalization() {
+        // from python's hashlib, which also zero-pads short values
+        let mut ctx = Blake2bContext::with_params(b"key", b"salt", b"personal", 20).unwrap();
+        ctx.update(b"hello world");
+        let mut out = [0u8; 20];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xe2\x55\x3d\x1d\xeb\x24\xc2\x2f\x75\x03\xa3\x0c\xd1\xcb\xb2\x08\
+               \xbe\xb3\x5d\xf4"
+        );
+
+        let mut ctx = Blake2sContext::with_params(b"key", b"salt", b"person", 20).unwrap();
+        ctx.update(b"hello world");
+        let mut out = [0u8; 20];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x4a\x40\x37\xbf\xcf\x32\xa9\xbe\x9d\xe5\x08\x89\x62\xa3\x1b\xb1\
+               \x82\xfc\x6f\x12"
+        );
+
+        let mut out = [0u8; 64];
+        Blake2bContext::with_params(b"", b"0123456789abcdef", b"fedcba9876543210", 64)
+            .unwrap()
+            .finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x7f\xc8\x1f\x1e\x08\x22\xef\xaf\xd5\x20\xda\x3b\x91\x61\x46\x67\
+               \x22\xaa\xa3\x56\x19\xdb\x33\x81\x4a\x80\x5d\x49\x50\x77\x71\x85\
+               \x57\x93\x3a\x3b\x23\x3d\x0e\x42\x98\x69\xc0\x4d\x9c\x36\xc0\xf7\
+               \x8f\x8f\x12\xc5\x6b\xb9\xd9\x9c\x3d\x2f\x89\xc9\x32\x2c\xbf\xbe"
+        );
+
+        let mut out = [0u8; 32];
+        Blake2sContext::with_params(b"", b"01234567", b"76543210", 32)
+            .unwrap()
+            .finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xfd\x88\x65\xc1\xde\x5f\xe4\x2e\xdc\xf6\x9d\x91\xaa\xff\x74\x4d\
+               \x16\x87\xfd\x3c\x5c\x4f\xaf\x48\x6a\xc3\x8a\x1b\x60\xe8\x33\x10"
+        );
+    }
+
+    #[test]
+    fn invalid_params() {
+        assert_eq!(Blake2bContext::new(0).err(), Some(Error::OutOfRange));
+        assert_eq!(Blake2bContext::new(65).err(), Some(Error::OutOfRange));
+        assert_eq!(
+            Blake2bContext::new_keyed(&[0u8; 65], 64).err(),
+            Some(Error::WrongLength)
+        );
+        assert_eq!(
+            Blake2bContext::with_params(b"", &[0u8; 17], b"", 64).err(),
+            Some(Error::WrongLength)
+        );
+        assert_eq!(
+            Blake2bContext::with_params(b"", b"", &[0u8; 17], 64).err(),
+            Some(Error::WrongLength)
+        );
+
+        assert_eq!(Blake2sContext::new(0).err(), Some(Error::OutOfRange));
+        assert_eq!(Blake2sContext::new(33).err(), Some(Error::OutOfRange));
+        assert_eq!(
+            Blake2sContext::new_keyed(&[0u8; 33], 32).err(),
+            Some(Error::WrongLength)
+        );
+        assert_eq!(
+            Blake2sContext::with_params(b"", &[0u8; 9], b"", 32).err(),
+            Some(Error::WrongLength)
+        );
+        assert_eq!(
+            Blake2sContext::with_params(b"", b"", &[0u8; 9], 32).err(),
+            Some(Error::WrongLength)
+        );
+    }
+}
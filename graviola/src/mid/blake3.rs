@@ -0,0 +1,676 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! The BLAKE3 hash function.
+//!
+//! BLAKE3 is described in [its specification](https://github.com/BLAKE3-team/BLAKE3-specs/blob/master/blake3.pdf).
+//! The hash, keyed hash and key derivation modes are supported, with
+//! extendable output.
+//!
+//! Input is divided into 1KiB chunks, which are the leaves of a
+//! binary tree.  Where a single call to `update()` provides several
+//! whole chunks, they are hashed several at a time.  For large inputs,
+//! [`Blake3Context::update_with_threads()`] additionally divides that
+//! work between threads.
+
+use crate::low::{blake3_compress, blake3_hash_chunks, zeroise};
+
+/// A context for incremental computation of BLAKE3.
+#[derive(Clone)]
+pub struct Blake3Context {
+    key: [u32; 8],
+    flags: u32,
+    chunk: ChunkState,
+
+    /// Chaining values of complete subtrees, largest first.
+    ///
+    /// There is one for each set bit of the number of complete
+    /// chunks, and this is only added to when more input follows:
+    /// so none of these can be the root.
+    stack: [[u32; 8]; MAX_DEPTH],
+    stack_len: usize,
+}
+
+impl Blake3Context {
+    /// Start a new BLAKE3 hash computation.
+    pub const fn new() -> Self {
+        Self::with_key_words(IV, 0)
+    }
+
+    /// Start a new keyed BLAKE3 hash computation.
+    ///
+    /// This is BLAKE3's MAC mode.
+    pub fn new_keyed(key: &[u8; Self::KEY_SZ]) -> Self {
+        Self::with_key_words(key_words(key), KEYED_HASH)
+    }
+
+    /// Start a new BLAKE3 key derivation.
+    ///
+    /// `context` should be a hardcoded, globally unique, application-specific
+    /// string.  The key material is then provided with [`Blake3Context::update()`],
+    /// and the derived key read from [`Blake3Context::finalize_xof()`].
+    pub fn new_derive_key(context: &str) -> Self {
+        let mut ctx = Self::with_key_words(IV, DERIVE_KEY_CONTEXT);
+        ctx.update(context.as_bytes());
+        let mut context_key = ctx.finish();
+        let ctx = Self::with_key_words(key_words(&context_key), DERIVE_KEY_MATERIAL);
+        zeroise(&mut context_key);
+        ctx
+    }
+
+    const fn with_key_words(key: [u32; 8], flags: u32) -> Self {
+        Self {
+            key,
+            flags,
+            chunk: ChunkState::new(key, 0),
+            stack: [[0u32; 8]; MAX_DEPTH],
+            stack_len: 0,
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.update_with_threads(bytes, 1);
+    }
+
+    /// Add `bytes` to the ongoing hash computation, using up
+    /// to `threads` threads.
+    ///
+    /// Work is divided between threads in 64KiB units, so this
+    /// is only useful for large `bytes`.  This does not affect the
+    /// output.  Zero is treated as one.
+    pub fn update_with_threads(&mut self, mut bytes: &[u8], threads: usize) {
+        // complete the current chunk first.  a complete chunk is
+        // only finished once we know more input follows: otherwise it
+        // may be the root.
+        if self.chunk.len() > 0 {
+            let take = core::cmp::min(CHUNK_LEN - self.chunk.len(), bytes.len());
+            let (taken, rest) = bytes.split_at(take);
+            self.chunk.update(taken, self.flags);
+            bytes = rest;
+
+            if bytes.is_empty() {
+                return;
+            }
+
+            let cv = self.chunk.output(self.flags).chaining_value();
+            self.push_chunk_cv(cv);
+        }
+
+        // then whole chunks, holding back the last (which may be partial)
+        let whole = bytes.len().saturating_sub(1) / CHUNK_LEN;
+        let (chunks, rest) = bytes.split_at(whole * CHUNK_LEN);
+        let (chunks, _) = chunks.as_chunks::<CHUNK_LEN>();
+        self.push_chunks(chunks, threads.max(1));
+        self.chunk.update(rest, self.flags);
+    }
+
+    /// Complete the BLAKE3 computation, returning the 32-byte hash.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let mut out = [0u8; Self::OUTPUT_SZ];
+        out.copy_from_slice(&self.root_output().root_block(0)[..Self::OUTPUT_SZ]);
+        out
+    }
+
+    /// Complete absorption, returning a [`Blake3Xof`] for reading
+    /// output of any length.
+    ///
+    /// The first 32 bytes of output are the same as [`Blake3Context::finish()`].
+    pub fn finalize_xof(self) -> Blake3Xof {
+        Blake3Xof {
+            output: self.root_output(),
+            counter: 0,
+            buffer: [0u8; BLOCK_LEN],
+            buffer_offset: BLOCK_LEN,
+        }
+    }
+
+    /// Hash whole chunks, which start at the current chunk counter.
+    fn push_chunks(&mut self, mut chunks: &[[u8; CHUNK_LEN]], threads: usize) {
+        // chunks until the chunk counter is aligned for a subtree
+        let misaligned = (self.chunk.counter % SUBTREE_CHUNKS as u64) as usize;
+        if misaligned != 0 {
+            let (head, rest) =
+                chunks.split_at(core::cmp::min(SUBTREE_CHUNKS - misaligned, chunks.len()));
+            self.push_few_chunks(head);
+            chunks = rest;
+        }
+
+        let (subtrees, tail) = chunks.as_chunks::<SUBTREE_CHUNKS>();
+        self.push_subtrees(subtrees, threads);
+        self.push_few_chunks(tail);
+    }
+
+    /// Hash up to `SUBTREE_CHUNKS` whole chunks.
+    fn push_few_chunks(&mut self, chunks: &[[u8; CHUNK_LEN]]) {
+        let mut cvs = [[0u32; 8]; SUBTREE_CHUNKS];
+        let cvs = &mut cvs[..chunks.len()];
+        blake3_hash_chunks(&self.key, chunks, self.chunk.counter, self.flags, cvs);
+        for cv in cvs {
+            self.push_chunk_cv(*cv);
+        }
+    }
+
+    /// Hash whole, aligned subtrees.
+    fn push_subtrees(&mut self, subtrees: &[[[u8; CHUNK_LEN]; SUBTREE_CHUNKS]], threads: usize) {
+        if threads == 1 || subtrees.len() < 2 {
+            for subtree in subtrees {
+                let cv = subtree_cv(&self.key, self.flags, subtree, self.chunk.counter);
+                self.push_subtree_cv(cv);
+            }
+            return;
+        }
+
+        // subtrees are independent, so are divided between threads
+        let mut cvs = vec![[0u32; 8]; subtrees.len()];
+        let per_thread = subtrees.len().div_ceil(threads);
+        let (key, flags, counter) = (&self.key, self.flags, self.chunk.counter);
+        std::thread::scope(|scope| {
+            let mut work = subtrees
+                .chunks(per_thread)
+                .zip(cvs.chunks_mut(per_thread))
+                .enumerate();
+            let (_, ours) = work.next().unwrap();
+            for (i, theirs) in work {
+                let counter = counter + (i * per_thread * SUBTREE_CHUNKS) as u64;
+                scope.spawn(move || subtree_cvs(key, flags, theirs, counter));
+            }
+            subtree_cvs(key, flags, ours, counter);
+        });
+
+        for cv in cvs {
+            self.push_subtree_cv(cv);
+        }
+    }
+
+    fn push_chunk_cv(&mut self, cv: [u32; 8]) {
+        self.push_cv(cv, 0);
+    }
+
+    fn push_subtree_cv(&mut self, cv: [u32; 8]) {
+        self.push_cv(cv, SUBTREE_HEIGHT);
+    }
+
+    /// Add the chaining value of a complete subtree of `2^height`
+    /// chunks, which starts at the current chunk counter.
+    ///
+    /// This merges it with equal-sized subtrees already on the stack,
+    /// and moves the chunk counter past it.
+    fn push_cv(&mut self, mut cv: [u32; 8], height: u32) {
+        let total_chunks = self.chunk.counter + (1 << height);
+        let mut total_subtrees = total_chunks >> height;
+        while total_subtrees & 1 == 0 {
+            self.stack_len -= 1;
+            cv = parent_output(&self.stack[self.stack_len], &cv, &self.key, self.flags)
+                .chaining_value();
+            total_subtrees >>= 1;
+        }
+        self.stack[self.stack_len] = cv;
+        self.stack_len += 1;
+        self.chunk = ChunkState::new(self.key, total_chunks);
+    }
+
+    fn root_output(&self) -> Output {
+        let mut output = self.chunk.output(self.flags);
+        for cv in self.stack[..self.stack_len].iter().rev() {
+            output = parent_output(cv, &output.chaining_value(), &self.key, self.flags);
+        }
+        output
+    }
+
+    /// The output size of BLAKE3 (when not used as an XOF).
+    pub const OUTPUT_SZ: usize = 32;
+
+    /// The internal block size of BLAKE3.
+    pub const BLOCK_SZ: usize = BLOCK_LEN;
+
+    /// The key size of keyed BLAKE3.
+    pub const KEY_SZ: usize = 32;
+}
+
+/// Output of BLAKE3.
+///
+/// This has incremental output behaviour.
+pub struct Blake3Xof {
+    output: Output,
+    counter: u64,
+    buffer: [u8; BLOCK_LEN],
+    buffer_offset: usize,
+}
+
+impl Blake3Xof {
+    /// Read data from this instance into `output`.
+    ///
+    /// This does not fail.  It always fills `output`.
+    pub fn read(&mut self, mut output: &mut [u8]) {
+        while !output.is_empty() {
+            if self.buffer_offset == BLOCK_LEN {
+                self.buffer = self.output.root_block(self.counter);
+                self.counter += 1;
+                self.buffer_offset = 0;
+            }
+
+            let take = core::cmp::min(output.len(), BLOCK_LEN - self.buffer_offset);
+            let (taken, rest) = output.split_at_mut(take);
+            taken.copy_from_slice(&self.buffer[self.buffer_offset..self.buffer_offset + take]);
+            self.buffer_offset += take;
+            output = rest;
+        }
+    }
+}
+
+/// A partially-hashed chunk.
+#[derive(Clone)]
+struct ChunkState {
+    cv: [u32; 8],
+    counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+}
+
+impl ChunkState {
+    const fn new(key: [u32; 8], counter: u64) -> Self {
+        Self {
+            cv: key,
+            counter,
+            block: [0u8; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.blocks_compressed * BLOCK_LEN + self.block_len
+    }
+
+    fn update(&mut self, mut bytes: &[u8], flags: u32) {
+        while !bytes.is_empty() {
+            // as for the chunk, the last block is treated differently,
+            // so a full block is only compressed once we know more follows.
+            if self.block_len == BLOCK_LEN {
+                let out = blake3_compress(
+                    &self.cv,
+                    &self.block,
+                    self.counter,
+                    BLOCK_LEN as u32,
+                    flags | self.start_flag(),
+                );
+                self.cv.copy_from_slice(&out[..8]);
+                self.blocks_compressed += 1;
+                self.block = [0u8; BLOCK_LEN];
+                self.block_len = 0;
+            }
+
+            let take = core::cmp::min(bytes.len(), BLOCK_LEN - self.block_len);
+            let (taken, rest) = bytes.split_at(take);
+            self.block[self.block_len..self.block_len + take].copy_from_slice(taken);
+            self.block_len += take;
+            bytes = rest;
+        }
+    }
+
+    fn output(&self, flags: u32) -> Output {
+        Output {
+            cv: self.cv,
+            block: self.block,
+            counter: self.counter,
+            block_len: self.block_len as u32,
+            flags: flags | self.start_flag() | CHUNK_END,
+        }
+    }
+
+    fn start_flag(&self) -> u32 {
+        match self.blocks_compressed {
+            0 => CHUNK_START,
+            _ => 0,
+        }
+    }
+}
+
+/// The inputs to a final compression, which produces either a
+/// chaining value, or (for the root) output.
+#[derive(Clone)]
+struct Output {
+    cv: [u32; 8],
+    block: [u8; BLOCK_LEN],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        let out = blake3_compress(
+            &self.cv,
+            &self.block,
+            self.counter,
+            self.block_len,
+            self.flags,
+        );
+        let mut cv = [0u32; 8];
+        cv.copy_from_slice(&out[..8]);
+        cv
+    }
+
+    /// Output block `counter` of the root.
+    fn root_block(&self, counter: u64) -> [u8; BLOCK_LEN] {
+        let out = blake3_compress(
+            &self.cv,
+            &self.block,
+            counter,
+            self.block_len,
+            self.flags | ROOT,
+        );
+        let mut bytes = [0u8; BLOCK_LEN];
+        for (bytes, word) in bytes.chunks_exact_mut(4).zip(out) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+fn parent_output(left: &[u32; 8], right: &[u32; 8], key: &[u32; 8], flags: u32) -> Output {
+    let mut block = [0u8; BLOCK_LEN];
+    for (bytes, word) in block.chunks_exact_mut(4).zip(left.iter().chain(right)) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    Output {
+        cv: *key,
+        block,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: flags | PARENT,
+    }
+}
+
+/// The chaining value of a subtree, which starts at chunk `counter`.
+fn subtree_cv(
+    key: &[u32; 8],
+    flags: u32,
+    chunks: &[[u8; CHUNK_LEN]; SUBTREE_CHUNKS],
+    counter: u64,
+) -> [u32; 8] {
+    let mut cvs = [[0u32; 8]; SUBTREE_CHUNKS];
+    blake3_hash_chunks(key, chunks, counter, flags, &mut cvs);
+
+    let mut len = SUBTREE_CHUNKS;
+    while len > 1 {
+        len /= 2;
+        for i in 0..len {
+            cvs[i] = parent_output(&cvs[2 * i], &cvs[2 * i + 1], key, flags).chaining_value();
+        }
+    }
+    cvs[0]
+}
+
+/// The chaining values of consecutive subtrees, which start at chunk `counter`.
+fn subtree_cvs(
+    key: &[u32; 8],
+    flags: u32,
+    (subtrees, cvs): (&[[[u8; CHUNK_LEN]; SUBTREE_CHUNKS]], &mut [[u32; 8]]),
+    counter: u64,
+) {
+    for (i, (subtree, cv)) in subtrees.iter().zip(cvs).enumerate() {
+        *cv = subtree_cv(key, flags, subtree, counter + (i * SUBTREE_CHUNKS) as u64);
+    }
+}
+
+fn key_words(key: &[u8; 32]) -> [u32; 8] {
+    let mut words = [0u32; 8];
+    for (word, bytes) in words.iter_mut().zip(key.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    words
+}
+
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+/// Subtrees of this many chunks are the unit of work for threads.
+const SUBTREE_HEIGHT: u32 = 6;
+const SUBTREE_CHUNKS: usize = 1 << SUBTREE_HEIGHT;
+
+/// Input is limited to 2<sup>64</sup> bytes, so 2<sup>54</sup> chunks.
+const MAX_DEPTH: usize = 54;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    // Inputs are as for the BLAKE3 reference test vectors.  Expected values
+    // are from the BLAKE3 reference C implementation.
+
+    const KEY: &[u8; 32] = b"whats the Elvish word for friend";
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    /// `n` bytes of the repeating pattern `00 01 .. FA`.
+    fn input(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn hash(message: &[u8]) -> [u8; 32] {
+        let mut ctx = Blake3Context::new();
+        ctx.update(message);
+        ctx.finish()
+    }
+
+    fn keyed_hash(message: &[u8]) -> [u8; 32] {
+        let mut ctx = Blake3Context::new_keyed(KEY);
+        ctx.update(message);
+        ctx.finish()
+    }
+
+    fn derive_key(message: &[u8]) -> [u8; 32] {
+        let mut ctx = Blake3Context::new_derive_key(CONTEXT);
+        ctx.update(message);
+        let mut out = [0u8; 32];
+        ctx.finalize_xof().read(&mut out);
+        out
+    }
+
+    #[test]
+    fn vectors() {
+        let message = input(0);
+        assert_eq!(
+            hash(&message),
+            *b"\xaf\x13\x49\xb9\xf5\xf9\xa1\xa6\xa0\x40\x4d\xea\x36\xdc\xc9\x49\
+               \x9b\xcb\x25\xc9\xad\xc1\x12\xb7\xcc\x9a\x93\xca\xe4\x1f\x32\x62"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x92\xb2\xb7\x56\x04\xed\x3c\x76\x1f\x9d\x6f\x62\x39\x2c\x8a\x92\
+               \x27\xad\x0e\xa3\xf0\x95\x73\xe7\x83\xf1\x49\x8a\x4e\xd6\x0d\x26"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\x2c\xc3\x97\x83\xc2\x23\x15\x4f\xea\x8d\xfb\x7c\x1b\x16\x60\xf2\
+               \xac\x2d\xcb\xd1\xc1\xde\x82\x77\xb0\xb0\xdd\x39\xb7\xe5\x0d\x7d"
+        );
+        let message = input(1);
+        assert_eq!(
+            hash(&message),
+            *b"\x2d\x3a\xde\xdf\xf1\x1b\x61\xf1\x4c\x88\x6e\x35\xaf\xa0\x36\x73\
+               \x6d\xcd\x87\xa7\x4d\x27\xb5\xc1\x51\x02\x25\xd0\xf5\x92\xe2\x13"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x6d\x78\x78\xdf\xff\x2f\x48\x56\x35\xd3\x90\x13\x27\x8a\xe1\x4f\
+               \x14\x54\xb8\xc0\xa3\xa2\xd3\x4b\xc1\xab\x38\x22\x8a\x80\xc9\x5b"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\xb3\xe2\xe3\x40\xa1\x17\xa4\x99\xc6\xcf\x23\x98\xa1\x9e\xe0\xd2\
+               \x9c\xca\x2b\xb7\x40\x4c\x73\x06\x33\x82\x69\x3b\xf6\x6c\xb0\x6c"
+        );
+        let message = input(1023);
+        assert_eq!(
+            hash(&message),
+            *b"\x10\x10\x89\x70\xee\xda\x3e\xb9\x32\xba\xac\x14\x28\xc7\xa2\x16\
+               \x3b\x0e\x92\x4c\x9a\x9e\x25\xb3\x5b\xba\x72\xb2\x8f\x70\xbd\x11"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\xc9\x51\xec\xdf\x03\x28\x8d\x0f\xcc\x96\xee\x34\x13\x56\x3d\x8a\
+               \x6d\x35\x89\x54\x7f\x2c\x2f\xb3\x6d\x97\x86\x47\x0f\x1b\x9d\x6e"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\x74\xa1\x6c\x1c\x3d\x44\x36\x8a\x86\xe1\xca\x6d\xf6\x4b\xe6\xa2\
+               \xf6\x4c\xce\x8f\x09\x22\x07\x87\x45\x07\x22\xd8\x57\x25\xde\xa5"
+        );
+        let message = input(1024);
+        assert_eq!(
+            hash(&message),
+            *b"\x42\x21\x47\x39\xf0\x95\xa4\x06\xf3\xfc\x83\xde\xb8\x89\x74\x4a\
+               \xc0\x0d\xf8\x31\xc1\x0d\xaa\x55\x18\x9b\x5d\x12\x1c\x85\x5a\xf7"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x75\xc4\x6f\x6f\x3d\x9e\xb4\xf5\x5e\xca\xae\xe4\x80\xdb\x73\x2e\
+               \x6c\x21\x05\x54\x6f\x1e\x67\x50\x03\x68\x7c\x31\x71\x9c\x7b\xa4"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\x73\x56\xcd\x77\x20\xd5\xb6\x6b\x6d\x06\x97\xeb\x31\x77\xd9\xf8\
+               \xd7\x3a\x4a\x5c\x5e\x96\x88\x96\xeb\x6a\x68\x96\x84\x30\x27\x06"
+        );
+        let message = input(1025);
+        assert_eq!(
+            hash(&message),
+            *b"\xd0\x02\x78\xae\x47\xeb\x27\xb3\x4f\xae\xcf\x67\xb4\xfe\x26\x3f\
+               \x82\xd5\x41\x29\x16\xc1\xff\xd9\x7c\x8c\xb7\xfb\x81\x4b\x84\x44"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x35\x7d\xc5\x5d\xe0\xc7\xe3\x82\xc9\x00\xfd\x6e\x32\x0a\xcc\x04\
+               \x14\x6b\xe0\x1d\xb6\xa8\xce\x72\x10\xb7\x18\x9b\xd6\x64\xea\x69"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\xef\xfa\xa2\x45\xf0\x65\xfb\xf8\x2a\xc1\x86\x83\x9a\x24\x97\x07\
+               \xc3\xbd\xdf\x6d\x3f\xdd\xa2\x2d\x1b\x95\xa3\xc9\x70\x37\x9b\xcb"
+        );
+        let message = input(8193);
+        assert_eq!(
+            hash(&message),
+            *b"\xba\xb6\xc0\x9c\xb8\xce\x8c\xf4\x59\x26\x13\x98\xd2\xe7\xae\xf3\
+               \x57\x00\xbf\x48\x81\x16\xce\xb9\x4a\x36\xd0\xf5\xf1\xb7\xbc\x3b"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x95\x4a\x2a\x75\x42\x0c\x8d\x65\x47\xe3\xba\x5b\x98\xd9\x63\xe6\
+               \xfa\x64\x91\xad\xdc\x8c\x02\x31\x89\xcc\x51\x98\x21\xb4\xa1\xf5"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\xaf\x1e\x03\x46\xe3\x89\xb1\x7c\x23\x20\x02\x70\xa6\x4a\xa4\xe1\
+               \xea\xd9\x8c\x61\x69\x5d\x91\x7d\xe7\xd5\xb0\x04\x91\xc9\xb0\xf1"
+        );
+        let message = input(102400);
+        assert_eq!(
+            hash(&message),
+            *b"\xbc\x3e\x3d\x41\xa1\x14\x6b\x06\x9a\xbf\xfa\xd3\xc0\xd4\x48\x60\
+               \xcf\x66\x43\x90\xaf\xce\x4d\x96\x61\xf7\x90\x2e\x79\x43\xe0\x85"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x1c\x35\xd1\xa5\x81\x10\x83\xfd\x71\x19\xf5\xd5\xd1\xba\x02\x7b\
+               \x4d\x01\xc0\xc6\xc4\x9f\xb6\xff\x2c\xf7\x53\x93\xea\x5d\xb4\xa7"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\x46\x52\xcf\xf7\xa3\xf3\x85\xa6\x10\x3b\x5c\x26\x0f\xc1\x59\x3e\
+               \x13\xc7\x78\xdb\xe6\x08\xef\xb0\x92\xfe\x7e\xe6\x9d\xf6\xe9\xc6"
+        );
+        let message = input(300000);
+        assert_eq!(
+            hash(&message),
+            *b"\x6c\xc9\xdc\xe0\x5d\x4c\xff\x8c\x5b\xef\x5c\x5a\x24\x68\x1e\x42\
+               \xb1\x3f\x03\xe3\x4a\x0b\xc5\xe6\x6f\x65\xa9\x1d\x48\xc9\x44\xfa"
+        );
+        assert_eq!(
+            keyed_hash(&message),
+            *b"\x81\x8a\xa0\xdf\xd8\x68\x3f\xa9\xd7\x5c\x1d\xb1\x45\xb3\xdd\x2c\
+               \xe9\xcd\xb7\x2c\xa4\x4a\x33\xc8\x43\x55\xc4\xb5\x5d\xc6\x3b\x18"
+        );
+        assert_eq!(
+            derive_key(&message),
+            *b"\x47\x1d\x15\x45\x68\x24\x02\x06\x7d\x05\x12\xf5\xb8\xa8\xdb\x49\
+               \xc3\xfb\xe4\x4c\x42\x8d\x00\x13\x01\x0a\x8f\x1b\x92\x86\x22\x15"
+        );
+    }
+
+    #[test]
+    fn xof() {
+        let mut ctx = Blake3Context::new();
+        ctx.update(&input(1025));
+        let expect = *b"\xd0\x02\x78\xae\x47\xeb\x27\xb3\x4f\xae\xcf\x67\xb4\xfe\x26\x3f\
+           \x82\xd5\x41\x29\x16\xc1\xff\xd9\x7c\x8c\xb7\xfb\x81\x4b\x84\x44\
+           \xf4\xc4\xa2\x2b\x4b\x39\x91\x55\x35\x8a\x99\x4e\x52\xbf\x25\x5d\
+           \xe6\x00\x35\x74\x2e\xc7\x1b\xd0\x8a\xc2\x75\xa1\xb5\x1c\xc6\xbf\
+           \xe3\x32\xb0\xef\x84\xb4\x09\x10\x8c\xda\x08\x0e\x62\x69\xed\x4b\
+           \x3e\x2c\x3f\x7d\x72\x2a\xa4\xcd\xc9\x8d\x16\xde\xb5\x54\xe5\x62\
+           \x7b\xe8\xf9\x55\xc9\x8e\x1d\x5f\x95\x65\xa9\x19\x4c\xad\x0c\x42\
+           \x85\xf9\x37\x00\x06\x2d\x95\x95\xad\xb9\x92\xae\x68\xff\x12\x80\
+           \x0a\xb6\x7a";
+
+        let mut out = [0u8; 131];
+        ctx.clone().finalize_xof().read(&mut out);
+        assert_eq!(out, expect);
+
+        // incremental output gives the same result
+        let mut xof = ctx.finalize_xof();
+        let mut out = [0u8; 131];
+        for chunk in out.chunks_mut(17) {
+            xof.read(chunk);
+        }
+        assert_eq!(out, expect);
+    }
+
+    #[test]
+    fn streaming() {
+        let message = input(300_000);
+        let expect = hash(&message);
+
+        for step in [1, 63, 64, 65, 1023, 1024, 1025, 4096, 65535, 65536, 65537] {
+            let mut ctx = Blake3Context::new();
+            for chunk in message.chunks(step) {
+                ctx.update(chunk);
+            }
+            assert_eq!(ctx.finish(), expect, "step {step}");
+        }
+    }
+
+    #[test]
+    fn threads() {
+        let message = input(300_000);
+        let expect = hash(&message);
+
+        for threads in [0, 2, 3, 8] {
+            for prefix in [0, 1, 1024, 1025, 65536, 70000] {
+                let mut ctx = Blake3Context::new();
+                ctx.update(&message[..prefix]);
+                ctx.update_with_threads(&message[prefix..], threads);
+                assert_eq!(ctx.finish(), expect, "threads {threads} prefix {prefix}");
+            }
+        }
+
+        let mut ctx = Blake3Context::new_keyed(KEY);
+        ctx.update_with_threads(&message, 4);
+        assert_eq!(ctx.finish(), keyed_hash(&message));
+    }
+}
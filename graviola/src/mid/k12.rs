@@ -0,0 +1,680 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! TurboSHAKE and KangarooTwelve.
+//!
+//! See <https://www.rfc-editor.org/rfc/rfc9861>
+//!
+//! TurboSHAKE is SHAKE with the number of Keccak rounds halved to 12.
+//! KangarooTwelve (KT128 and KT256) builds a tree hash on TurboSHAKE:
+//! input is divided into 8KiB chunks, which are hashed independently.
+//! Where a single call to `update()` provides several whole chunks,
+//! they are hashed several at a time.
+
+use super::sha3::{SHAKE_128_R_BYTES, SHAKE_256_R_BYTES, Sponge, SqueezingSponge, sponge_lanes};
+use crate::Error;
+
+/// A context for incremental absorption of TurboSHAKE128 input.
+///
+/// Once all input is absorbed, use [`TurboShake128Context::finalize_xof`] to
+/// obtain a [`TurboShake128`] from which output can be read.
+#[derive(Clone)]
+pub struct TurboShake128Context {
+    sponge: TurboSponge<SHAKE_128_R_BYTES>,
+    domain: u8,
+}
+
+impl TurboShake128Context {
+    /// Start a new TurboSHAKE128 computation, with the default domain
+    /// separation byte of `0x1f`.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(),
+            domain: DEFAULT_DOMAIN,
+        }
+    }
+
+    /// Start a new TurboSHAKE128 computation, with the given domain
+    /// separation byte.
+    ///
+    /// `Error::OutOfRange` is returned if `domain` is not in the range
+    /// `0x01..=0x7f`.
+    pub fn with_domain(domain: u8) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::new(),
+            domain: check_domain(domain)?,
+        })
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Complete absorption, returning a [`TurboShake128`] for reading output.
+    pub fn finalize_xof(self) -> TurboShake128 {
+        TurboShake128::from_sponge(self.sponge.absorb_final_with_pad(self.domain))
+    }
+}
+
+/// Output of TurboSHAKE128 or KT128.
+///
+/// This has incremental output behaviour.
+pub struct TurboShake128 {
+    sponge: SqueezingSponge<SHAKE_128_R_BYTES, 12>,
+    buffer: [u8; SHAKE_128_R_BYTES],
+    buffer_offset: usize,
+}
+
+impl TurboShake128 {
+    fn from_sponge(sponge: SqueezingSponge<SHAKE_128_R_BYTES, 12>) -> Self {
+        Self {
+            sponge,
+            buffer: [0u8; SHAKE_128_R_BYTES],
+            buffer_offset: SHAKE_128_R_BYTES,
+        }
+    }
+
+    /// Read data from this instance into `output`.
+    ///
+    /// This does not fail.  It always fills `output`.
+    pub fn read(&mut self, output: &mut [u8]) {
+        self.sponge
+            .shake_read(output, &mut self.buffer, &mut self.buffer_offset);
+    }
+}
+
+/// A context for incremental absorption of TurboSHAKE256 input.
+///
+/// Once all input is absorbed, use [`TurboShake256Context::finalize_xof`] to
+/// obtain a [`TurboShake256`] from which output can be read.
+#[derive(Clone)]
+pub struct TurboShake256Context {
+    sponge: TurboSponge<SHAKE_256_R_BYTES>,
+    domain: u8,
+}
+
+impl TurboShake256Context {
+    /// Start a new TurboSHAKE256 computation, with the default domain
+    /// separation byte of `0x1f`.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(),
+            domain: DEFAULT_DOMAIN,
+        }
+    }
+
+    /// Start a new TurboSHAKE256 computation, with the given domain
+    /// separation byte.
+    ///
+    /// `Error::OutOfRange` is returned if `domain` is not in the range
+    /// `0x01..=0x7f`.
+    pub fn with_domain(domain: u8) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::new(),
+            domain: check_domain(domain)?,
+        })
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Complete absorption, returning a [`TurboShake256`] for reading output.
+    pub fn finalize_xof(self) -> TurboShake256 {
+        TurboShake256::from_sponge(self.sponge.absorb_final_with_pad(self.domain))
+    }
+}
+
+/// Output of TurboSHAKE256 or KT256.
+///
+/// This has incremental output behaviour.
+pub struct TurboShake256 {
+    sponge: SqueezingSponge<SHAKE_256_R_BYTES, 12>,
+    buffer: [u8; SHAKE_256_R_BYTES],
+    buffer_offset: usize,
+}
+
+impl TurboShake256 {
+    fn from_sponge(sponge: SqueezingSponge<SHAKE_256_R_BYTES, 12>) -> Self {
+        Self {
+            sponge,
+            buffer: [0u8; SHAKE_256_R_BYTES],
+            buffer_offset: SHAKE_256_R_BYTES,
+        }
+    }
+
+    /// Read data from this instance into `output`.
+    ///
+    /// This does not fail.  It always fills `output`.
+    pub fn read(&mut self, output: &mut [u8]) {
+        self.sponge
+            .shake_read(output, &mut self.buffer, &mut self.buffer_offset);
+    }
+}
+
+/// A context for incremental computation of KT128 (also known
+/// as KangarooTwelve).
+#[derive(Clone)]
+pub struct Kt128Context {
+    inner: KangarooTwelve<SHAKE_128_R_BYTES, 32>,
+}
+
+impl Kt128Context {
+    /// Start a new KT128 computation.
+    pub const fn new() -> Self {
+        Self {
+            inner: KangarooTwelve::new(),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete the computation, returning a [`TurboShake128`] for reading output.
+    ///
+    /// `customization` is a caller-chosen domain separator, and may be empty.
+    /// It is taken here because KT128 absorbs it after the message.
+    pub fn finalize_xof(self, customization: &[u8]) -> TurboShake128 {
+        TurboShake128::from_sponge(self.inner.finish(customization))
+    }
+}
+
+/// A context for incremental computation of KT256.
+#[derive(Clone)]
+pub struct Kt256Context {
+    inner: KangarooTwelve<SHAKE_256_R_BYTES, 64>,
+}
+
+impl Kt256Context {
+    /// Start a new KT256 computation.
+    pub const fn new() -> Self {
+        Self {
+            inner: KangarooTwelve::new(),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete the computation, returning a [`TurboShake256`] for reading output.
+    ///
+    /// `customization` is a caller-chosen domain separator, and may be empty.
+    /// It is taken here because KT256 absorbs it after the message.
+    pub fn finalize_xof(self, customization: &[u8]) -> TurboShake256 {
+        TurboShake256::from_sponge(self.inner.finish(customization))
+    }
+}
+
+/// KangarooTwelve, where `R` is the rate and `CV` is the length of each
+/// leaf's chaining value.
+///
+/// The input `S` is `message || customization || length_encode(|customization|)`.
+#[derive(Clone)]
+struct KangarooTwelve<const R: usize, const CV: usize> {
+    /// The final node.  This absorbs the first chunk of `S`, then (if
+    /// there is more than one chunk) the chaining values of the others.
+    final_node: TurboSponge<R>,
+    first_chunk_len: usize,
+
+    /// Whether `S` is longer than one chunk.
+    tree: bool,
+    leaves: u64,
+
+    /// A partially-provided leaf, and how much of it has been provided so far.
+    leaf: TurboSponge<R>,
+    leaf_len: usize,
+}
+
+impl<const R: usize, const CV: usize> KangarooTwelve<R, CV> {
+    const fn new() -> Self {
+        Self {
+            final_node: Sponge::new(),
+            first_chunk_len: 0,
+            tree: false,
+            leaves: 0,
+            leaf: Sponge::new(),
+            leaf_len: 0,
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes;
+
+        if !self.tree {
+            let take = Ord::min(CHUNK_SIZE - self.first_chunk_len, bytes.len());
+            let (taken, rest) = bytes.split_at(take);
+            self.final_node.absorb(taken);
+            self.first_chunk_len += take;
+            bytes = rest;
+
+            if bytes.is_empty() {
+                return;
+            }
+
+            // we now know there is more than one chunk
+            self.final_node.absorb(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+            self.tree = true;
+        }
+
+        // complete any partial leaf first
+        if self.leaf_len > 0 {
+            let take = Ord::min(CHUNK_SIZE - self.leaf_len, bytes.len());
+            let (taken, rest) = bytes.split_at(take);
+            self.leaf.absorb(taken);
+            self.leaf_len += take;
+            bytes = rest;
+
+            if self.leaf_len < CHUNK_SIZE {
+                return;
+            }
+            self.finish_leaf();
+        }
+
+        // then whole leaves, as many at once as possible
+        let (chunks, remainder) = bytes.as_chunks::<CHUNK_SIZE>();
+        let (quads, chunks) = chunks.as_chunks::<4>();
+        for [c0, c1, c2, c3] in quads {
+            let mut cvs = [[0u8; CV]; 4];
+            let [cv0, cv1, cv2, cv3] = &mut cvs;
+            sponge_lanes::<R, 4, 12>([c0, c1, c2, c3], LEAF_NODE, [cv0, cv1, cv2, cv3]);
+            for cv in &cvs {
+                self.absorb_chaining_value(cv);
+            }
+        }
+
+        let (pairs, chunks) = chunks.as_chunks::<2>();
+        for [c0, c1] in pairs {
+            let mut cvs = [[0u8; CV]; 2];
+            let [cv0, cv1] = &mut cvs;
+            sponge_lanes::<R, 2, 12>([c0, c1], LEAF_NODE, [cv0, cv1]);
+            for cv in &cvs {
+                self.absorb_chaining_value(cv);
+            }
+        }
+
+        for chunk in chunks {
+            let mut leaf = TurboSponge::<R>::new();
+            leaf.absorb(chunk);
+            let mut cv = [0u8; CV];
+            leaf.absorb_final_with_pad(LEAF_NODE)
+                .into_single_squeeze(&mut cv);
+            self.absorb_chaining_value(&cv);
+        }
+
+        // retain the remainder
+        self.leaf.absorb(remainder);
+        self.leaf_len = remainder.len();
+    }
+
+    fn finish(mut self, customization: &[u8]) -> SqueezingSponge<R, 12> {
+        self.update(customization);
+        self.update(length_encode(customization.len() as u64).as_ref());
+
+        if !self.tree {
+            return self.final_node.absorb_final_with_pad(SINGLE_NODE);
+        }
+
+        if self.leaf_len > 0 {
+            self.finish_leaf();
+        }
+        self.final_node.absorb(length_encode(self.leaves).as_ref());
+        self.final_node.absorb(&[0xff, 0xff]);
+        self.final_node.absorb_final_with_pad(FINAL_NODE)
+    }
+
+    fn finish_leaf(&mut self) {
+        let leaf = core::mem::replace(&mut self.leaf, Sponge::new());
+        self.leaf_len = 0;
+
+        let mut cv = [0u8; CV];
+        leaf.absorb_final_with_pad(LEAF_NODE)
+            .into_single_squeeze(&mut cv);
+        self.absorb_chaining_value(&cv);
+    }
+
+    fn absorb_chaining_value(&mut self, cv: &[u8; CV]) {
+        self.final_node.absorb(cv);
+        self.leaves += 1;
+    }
+}
+
+fn check_domain(domain: u8) -> Result<u8, Error> {
+    match domain {
+        0x01..=0x7f => Ok(domain),
+        _ => Err(Error::OutOfRange),
+    }
+}
+
+/// `length_encode(x)`: the minimal big-endian encoding of `x`, followed
+/// by its length.  Zero is encoded in zero bytes.
+fn length_encode(x: u64) -> LengthEncoded {
+    let len = 8 - (x.leading_zeros() as usize / 8);
+    let mut bytes = [0u8; 9];
+    bytes[..len].copy_from_slice(&x.to_be_bytes()[8 - len..]);
+    bytes[len] = len as u8;
+    LengthEncoded {
+        bytes,
+        len: len + 1,
+    }
+}
+
+struct LengthEncoded {
+    bytes: [u8; 9],
+    len: usize,
+}
+
+impl AsRef<[u8]> for LengthEncoded {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+/// TurboSHAKE's sponge.  The padding byte is given at the end.
+type TurboSponge<const R: usize> = Sponge<R, DEFAULT_DOMAIN, 12>;
+
+/// The domain separation byte for TurboSHAKE, when used as a plain XOF.
+const DEFAULT_DOMAIN: u8 = 0x1f;
+
+/// Domain separation bytes for KangarooTwelve's nodes.
+const SINGLE_NODE: u8 = 0x07;
+const FINAL_NODE: u8 = 0x06;
+const LEAF_NODE: u8 = 0x0b;
+
+const CHUNK_SIZE: usize = 8192;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    // Vectors are from RFC9861 section 5, except where noted.
+
+    /// `ptn(n)` from RFC9861: `n` bytes of the repeating pattern `00 01 .. FA`.
+    fn ptn(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn turboshake128(message: &[u8], domain: u8) -> [u8; 32] {
+        let mut ctx = TurboShake128Context::with_domain(domain).unwrap();
+        ctx.update(message);
+        let mut out = [0u8; 32];
+        ctx.finalize_xof().read(&mut out);
+        out
+    }
+
+    fn turboshake256(message: &[u8], domain: u8) -> [u8; 64] {
+        let mut ctx = TurboShake256Context::with_domain(domain).unwrap();
+        ctx.update(message);
+        let mut out = [0u8; 64];
+        ctx.finalize_xof().read(&mut out);
+        out
+    }
+
+    fn kt128(message: &[u8], customization: &[u8]) -> [u8; 32] {
+        let mut ctx = Kt128Context::new();
+        ctx.update(message);
+        let mut out = [0u8; 32];
+        ctx.finalize_xof(customization).read(&mut out);
+        out
+    }
+
+    fn kt256(message: &[u8], customization: &[u8]) -> [u8; 64] {
+        let mut ctx = Kt256Context::new();
+        ctx.update(message);
+        let mut out = [0u8; 64];
+        ctx.finalize_xof(customization).read(&mut out);
+        out
+    }
+
+    #[test]
+    fn turboshake128_vectors() {
+        assert_eq!(
+            turboshake128(b"", 0x1f),
+            *b"\x1e\x41\x5f\x1c\x59\x83\xaf\xf2\x16\x92\x17\x27\x7d\x17\xbb\x53\
+               \x8c\xd9\x45\xa3\x97\xdd\xec\x54\x1f\x1c\xe4\x1a\xf2\xc1\xb7\x4c"
+        );
+
+        let mut out = [0u8; 64];
+        TurboShake128Context::new().finalize_xof().read(&mut out);
+        assert_eq!(
+            out,
+            *b"\x1e\x41\x5f\x1c\x59\x83\xaf\xf2\x16\x92\x17\x27\x7d\x17\xbb\x53\
+               \x8c\xd9\x45\xa3\x97\xdd\xec\x54\x1f\x1c\xe4\x1a\xf2\xc1\xb7\x4c\
+               \x3e\x8c\xca\xe2\xa4\xda\xe5\x6c\x84\xa0\x4c\x23\x85\xc0\x3c\x15\
+               \xe8\x19\x3b\xdf\x58\x73\x73\x63\x32\x16\x91\xc0\x54\x62\xc8\xdf"
+        );
+
+        let mut out = [0u8; 10032];
+        TurboShake128Context::new().finalize_xof().read(&mut out);
+        assert_eq!(
+            out[10000..],
+            *b"\xa3\xb9\xb0\x38\x59\x00\xce\x76\x1f\x22\xae\xd5\x48\xe7\x54\xda\
+               \x10\xa5\x24\x2d\x62\xe8\xc6\x58\xe3\xf3\xa9\x23\xa7\x55\x56\x07"
+        );
+        assert_eq!(
+            turboshake128(&ptn(17), 0x1f),
+            *b"\x9c\x97\xd0\x36\xa3\xba\xc8\x19\xdb\x70\xed\xe0\xca\x55\x4e\xc6\
+               \xe4\xc2\xa1\xa4\xff\xbf\xd9\xec\x26\x9c\xa6\xa1\x11\x16\x12\x33"
+        );
+        assert_eq!(
+            turboshake128(&ptn(17 * 17 * 17), 0x1f),
+            *b"\xd4\x97\x6e\xb5\x6b\xcf\x11\x85\x20\x58\x2b\x70\x9f\x73\xe1\xd6\
+               \x85\x3e\x00\x1f\xda\xf8\x0e\x1b\x13\xe0\xd0\x59\x9d\x5f\xb3\x72"
+        );
+        assert_eq!(
+            turboshake128(b"\xff\xff\xff", 0x01),
+            *b"\xbf\x32\x3f\x94\x04\x94\xe8\x8e\xe1\xc5\x40\xfe\x66\x0b\xe8\xa0\
+               \xc9\x3f\x43\xd1\x5e\xc0\x06\x99\x84\x62\xfa\x99\x4e\xed\x5d\xab"
+        );
+        assert_eq!(
+            turboshake128(b"\xff", 0x06),
+            *b"\x8e\xc9\xc6\x64\x65\xed\x0d\x4a\x6c\x35\xd1\x35\x06\x71\x8d\x68\
+               \x7a\x25\xcb\x05\xc7\x4c\xca\x1e\x42\x50\x1a\xbd\x83\x87\x4a\x67"
+        );
+
+        // from the TurboSHAKE vectors of draft-irtf-cfrg-kangarootwelve, which used D=07
+        assert_eq!(
+            turboshake128(&ptn(17 * 17 * 17 * 17), 0x07),
+            *b"\xca\x5f\x1f\x3e\xea\xc9\x92\xcd\xc2\xab\xeb\xca\x0e\x21\x67\x65\
+               \xdb\xf7\x79\xc3\xc1\x09\x46\x05\x5a\x94\xab\x32\x72\x57\x35\x22"
+        );
+    }
+
+    #[test]
+    fn turboshake256_vectors() {
+        assert_eq!(
+            turboshake256(b"", 0x1f),
+            *b"\x36\x7a\x32\x9d\xaf\xea\x87\x1c\x78\x02\xec\x67\xf9\x05\xae\x13\
+               \xc5\x76\x95\xdc\x2c\x66\x63\xc6\x10\x35\xf5\x9a\x18\xf8\xe7\xdb\
+               \x11\xed\xc0\xe1\x2e\x91\xea\x60\xeb\x6b\x32\xdf\x06\xdd\x7f\x00\
+               \x2f\xba\xfa\xbb\x6e\x13\xec\x1c\xc2\x0d\x99\x55\x47\x60\x0d\xb0"
+        );
+        assert_eq!(
+            turboshake256(&ptn(17 * 17), 0x1f),
+            *b"\x66\xb8\x10\xdb\x8e\x90\x78\x04\x24\xc0\x84\x73\x72\xfd\xc9\x57\
+               \x10\x88\x2f\xde\x31\xc6\xdf\x75\xbe\xb9\xd4\xcd\x93\x05\xcf\xca\
+               \xe3\x5e\x7b\x83\xe8\xb7\xe6\xeb\x4b\x78\x60\x58\x80\x11\x63\x16\
+               \xfe\x2c\x07\x8a\x09\xb9\x4a\xd7\xb8\x21\x3c\x0a\x73\x8b\x65\xc0"
+        );
+
+        // from the TurboSHAKE vectors of draft-irtf-cfrg-kangarootwelve, which used D=07
+        assert_eq!(
+            turboshake256(&ptn(17 * 17 * 17 * 17), 0x07),
+            *b"\x52\x3c\x06\x47\x18\x2d\x89\x41\xf0\xdd\x5c\x5c\x0a\xb6\x2d\x4f\
+               \xc2\x95\x61\x61\x53\x96\xbb\x5b\x9a\x9d\xeb\x02\x2b\x80\xc5\xbf\
+               \x2d\x83\xa3\xbb\x36\xff\xc0\x4f\xac\x58\xcf\x11\x49\xc6\x6d\xec\
+               \x4a\x59\x52\x6e\x51\xf2\x95\x96\xd8\x24\x42\x1a\x4b\x84\xb4\x4d"
+        );
+    }
+
+    #[test]
+    fn turboshake_domain() {
+        for domain in [0x00, 0x80, 0xff] {
+            assert_eq!(
+                TurboShake128Context::with_domain(domain).err(),
+                Some(Error::OutOfRange)
+            );
+            assert_eq!(
+                TurboShake256Context::with_domain(domain).err(),
+                Some(Error::OutOfRange)
+            );
+        }
+        TurboShake128Context::with_domain(0x01).unwrap();
+        TurboShake256Context::with_domain(0x7f).unwrap();
+    }
+
+    #[test]
+    fn kt128_vectors() {
+        assert_eq!(
+            kt128(b"", b""),
+            *b"\x1a\xc2\xd4\x50\xfc\x3b\x42\x05\xd1\x9d\xa7\xbf\xca\x1b\x37\x51\
+               \x3c\x08\x03\x57\x7a\xc7\x16\x7f\x06\xfe\x2c\xe1\xf0\xef\x39\xe5"
+        );
+
+        let mut out = [0u8; 64];
+        Kt128Context::new().finalize_xof(b"").read(&mut out);
+        assert_eq!(
+            out,
+            *b"\x1a\xc2\xd4\x50\xfc\x3b\x42\x05\xd1\x9d\xa7\xbf\xca\x1b\x37\x51\
+               \x3c\x08\x03\x57\x7a\xc7\x16\x7f\x06\xfe\x2c\xe1\xf0\xef\x39\xe5\
+               \x42\x69\xc0\x56\xb8\xc8\x2e\x48\x27\x60\x38\xb6\xd2\x92\x96\x6c\
+               \xc0\x7a\x3d\x46\x45\x27\x2e\x31\xff\x38\x50\x81\x39\xeb\x0a\x71"
+        );
+
+        let mut out = [0u8; 10032];
+        Kt128Context::new().finalize_xof(b"").read(&mut out);
+        assert_eq!(
+            out[10000..],
+            *b"\xe8\xdc\x56\x36\x42\xf7\x22\x8c\x84\x68\x4c\x89\x84\x05\xd3\xa8\
+               \x34\x79\x91\x58\xc0\x79\xb1\x28\x80\x27\x7a\x1d\x28\xe2\xff\x6d"
+        );
+        assert_eq!(
+            kt128(&ptn(1), b""),
+            *b"\x2b\xda\x92\x45\x0e\x8b\x14\x7f\x8a\x7c\xb6\x29\xe7\x84\xa0\x58\
+               \xef\xca\x7c\xf7\xd8\x21\x8e\x02\xd3\x45\xdf\xaa\x65\x24\x4a\x1f"
+        );
+        assert_eq!(
+            kt128(&ptn(17 * 17 * 17), b""),
+            *b"\xcb\x55\x2e\x2e\xc7\x7d\x99\x10\x70\x1d\x57\x8b\x45\x7d\xdf\x77\
+               \x2c\x12\xe3\x22\xe4\xee\x7f\xe4\x17\xf9\x2c\x75\x8f\x0d\x59\xd0"
+        );
+        assert_eq!(
+            kt128(&ptn(17 * 17 * 17 * 17), b""),
+            *b"\x87\x01\x04\x5e\x22\x20\x53\x45\xff\x4d\xda\x05\x55\x5c\xbb\x5c\
+               \x3a\xf1\xa7\x71\xc2\xb8\x9b\xae\xf3\x7d\xb4\x3d\x99\x98\xb9\xfe"
+        );
+        assert_eq!(
+            kt128(b"", &ptn(1)),
+            *b"\xfa\xb6\x58\xdb\x63\xe9\x4a\x24\x61\x88\xbf\x7a\xf6\x9a\x13\x30\
+               \x45\xf4\x6e\xe9\x84\xc5\x6e\x3c\x33\x28\xca\xaf\x1a\xa1\xa5\x83"
+        );
+        assert_eq!(
+            kt128(b"\xff", &ptn(41)),
+            *b"\xd8\x48\xc5\x06\x8c\xed\x73\x6f\x44\x62\x15\x9b\x98\x67\xfd\x4c\
+               \x20\xb8\x08\xac\xc3\xd5\xbc\x48\xe0\xb0\x6b\xa0\xa3\x76\x2e\xc4"
+        );
+        assert_eq!(
+            kt128(b"\xff\xff\xff", &ptn(41 * 41)),
+            *b"\xc3\x89\xe5\x00\x9a\xe5\x71\x20\x85\x4c\x2e\x8c\x64\x67\x0a\xc0\
+               \x13\x58\xcf\x4c\x1b\xaf\x89\x44\x7a\x72\x42\x34\xdc\x7c\xed\x74"
+        );
+        assert_eq!(
+            kt128(&ptn(8191), b""),
+            *b"\x1b\x57\x76\x36\xf7\x23\x64\x3e\x99\x0c\xc7\xd6\xa6\x59\x83\x74\
+               \x36\xfd\x6a\x10\x36\x26\x60\x0e\xb8\x30\x1c\xd1\xdb\xe5\x53\xd6"
+        );
+        assert_eq!(
+            kt128(&ptn(8192), b""),
+            *b"\x48\xf2\x56\xf6\x77\x2f\x9e\xdf\xb6\xa8\xb6\x61\xec\x92\xdc\x93\
+               \xb9\x5e\xbd\x05\xa0\x8a\x17\xb3\x9a\xe3\x49\x08\x70\xc9\x26\xc3"
+        );
+        assert_eq!(
+            kt128(&ptn(8192), &ptn(8189)),
+            *b"\x3e\xd1\x2f\x70\xfb\x05\xdd\xb5\x86\x89\x51\x0a\xb3\xe4\xd2\x3c\
+               \x6c\x60\x33\x84\x9a\xa0\x1e\x1d\x8c\x22\x0a\x29\x7f\xed\xcd\x0b"
+        );
+        assert_eq!(
+            kt128(&ptn(8192), &ptn(8190)),
+            *b"\x6a\x7c\x1b\x6a\x5c\xd0\xd8\xc9\xca\x94\x3a\x4a\x21\x6c\xc6\x46\
+               \x04\x55\x9a\x2e\xa4\x5f\x78\x57\x0a\x15\x25\x3d\x67\xba\x00\xae"
+        );
+    }
+
+    #[test]
+    fn kt256_vectors() {
+        assert_eq!(
+            kt256(b"", b""),
+            *b"\xb2\x3d\x2e\x9c\xea\x9f\x49\x04\xe0\x2b\xec\x06\x81\x7f\xc1\x0c\
+               \xe3\x8c\xe8\xe9\x3e\xf4\xc8\x9e\x65\x37\x07\x6a\xf8\x64\x64\x04\
+               \xe3\xe8\xb6\x81\x07\xb8\x83\x3a\x5d\x30\x49\x0a\xa3\x34\x82\x35\
+               \x3f\xd4\xad\xc7\x14\x8e\xcb\x78\x28\x55\x00\x3a\xae\xbd\xe4\xa9"
+        );
+        assert_eq!(
+            kt256(&ptn(17 * 17 * 17 * 17), b""),
+            *b"\xb0\x62\x75\xd2\x84\xcd\x1c\xf2\x05\xbc\xbe\x57\xdc\xcd\x3e\xc1\
+               \xff\x66\x86\xe3\xed\x15\x77\x63\x83\xe1\xf2\xfa\x3c\x6a\xc8\xf0\
+               \x8b\xf8\xa1\x62\x82\x9d\xb1\xa4\x4b\x2a\x43\xff\x83\xdd\x89\xc3\
+               \xcf\x1c\xeb\x61\xed\xe6\x59\x76\x6d\x5c\xcf\x81\x7a\x62\xba\x8d"
+        );
+        assert_eq!(
+            kt256(b"\xff", &ptn(41)),
+            *b"\x47\xef\x96\xdd\x61\x6f\x20\x09\x37\xaa\x78\x47\xe3\x4e\xc2\xfe\
+               \xae\x80\x87\xe3\x76\x1d\xc0\xf8\xc1\xa1\x54\xf5\x1d\xc9\xcc\xf8\
+               \x45\xd7\xad\xbc\xe5\x7f\xf6\x4b\x63\x97\x22\xc6\xa1\x67\x2e\x3b\
+               \xf5\x37\x2d\x87\xe0\x0a\xff\x89\xbe\x97\x24\x07\x56\x99\x88\x53"
+        );
+    }
+
+    #[test]
+    fn kt_update_sizes() {
+        // 11 chunks, the last partial.  This exercises the four-, two-
+        // and one-at-a-time paths.
+        let input = ptn(17 * 17 * 17 * 17);
+
+        for chunk in [
+            1,
+            167,
+            168,
+            8191,
+            8192,
+            8193,
+            16384,
+            32768,
+            40000,
+            input.len(),
+        ] {
+            let mut ctx = Kt128Context::new();
+            for c in input.chunks(chunk) {
+                ctx.update(c);
+            }
+            let mut out = [0u8; 32];
+            ctx.finalize_xof(b"").read(&mut out);
+            assert_eq!(out, kt128(&input, b""));
+
+            let mut ctx = Kt256Context::new();
+            for c in input.chunks(chunk) {
+                ctx.update(c);
+            }
+            let mut out = [0u8; 64];
+            ctx.finalize_xof(b"").read(&mut out);
+            assert_eq!(out, kt256(&input, b""));
+        }
+    }
+
+    #[test]
+    fn length_encoding() {
+        assert_eq!(length_encode(0).as_ref(), &[0]);
+        assert_eq!(length_encode(12).as_ref(), &[12, 1]);
+        assert_eq!(length_encode(65538).as_ref(), &[1, 0, 2, 3]);
+        assert_eq!(
+            length_encode(u64::MAX).as_ref(),
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 8]
+        );
+    }
+}
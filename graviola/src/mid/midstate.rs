@@ -0,0 +1,126 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! Exported hash states.
+//!
+//! An exported state starts with a version byte and an algorithm
+//! identifier.  The remainder depends on the hash function:
+//!
+//! - SHA256 and SHA512: the chaining value (as big-endian words),
+//!   the number of bytes hashed so far (as a big-endian `u64`), then
+//!   one block containing any buffered input followed by zeroes.
+//! - SHA-3 and SHAKE: the Keccak state (as 25 little-endian lanes),
+//!   the number of buffered bytes (as one byte), then one block (of
+//!   the sponge's rate) containing any buffered input followed by zeroes.
+//!
+//! Importing checks only the header, the overall length, and the
+//! count of buffered bytes (and that any unused buffer space is zero).
+//! So a state is rejected if it is for a different hash function or
+//! has the wrong length, but a corrupted chaining value or Keccak state
+//! cannot be detected.  If the integrity of a state matters, it must be
+//! protected separately (for example, with HMAC).
+
+use crate::Error;
+
+/// The current (and only) format version.
+const VERSION: u8 = 1;
+
+/// The length of the header: version and algorithm.
+pub(crate) const HEADER_SZ: usize = 2;
+
+/// Identifies the hash function an exported state is for.
+#[derive(Clone, Copy)]
+#[repr(u8)]
+pub(crate) enum Algorithm {
+    Sha256 = 1,
+    Sha512 = 2,
+    Sha3_224 = 3,
+    Sha3_256 = 4,
+    Sha3_384 = 5,
+    Sha3_512 = 6,
+    Shake128 = 7,
+    Shake256 = 8,
+}
+
+/// Writes an exported state, front to back.
+pub(crate) struct Writer<'a> {
+    out: &'a mut [u8],
+}
+
+impl<'a> Writer<'a> {
+    pub(crate) fn new(out: &'a mut [u8], algorithm: Algorithm) -> Self {
+        let mut w = Self { out };
+        w.put(&[VERSION, algorithm as u8]);
+        w
+    }
+
+    pub(crate) fn put(&mut self, bytes: &[u8]) {
+        let (dest, rest) = core::mem::take(&mut self.out).split_at_mut(bytes.len());
+        dest.copy_from_slice(bytes);
+        self.out = rest;
+    }
+
+    /// Write `used` into a block of `len` bytes, followed by zeroes.
+    pub(crate) fn put_block(&mut self, used: &[u8], len: usize) {
+        let (dest, rest) = core::mem::take(&mut self.out).split_at_mut(len);
+        let (data, zeroes) = dest.split_at_mut(used.len());
+        data.copy_from_slice(used);
+        zeroes.fill(0);
+        self.out = rest;
+    }
+
+    pub(crate) fn finish(self) {
+        debug_assert!(self.out.is_empty());
+    }
+}
+
+/// Reads an exported state, front to back.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Check the header of `bytes`, which must be `len` bytes long
+    /// and for `algorithm`.
+    pub(crate) fn new(bytes: &'a [u8], algorithm: Algorithm, len: usize) -> Result<Self, Error> {
+        if bytes.len() != len {
+            return Err(Error::WrongLength);
+        }
+
+        let mut r = Self { bytes };
+        match r.take()? {
+            [VERSION, alg] if alg == algorithm as u8 => Ok(r),
+            _ => Err(Error::BadHashState),
+        }
+    }
+
+    pub(crate) fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let (taken, rest) = self.bytes.split_first_chunk().ok_or(Error::BadHashState)?;
+        self.bytes = rest;
+        Ok(*taken)
+    }
+
+    /// Read a block of `len` bytes, of which only the first `used`
+    /// may be non-zero.  Returns those.
+    pub(crate) fn take_block(&mut self, used: usize, len: usize) -> Result<&'a [u8], Error> {
+        if used > len || self.bytes.len() < len {
+            return Err(Error::BadHashState);
+        }
+
+        let (block, rest) = self.bytes.split_at(len);
+        let (data, zeroes) = block.split_at(used);
+        if zeroes.iter().fold(0, |acc, b| acc | b) != 0 {
+            return Err(Error::BadHashState);
+        }
+
+        self.bytes = rest;
+        Ok(data)
+    }
+
+    pub(crate) fn finish(self) -> Result<(), Error> {
+        match self.bytes.is_empty() {
+            true => Ok(()),
+            false => Err(Error::BadHashState),
+        }
+    }
+}
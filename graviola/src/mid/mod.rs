@@ -3,17 +3,33 @@
 
 #![deny(unsafe_code)]
 
+pub(super) mod aegis;
+pub mod aes;
+pub(super) mod aes_ccm;
+pub(super) mod aes_cmac;
 pub(super) mod aes_gcm;
+pub(super) mod aes_gcm_siv;
+pub(super) mod aes_kw;
+pub(super) mod aes_siv;
+pub mod ascon;
+pub(super) mod ascon_aead;
+pub mod blake2;
+pub mod blake3;
 pub(super) mod chacha20poly1305;
 pub(super) mod ed25519;
+pub mod k12;
+mod midstate;
 pub mod mlkem768;
 pub(super) mod p256;
 pub(super) mod p384;
 pub(super) mod rng;
 pub(super) mod rsa_priv;
 pub(super) mod rsa_pub;
+#[cfg(feature = "legacy-sha1")]
+pub mod sha1;
 pub mod sha2;
 pub mod sha3;
+pub mod sp800_185;
 pub(super) mod util;
 pub(super) mod x25519;
 pub(super) mod xchacha20poly1305;
@@ -128,7 +128,7 @@ impl PrivateKey {
         }
     }
 
-    fn generate(rng: &mut dyn RandomSource) -> Result<Self, Error> {
+    pub(crate) fn generate(rng: &mut dyn RandomSource) -> Result<Self, Error> {
         for _ in 0..64 {
             let mut r = [0u8; Scalar::BYTES];
             rng.fill(&mut r)?;
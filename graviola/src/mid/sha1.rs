@@ -0,0 +1,765 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! SHA-1, with collision detection.
+//!
+//! SHA-1 is described in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+//! It is broken: chosen-prefix collisions are practical.  It is provided
+//! here only so old signatures can be verified, and is not available
+//! for signing.
+//!
+//! Every block is checked for the traces of a cryptanalytic collision attack, as
+//! done by [SHA-1-DC](https://github.com/cr-marcstevens/sha1collisiondetection).
+//! That is the method of [Counter-cryptanalysis](https://marc-stevens.nl/research/papers/C13-S.pdf)
+//! (Stevens, 2013).  If one is found, [`Sha1Context::collision_detected()`]
+//! returns true, and the output is deliberately not SHA-1 (like SHA-1-DC's
+//! "safe hash" mode), so a signature over it will not verify.
+//!
+//! As in SHA-1-DC, each block is first checked against the unavoidable
+//! bit conditions of each disturbance vector, and only the (rare) vectors
+//! that pass are checked fully.
+
+use core::ops::Range;
+
+use crate::low::Blockwise;
+
+/// A context for incremental computation of SHA-1.
+#[derive(Clone)]
+pub struct Sha1Context {
+    h: [u32; 5],
+    blockwise: Blockwise<{ Self::BLOCK_SZ }>,
+    nblocks: usize,
+    collision_detected: bool,
+}
+
+impl Sha1Context {
+    /// Start a new SHA-1 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            h: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0],
+            blockwise: Blockwise::new(),
+            nblocks: 0,
+            collision_detected: false,
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        if self.blockwise.used() == 0 && bytes.len().is_multiple_of(Self::BLOCK_SZ) {
+            self.update_blocks(bytes);
+            return;
+        }
+
+        let bytes = self.blockwise.add_leading(bytes);
+
+        if let Some(block) = self.blockwise.take() {
+            self.update_blocks(&block);
+        }
+
+        let (whole_blocks, remainder) = {
+            let whole_len = bytes.len() - (bytes.len() & (Self::BLOCK_SZ - 1));
+            (&bytes[..whole_len], &bytes[whole_len..])
+        };
+
+        self.update_blocks(whole_blocks);
+
+        self.blockwise.add_trailing(remainder);
+    }
+
+    /// Returns true if the input so far contains a block from
+    /// a collision attack.
+    ///
+    /// When this is true, the output of [`Sha1Context::finish()`]
+    /// is not the SHA-1 of the input.
+    pub fn collision_detected(&self) -> bool {
+        self.collision_detected
+    }
+
+    /// Complete the SHA-1 computation, returning the hash output.
+    pub fn finish(mut self) -> [u8; Self::OUTPUT_SZ] {
+        let bytes = self
+            .nblocks
+            .checked_mul(Self::BLOCK_SZ)
+            .and_then(|bytes| bytes.checked_add(self.blockwise.used()))
+            .unwrap();
+
+        let bits = bytes
+            .checked_mul(8)
+            .expect("excess data processed by hash function");
+
+        let last_blocks = self
+            .blockwise
+            .md_pad_with_length(&(bits as u64).to_be_bytes());
+        self.update_blocks(last_blocks.as_ref());
+
+        let mut r = [0u8; Self::OUTPUT_SZ];
+        for (out, state) in r.chunks_exact_mut(4).zip(self.h.iter()) {
+            out.copy_from_slice(&state.to_be_bytes());
+        }
+        r
+    }
+
+    fn update_blocks(&mut self, blocks: &[u8]) {
+        debug_assert!(blocks.len().is_multiple_of(Self::BLOCK_SZ));
+        for block in blocks.chunks_exact(Self::BLOCK_SZ) {
+            let ihv_in = self.h;
+            crate::low::sha1_compress_blocks(&mut self.h, block);
+
+            if is_collision_block(&ihv_in, block, &self.h) {
+                self.collision_detected = true;
+                // as SHA-1-DC: two extra compressions give an output that
+                // differs from both sides of the collision.
+                crate::low::sha1_compress_blocks(&mut self.h, block);
+                crate::low::sha1_compress_blocks(&mut self.h, block);
+            }
+        }
+        self.nblocks = self.nblocks.saturating_add(blocks.len() / Self::BLOCK_SZ);
+    }
+
+    /// The internal block size of SHA-1.
+    pub const BLOCK_SZ: usize = 64;
+
+    /// The output size of SHA-1.
+    pub const OUTPUT_SZ: usize = 20;
+}
+
+/// Returns true if `block` (compressed from `ihv_in` to `ihv_out`)
+/// looks like the second block of a collision.
+///
+/// For each disturbance vector, this computes the message block
+/// that would pair with `block` in a collision attack using that vector.
+/// The two compressions share a state at the vector's test step, so the
+/// other compression can be recomputed from there (backwards to its
+/// input, and forwards to its output).  A collision is when its output
+/// equals `ihv_out`.
+fn is_collision_block(ihv_in: &[u32; 5], block: &[u8], ihv_out: &[u32; 5]) -> bool {
+    let w = expand(block);
+    check_disturbance_vectors(ihv_in, &w, ihv_out, ubc_check(&w))
+}
+
+/// Returns a mask of the disturbance vectors (bit `i` being
+/// `DISTURBANCE_VECTORS[i]`) for which the message `w` meets all the
+/// unavoidable bit conditions.
+///
+/// This is SHA-1-DC's `ubc_check`.  It is a cheap filter: a block from
+/// a collision attack must meet these for the vector that was used.
+fn ubc_check(w: &[u32; 80]) -> u32 {
+    let mut mask = !0;
+    for cond in UNAVOIDABLE_BIT_CONDITIONS.iter() {
+        let bit = (w[cond.a] >> cond.i) ^ (w[cond.b] >> cond.j);
+        if bit & 1 != cond.xor {
+            mask &= !cond.dvs;
+            if mask == 0 {
+                break;
+            }
+        }
+    }
+    mask
+}
+
+/// Checks the disturbance vectors in `dv_mask` (as returned by
+/// [`ubc_check`]), by recompression.
+fn check_disturbance_vectors(
+    ihv_in: &[u32; 5],
+    w: &[u32; 80],
+    ihv_out: &[u32; 5],
+    dv_mask: u32,
+) -> bool {
+    if dv_mask == 0 {
+        return false;
+    }
+
+    // recover the states before steps 58 and 65, by stepping back
+    // from the output.
+    let mut state = [0u32; 5];
+    for (s, (o, i)) in state.iter_mut().zip(ihv_out.iter().zip(ihv_in)) {
+        *s = o.wrapping_sub(*i);
+    }
+    let state65 = steps_back(state, w, 65..80);
+    let state58 = steps_back(state65, w, 58..65);
+
+    DISTURBANCE_VECTORS
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| dv_mask & (1 << i) != 0)
+        .any(|(_, dv)| {
+            let mut w2 = *w;
+            for (w2, dm) in w2.iter_mut().zip(dv.dm.iter()) {
+                *w2 ^= dm;
+            }
+
+            let state = match dv.test_step {
+                58 => &state58,
+                _ => &state65,
+            };
+
+            recompress(dv.test_step, state, &w2) == *ihv_out
+        })
+}
+
+/// Compute the output of a compression given its message `w` and its
+/// state before step `test_step`.
+fn recompress(test_step: usize, state: &[u32; 5], w: &[u32; 80]) -> [u32; 5] {
+    let ihv_in = steps_back(*state, w, 0..test_step);
+    let state = steps(*state, w, test_step..80);
+
+    let mut ihv_out = [0u32; 5];
+    for (o, (i, s)) in ihv_out.iter_mut().zip(ihv_in.iter().zip(state)) {
+        *o = i.wrapping_add(s);
+    }
+    ihv_out
+}
+
+/// Run `steps` of the compression function.
+///
+/// This is split by round, so each loop has a fixed step function.
+fn steps(mut state: [u32; 5], w: &[u32; 80], steps: Range<usize>) -> [u32; 5] {
+    let round =
+        |r: usize| steps.start.clamp(r * 20, r * 20 + 20)..steps.end.clamp(r * 20, r * 20 + 20);
+    for t in round(0) {
+        state = step::<0>(&state, w[t]);
+    }
+    for t in round(1) {
+        state = step::<1>(&state, w[t]);
+    }
+    for t in round(2) {
+        state = step::<2>(&state, w[t]);
+    }
+    for t in round(3) {
+        state = step::<3>(&state, w[t]);
+    }
+    state
+}
+
+/// Undo `steps` of the compression function, in reverse order.
+fn steps_back(mut state: [u32; 5], w: &[u32; 80], steps: Range<usize>) -> [u32; 5] {
+    let round =
+        |r: usize| steps.start.clamp(r * 20, r * 20 + 20)..steps.end.clamp(r * 20, r * 20 + 20);
+    for t in round(3).rev() {
+        state = step_back::<3>(&state, w[t]);
+    }
+    for t in round(2).rev() {
+        state = step_back::<2>(&state, w[t]);
+    }
+    for t in round(1).rev() {
+        state = step_back::<1>(&state, w[t]);
+    }
+    for t in round(0).rev() {
+        state = step_back::<0>(&state, w[t]);
+    }
+    state
+}
+
+fn expand(block: &[u8]) -> [u32; 80] {
+    let mut w = [0u32; 80];
+    for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+    }
+    w
+}
+
+#[inline(always)]
+fn f_plus_k<const ROUND: usize>(b: u32, c: u32, d: u32) -> u32 {
+    match ROUND {
+        0 => ((b & c) | (!b & d)).wrapping_add(0x5a827999),
+        1 => (b ^ c ^ d).wrapping_add(0x6ed9eba1),
+        2 => ((b & c) | (b & d) | (c & d)).wrapping_add(0x8f1bbcdc),
+        _ => (b ^ c ^ d).wrapping_add(0xca62c1d6),
+    }
+}
+
+/// A step of round `ROUND` of the SHA-1 compression function,
+/// on `[a, b, c, d, e]`.
+#[inline(always)]
+fn step<const ROUND: usize>(&[a, b, c, d, e]: &[u32; 5], w: u32) -> [u32; 5] {
+    let a_next = a
+        .rotate_left(5)
+        .wrapping_add(f_plus_k::<ROUND>(b, c, d))
+        .wrapping_add(e)
+        .wrapping_add(w);
+    [a_next, a, b.rotate_left(30), c, d]
+}
+
+/// The inverse of [`step`].
+#[inline(always)]
+fn step_back<const ROUND: usize>(&[a_next, a, b, c, d]: &[u32; 5], w: u32) -> [u32; 5] {
+    let b = b.rotate_right(30);
+    let e = a_next
+        .wrapping_sub(a.rotate_left(5))
+        .wrapping_sub(f_plus_k::<ROUND>(b, c, d))
+        .wrapping_sub(w);
+    [a, b, c, d, e]
+}
+
+struct DisturbanceVector {
+    /// The step before which there is no state difference.
+    test_step: usize,
+
+    /// The message difference.
+    dm: [u32; 80],
+}
+
+impl DisturbanceVector {
+    /// Type I and II disturbance vectors, as named by
+    /// [Manuel](https://eprint.iacr.org/2008/469).
+    ///
+    /// These are defined by the sixteen words starting at step `k`.
+    const fn new(type_ii: bool, k: usize, b: u32) -> Self {
+        // the disturbance vector for steps -5 to 79
+        const OFFSET: usize = 5;
+        let mut dv = [0u32; 80 + OFFSET];
+        dv[k + 15 + OFFSET] = 1u32.rotate_left(b);
+        if type_ii {
+            dv[k + 1 + OFFSET] = 1u32.rotate_left(31 + b);
+            dv[k + 3 + OFFSET] = 1u32.rotate_left(31 + b);
+        }
+
+        // which is extended by the message expansion, forwards ...
+        let mut t = k + 16 + OFFSET;
+        while t < dv.len() {
+            dv[t] = (dv[t - 3] ^ dv[t - 8] ^ dv[t - 14] ^ dv[t - 16]).rotate_left(1);
+            t += 1;
+        }
+
+        // ... and backwards
+        let mut t = k + OFFSET;
+        while t > 0 {
+            t -= 1;
+            dv[t] = dv[t + 16].rotate_right(1) ^ dv[t + 13] ^ dv[t + 8] ^ dv[t + 2];
+        }
+
+        // each disturbance is a local collision: a difference introduced
+        // at step t, corrected in the following five steps.
+        let mut dm = [0u32; 80];
+        let mut t = 0;
+        while t < 80 {
+            let i = t + OFFSET;
+            dm[t] = dv[i]
+                ^ dv[i - 1].rotate_left(5)
+                ^ dv[i - 2]
+                ^ dv[i - 3].rotate_left(30)
+                ^ dv[i - 4].rotate_left(30)
+                ^ dv[i - 5].rotate_left(30);
+            t += 1;
+        }
+
+        Self {
+            test_step: if k < 50 { 58 } else { 65 },
+            dm,
+        }
+    }
+}
+
+/// The disturbance vectors checked by SHA-1-DC.
+///
+/// These cover all published SHA-1 collision attacks.
+static DISTURBANCE_VECTORS: [DisturbanceVector; 32] = [
+    DisturbanceVector::new(false, 43, 0),
+    DisturbanceVector::new(false, 44, 0),
+    DisturbanceVector::new(false, 45, 0),
+    DisturbanceVector::new(false, 46, 0),
+    DisturbanceVector::new(false, 46, 2),
+    DisturbanceVector::new(false, 47, 0),
+    DisturbanceVector::new(false, 47, 2),
+    DisturbanceVector::new(false, 48, 0),
+    DisturbanceVector::new(false, 48, 2),
+    DisturbanceVector::new(false, 49, 0),
+    DisturbanceVector::new(false, 49, 2),
+    DisturbanceVector::new(false, 50, 0),
+    DisturbanceVector::new(false, 50, 2),
+    DisturbanceVector::new(false, 51, 0),
+    DisturbanceVector::new(false, 51, 2),
+    DisturbanceVector::new(false, 52, 0),
+    DisturbanceVector::new(true, 45, 0),
+    DisturbanceVector::new(true, 46, 0),
+    DisturbanceVector::new(true, 46, 2),
+    DisturbanceVector::new(true, 47, 0),
+    DisturbanceVector::new(true, 48, 0),
+    DisturbanceVector::new(true, 49, 0),
+    DisturbanceVector::new(true, 49, 2),
+    DisturbanceVector::new(true, 50, 0),
+    DisturbanceVector::new(true, 50, 2),
+    DisturbanceVector::new(true, 51, 0),
+    DisturbanceVector::new(true, 51, 2),
+    DisturbanceVector::new(true, 52, 0),
+    DisturbanceVector::new(true, 53, 0),
+    DisturbanceVector::new(true, 54, 0),
+    DisturbanceVector::new(true, 55, 0),
+    DisturbanceVector::new(true, 56, 0),
+];
+
+/// A condition on the message words: bit `i` of `W[a]` XOR bit `j`
+/// of `W[b]` equals `xor`.
+///
+/// A collision attack using any of the disturbance vectors in `dvs`
+/// cannot avoid this.
+struct BitCondition {
+    a: usize,
+    i: u32,
+    b: usize,
+    j: u32,
+    xor: u32,
+    dvs: u32,
+}
+
+impl BitCondition {
+    const fn new(a: usize, i: u32, b: usize, j: u32, xor: u32, dvs: u32) -> Self {
+        Self {
+            a,
+            i,
+            b,
+            j,
+            xor,
+            dvs,
+        }
+    }
+}
+
+/// The unavoidable bit conditions checked by SHA-1-DC's `ubc_check`,
+/// in the same order (which puts the most selective first).
+static UNAVOIDABLE_BIT_CONDITIONS: [BitCondition; 156] = [
+    BitCondition::new(44, 29, 45, 29, 0, 0x0283a080),
+    BitCondition::new(49, 29, 50, 29, 0, 0xc2810008),
+    BitCondition::new(48, 29, 49, 29, 0, 0x60a08004),
+    BitCondition::new(47, 4, 50, 29, 0, 0x82012220),
+    BitCondition::new(47, 29, 48, 29, 0, 0x30302002),
+    BitCondition::new(46, 4, 49, 29, 0, 0x40808888),
+    BitCondition::new(46, 29, 47, 29, 0, 0x18180801),
+    BitCondition::new(45, 4, 48, 29, 0, 0x20202224),
+    BitCondition::new(45, 29, 46, 29, 0, 0x0a0a8200),
+    BitCondition::new(44, 4, 47, 29, 0, 0x1010088a),
+    BitCondition::new(43, 4, 46, 29, 0, 0x08080225),
+    BitCondition::new(43, 29, 44, 29, 0, 0x00a12820),
+    BitCondition::new(42, 4, 45, 29, 0, 0x0202808a),
+    BitCondition::new(41, 4, 44, 29, 0, 0x00812025),
+    BitCondition::new(40, 29, 41, 29, 0, 0x800a00a2),
+    BitCondition::new(54, 29, 55, 29, 0, 0xc0882000),
+    BitCondition::new(53, 29, 54, 29, 0, 0x60220800),
+    BitCondition::new(52, 29, 53, 29, 0, 0x30110200),
+    BitCondition::new(50, 4, 53, 29, 0, 0x20128800),
+    BitCondition::new(50, 29, 51, 29, 0, 0x8a020020),
+    BitCondition::new(49, 4, 52, 29, 0, 0x10092200),
+    BitCondition::new(48, 4, 51, 29, 0, 0x08028880),
+    BitCondition::new(42, 29, 43, 29, 0, 0x00300a08),
+    BitCondition::new(41, 29, 42, 29, 0, 0x00180284),
+    BitCondition::new(40, 4, 43, 29, 0, 0x8020080a),
+    BitCondition::new(39, 4, 42, 29, 0, 0x40100205),
+    BitCondition::new(38, 4, 41, 29, 0, 0xa0080082),
+    BitCondition::new(37, 4, 40, 29, 0, 0x50020021),
+    BitCondition::new(55, 29, 56, 29, 0, 0x82108000),
+    BitCondition::new(52, 4, 55, 29, 0, 0x80908000),
+    BitCondition::new(51, 4, 54, 29, 0, 0x40282000),
+    BitCondition::new(51, 29, 52, 29, 0, 0x18080080),
+    BitCondition::new(36, 4, 40, 29, 0, 0x00110208),
+    BitCondition::new(53, 29, 56, 29, 1, 0x00308000),
+    BitCondition::new(51, 29, 54, 29, 1, 0x000a0800),
+    BitCondition::new(50, 29, 52, 29, 1, 0x00012200),
+    BitCondition::new(49, 29, 51, 29, 1, 0x00008880),
+    BitCondition::new(48, 29, 50, 29, 1, 0x00002220),
+    BitCondition::new(47, 29, 49, 29, 1, 0x00000888),
+    BitCondition::new(46, 29, 48, 29, 1, 0x00000224),
+    BitCondition::new(45, 6, 47, 6, 0, 0x00004440),
+    BitCondition::new(45, 29, 47, 29, 1, 0x0000008a),
+    BitCondition::new(44, 6, 46, 6, 0, 0x00001110),
+    BitCondition::new(44, 29, 46, 29, 1, 0x00000025),
+    BitCondition::new(41, 1, 42, 6, 1, 0x04040100),
+    BitCondition::new(40, 1, 41, 6, 1, 0x01004040),
+    BitCondition::new(40, 4, 42, 4, 1, 0x8000000a),
+    BitCondition::new(39, 1, 40, 6, 1, 0x00401010),
+    BitCondition::new(39, 4, 41, 4, 1, 0x40000005),
+    BitCondition::new(38, 4, 40, 4, 1, 0xa0000002),
+    BitCondition::new(37, 4, 39, 4, 1, 0x50000001),
+    BitCondition::new(36, 1, 37, 6, 1, 0x00041040),
+    BitCondition::new(35, 4, 39, 29, 0, 0x00080084),
+    BitCondition::new(63, 0, 64, 5, 1, 0x00100080),
+    BitCondition::new(63, 1, 64, 6, 1, 0x00010004),
+    BitCondition::new(62, 0, 63, 5, 1, 0x00080020),
+    BitCondition::new(61, 0, 62, 5, 1, 0x00020008),
+    BitCondition::new(61, 2, 62, 7, 1, 0x00040010),
+    BitCondition::new(60, 0, 61, 5, 1, 0x00010004),
+    BitCondition::new(58, 29, 59, 29, 0, 0x22000000),
+    BitCondition::new(57, 29, 58, 29, 0, 0x10800000),
+    BitCondition::new(56, 4, 59, 29, 0, 0x28000000),
+    BitCondition::new(56, 29, 59, 29, 1, 0x0a000000),
+    BitCondition::new(56, 29, 57, 29, 0, 0x08200000),
+    BitCondition::new(55, 4, 58, 29, 0, 0x12000000),
+    BitCondition::new(54, 4, 57, 29, 0, 0x08800000),
+    BitCondition::new(53, 4, 56, 29, 0, 0x02200000),
+    BitCondition::new(51, 1, 50, 6, 0, 0x00041000),
+    BitCondition::new(48, 6, 50, 6, 0, 0x00041000),
+    BitCondition::new(48, 29, 55, 29, 1, 0x0000a000),
+    BitCondition::new(47, 6, 49, 6, 0, 0x00004400),
+    BitCondition::new(48, 1, 47, 6, 0, 0x04000040),
+    BitCondition::new(46, 6, 48, 6, 0, 0x00001100),
+    BitCondition::new(47, 1, 46, 6, 0, 0x01000010),
+    BitCondition::new(44, 1, 45, 6, 1, 0x00404000),
+    BitCondition::new(43, 6, 45, 6, 0, 0x00000440),
+    BitCondition::new(42, 6, 44, 6, 0, 0x00000110),
+    BitCondition::new(43, 1, 42, 6, 0, 0x04040000),
+    BitCondition::new(42, 1, 41, 6, 0, 0x01004000),
+    BitCondition::new(41, 1, 40, 6, 0, 0x00401000),
+    BitCondition::new(39, 4, 43, 29, 0, 0x02008000),
+    BitCondition::new(38, 4, 42, 29, 0, 0x00802000),
+    BitCondition::new(37, 1, 38, 6, 1, 0x00004100),
+    BitCondition::new(37, 4, 41, 29, 0, 0x00200800),
+    BitCondition::new(36, 4, 38, 4, 1, 0x28000000),
+    BitCondition::new(35, 1, 36, 6, 1, 0x00000410),
+    BitCondition::new(35, 3, 39, 28, 0, 0x00082000),
+    BitCondition::new(40, 6, 42, 6, 0, 0x00000010),
+    BitCondition::new(36, 4, 37, 4, 1, 0x00000800),
+    BitCondition::new(43, 1, 51, 1, 1, 0x00001000),
+    BitCondition::new(37, 4, 38, 4, 1, 0x00002000),
+    BitCondition::new(38, 4, 39, 4, 1, 0x00008000),
+    BitCondition::new(47, 1, 51, 1, 1, 0x00040000),
+    BitCondition::new(61, 1, 62, 6, 1, 0x00000001),
+    BitCondition::new(59, 5, 63, 30, 0, 0x00000001),
+    BitCondition::new(58, 0, 63, 30, 1, 0x00000001),
+    BitCondition::new(62, 1, 63, 6, 1, 0x00000002),
+    BitCondition::new(60, 5, 64, 30, 0, 0x00000002),
+    BitCondition::new(59, 0, 64, 30, 1, 0x00000002),
+    BitCondition::new(62, 2, 63, 7, 1, 0x00000040),
+    BitCondition::new(41, 6, 43, 6, 0, 0x00000040),
+    BitCondition::new(63, 2, 64, 7, 1, 0x00000100),
+    BitCondition::new(48, 6, 49, 1, 0, 0x00000100),
+    BitCondition::new(49, 6, 50, 1, 0, 0x00000400),
+    BitCondition::new(42, 1, 50, 1, 1, 0x00000400),
+    BitCondition::new(39, 6, 40, 1, 0, 0x00000400),
+    BitCondition::new(38, 1, 40, 1, 1, 0x00000400),
+    BitCondition::new(51, 6, 52, 1, 0, 0x00004000),
+    BitCondition::new(49, 6, 51, 6, 0, 0x00004000),
+    BitCondition::new(37, 1, 37, 6, 0, 0x00004000),
+    BitCondition::new(35, 5, 39, 30, 0, 0x00004000),
+    BitCondition::new(36, 3, 40, 28, 0, 0x00100000),
+    BitCondition::new(35, 30, 40, 28, 1, 0x00100000),
+    BitCondition::new(37, 3, 41, 28, 0, 0x00200000),
+    BitCondition::new(36, 30, 41, 28, 1, 0x00200000),
+    BitCondition::new(53, 6, 54, 1, 0, 0x00400000),
+    BitCondition::new(51, 6, 53, 6, 0, 0x00400000),
+    BitCondition::new(50, 1, 54, 1, 1, 0x00400000),
+    BitCondition::new(45, 6, 46, 1, 0, 0x00400000),
+    BitCondition::new(37, 5, 41, 30, 0, 0x00400000),
+    BitCondition::new(36, 0, 41, 30, 1, 0x00400000),
+    BitCondition::new(55, 29, 58, 29, 1, 0x00800000),
+    BitCondition::new(38, 3, 42, 28, 0, 0x00800000),
+    BitCondition::new(37, 30, 42, 28, 1, 0x00800000),
+    BitCondition::new(54, 6, 55, 1, 0, 0x01000000),
+    BitCondition::new(52, 6, 54, 6, 0, 0x01000000),
+    BitCondition::new(51, 1, 55, 1, 1, 0x01000000),
+    BitCondition::new(45, 1, 47, 1, 1, 0x01000000),
+    BitCondition::new(38, 5, 42, 30, 0, 0x01000000),
+    BitCondition::new(37, 0, 42, 30, 1, 0x01000000),
+    BitCondition::new(39, 3, 43, 28, 0, 0x02000000),
+    BitCondition::new(38, 30, 43, 28, 1, 0x02000000),
+    BitCondition::new(55, 6, 56, 1, 0, 0x04000000),
+    BitCondition::new(53, 6, 55, 6, 0, 0x04000000),
+    BitCondition::new(52, 1, 56, 1, 1, 0x04000000),
+    BitCondition::new(46, 1, 48, 1, 1, 0x04000000),
+    BitCondition::new(39, 5, 43, 30, 0, 0x04000000),
+    BitCondition::new(38, 0, 43, 30, 1, 0x04000000),
+    BitCondition::new(59, 29, 60, 29, 0, 0x08000000),
+    BitCondition::new(40, 3, 44, 28, 0, 0x08000000),
+    BitCondition::new(40, 4, 44, 29, 0, 0x08000000),
+    BitCondition::new(39, 30, 44, 28, 1, 0x08000000),
+    BitCondition::new(58, 29, 61, 29, 1, 0x10000000),
+    BitCondition::new(57, 4, 61, 29, 0, 0x10000000),
+    BitCondition::new(41, 3, 45, 28, 0, 0x10000000),
+    BitCondition::new(41, 4, 45, 29, 0, 0x10000000),
+    BitCondition::new(58, 4, 62, 29, 0, 0x20000000),
+    BitCondition::new(42, 3, 46, 28, 0, 0x20000000),
+    BitCondition::new(42, 4, 46, 29, 0, 0x20000000),
+    BitCondition::new(59, 4, 63, 29, 0, 0x40000000),
+    BitCondition::new(57, 4, 59, 29, 0, 0x40000000),
+    BitCondition::new(43, 3, 47, 28, 0, 0x40000000),
+    BitCondition::new(43, 4, 47, 29, 0, 0x40000000),
+    BitCondition::new(60, 4, 64, 29, 0, 0x80000000),
+    BitCondition::new(44, 3, 48, 28, 0, 0x80000000),
+    BitCondition::new(44, 4, 48, 29, 0, 0x80000000),
+];
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn sha1(bytes: &[u8]) -> ([u8; 20], bool) {
+        let mut ctx = Sha1Context::new();
+        ctx.update(bytes);
+        let detected = ctx.collision_detected();
+        (ctx.finish(), detected)
+    }
+
+    #[test]
+    fn vectors() {
+        assert_eq!(
+            sha1(b""),
+            (
+                *b"\xda\x39\xa3\xee\x5e\x6b\x4b\x0d\x32\x55\xbf\xef\x95\x60\x18\x90\xaf\xd8\x07\x09",
+                false
+            )
+        );
+        assert_eq!(
+            sha1(b"abc"),
+            (
+                *b"\xa9\x99\x3e\x36\x47\x06\x81\x6a\xba\x3e\x25\x71\x78\x50\xc2\x6c\x9c\xd0\xd8\x9d",
+                false
+            )
+        );
+        assert_eq!(
+            sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            (
+                *b"\x84\x98\x3e\x44\x1c\x3b\xd2\x6e\xba\xae\x4a\xa1\xf9\x51\x29\xe5\xe5\x46\x70\xf1",
+                false
+            )
+        );
+
+        let mut ctx = Sha1Context::new();
+        for _ in 0..1000 {
+            ctx.update(&[b'a'; 1000]);
+        }
+        assert!(!ctx.collision_detected());
+        assert_eq!(
+            ctx.finish(),
+            *b"\x34\xaa\x97\x3c\xd4\xc4\xda\xa4\xf6\x1e\xeb\x2b\xdb\xad\x27\x31\x65\x34\x01\x6f"
+        );
+    }
+
+    #[test]
+    fn shattered() {
+        // The colliding prefixes of the SHAttered PDFs (an identical-prefix
+        // collision), from <https://shattered.io>.
+        //
+        // Expected values are from SHA-1-DC.
+        assert_eq!(
+            sha1(include_bytes!("testdata/shattered-1-prefix.bin")),
+            (
+                *b"\x71\x17\xb3\xcb\x92\x25\xaa\xf0\xd8\xef\x1a\x40\xe4\x93\x95\x7b\x0b\xf8\x69\x3d",
+                true
+            )
+        );
+        assert_eq!(
+            sha1(include_bytes!("testdata/shattered-2-prefix.bin")),
+            (
+                *b"\x29\xf3\x8a\xe9\xfd\x98\xe2\x93\x11\x20\xfa\x0b\xf2\x13\xe0\x24\x25\x0d\x3f\x6a",
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn shambles() {
+        // The SHA-1 is a Shambles chosen-prefix collision, from
+        // <https://sha-mbles.github.io>.
+        //
+        // Expected values are from SHA-1-DC.
+        let one = include_bytes!("testdata/sha-mbles-1.bin");
+        let two = include_bytes!("testdata/sha-mbles-2.bin");
+        assert_eq!(
+            sha1(one),
+            (
+                *b"\x4f\x3d\x9b\xe4\xa4\x72\xc4\xda\xe8\x3c\x63\x14\xaa\x6c\x36\xa0\x64\xc1\xfd\x14",
+                true
+            )
+        );
+        assert_eq!(
+            sha1(two),
+            (
+                *b"\x9e\xd5\xd7\x7a\x4f\x48\xbe\x1d\xbf\x3e\x9e\x15\x65\x07\x33\xeb\x85\x08\x97\xf2",
+                true
+            )
+        );
+
+        // the chosen prefixes are not themselves detected
+        assert!(!sha1(&one[..320]).1);
+        assert!(!sha1(&two[..320]).1);
+    }
+
+    #[test]
+    fn ubc_check_agrees_with_full_check() {
+        for input in [
+            &include_bytes!("testdata/shattered-1-prefix.bin")[..],
+            &include_bytes!("testdata/shattered-2-prefix.bin")[..],
+            &include_bytes!("testdata/sha-mbles-1.bin")[..],
+            &include_bytes!("testdata/sha-mbles-2.bin")[..],
+        ] {
+            let mut ihv = Sha1Context::new().h;
+            let mut collisions = 0;
+            for block in input.chunks_exact(64) {
+                let ihv_in = ihv;
+                crate::low::sha1_compress_blocks(&mut ihv, block);
+
+                let w = expand(block);
+                let filtered = check_disturbance_vectors(&ihv_in, &w, &ihv, ubc_check(&w));
+                let unfiltered = check_disturbance_vectors(&ihv_in, &w, &ihv, !0);
+                assert_eq!(filtered, unfiltered);
+                collisions += filtered as usize;
+            }
+            assert!(collisions > 0);
+        }
+    }
+
+    #[test]
+    fn disturbance_vectors_match_sha1dc() {
+        // first and last words of `dm` for each of SHA-1-DC's `sha1_dvs`
+        let expect = [
+            (0x08000000, 0x80000599),
+            (0xb4000008, 0x80000161),
+            (0xf4000014, 0x00000803),
+            (0x2c000010, 0x8000004c),
+            (0xb0000040, 0x00000132),
+            (0xc8000010, 0x800000e6),
+            (0x20000043, 0x0000039a),
+            (0xb800000a, 0x00000408),
+            (0xe000002a, 0x00001020),
+            (0x18000000, 0x00000164),
+            (0x60000000, 0x00000590),
+            (0x0800000c, 0x00000018),
+            (0x20000030, 0x00000060),
+            (0xe8000000, 0x80000202),
+            (0xa0000003, 0x0000080a),
+            (0x04000010, 0x80000012),
+            (0xec000014, 0x00000967),
+            (0x2400001c, 0x80000054),
+            (0x90000070, 0x00000152),
+            (0x20000010, 0x000002e4),
+            (0xbc00001a, 0x8000041a),
+            (0x3c000004, 0x8000016d),
+            (0xf0000010, 0x000005b6),
+            (0xb400001c, 0x0000011b),
+            (0xd0000072, 0x0000046c),
+            (0xc0000010, 0x8000024b),
+            (0x00000043, 0x0000092e),
+            (0x0c000002, 0x00000014),
+            (0xcc000014, 0x00000089),
+            (0x0400001c, 0x80000107),
+            (0x00000010, 0x4000004b),
+            (0x2600001a, 0xc0000046),
+        ];
+
+        for (dv, (first, last)) in DISTURBANCE_VECTORS.iter().zip(expect) {
+            assert_eq!((dv.dm[0], dv.dm[79]), (first, last));
+        }
+    }
+
+    #[test]
+    fn recompress() {
+        let mut block = [0u8; 64];
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = (i * 13 + 5) as u8;
+        }
+        let w = expand(&block);
+
+        let ihv_in = Sha1Context::new().h;
+        let mut ihv_out = ihv_in;
+        crate::low::sha1_compress_blocks(&mut ihv_out, &block);
+
+        for t in 0..80 {
+            let state = steps(ihv_in, &w, 0..t);
+            assert_eq!(steps_back(state, &w, 0..t), ihv_in);
+            assert_eq!(super::recompress(t, &state, &w), ihv_out);
+        }
+        assert!(!is_collision_block(&ihv_in, &block, &ihv_out));
+    }
+}
@@ -3,10 +3,49 @@
 
 //! SHA2-family hash functions.
 //!
-//! This is SHA256, SHA384, and SHA512.
+//! This is SHA224, SHA256, SHA384, SHA512, SHA512/224 and SHA512/256.
 //! These are all described in [FIPS180](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
 
-use crate::low::Blockwise;
+use crate::Error;
+use crate::low::{Blockwise, Zeroable, zeroise};
+use crate::mid::midstate::{Algorithm, HEADER_SZ, Reader, Writer};
+
+/// A context for incremental computation of SHA224.
+#[derive(Clone)]
+pub struct Sha224Context {
+    inner: Sha256Context,
+}
+
+impl Sha224Context {
+    /// Start a new SHA224 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            inner: Sha256Context {
+                h: [
+                    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511,
+                    0x64f98fa7, 0xbefa4fa4,
+                ],
+                blockwise: Blockwise::new(),
+                nblocks: 0,
+            },
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes)
+    }
+
+    /// Complete the SHA224 computation, returning the hash output.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let inner = self.inner.finish();
+        // SAFETY: 28 is less than 32.
+        inner[..Self::OUTPUT_SZ].try_into().unwrap()
+    }
+
+    /// The output size of SHA224.
+    pub const OUTPUT_SZ: usize = 28;
+}
 
 /// A context for incremental computation of SHA256.
 #[derive(Clone)]
@@ -76,6 +115,109 @@ impl Sha256Context {
         r
     }
 
+    /// Compute the SHA256 hash of each of `messages`, writing them to
+    /// the same positions in `outputs`.
+    ///
+    /// This gives the same results as hashing each message separately,
+    /// but several messages are hashed in parallel.  This is much faster
+    /// for large numbers of short messages.
+    ///
+    /// `messages` and `outputs` must be the same length.  This function
+    /// panics otherwise.
+    pub fn hash_many(messages: &[&[u8]], outputs: &mut [[u8; Self::OUTPUT_SZ]]) {
+        assert_eq!(messages.len(), outputs.len());
+        hash_many(
+            messages,
+            Self::new().h,
+            8,
+            crate::low::sha256_compress_lanes,
+            |i, h| {
+                for (out, state) in outputs[i].chunks_exact_mut(4).zip(h.iter()) {
+                    out.copy_from_slice(&state.to_be_bytes());
+                }
+            },
+        );
+    }
+
+    /// Do PBKDF2-HMAC-SHA256 iterations 2 to `iterations` for several
+    /// output blocks at once.
+    ///
+    /// `inner` and `outer` are the HMAC contexts after absorbing the
+    /// padded key.  On entry, each 32-byte chunk of `inout` is U_1 for
+    /// one output block; on exit it is T for that block.
+    pub(crate) fn pbkdf2_iterate(inner: &Self, outer: &Self, iterations: u32, inout: &mut [u8]) {
+        debug_assert!(inner.nblocks == 1 && inner.blockwise.used() == 0);
+        debug_assert!(outer.nblocks == 1 && outer.blockwise.used() == 0);
+        pbkdf2_iterate::<u32, { Self::BLOCK_SZ }, { Self::OUTPUT_SZ }>(
+            inner.h,
+            outer.h,
+            iterations,
+            crate::low::sha256_compress_lanes,
+            |h, out| {
+                for (out, state) in out.chunks_exact_mut(4).zip(h.iter()) {
+                    out.copy_from_slice(&state.to_be_bytes());
+                }
+            },
+            inout,
+        );
+    }
+
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// The computation can later be resumed from this state with
+    /// [`Sha256Context::import()`], perhaps in a different process.
+    /// The format is versioned, and will be accepted by future
+    /// versions of this crate.
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let bytes = (self.nblocks as u64)
+            .checked_mul(Self::BLOCK_SZ as u64)
+            .and_then(|bytes| bytes.checked_add(self.blockwise.used() as u64))
+            .expect("excess data processed by hash function");
+
+        let mut r = [0u8; Self::EXPORT_SZ];
+        let mut w = Writer::new(&mut r, Algorithm::Sha256);
+        for h in self.h {
+            w.put(&h.to_be_bytes());
+        }
+        w.put(&bytes.to_be_bytes());
+        w.put_block(
+            self.blockwise.peek_remaining().unwrap_or_default(),
+            Self::BLOCK_SZ,
+        );
+        w.finish();
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Sha256Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Sha256Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHA256 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        let mut r = Reader::new(bytes, Algorithm::Sha256, Self::EXPORT_SZ)?;
+
+        let mut h = [0; 8];
+        for h in h.iter_mut() {
+            *h = u32::from_be_bytes(r.take()?);
+        }
+
+        let bytes = u64::from_be_bytes(r.take()?);
+        let nblocks =
+            usize::try_from(bytes / Self::BLOCK_SZ as u64).map_err(|_| Error::BadHashState)?;
+        let used = (bytes % Self::BLOCK_SZ as u64) as usize;
+
+        let mut blockwise = Blockwise::new();
+        blockwise.add_trailing(r.take_block(used, Self::BLOCK_SZ)?);
+        r.finish()?;
+
+        Ok(Self {
+            h,
+            blockwise,
+            nblocks,
+        })
+    }
+
     fn update_blocks(&mut self, blocks: &[u8]) {
         debug_assert!(blocks.len().is_multiple_of(Self::BLOCK_SZ));
         if !blocks.is_empty() {
@@ -89,6 +231,9 @@ impl Sha256Context {
 
     /// The output size of SHA256.
     pub const OUTPUT_SZ: usize = 32;
+
+    /// The size of an exported state: see [`Sha256Context::export()`].
+    pub const EXPORT_SZ: usize = HEADER_SZ + 32 + 8 + Self::BLOCK_SZ;
 }
 
 /// A context for incremental computation of SHA384.
@@ -206,6 +351,105 @@ impl Sha512Context {
         r
     }
 
+    /// Compute the SHA512 hash of each of `messages`, writing them to
+    /// the same positions in `outputs`.
+    ///
+    /// See [`Sha256Context::hash_many()`].
+    ///
+    /// `messages` and `outputs` must be the same length.  This function
+    /// panics otherwise.
+    pub fn hash_many(messages: &[&[u8]], outputs: &mut [[u8; Self::OUTPUT_SZ]]) {
+        assert_eq!(messages.len(), outputs.len());
+        hash_many(
+            messages,
+            Self::new().h,
+            16,
+            crate::low::sha512_compress_lanes,
+            |i, h| {
+                for (out, state) in outputs[i].chunks_exact_mut(8).zip(h.iter()) {
+                    out.copy_from_slice(&state.to_be_bytes());
+                }
+            },
+        );
+    }
+
+    /// Do PBKDF2-HMAC-SHA512 iterations 2 to `iterations` for several
+    /// output blocks at once.
+    ///
+    /// See [`Sha256Context::pbkdf2_iterate()`].
+    pub(crate) fn pbkdf2_iterate(inner: &Self, outer: &Self, iterations: u32, inout: &mut [u8]) {
+        debug_assert!(inner.nblocks == 1 && inner.blockwise.used() == 0);
+        debug_assert!(outer.nblocks == 1 && outer.blockwise.used() == 0);
+        pbkdf2_iterate::<u64, { Self::BLOCK_SZ }, { Self::OUTPUT_SZ }>(
+            inner.h,
+            outer.h,
+            iterations,
+            crate::low::sha512_compress_lanes,
+            |h, out| {
+                for (out, state) in out.chunks_exact_mut(8).zip(h.iter()) {
+                    out.copy_from_slice(&state.to_be_bytes());
+                }
+            },
+            inout,
+        );
+    }
+
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// The computation can later be resumed from this state with
+    /// [`Sha512Context::import()`], perhaps in a different process.
+    /// The format is versioned, and will be accepted by future
+    /// versions of this crate.
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let bytes = (self.nblocks as u64)
+            .checked_mul(Self::BLOCK_SZ as u64)
+            .and_then(|bytes| bytes.checked_add(self.blockwise.used() as u64))
+            .expect("excess data processed by hash function");
+
+        let mut r = [0u8; Self::EXPORT_SZ];
+        let mut w = Writer::new(&mut r, Algorithm::Sha512);
+        for h in self.h {
+            w.put(&h.to_be_bytes());
+        }
+        w.put(&bytes.to_be_bytes());
+        w.put_block(
+            self.blockwise.peek_remaining().unwrap_or_default(),
+            Self::BLOCK_SZ,
+        );
+        w.finish();
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Sha512Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Sha512Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHA512 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        let mut r = Reader::new(bytes, Algorithm::Sha512, Self::EXPORT_SZ)?;
+
+        let mut h = [0; 8];
+        for h in h.iter_mut() {
+            *h = u64::from_be_bytes(r.take()?);
+        }
+
+        let bytes = u64::from_be_bytes(r.take()?);
+        let nblocks =
+            usize::try_from(bytes / Self::BLOCK_SZ as u64).map_err(|_| Error::BadHashState)?;
+        let used = (bytes % Self::BLOCK_SZ as u64) as usize;
+
+        let mut blockwise = Blockwise::new();
+        blockwise.add_trailing(r.take_block(used, Self::BLOCK_SZ)?);
+        r.finish()?;
+
+        Ok(Self {
+            h,
+            blockwise,
+            nblocks,
+        })
+    }
+
     fn update_blocks(&mut self, blocks: &[u8]) {
         debug_assert!(blocks.len().is_multiple_of(Self::BLOCK_SZ));
         if !blocks.is_empty() {
@@ -219,6 +463,279 @@ impl Sha512Context {
 
     /// The output size of SHA512.
     pub const OUTPUT_SZ: usize = 64;
+
+    /// The size of an exported state: see [`Sha512Context::export()`].
+    pub const EXPORT_SZ: usize = HEADER_SZ + 64 + 8 + Self::BLOCK_SZ;
+}
+
+/// A context for incremental computation of SHA512/224.
+#[derive(Clone)]
+pub struct Sha512_224Context {
+    inner: Sha512Context,
+}
+
+impl Sha512_224Context {
+    /// Start a new SHA512/224 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            inner: Sha512Context {
+                h: [
+                    0x8c3d37c819544da2,
+                    0x73e1996689dcd4d6,
+                    0x1dfab7ae32ff9c82,
+                    0x679dd514582f9fcf,
+                    0x0f6d2b697bd44da8,
+                    0x77e36f7304c48942,
+                    0x3f9d85a86a1d36c8,
+                    0x1112e6ad91d692a1,
+                ],
+                blockwise: Blockwise::new(),
+                nblocks: 0,
+            },
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes)
+    }
+
+    /// Complete the SHA512/224 computation, returning the hash output.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let inner = self.inner.finish();
+        // SAFETY: 28 is less than 64.
+        inner[..Self::OUTPUT_SZ].try_into().unwrap()
+    }
+
+    /// The output size of SHA512/224.
+    pub const OUTPUT_SZ: usize = 28;
+}
+
+/// A context for incremental computation of SHA512/256.
+#[derive(Clone)]
+pub struct Sha512_256Context {
+    inner: Sha512Context,
+}
+
+impl Sha512_256Context {
+    /// Start a new SHA512/256 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            inner: Sha512Context {
+                h: [
+                    0x22312194fc2bf72c,
+                    0x9f555fa3c84c64c2,
+                    0x2393b86b6f53b151,
+                    0x963877195940eabd,
+                    0x96283ee2a88effe3,
+                    0xbe5e1e2553863992,
+                    0x2b0199fc2c85b8aa,
+                    0x0eb72ddc81c52ca2,
+                ],
+                blockwise: Blockwise::new(),
+                nblocks: 0,
+            },
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes)
+    }
+
+    /// Complete the SHA512/256 computation, returning the hash output.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let inner = self.inner.finish();
+        // SAFETY: 32 is less than 64.
+        inner[..Self::OUTPUT_SZ].try_into().unwrap()
+    }
+
+    /// The output size of SHA512/256.
+    pub const OUTPUT_SZ: usize = 32;
+}
+
+/// Hash each of `messages`, starting from `initial`.
+///
+/// Up to `LANES` messages are in progress at once, and each call to
+/// `compress` processes the next block of each.  When a message is
+/// complete, its final state is passed to `output` (with the message's
+/// index) and the next message takes its place.
+///
+/// `length_sz` is the size of the big-endian bit length at the end
+/// of the padding.
+fn hash_many<S: Copy, const B: usize>(
+    messages: &[&[u8]],
+    initial: S,
+    length_sz: usize,
+    compress: fn(&mut [S], &[&[u8; B]]),
+    mut output: impl FnMut(usize, &S),
+) {
+    const LANES: usize = 8;
+
+    let mut pending = messages.iter().enumerate();
+    let mut lanes = [(); LANES].map(|_| Lane::<B>::new());
+    let mut states = [initial; LANES];
+    let mut active = 0;
+
+    for lane in lanes.iter_mut() {
+        let Some((index, message)) = pending.next() else {
+            break;
+        };
+        lane.start(index, message, length_sz);
+        active += 1;
+    }
+
+    while active > 0 {
+        let mut blocks = [&[0u8; B]; LANES];
+        for (block, lane) in blocks.iter_mut().zip(&lanes[..active]) {
+            *block = lane.next_block();
+        }
+        compress(&mut states[..active], &blocks[..active]);
+
+        let mut i = 0;
+        while i < active {
+            let lane = &mut lanes[i];
+            lane.done += 1;
+
+            if lane.done < lane.blocks() {
+                i += 1;
+                continue;
+            }
+
+            output(lane.index, &states[i]);
+
+            match pending.next() {
+                Some((index, message)) => {
+                    lane.start(index, message, length_sz);
+                    states[i] = initial;
+                    i += 1;
+                }
+                None => {
+                    // no more messages: move the last active lane here
+                    active -= 1;
+                    lanes.swap(i, active);
+                    states.swap(i, active);
+                }
+            }
+        }
+    }
+}
+
+/// PBKDF2's iterated HMAC, for up to `LANES` output blocks at a time.
+///
+/// `inner` and `outer` are the HMAC hash states after absorbing one
+/// block of padded key, so each HMAC of an `H`-byte `U` is one
+/// compression with each.  Each `H`-byte chunk of `inout` holds U_1
+/// for one output block on entry, and T for that block on exit.
+fn pbkdf2_iterate<W: Copy + Zeroable, const B: usize, const H: usize>(
+    inner: [W; 8],
+    outer: [W; 8],
+    iterations: u32,
+    compress: fn(&mut [[W; 8]], &[&[u8; B]]),
+    encode: fn(&[W; 8], &mut [u8]),
+    inout: &mut [u8],
+) {
+    const LANES: usize = 8;
+
+    let (ts, rest) = inout.as_chunks_mut::<H>();
+    debug_assert!(rest.is_empty());
+
+    for ts in ts.chunks_mut(LANES) {
+        let n = ts.len();
+
+        // Each block is U, padded as the final block of a message that
+        // follows the key block.  Only U changes between iterations.
+        let mut blocks = [[0u8; B]; LANES];
+        for (block, t) in blocks.iter_mut().zip(ts.iter()) {
+            block[..H].copy_from_slice(t);
+            block[H] = 0x80;
+            block[B - 8..].copy_from_slice(&(((B + H) * 8) as u64).to_be_bytes());
+        }
+        let mut states = [inner; LANES];
+
+        for _ in 1..iterations {
+            states[..n].fill(inner);
+            compress(
+                &mut states[..n],
+                &core::array::from_fn::<_, LANES, _>(|i| &blocks[i])[..n],
+            );
+            for (block, state) in blocks.iter_mut().zip(&states[..n]) {
+                encode(state, &mut block[..H]);
+            }
+
+            states[..n].fill(outer);
+            compress(
+                &mut states[..n],
+                &core::array::from_fn::<_, LANES, _>(|i| &blocks[i])[..n],
+            );
+            for ((block, state), t) in blocks.iter_mut().zip(&states[..n]).zip(ts.iter_mut()) {
+                encode(state, &mut block[..H]);
+                for (t, u) in t.iter_mut().zip(&block[..H]) {
+                    *t ^= *u;
+                }
+            }
+        }
+
+        zeroise(blocks.as_flattened_mut());
+        zeroise(states.as_flattened_mut());
+    }
+}
+
+/// One message being hashed by [`hash_many()`].
+struct Lane<'a, const B: usize> {
+    index: usize,
+    body: &'a [[u8; B]],
+    tail: [[u8; B]; 2],
+    tail_blocks: usize,
+    done: usize,
+}
+
+impl<'a, const B: usize> Lane<'a, B> {
+    fn new() -> Self {
+        Self {
+            index: 0,
+            body: &[],
+            tail: [[0u8; B]; 2],
+            tail_blocks: 0,
+            done: 0,
+        }
+    }
+
+    /// Start hashing `message`, which is `messages[index]`.
+    fn start(&mut self, index: usize, message: &'a [u8], length_sz: usize) {
+        let (body, remainder) = message.as_chunks::<B>();
+        let tail_blocks = if remainder.len() + 1 + length_sz > B {
+            2
+        } else {
+            1
+        };
+
+        let tail = &mut self.tail.as_flattened_mut()[..tail_blocks * B];
+        let (tail, length) = tail.split_at_mut(tail.len() - length_sz);
+        let (message_end, padding) = tail.split_at_mut(remainder.len());
+        message_end.copy_from_slice(remainder);
+        padding[0] = 0x80;
+        padding[1..].fill(0x00);
+
+        let bits = (message.len() as u128 * 8).to_be_bytes();
+        length.copy_from_slice(&bits[bits.len() - length_sz..]);
+
+        self.index = index;
+        self.body = body;
+        self.tail_blocks = tail_blocks;
+        self.done = 0;
+    }
+
+    fn blocks(&self) -> usize {
+        self.body.len() + self.tail_blocks
+    }
+
+    fn next_block(&self) -> &[u8; B] {
+        match self.body.get(self.done) {
+            Some(block) => block,
+            None => &self.tail[self.done - self.body.len()],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -293,4 +810,144 @@ mod tests {
         assert_eq!(&outer.finish(),
                    b"\x61\x20\x81\x2e\xd5\x0c\xc3\x11\x67\x04\x3f\x1f\x06\x9d\xcd\x4a\xd8\x83\x23\xd9\x96\x53\xd9\x67\x38\x2c\xc3\x44\x25\x69\x53\x1c\xd0\x3d\xe4\x79\x0a\x71\xde\x88\x45\x44\x66\x80\xb8\xc5\x90\xb3\x07\xc8\xae\x52\x57\x67\xf9\x28\xf8\xda\x9e\x9e\x80\xc9\x35\x5e");
     }
+
+    #[test]
+    fn hash_many_all_lengths() {
+        // as `sha256_all_lengths` and `sha512_all_lengths`
+        let messages = (0..1024)
+            .map(|len| vec![len as u8; len])
+            .collect::<Vec<_>>();
+        let messages = messages.iter().map(|m| &m[..]).collect::<Vec<_>>();
+
+        let mut outputs = vec![[0u8; 32]; messages.len()];
+        Sha256Context::hash_many(&messages, &mut outputs);
+        let mut outer = Sha256Context::new();
+        outer.update(outputs.as_flattened());
+        assert_eq!(&outer.finish(),
+                   b"\x55\x7b\xfd\xd5\xef\xda\xfd\x63\x06\x5e\xb7\x98\x87\xde\x86\xdb\x54\xc3\xfe\xdf\x7b\xcc\xcb\x97\x08\xfa\x87\xf0\x11\x87\x61\xdc");
+
+        let mut outputs = vec![[0u8; 64]; messages.len()];
+        Sha512Context::hash_many(&messages, &mut outputs);
+        let mut outer = Sha512Context::new();
+        outer.update(outputs.as_flattened());
+        assert_eq!(&outer.finish(),
+                   b"\x61\x20\x81\x2e\xd5\x0c\xc3\x11\x67\x04\x3f\x1f\x06\x9d\xcd\x4a\xd8\x83\x23\xd9\x96\x53\xd9\x67\x38\x2c\xc3\x44\x25\x69\x53\x1c\xd0\x3d\xe4\x79\x0a\x71\xde\x88\x45\x44\x66\x80\xb8\xc5\x90\xb3\x07\xc8\xae\x52\x57\x67\xf9\x28\xf8\xda\x9e\x9e\x80\xc9\x35\x5e");
+    }
+
+    #[test]
+    fn hash_many_matches_single() {
+        let data = (0..1000).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        // every number of messages up to, and past, a whole set of lanes
+        for count in 0..20 {
+            let messages = (0..count)
+                .map(|i| &data[i * 37..i * 37 + (i * 13 + 64) % 300])
+                .collect::<Vec<_>>();
+
+            let mut outputs = vec![[0u8; 32]; count];
+            Sha256Context::hash_many(&messages, &mut outputs);
+            for (message, output) in messages.iter().zip(outputs.iter()) {
+                let mut ctx = Sha256Context::new();
+                ctx.update(message);
+                assert_eq!(&ctx.finish(), output);
+            }
+
+            let mut outputs = vec![[0u8; 64]; count];
+            Sha512Context::hash_many(&messages, &mut outputs);
+            for (message, output) in messages.iter().zip(outputs.iter()) {
+                let mut ctx = Sha512Context::new();
+                ctx.update(message);
+                assert_eq!(&ctx.finish(), output);
+            }
+        }
+    }
+
+    #[test]
+    fn export_import() {
+        use crate::high::hash::Hash;
+
+        let input = [0x5au8; 300];
+
+        for split in [0, 1, 63, 64, 65, 127, 128, 129, 300] {
+            let mut ctx = Sha256Context::new();
+            ctx.update(&input[..split]);
+            let state = ctx.export();
+            let mut ctx = Sha256Context::import(&state).unwrap();
+            ctx.update(&input[split..]);
+            assert_eq!(
+                ctx.finish(),
+                crate::high::hash::Sha256::hash(&input).as_ref()
+            );
+
+            let mut ctx = Sha512Context::new();
+            ctx.update(&input[..split]);
+            let state = ctx.export();
+            let mut ctx = Sha512Context::import(&state).unwrap();
+            ctx.update(&input[split..]);
+            assert_eq!(
+                ctx.finish(),
+                crate::high::hash::Sha512::hash(&input).as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn import_rejects_bad_states() {
+        let mut ctx = Sha256Context::new();
+        ctx.update(b"hello");
+        let state = ctx.export();
+        assert!(Sha256Context::import(&state).is_ok());
+
+        assert_eq!(
+            Sha256Context::import(&state[..state.len() - 1]).err(),
+            Some(Error::WrongLength)
+        );
+        assert_eq!(
+            Sha512Context::import(&state).err(),
+            Some(Error::WrongLength)
+        );
+
+        // version, algorithm, and first padding byte after "hello"
+        for (offset, value) in [(0, 2), (1, 2), (Sha256Context::EXPORT_SZ - 59, 1)] {
+            let mut bad = state;
+            bad[offset] = value;
+            assert_eq!(Sha256Context::import(&bad).err(), Some(Error::BadHashState));
+        }
+    }
+
+    #[test]
+    fn truncated_variants() {
+        // FIPS180 "abc" examples, and python's hashlib over bytes(range(256)) * 4
+        let mut long = Vec::with_capacity(1024);
+        for i in 0..1024 {
+            long.push(i as u8);
+        }
+
+        let mut ctx = Sha224Context::new();
+        ctx.update(b"abc");
+        assert_eq!(&ctx.finish(),
+                   b"\x23\x09\x7d\x22\x34\x05\xd8\x22\x86\x42\xa4\x77\xbd\xa2\x55\xb3\x2a\xad\xbc\xe4\xbd\xa0\xb3\xf7\xe3\x6c\x9d\xa7");
+        let mut ctx = Sha224Context::new();
+        ctx.update(&long);
+        assert_eq!(&ctx.finish(),
+                   b"\x62\x90\x81\x7f\x60\x01\x43\x2c\xd4\x41\x05\x8d\x2b\xb8\x2d\x88\xb3\xf3\x24\x25\xad\xe4\xc9\x3d\x56\x20\x78\x38");
+
+        let mut ctx = Sha512_224Context::new();
+        ctx.update(b"abc");
+        assert_eq!(&ctx.finish(),
+                   b"\x46\x34\x27\x0f\x70\x7b\x6a\x54\xda\xae\x75\x30\x46\x08\x42\xe2\x0e\x37\xed\x26\x5c\xee\xe9\xa4\x3e\x89\x24\xaa");
+        let mut ctx = Sha512_224Context::new();
+        ctx.update(&long);
+        assert_eq!(&ctx.finish(),
+                   b"\xd2\xf8\x44\x18\x5b\x8b\xda\x68\x00\x76\x4f\xe0\x9c\x15\xc3\xd1\xb1\xbb\xe8\x6e\x85\x41\x01\x38\xdd\x2f\x0a\x56");
+
+        let mut ctx = Sha512_256Context::new();
+        ctx.update(b"abc");
+        assert_eq!(&ctx.finish(),
+                   b"\x53\x04\x8e\x26\x81\x94\x1e\xf9\x9b\x2e\x29\xb7\x6b\x4c\x7d\xab\xe4\xc2\xd0\xc6\x34\xfc\x6d\x46\xe0\xe2\xf1\x31\x07\xe7\xaf\x23");
+        let mut ctx = Sha512_256Context::new();
+        ctx.update(&long);
+        assert_eq!(&ctx.finish(),
+                   b"\x14\xeb\xba\xa3\x51\xdf\x98\xd3\xfb\xb7\x47\x09\xd6\x36\xf9\xe7\xed\x7e\xd3\x0a\x14\x85\x76\xea\x8e\xe8\xcf\x09\x9b\x90\x75\xc6");
+    }
 }
@@ -1,15 +1,79 @@
 // Written for Graviola by Joe Birr-Pixton, 2026.
 // SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
 
-//! The SHA3 hash function and SHAKE construction.
+//! The SHA3 hash functions and SHAKE construction.
 //!
 //! See <https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.202.pdf>
 
 use core::ops::Range;
 
-use crate::low::{Blockwise, sha3_keccak_f1600, sha3_keccak2of4_f1600, sha3_keccak4_f1600};
+use crate::Error;
+use crate::low::{
+    Blockwise, sha3_keccak_f1600, sha3_keccak_p1600_12, sha3_keccak2of4_f1600,
+    sha3_keccak2of4_p1600_12, sha3_keccak4_f1600, sha3_keccak4_p1600_12,
+};
+use crate::mid::midstate::{Algorithm, HEADER_SZ, Reader, Writer};
+
+/// A context for incremental computation of SHA3-224.
+#[derive(Clone)]
+pub struct Sha3_224Context {
+    sponge: Sponge<SHA3_224_R_BYTES, SHA_PAD_BYTE>,
+}
+
+impl Sha3_224Context {
+    /// Start a new SHA3-224 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(),
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// See [`Sha256Context::export()`](crate::hashing::sha2::Sha256Context::export).
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let mut r = [0u8; Self::EXPORT_SZ];
+        self.sponge.export(Algorithm::Sha3_224, &mut r);
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Sha3_224Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Sha3_224Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHA3-224 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::import(Algorithm::Sha3_224, bytes)?,
+        })
+    }
+
+    /// Complete the SHA3-224 computation, returning the hash output.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let squeezing = self.sponge.absorb_final();
+        let mut digest = [0u8; Self::OUTPUT_SZ];
+        squeezing.into_single_squeeze(&mut digest);
+        digest
+    }
+
+    /// The internal block size (rate) of SHA3-224.
+    pub const BLOCK_SZ: usize = SHA3_224_R_BYTES;
+
+    /// The output size of SHA3-224.
+    pub const OUTPUT_SZ: usize = 28;
+
+    /// The size of an exported state: see [`Sha3_224Context::export()`].
+    pub const EXPORT_SZ: usize = Sponge::<SHA3_224_R_BYTES, SHA_PAD_BYTE>::EXPORT_SZ;
+}
 
 /// A context for incremental computation of SHA3-256.
+#[derive(Clone)]
 pub struct Sha3_256Context {
     sponge: Sponge<SHA3_256_R_BYTES, SHA_PAD_BYTE>,
 }
@@ -27,6 +91,27 @@ impl Sha3_256Context {
         self.sponge.absorb(bytes);
     }
 
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// See [`Sha256Context::export()`](crate::hashing::sha2::Sha256Context::export).
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let mut r = [0u8; Self::EXPORT_SZ];
+        self.sponge.export(Algorithm::Sha3_256, &mut r);
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Sha3_256Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Sha3_256Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHA3-256 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::import(Algorithm::Sha3_256, bytes)?,
+        })
+    }
+
     /// Complete the SHA3-256 computation, returning the hash output.
     pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
         let squeezing = self.sponge.absorb_final();
@@ -35,11 +120,76 @@ impl Sha3_256Context {
         digest
     }
 
+    /// The internal block size (rate) of SHA3-256.
+    pub const BLOCK_SZ: usize = SHA3_256_R_BYTES;
+
     /// The output size of SHA3-256.
     pub const OUTPUT_SZ: usize = 32;
+
+    /// The size of an exported state: see [`Sha3_256Context::export()`].
+    pub const EXPORT_SZ: usize = Sponge::<SHA3_256_R_BYTES, SHA_PAD_BYTE>::EXPORT_SZ;
+}
+
+/// A context for incremental computation of SHA3-384.
+#[derive(Clone)]
+pub struct Sha3_384Context {
+    sponge: Sponge<SHA3_384_R_BYTES, SHA_PAD_BYTE>,
+}
+
+impl Sha3_384Context {
+    /// Start a new SHA3-384 hash computation.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(),
+        }
+    }
+
+    /// Add `bytes` to the ongoing hash computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// See [`Sha256Context::export()`](crate::hashing::sha2::Sha256Context::export).
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let mut r = [0u8; Self::EXPORT_SZ];
+        self.sponge.export(Algorithm::Sha3_384, &mut r);
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Sha3_384Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Sha3_384Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHA3-384 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::import(Algorithm::Sha3_384, bytes)?,
+        })
+    }
+
+    /// Complete the SHA3-384 computation, returning the hash output.
+    pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
+        let squeezing = self.sponge.absorb_final();
+        let mut digest = [0u8; Self::OUTPUT_SZ];
+        squeezing.into_single_squeeze(&mut digest);
+        digest
+    }
+
+    /// The internal block size (rate) of SHA3-384.
+    pub const BLOCK_SZ: usize = SHA3_384_R_BYTES;
+
+    /// The output size of SHA3-384.
+    pub const OUTPUT_SZ: usize = 48;
+
+    /// The size of an exported state: see [`Sha3_384Context::export()`].
+    pub const EXPORT_SZ: usize = Sponge::<SHA3_384_R_BYTES, SHA_PAD_BYTE>::EXPORT_SZ;
 }
 
 /// A context for incremental computation of SHA3-512.
+#[derive(Clone)]
 pub struct Sha3_512Context {
     sponge: Sponge<SHA3_512_R_BYTES, SHA_PAD_BYTE>,
 }
@@ -57,6 +207,27 @@ impl Sha3_512Context {
         self.sponge.absorb(bytes);
     }
 
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// See [`Sha256Context::export()`](crate::hashing::sha2::Sha256Context::export).
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let mut r = [0u8; Self::EXPORT_SZ];
+        self.sponge.export(Algorithm::Sha3_512, &mut r);
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Sha3_512Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Sha3_512Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHA3-512 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::import(Algorithm::Sha3_512, bytes)?,
+        })
+    }
+
     /// Complete the SHA3-512 computation, returning the hash output.
     pub fn finish(self) -> [u8; Self::OUTPUT_SZ] {
         let sponge = self.sponge.absorb_final();
@@ -65,14 +236,73 @@ impl Sha3_512Context {
         digest
     }
 
+    /// The internal block size (rate) of SHA3-512.
+    pub const BLOCK_SZ: usize = SHA3_512_R_BYTES;
+
     /// The output size of SHA3-512.
     pub const OUTPUT_SZ: usize = 64;
+
+    /// The size of an exported state: see [`Sha3_512Context::export()`].
+    pub const EXPORT_SZ: usize = Sponge::<SHA3_512_R_BYTES, SHA_PAD_BYTE>::EXPORT_SZ;
+}
+
+/// A context for incremental absorption of SHAKE128 input.
+///
+/// Once all input is absorbed, use [`Shake128Context::finalize_xof`] to
+/// obtain a [`Shake128`] from which output can be read.
+#[derive(Clone)]
+pub struct Shake128Context {
+    sponge: Sponge<SHAKE_128_R_BYTES, SHAKE_PAD_BYTE>,
+}
+
+impl Shake128Context {
+    /// Start a new SHAKE128 computation.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// See [`Sha256Context::export()`](crate::hashing::sha2::Sha256Context::export).
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let mut r = [0u8; Self::EXPORT_SZ];
+        self.sponge.export(Algorithm::Shake128, &mut r);
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Shake128Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Shake128Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHAKE128 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::import(Algorithm::Shake128, bytes)?,
+        })
+    }
+
+    /// Complete absorption, returning a [`Shake128`] for reading output.
+    pub fn finalize_xof(self) -> Shake128 {
+        Shake128::from_sponge(self.sponge.absorb_final())
+    }
+
+    /// The size of an exported state: see [`Shake128Context::export()`].
+    pub const EXPORT_SZ: usize = Sponge::<SHAKE_128_R_BYTES, SHAKE_PAD_BYTE>::EXPORT_SZ;
 }
 
 /// This is SHAKE128.
 ///
-/// This has one-shot input behaviour (all input must be provided to the
-/// constructor [`Shake128::new`]) and incremental output behaviour.
+/// This has incremental output behaviour.  Input can be provided
+/// all at once to the constructor [`Shake128::new`], or incrementally
+/// via [`Shake128Context`].
 pub struct Shake128 {
     sponge: SqueezingSponge<SHAKE_128_R_BYTES>,
     buffer: [u8; SHAKE_128_R_BYTES],
@@ -84,8 +314,12 @@ impl Shake128 {
     ///
     /// The items of `message` are processed in order, as if concatenated.
     pub fn new(message: &[&[u8]]) -> Self {
+        Self::from_sponge(Sponge::<SHAKE_128_R_BYTES, SHAKE_PAD_BYTE>::new_for_message(message))
+    }
+
+    pub(crate) fn from_sponge(sponge: SqueezingSponge<SHAKE_128_R_BYTES>) -> Self {
         Self {
-            sponge: Sponge::<SHAKE_128_R_BYTES, SHAKE_PAD_BYTE>::new_for_message(message),
+            sponge,
             buffer: [0u8; SHAKE_128_R_BYTES],
             buffer_offset: SHAKE_128_R_BYTES,
         }
@@ -103,10 +337,63 @@ impl Shake128 {
 pub(crate) type Shake128Sponge = Sponge<SHAKE_128_R_BYTES, SHAKE_PAD_BYTE>;
 pub(crate) type Shake128SqueezingSponge = SqueezingSponge<SHAKE_128_R_BYTES>;
 
+/// A context for incremental absorption of SHAKE256 input.
+///
+/// Once all input is absorbed, use [`Shake256Context::finalize_xof`] to
+/// obtain a [`Shake256`] from which output can be read.
+#[derive(Clone)]
+pub struct Shake256Context {
+    sponge: Sponge<SHAKE_256_R_BYTES, SHAKE_PAD_BYTE>,
+}
+
+impl Shake256Context {
+    /// Start a new SHAKE256 computation.
+    pub const fn new() -> Self {
+        Self {
+            sponge: Sponge::new(),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Export the state of the ongoing hash computation.
+    ///
+    /// See [`Sha256Context::export()`](crate::hashing::sha2::Sha256Context::export).
+    pub fn export(&self) -> [u8; Self::EXPORT_SZ] {
+        let mut r = [0u8; Self::EXPORT_SZ];
+        self.sponge.export(Algorithm::Shake256, &mut r);
+        r
+    }
+
+    /// Resume a hash computation from a state previously returned
+    /// by [`Shake256Context::export()`].
+    ///
+    /// Returns [`Error::WrongLength`] if `bytes` is not [`Shake256Context::EXPORT_SZ`]
+    /// bytes long, and [`Error::BadHashState`] if it is not a valid
+    /// exported SHAKE256 state.
+    pub fn import(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            sponge: Sponge::import(Algorithm::Shake256, bytes)?,
+        })
+    }
+
+    /// Complete absorption, returning a [`Shake256`] for reading output.
+    pub fn finalize_xof(self) -> Shake256 {
+        Shake256::from_sponge(self.sponge.absorb_final())
+    }
+
+    /// The size of an exported state: see [`Shake256Context::export()`].
+    pub const EXPORT_SZ: usize = Sponge::<SHAKE_256_R_BYTES, SHAKE_PAD_BYTE>::EXPORT_SZ;
+}
+
 /// This is SHAKE256.
 ///
-/// This has one-shot input behaviour (all input must be provided to the
-/// constructor [`Shake256::new`]) and incremental output behaviour.
+/// This has incremental output behaviour.  Input can be provided
+/// all at once to the constructor [`Shake256::new`], or incrementally
+/// via [`Shake256Context`].
 pub struct Shake256 {
     sponge: SqueezingSponge<SHAKE_256_R_BYTES>,
     buffer: [u8; SHAKE_256_R_BYTES],
@@ -118,8 +405,12 @@ impl Shake256 {
     ///
     /// The items of `message` are processed in order, as if concatenated.
     pub fn new(message: &[&[u8]]) -> Self {
+        Self::from_sponge(Sponge::<SHAKE_256_R_BYTES, SHAKE_PAD_BYTE>::new_for_message(message))
+    }
+
+    pub(crate) fn from_sponge(sponge: SqueezingSponge<SHAKE_256_R_BYTES>) -> Self {
         Self {
-            sponge: Sponge::<SHAKE_256_R_BYTES, SHAKE_PAD_BYTE>::new_for_message(message),
+            sponge,
             buffer: [0u8; SHAKE_256_R_BYTES],
             buffer_offset: SHAKE_256_R_BYTES,
         }
@@ -210,21 +501,23 @@ impl Shake256 {
     }
 }
 
-pub(crate) struct SqueezingSponge<const R: usize> {
+/// A sponge after absorption, using Keccak-p[1600, ROUNDS].
+#[derive(Clone)]
+pub(crate) struct SqueezingSponge<const R: usize, const ROUNDS: usize = 24> {
     s: [u64; 25],
 }
 
-impl<const R: usize> SqueezingSponge<R> {
+impl<const R: usize, const ROUNDS: usize> SqueezingSponge<R, ROUNDS> {
     /// Squeeze into `output`.
     pub(crate) fn squeeze(&mut self, output: &mut [u8]) {
         for chunk in output.chunks_mut(R) {
             self.squeeze_current(chunk);
-            sha3_keccak_f1600(&mut self.s, &RC);
+            keccak_p::<ROUNDS>(&mut self.s);
         }
     }
 
     /// Squeeze into `output`, which must be below `R` in length.
-    fn into_single_squeeze(mut self, output: &mut [u8]) {
+    pub(crate) fn into_single_squeeze(mut self, output: &mut [u8]) {
         debug_assert!(output.len() < R);
         self.squeeze_current(output);
     }
@@ -235,7 +528,12 @@ impl<const R: usize> SqueezingSponge<R> {
         }
     }
 
-    fn shake_read(&mut self, output: &mut [u8], buffer: &mut [u8; R], buffer_offset: &mut usize) {
+    pub(crate) fn shake_read(
+        &mut self,
+        output: &mut [u8],
+        buffer: &mut [u8; R],
+        buffer_offset: &mut usize,
+    ) {
         let output = match *buffer_offset == buffer.len() {
             true => output,
             false => {
@@ -371,20 +669,24 @@ impl<const R: usize> SqueezingSpongeObligation<R> {
     }
 }
 
-pub(crate) struct Sponge<const R: usize, const PAD: u8> {
-    sponge: SqueezingSponge<R>,
+/// A sponge during absorption, using Keccak-p[1600, ROUNDS].
+///
+/// `PAD` is the first byte of padding, which includes any domain separation bits.
+#[derive(Clone)]
+pub(crate) struct Sponge<const R: usize, const PAD: u8, const ROUNDS: usize = 24> {
+    sponge: SqueezingSponge<R, ROUNDS>,
     buffer: Blockwise<R>,
 }
 
-impl<const R: usize, const PAD: u8> Sponge<R, PAD> {
-    const fn new() -> Self {
+impl<const R: usize, const PAD: u8, const ROUNDS: usize> Sponge<R, PAD, ROUNDS> {
+    pub(crate) const fn new() -> Self {
         Self {
             sponge: SqueezingSponge { s: [0; _] },
             buffer: Blockwise::new(),
         }
     }
 
-    pub(crate) fn new_for_message(message: &[&[u8]]) -> SqueezingSponge<R> {
+    pub(crate) fn new_for_message(message: &[&[u8]]) -> SqueezingSponge<R, ROUNDS> {
         let mut s = Self::new();
         for m in message {
             s.absorb(m);
@@ -392,7 +694,7 @@ impl<const R: usize, const PAD: u8> Sponge<R, PAD> {
         s.absorb_final()
     }
 
-    fn absorb(&mut self, bytes: &[u8]) {
+    pub(crate) fn absorb(&mut self, bytes: &[u8]) {
         let bytes = self.buffer.add_leading(bytes);
 
         if let Some(block) = self.buffer.take() {
@@ -407,17 +709,32 @@ impl<const R: usize, const PAD: u8> Sponge<R, PAD> {
         self.buffer.add_trailing(remainder);
     }
 
+    /// Absorb zero bytes until the input so far is a multiple of the rate.
+    ///
+    /// This is the tail of SP800-185's `bytepad()`.
+    pub(crate) fn absorb_zero_padding(&mut self) {
+        if self.buffer.used() != 0 {
+            self.absorb(&R_ZEROES[..R - self.buffer.used()]);
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn absorb_final(self) -> SqueezingSponge<R, ROUNDS> {
+        self.absorb_final_with_pad(PAD)
+    }
+
+    /// Like `absorb_final`, but with padding byte `pad` instead of `PAD`.
     #[must_use]
-    fn absorb_final(mut self) -> SqueezingSponge<R> {
+    pub(crate) fn absorb_final_with_pad(mut self, pad: u8) -> SqueezingSponge<R, ROUNDS> {
         match R - self.buffer.used() {
             1 => {
-                self.buffer.add_leading(&[PAD | 0x80]);
+                self.buffer.add_leading(&[pad | 0x80]);
             }
             2 => {
-                self.buffer.add_leading(&[PAD, 0x80]);
+                self.buffer.add_leading(&[pad, 0x80]);
             }
             n => {
-                self.buffer.add(&[PAD]);
+                self.buffer.add(&[pad]);
                 self.buffer.add_leading(&R_ZEROES[..n - 2]);
                 self.buffer.add_leading(&[0x80]);
             }
@@ -427,11 +744,123 @@ impl<const R: usize, const PAD: u8> Sponge<R, PAD> {
         self.sponge
     }
 
+    /// Write this sponge's state to `out`, in the format described
+    /// in `mid::midstate`.
+    ///
+    /// `out` must be `EXPORT_SZ` bytes long.
+    pub(crate) fn export(&self, algorithm: Algorithm, out: &mut [u8]) {
+        let buffered = self.buffer.peek_remaining().unwrap_or_default();
+
+        let mut w = Writer::new(out, algorithm);
+        for lane in self.sponge.s {
+            w.put(&lane.to_le_bytes());
+        }
+        // nb. `R` is at most 168, so this fits
+        w.put(&[buffered.len() as u8]);
+        w.put_block(buffered, R);
+        w.finish();
+    }
+
+    pub(crate) fn import(algorithm: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+        let mut r = Reader::new(bytes, algorithm, Self::EXPORT_SZ)?;
+
+        let mut s = [0; 25];
+        for lane in s.iter_mut() {
+            *lane = u64::from_le_bytes(r.take()?);
+        }
+
+        let [used] = r.take()?;
+        let used = used as usize;
+        if used >= R {
+            return Err(Error::BadHashState);
+        }
+
+        let mut buffer = Blockwise::new();
+        buffer.add_trailing(r.take_block(used, R)?);
+        r.finish()?;
+
+        Ok(Self {
+            sponge: SqueezingSponge { s },
+            buffer,
+        })
+    }
+
+    /// The size of an exported state: header, Keccak state, buffer
+    /// length and buffer.
+    pub(crate) const EXPORT_SZ: usize = HEADER_SZ + 200 + 1 + R;
+
     fn absorb_block(&mut self, block: &[u8; R]) {
         for (i, block) in block.chunks_exact(8).enumerate() {
             self.sponge.s[i] ^= u64::from_le_bytes(block.try_into().unwrap());
         }
-        sha3_keccak_f1600(&mut self.sponge.s, &RC);
+        keccak_p::<ROUNDS>(&mut self.sponge.s);
+    }
+}
+
+/// Computes a sponge (with rate `R`, padding byte `pad`, and using
+/// Keccak-p[1600, ROUNDS]) over `N` equal-length messages at once,
+/// writing the first `outputs[i].len()` bytes of each to `outputs[i]`.
+///
+/// `N` must be 2 or 4, and each output must be shorter than `R`.
+pub(crate) fn sponge_lanes<const R: usize, const N: usize, const ROUNDS: usize>(
+    messages: [&[u8]; N],
+    pad: u8,
+    outputs: [&mut [u8]; N],
+) {
+    debug_assert!(messages.iter().all(|m| m.len() == messages[0].len()));
+    debug_assert!(outputs.iter().all(|o| o.len() < R));
+
+    fn permute<const N: usize, const ROUNDS: usize>(states: &mut [[u64; 25]; 4]) {
+        match (N, ROUNDS) {
+            (4, 24) => sha3_keccak4_f1600(states, &RC),
+            (2, 24) => sha3_keccak2of4_f1600(states, &RC),
+            (4, 12) => sha3_keccak4_p1600_12(states, RC_12),
+            (2, 12) => sha3_keccak2of4_p1600_12(states, RC_12),
+            _ => unreachable!(),
+        }
+    }
+
+    fn xor_block(state: &mut [u64; 25], block: &[u8]) {
+        for (i, block) in block.chunks_exact(8).enumerate() {
+            state[i] ^= u64::from_le_bytes(block.try_into().unwrap());
+        }
+    }
+
+    let mut states = [[0u64; 25]; 4];
+    let whole_blocks = messages[0].len() / R;
+
+    for b in 0..whole_blocks {
+        for (state, message) in states.iter_mut().zip(messages) {
+            xor_block(state, &message[b * R..(b + 1) * R]);
+        }
+        permute::<N, ROUNDS>(&mut states);
+    }
+
+    for (state, message) in states.iter_mut().zip(messages) {
+        let tail = &message[whole_blocks * R..];
+        let mut padded = [0u8; R];
+        padded[..tail.len()].copy_from_slice(tail);
+        padded[tail.len()] ^= pad;
+        padded[R - 1] ^= 0x80;
+        xor_block(state, &padded);
+    }
+    permute::<N, ROUNDS>(&mut states);
+
+    for (state, output) in states.iter().zip(outputs) {
+        for (i, ch) in output.chunks_mut(8).enumerate() {
+            ch.copy_from_slice(&state[i].to_le_bytes()[..ch.len()]);
+        }
+    }
+}
+
+/// Applies Keccak-p[1600, ROUNDS], where `ROUNDS` is 24 (so this is
+/// Keccak-f1600) or 12.
+#[inline]
+fn keccak_p<const ROUNDS: usize>(s: &mut [u64; 25]) {
+    match ROUNDS {
+        24 => sha3_keccak_f1600(s, &RC),
+        12 => sha3_keccak_p1600_12(s, RC_12),
+        _ => unreachable!(),
     }
 }
 
@@ -462,12 +891,17 @@ const RC: [u64; 24] = [
     0x80000000_80008008,
 ];
 
+/// Keccak-p[1600, 12] uses the last 12 round constants.
+const RC_12: &[u64; 12] = RC.last_chunk().unwrap();
+
 const R_ZEROES: [u8; SHAKE_128_R_BYTES] = [0; SHAKE_128_R_BYTES];
 
 pub(crate) const SHAKE_128_R_BYTES: usize = (1600 - 256) / 8;
-const SHAKE_256_R_BYTES: usize = (1600 - 512) / 8;
+pub(crate) const SHAKE_256_R_BYTES: usize = (1600 - 512) / 8;
 
+const SHA3_224_R_BYTES: usize = (1600 - 448) / 8;
 const SHA3_256_R_BYTES: usize = (1600 - 512) / 8;
+const SHA3_384_R_BYTES: usize = (1600 - 768) / 8;
 const SHA3_512_R_BYTES: usize = (1600 - 1024) / 8;
 
 /// This is 0b01_1 in keccak bit ordering.
@@ -482,6 +916,12 @@ const SHA_PAD_BYTE: u8 = 0b0000_0110;
 /// padding bit.
 pub(crate) const SHAKE_PAD_BYTE: u8 = 0b0001_1111;
 
+/// This is 0b00_1 in keccak bit ordering.
+///
+/// `00` is the cSHAKE domain separation constant (see SP800-185), `1` is the
+/// multi-rate padding bit.
+pub(crate) const CSHAKE_PAD_BYTE: u8 = 0b0000_0100;
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -501,6 +941,38 @@ mod tests {
             b"\x75\xd5\x27\xc3\x68\xf2\xef\xe8\x48\xec\xf6\xb0\x73\xa3\x67\x67\x80\x08\x05\xe9\xee\xf2\xb1\x85\x7d\x5f\x98\x4f\x03\x6e\xb6\xdf\x89\x1d\x75\xf7\x2d\x9b\x15\x45\x18\xc1\xcd\x58\x83\x52\x86\xd1\xda\x9a\x38\xde\xba\x3d\xe9\x8b\x5a\x53\xe5\xed\x78\xa8\x49\x76");
     }
 
+    #[test]
+    fn hello_224_384() {
+        // from python's hashlib
+        let mut ctx = Sha3_224Context::new();
+        ctx.update(b"hello");
+        assert_eq!(&ctx.finish(),
+                   b"\xb8\x7f\x88\xc7\x27\x02\xff\xf1\x74\x8e\x58\xb8\x7e\x91\x41\xa4\x2c\x0d\xbe\xdc\x29\xa7\x8c\xb0\xd4\xa5\xcd\x81");
+
+        let mut ctx = Sha3_384Context::new();
+        ctx.update(b"hello");
+        assert_eq!(&ctx.finish(),
+                   b"\x72\x0a\xea\x11\x01\x9e\xf0\x64\x40\xfb\xf0\x5d\x87\xaa\x24\x68\x0a\x21\x53\xdf\x39\x07\xb2\x36\x31\xe7\x17\x7c\xe6\x20\xfa\x13\x30\xff\x07\xc0\xfd\xde\xe5\x46\x99\xa4\xc3\xee\x0e\xe9\xd8\x87");
+    }
+
+    #[test]
+    fn sha3_224_all_lengths() {
+        let mut outer = Sha3_224Context::new();
+
+        for len in 0..1024 {
+            let mut inner = Sha3_224Context::new();
+
+            for _ in 0..len {
+                inner.update(&[len as u8]);
+            }
+
+            outer.update(&inner.finish());
+        }
+
+        assert_eq!(&outer.finish(),
+                   b"\xf2\x54\xf3\x67\x6d\xc6\xc0\x0f\x2f\xee\x50\x59\x07\x62\x8b\x0d\x5b\x9e\xdf\xf8\xe8\xc2\x9e\x9b\xa7\xd6\x05\xdd");
+    }
+
     #[test]
     fn sha3_256_all_lengths() {
         // see cifra `vector_length` and associated
@@ -520,6 +992,24 @@ mod tests {
                    b"\xf7\xed\xf7\x2b\x34\x8c\xb4\xab\x5e\xe7\x4f\x6c\xae\xaf\x11\xad\xe2\x2f\x04\x65\x84\x8e\x5c\xaa\x14\x38\x7f\xd4\xeb\xdb\x9d\x70");
     }
 
+    #[test]
+    fn sha3_384_all_lengths() {
+        let mut outer = Sha3_384Context::new();
+
+        for len in 0..1024 {
+            let mut inner = Sha3_384Context::new();
+
+            for _ in 0..len {
+                inner.update(&[len as u8]);
+            }
+
+            outer.update(&inner.finish());
+        }
+
+        assert_eq!(&outer.finish(),
+                   b"\xc2\x16\x48\x6a\x00\x32\xb1\xe1\x98\xf8\x72\x52\x01\x87\xba\xd4\xcf\x39\x13\x9c\x54\x21\x6d\x78\x86\x93\x88\xf9\x75\x03\xc1\x11\xcc\x7f\x5a\xc3\x21\x00\x3f\xc8\xa1\xf7\xfa\x10\x75\x60\xdb\xb1");
+    }
+
     #[test]
     fn sha3_512_all_lengths() {
         let mut outer = Sha3_512Context::new();
@@ -561,6 +1051,144 @@ mod tests {
         }
     }
 
+    #[test]
+    fn shake_incremental_absorb() {
+        // Absorb input in a variety of chunk sizes, straddling the rate
+        // of both SHAKE128 and SHAKE256.
+        //
+        // The outputs must agree with the one-shot constructor.
+        let mut input = [0u8; 1024];
+        for (i, b) in input.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+
+        let mut expected_128 = [0u8; 300];
+        Shake128::new(&[&input]).read(&mut expected_128);
+        let mut expected_256 = [0u8; 300];
+        Shake256::new(&[&input]).read(&mut expected_256);
+
+        for chunk in [1, 7, 135, 136, 137, 167, 168, 169, 1024] {
+            let mut ctx = Shake128Context::new();
+            for c in input.chunks(chunk) {
+                ctx.update(c);
+            }
+            let mut output = [0u8; 300];
+            ctx.finalize_xof().read(&mut output);
+            assert_eq!(output, expected_128);
+
+            let mut ctx = Shake256Context::new();
+            for c in input.chunks(chunk) {
+                ctx.update(c);
+            }
+            let mut output = [0u8; 300];
+            ctx.finalize_xof().read(&mut output);
+            assert_eq!(output, expected_256);
+        }
+
+        // empty input
+        let mut expected = [0u8; 32];
+        Shake256::new(&[]).read(&mut expected);
+        let mut output = [0u8; 32];
+        Shake256Context::new().finalize_xof().read(&mut output);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn export_import() {
+        use crate::high::hash::Hash;
+
+        let input = [0xa5u8; 400];
+
+        for split in [0, 1, 71, 72, 73, 135, 136, 137, 167, 168, 169, 400] {
+            let mut ctx = Sha3_224Context::new();
+            ctx.update(&input[..split]);
+            let mut ctx = Sha3_224Context::import(&ctx.export()).unwrap();
+            ctx.update(&input[split..]);
+            assert_eq!(
+                ctx.finish(),
+                crate::high::hash::Sha3_224::hash(&input).as_ref()
+            );
+
+            let mut ctx = Sha3_256Context::new();
+            ctx.update(&input[..split]);
+            let mut ctx = Sha3_256Context::import(&ctx.export()).unwrap();
+            ctx.update(&input[split..]);
+            assert_eq!(
+                ctx.finish(),
+                crate::high::hash::Sha3_256::hash(&input).as_ref()
+            );
+
+            let mut ctx = Sha3_384Context::new();
+            ctx.update(&input[..split]);
+            let mut ctx = Sha3_384Context::import(&ctx.export()).unwrap();
+            ctx.update(&input[split..]);
+            assert_eq!(
+                ctx.finish(),
+                crate::high::hash::Sha3_384::hash(&input).as_ref()
+            );
+
+            let mut ctx = Sha3_512Context::new();
+            ctx.update(&input[..split]);
+            let mut ctx = Sha3_512Context::import(&ctx.export()).unwrap();
+            ctx.update(&input[split..]);
+            assert_eq!(
+                ctx.finish(),
+                crate::high::hash::Sha3_512::hash(&input).as_ref()
+            );
+
+            let mut expected = [0u8; 64];
+            Shake128::new(&[&input]).read(&mut expected);
+            let mut ctx = Shake128Context::new();
+            ctx.update(&input[..split]);
+            let mut ctx = Shake128Context::import(&ctx.export()).unwrap();
+            ctx.update(&input[split..]);
+            let mut output = [0u8; 64];
+            ctx.finalize_xof().read(&mut output);
+            assert_eq!(output, expected);
+
+            Shake256::new(&[&input]).read(&mut expected);
+            let mut ctx = Shake256Context::new();
+            ctx.update(&input[..split]);
+            let mut ctx = Shake256Context::import(&ctx.export()).unwrap();
+            ctx.update(&input[split..]);
+            ctx.finalize_xof().read(&mut output);
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn import_rejects_bad_states() {
+        let mut ctx = Sha3_256Context::new();
+        ctx.update(b"hello");
+        let state = ctx.export();
+        assert!(Sha3_256Context::import(&state).is_ok());
+
+        assert_eq!(
+            Sha3_224Context::import(&state).err(),
+            Some(Error::WrongLength)
+        );
+        // same rate, so same length
+        assert_eq!(
+            Shake256Context::import(&state).err(),
+            Some(Error::BadHashState)
+        );
+
+        // version, buffer length too large, and first padding byte after "hello"
+        let len_offset = HEADER_SZ + 200;
+        for (offset, value) in [
+            (0, 2),
+            (len_offset, SHA3_256_R_BYTES as u8),
+            (len_offset + 1 + 5, 1),
+        ] {
+            let mut bad = state;
+            bad[offset] = value;
+            assert_eq!(
+                Sha3_256Context::import(&bad).err(),
+                Some(Error::BadHashState)
+            );
+        }
+    }
+
     #[test]
     fn one_shot_sextet_matches_single() {
         // build the six 40-byte inputs in the format `one_shot_sextet` requires:
@@ -629,7 +1257,9 @@ mod tests {
         #[derive(Debug)]
         enum Kind {
             None,
+            Sha3_224,
             Sha3_256,
+            Sha3_384,
             Sha3_512,
         }
 
@@ -653,7 +1283,9 @@ mod tests {
         impl CavpSink for Cavp {
             fn on_meta(&mut self, meta: &str) {
                 self.kind = match meta {
+                    "L = 224" => Kind::Sha3_224,
                     "L = 256" => Kind::Sha3_256,
+                    "L = 384" => Kind::Sha3_384,
                     "L = 512" => Kind::Sha3_512,
                     _ => panic!("unhandled {meta:?}"),
                 };
@@ -665,11 +1297,21 @@ mod tests {
                     "Msg" => self.message = value.bytes(),
                     "MD" => match self.kind {
                         Kind::None => {}
+                        Kind::Sha3_224 => {
+                            let mut h = Sha3_224Context::new();
+                            h.update(&self.message[..self.len]);
+                            assert_eq!(value.bytes(), h.finish());
+                        }
                         Kind::Sha3_256 => {
                             let mut h = Sha3_256Context::new();
                             h.update(&self.message[..self.len]);
                             assert_eq!(value.bytes(), h.finish());
                         }
+                        Kind::Sha3_384 => {
+                            let mut h = Sha3_384Context::new();
+                            h.update(&self.message[..self.len]);
+                            assert_eq!(value.bytes(), h.finish());
+                        }
                         Kind::Sha3_512 => {
                             let mut h = Sha3_512Context::new();
                             h.update(&self.message[..self.len]);
@@ -683,6 +1325,14 @@ mod tests {
             }
         }
 
+        process_cavp(
+            "../thirdparty/cavp/sha3/SHA3_224ShortMsg.rsp",
+            &mut Cavp::default(),
+        );
+        process_cavp(
+            "../thirdparty/cavp/sha3/SHA3_224LongMsg.rsp",
+            &mut Cavp::default(),
+        );
         process_cavp(
             "../thirdparty/cavp/sha3/SHA3_256ShortMsg.rsp",
             &mut Cavp::default(),
@@ -691,6 +1341,14 @@ mod tests {
             "../thirdparty/cavp/sha3/SHA3_256LongMsg.rsp",
             &mut Cavp::default(),
         );
+        process_cavp(
+            "../thirdparty/cavp/sha3/SHA3_384ShortMsg.rsp",
+            &mut Cavp::default(),
+        );
+        process_cavp(
+            "../thirdparty/cavp/sha3/SHA3_384LongMsg.rsp",
+            &mut Cavp::default(),
+        );
         process_cavp(
             "../thirdparty/cavp/sha3/SHA3_512ShortMsg.rsp",
             &mut Cavp::default(),
@@ -0,0 +1,1100 @@
+// Written for Graviola by Joe Birr-Pixton, 2026.
+// SPDX-License-Identifier: Apache-2.0 OR ISC OR MIT-0
+
+//! The SHA3-derived functions: cSHAKE, KMAC, TupleHash and ParallelHash.
+//!
+//! See <https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-185.pdf>
+//!
+//! The variable-length output of each function is read from a
+//! [`Shake128`] or [`Shake256`], as appropriate.  Note that SP800-185
+//! binds the requested output length into the non-XOF variants, so
+//! (for example) `KMAC128` with a 32-byte output is not a prefix of
+//! `KMAC128` with a 64-byte output.
+
+use super::sha3::{
+    CSHAKE_PAD_BYTE, SHAKE_128_R_BYTES, SHAKE_256_R_BYTES, SHAKE_PAD_BYTE, Shake128, Shake256,
+    Sponge, SqueezingSponge, sponge_lanes,
+};
+use crate::Error;
+use crate::low::ct_equal;
+
+/// A context for incremental computation of cSHAKE128.
+#[derive(Clone)]
+pub struct CShake128 {
+    inner: CShake<SHAKE_128_R_BYTES>,
+}
+
+impl CShake128 {
+    /// Start a new cSHAKE128 computation.
+    ///
+    /// `function_name` is reserved for functions defined by NIST, and should
+    /// usually be empty.  `customization` is a caller-chosen domain separator.
+    ///
+    /// If both are empty, this is exactly SHAKE128.
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        Self {
+            inner: CShake::new(function_name, customization),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete absorption, returning a [`Shake128`] for reading output.
+    pub fn finalize_xof(self) -> Shake128 {
+        Shake128::from_sponge(self.inner.absorb_final())
+    }
+}
+
+/// A context for incremental computation of cSHAKE256.
+#[derive(Clone)]
+pub struct CShake256 {
+    inner: CShake<SHAKE_256_R_BYTES>,
+}
+
+impl CShake256 {
+    /// Start a new cSHAKE256 computation.
+    ///
+    /// `function_name` is reserved for functions defined by NIST, and should
+    /// usually be empty.  `customization` is a caller-chosen domain separator.
+    ///
+    /// If both are empty, this is exactly SHAKE256.
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        Self {
+            inner: CShake::new(function_name, customization),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete absorption, returning a [`Shake256`] for reading output.
+    pub fn finalize_xof(self) -> Shake256 {
+        Shake256::from_sponge(self.inner.absorb_final())
+    }
+}
+
+/// A context for incremental computation of KMAC128 and KMACXOF128.
+#[derive(Clone)]
+pub struct Kmac128 {
+    inner: Kmac<SHAKE_128_R_BYTES>,
+}
+
+impl Kmac128 {
+    /// Start a new KMAC128 computation, with the given `key` and `customization`
+    /// string.
+    ///
+    /// `customization` may be empty.
+    pub fn new(key: &[u8], customization: &[u8]) -> Self {
+        Self {
+            inner: Kmac::new(key, customization),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete the KMAC128 computation, filling `out`.
+    ///
+    /// The length of `out` is an input to the computation.
+    pub fn finish(self, out: &mut [u8]) {
+        self.inner.finish(out);
+    }
+
+    /// Complete the KMAC128 computation and compare the result against `expected_tag`.
+    ///
+    /// This is done in constant-time.  The length of `expected_tag` is an
+    /// input to the computation, so it may not be truncated.  An empty
+    /// `expected_tag` is always rejected.
+    pub fn verify(self, expected_tag: &[u8]) -> Result<(), Error> {
+        self.inner.verify(expected_tag)
+    }
+
+    /// Complete absorption for KMACXOF128, returning a [`Shake128`] for reading output.
+    pub fn finalize_xof(self) -> Shake128 {
+        Shake128::from_sponge(self.inner.absorb_final_xof())
+    }
+}
+
+/// A context for incremental computation of KMAC256 and KMACXOF256.
+#[derive(Clone)]
+pub struct Kmac256 {
+    inner: Kmac<SHAKE_256_R_BYTES>,
+}
+
+impl Kmac256 {
+    /// Start a new KMAC256 computation, with the given `key` and `customization`
+    /// string.
+    ///
+    /// `customization` may be empty.
+    pub fn new(key: &[u8], customization: &[u8]) -> Self {
+        Self {
+            inner: Kmac::new(key, customization),
+        }
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete the KMAC256 computation, filling `out`.
+    ///
+    /// The length of `out` is an input to the computation.
+    pub fn finish(self, out: &mut [u8]) {
+        self.inner.finish(out);
+    }
+
+    /// Complete the KMAC256 computation and compare the result against `expected_tag`.
+    ///
+    /// This is done in constant-time.  The length of `expected_tag` is an
+    /// input to the computation, so it may not be truncated.  An empty
+    /// `expected_tag` is always rejected.
+    pub fn verify(self, expected_tag: &[u8]) -> Result<(), Error> {
+        self.inner.verify(expected_tag)
+    }
+
+    /// Complete absorption for KMACXOF256, returning a [`Shake256`] for reading output.
+    pub fn finalize_xof(self) -> Shake256 {
+        Shake256::from_sponge(self.inner.absorb_final_xof())
+    }
+}
+
+/// A context for incremental computation of TupleHash128 and TupleHashXOF128.
+#[derive(Clone)]
+pub struct TupleHash128 {
+    inner: TupleHash<SHAKE_128_R_BYTES>,
+}
+
+impl TupleHash128 {
+    /// Start a new TupleHash128 computation, with the given `customization`
+    /// string.
+    ///
+    /// `customization` may be empty.
+    pub fn new(customization: &[u8]) -> Self {
+        Self {
+            inner: TupleHash::new(customization),
+        }
+    }
+
+    /// Add `item` as the next element of the tuple.
+    ///
+    /// Unlike the `update()` function of a hash, the boundaries between
+    /// items are significant: `push(b"ab")` is not the same as `push(b"a")`
+    /// followed by `push(b"b")`.
+    pub fn push(&mut self, item: &[u8]) {
+        self.inner.push(item);
+    }
+
+    /// Complete the TupleHash128 computation, filling `out`.
+    ///
+    /// The length of `out` is an input to the computation.
+    pub fn finish(self, out: &mut [u8]) {
+        squeeze_with_length(self.inner.sponge, out);
+    }
+
+    /// Complete absorption for TupleHashXOF128, returning a [`Shake128`] for reading output.
+    pub fn finalize_xof(self) -> Shake128 {
+        Shake128::from_sponge(absorb_final_xof(self.inner.sponge))
+    }
+}
+
+/// A context for incremental computation of TupleHash256 and TupleHashXOF256.
+#[derive(Clone)]
+pub struct TupleHash256 {
+    inner: TupleHash<SHAKE_256_R_BYTES>,
+}
+
+impl TupleHash256 {
+    /// Start a new TupleHash256 computation, with the given `customization`
+    /// string.
+    ///
+    /// `customization` may be empty.
+    pub fn new(customization: &[u8]) -> Self {
+        Self {
+            inner: TupleHash::new(customization),
+        }
+    }
+
+    /// Add `item` as the next element of the tuple.
+    ///
+    /// Unlike the `update()` function of a hash, the boundaries between
+    /// items are significant: `push(b"ab")` is not the same as `push(b"a")`
+    /// followed by `push(b"b")`.
+    pub fn push(&mut self, item: &[u8]) {
+        self.inner.push(item);
+    }
+
+    /// Complete the TupleHash256 computation, filling `out`.
+    ///
+    /// The length of `out` is an input to the computation.
+    pub fn finish(self, out: &mut [u8]) {
+        squeeze_with_length(self.inner.sponge, out);
+    }
+
+    /// Complete absorption for TupleHashXOF256, returning a [`Shake256`] for reading output.
+    pub fn finalize_xof(self) -> Shake256 {
+        Shake256::from_sponge(absorb_final_xof(self.inner.sponge))
+    }
+}
+
+/// A context for incremental computation of ParallelHash128 and ParallelHashXOF128.
+///
+/// Input is divided into blocks of a caller-chosen size, which are hashed
+/// independently.  Where a single call to [`ParallelHash128::update`] provides
+/// several whole blocks, they are hashed several at a time.
+#[derive(Clone)]
+pub struct ParallelHash128 {
+    inner: ParallelHash<SHAKE_128_R_BYTES, 32>,
+}
+
+impl ParallelHash128 {
+    /// Start a new ParallelHash128 computation, with the given `block_size`
+    /// (in bytes) and `customization` string.
+    ///
+    /// `customization` may be empty.  `Error::OutOfRange` is returned if
+    /// `block_size` is zero.
+    pub fn new(block_size: usize, customization: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ParallelHash::new(block_size, customization)?,
+        })
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete the ParallelHash128 computation, filling `out`.
+    ///
+    /// The length of `out` is an input to the computation.
+    pub fn finish(self, out: &mut [u8]) {
+        squeeze_with_length(self.inner.finish_blocks(), out);
+    }
+
+    /// Complete absorption for ParallelHashXOF128, returning a [`Shake128`] for reading output.
+    pub fn finalize_xof(self) -> Shake128 {
+        Shake128::from_sponge(absorb_final_xof(self.inner.finish_blocks()))
+    }
+}
+
+/// A context for incremental computation of ParallelHash256 and ParallelHashXOF256.
+///
+/// Input is divided into blocks of a caller-chosen size, which are hashed
+/// independently.  Where a single call to [`ParallelHash256::update`] provides
+/// several whole blocks, they are hashed several at a time.
+#[derive(Clone)]
+pub struct ParallelHash256 {
+    inner: ParallelHash<SHAKE_256_R_BYTES, 64>,
+}
+
+impl ParallelHash256 {
+    /// Start a new ParallelHash256 computation, with the given `block_size`
+    /// (in bytes) and `customization` string.
+    ///
+    /// `customization` may be empty.  `Error::OutOfRange` is returned if
+    /// `block_size` is zero.
+    pub fn new(block_size: usize, customization: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ParallelHash::new(block_size, customization)?,
+        })
+    }
+
+    /// Add `bytes` to the ongoing computation.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    /// Complete the ParallelHash256 computation, filling `out`.
+    ///
+    /// The length of `out` is an input to the computation.
+    pub fn finish(self, out: &mut [u8]) {
+        squeeze_with_length(self.inner.finish_blocks(), out);
+    }
+
+    /// Complete absorption for ParallelHashXOF256, returning a [`Shake256`] for reading output.
+    pub fn finalize_xof(self) -> Shake256 {
+        Shake256::from_sponge(absorb_final_xof(self.inner.finish_blocks()))
+    }
+}
+
+#[derive(Clone)]
+enum CShake<const R: usize> {
+    /// cSHAKE with empty `N` and `S` is defined to be SHAKE.
+    Shake(Sponge<R, SHAKE_PAD_BYTE>),
+    CShake(Sponge<R, CSHAKE_PAD_BYTE>),
+}
+
+impl<const R: usize> CShake<R> {
+    fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        match function_name.is_empty() && customization.is_empty() {
+            true => Self::Shake(Sponge::new()),
+            false => Self::CShake(cshake_sponge(function_name, customization)),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Shake(s) => s.absorb(bytes),
+            Self::CShake(s) => s.absorb(bytes),
+        }
+    }
+
+    fn absorb_final(self) -> SqueezingSponge<R> {
+        match self {
+            Self::Shake(s) => s.absorb_final(),
+            Self::CShake(s) => s.absorb_final(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Kmac<const R: usize> {
+    sponge: Sponge<R, CSHAKE_PAD_BYTE>,
+}
+
+impl<const R: usize> Kmac<R> {
+    fn new(key: &[u8], customization: &[u8]) -> Self {
+        let mut sponge = cshake_sponge(b"KMAC", customization);
+        // bytepad(encode_string(K), rate)
+        sponge.absorb(left_encode(R as u64).as_ref());
+        absorb_encode_string(&mut sponge, key);
+        sponge.absorb_zero_padding();
+        Self { sponge }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(bytes);
+    }
+
+    fn finish(self, out: &mut [u8]) {
+        squeeze_with_length(self.sponge, out);
+    }
+
+    fn verify(mut self, expected_tag: &[u8]) -> Result<(), Error> {
+        if expected_tag.is_empty() {
+            return Err(Error::BadSignature);
+        }
+
+        self.sponge
+            .absorb(right_encode(bit_length(expected_tag.len())).as_ref());
+        let mut sponge = self.sponge.absorb_final();
+
+        let mut block = [0u8; R];
+        let mut equal = true;
+        for expected in expected_tag.chunks(R) {
+            let got = &mut block[..expected.len()];
+            sponge.squeeze(got);
+            equal &= ct_equal(got, expected);
+        }
+
+        match equal {
+            true => Ok(()),
+            false => Err(Error::BadSignature),
+        }
+    }
+
+    fn absorb_final_xof(self) -> SqueezingSponge<R> {
+        absorb_final_xof(self.sponge)
+    }
+}
+
+#[derive(Clone)]
+struct TupleHash<const R: usize> {
+    sponge: Sponge<R, CSHAKE_PAD_BYTE>,
+}
+
+impl<const R: usize> TupleHash<R> {
+    fn new(customization: &[u8]) -> Self {
+        Self {
+            sponge: cshake_sponge(b"TupleHash", customization),
+        }
+    }
+
+    fn push(&mut self, item: &[u8]) {
+        absorb_encode_string(&mut self.sponge, item);
+    }
+}
+
+/// ParallelHash, where `R` is the rate and `CV` is the length of each
+/// block's chaining value.
+#[derive(Clone)]
+struct ParallelHash<const R: usize, const CV: usize> {
+    /// The outer cSHAKE computation, which absorbs the chaining values.
+    outer: Sponge<R, CSHAKE_PAD_BYTE>,
+    block_size: usize,
+    blocks: u64,
+
+    /// The inner SHAKE computation for a partially-provided block,
+    /// and how much of it has been provided so far.
+    current: Sponge<R, SHAKE_PAD_BYTE>,
+    current_len: usize,
+}
+
+impl<const R: usize, const CV: usize> ParallelHash<R, CV> {
+    fn new(block_size: usize, customization: &[u8]) -> Result<Self, Error> {
+        if block_size == 0 {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut outer = cshake_sponge(b"ParallelHash", customization);
+        outer.absorb(left_encode(block_size as u64).as_ref());
+
+        Ok(Self {
+            outer,
+            block_size,
+            blocks: 0,
+            current: Sponge::new(),
+            current_len: 0,
+        })
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes;
+
+        // complete any partial block first
+        if self.current_len > 0 {
+            let take = Ord::min(self.block_size - self.current_len, bytes.len());
+            let (taken, rest) = bytes.split_at(take);
+            self.current.absorb(taken);
+            self.current_len += take;
+            bytes = rest;
+
+            if self.current_len < self.block_size {
+                return;
+            }
+            self.finish_current_block();
+        }
+
+        // then whole blocks, as many at once as possible
+        let b = self.block_size;
+        while bytes.len() >= b * 4 {
+            let (blocks, rest) = bytes.split_at(b * 4);
+            let mut cvs = [[0u8; CV]; 4];
+            let [cv0, cv1, cv2, cv3] = &mut cvs;
+            sponge_lanes::<R, 4, 24>(
+                [
+                    &blocks[..b],
+                    &blocks[b..b * 2],
+                    &blocks[b * 2..b * 3],
+                    &blocks[b * 3..],
+                ],
+                SHAKE_PAD_BYTE,
+                [cv0, cv1, cv2, cv3],
+            );
+            for cv in &cvs {
+                self.absorb_chaining_value(cv);
+            }
+            bytes = rest;
+        }
+
+        if bytes.len() >= b * 2 {
+            let (blocks, rest) = bytes.split_at(b * 2);
+            let mut cvs = [[0u8; CV]; 2];
+            let [cv0, cv1] = &mut cvs;
+            sponge_lanes::<R, 2, 24>([&blocks[..b], &blocks[b..]], SHAKE_PAD_BYTE, [cv0, cv1]);
+            for cv in &cvs {
+                self.absorb_chaining_value(cv);
+            }
+            bytes = rest;
+        }
+
+        if bytes.len() >= b {
+            let (block, rest) = bytes.split_at(b);
+            let mut cv = [0u8; CV];
+            Sponge::<R, SHAKE_PAD_BYTE>::new_for_message(&[block]).into_single_squeeze(&mut cv);
+            self.absorb_chaining_value(&cv);
+            bytes = rest;
+        }
+
+        // retain the remainder
+        self.current.absorb(bytes);
+        self.current_len = bytes.len();
+    }
+
+    /// Completes any partial block, and absorbs the number of blocks.
+    fn finish_blocks(mut self) -> Sponge<R, CSHAKE_PAD_BYTE> {
+        if self.current_len > 0 {
+            self.finish_current_block();
+        }
+        self.outer.absorb(right_encode(self.blocks).as_ref());
+        self.outer
+    }
+
+    fn finish_current_block(&mut self) {
+        let current = core::mem::replace(&mut self.current, Sponge::new());
+        self.current_len = 0;
+
+        let mut cv = [0u8; CV];
+        current.absorb_final().into_single_squeeze(&mut cv);
+        self.absorb_chaining_value(&cv);
+    }
+
+    fn absorb_chaining_value(&mut self, cv: &[u8; CV]) {
+        self.outer.absorb(cv);
+        self.blocks += 1;
+    }
+}
+
+/// Starts cSHAKE with the given (not both empty) `N` and `S`.
+///
+/// This absorbs `bytepad(encode_string(N) || encode_string(S), rate)`.
+fn cshake_sponge<const R: usize>(
+    function_name: &[u8],
+    customization: &[u8],
+) -> Sponge<R, CSHAKE_PAD_BYTE> {
+    let mut sponge = Sponge::new();
+    sponge.absorb(left_encode(R as u64).as_ref());
+    absorb_encode_string(&mut sponge, function_name);
+    absorb_encode_string(&mut sponge, customization);
+    sponge.absorb_zero_padding();
+    sponge
+}
+
+/// Completes a fixed-length computation: `right_encode(L)` is absorbed,
+/// where `L` is the bit length of `out`, then `out` is filled.
+fn squeeze_with_length<const R: usize>(mut sponge: Sponge<R, CSHAKE_PAD_BYTE>, out: &mut [u8]) {
+    sponge.absorb(right_encode(bit_length(out.len())).as_ref());
+    sponge.absorb_final().squeeze(out);
+}
+
+/// Completes an XOF computation: this absorbs `right_encode(0)`.
+fn absorb_final_xof<const R: usize>(mut sponge: Sponge<R, CSHAKE_PAD_BYTE>) -> SqueezingSponge<R> {
+    sponge.absorb(right_encode(0).as_ref());
+    sponge.absorb_final()
+}
+
+/// `encode_string(S)`: `S` preceded by `left_encode()` of its length in bits.
+fn absorb_encode_string<const R: usize, const PAD: u8>(sponge: &mut Sponge<R, PAD>, s: &[u8]) {
+    sponge.absorb(left_encode(bit_length(s.len())).as_ref());
+    sponge.absorb(s);
+}
+
+fn bit_length(bytes: usize) -> u64 {
+    (bytes as u64) * 8
+}
+
+/// `left_encode(x)`: the minimal big-endian encoding of `x`, preceded by its length.
+fn left_encode(x: u64) -> Encoded {
+    let len = encoded_len(x);
+    let mut bytes = [0u8; 9];
+    bytes[0] = len as u8;
+    bytes[1..len + 1].copy_from_slice(&x.to_be_bytes()[8 - len..]);
+    Encoded {
+        bytes,
+        len: len + 1,
+    }
+}
+
+/// `right_encode(x)`: the minimal big-endian encoding of `x`, followed by its length.
+fn right_encode(x: u64) -> Encoded {
+    let len = encoded_len(x);
+    let mut bytes = [0u8; 9];
+    bytes[..len].copy_from_slice(&x.to_be_bytes()[8 - len..]);
+    bytes[len] = len as u8;
+    Encoded {
+        bytes,
+        len: len + 1,
+    }
+}
+
+/// The number of bytes needed to encode `x`, which is at least one.
+fn encoded_len(x: u64) -> usize {
+    Ord::max(1, 8 - (x.leading_zeros() as usize / 8))
+}
+
+struct Encoded {
+    bytes: [u8; 9],
+    len: usize,
+}
+
+impl AsRef<[u8]> for Encoded {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    // Vectors are from the SP800-185 samples, at
+    // <https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values>
+    const X4: &[u8] = b"\x00\x01\x02\x03";
+    const KEY: &[u8] = b"\x40\x41\x42\x43\x44\x45\x46\x47\x48\x49\x4a\x4b\x4c\x4d\x4e\x4f\
+                         \x50\x51\x52\x53\x54\x55\x56\x57\x58\x59\x5a\x5b\x5c\x5d\x5e\x5f";
+
+    fn x200() -> [u8; 200] {
+        core::array::from_fn(|i| i as u8)
+    }
+
+    fn read128(mut ctx: Shake128) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        ctx.read(&mut out);
+        out
+    }
+
+    fn read256(mut ctx: Shake256) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        ctx.read(&mut out);
+        out
+    }
+
+    fn cshake128(n: &[u8], s: &[u8], x: &[u8]) -> [u8; 32] {
+        let mut ctx = CShake128::new(n, s);
+        ctx.update(x);
+        read128(ctx.finalize_xof())
+    }
+
+    fn cshake256(n: &[u8], s: &[u8], x: &[u8]) -> [u8; 64] {
+        let mut ctx = CShake256::new(n, s);
+        ctx.update(x);
+        read256(ctx.finalize_xof())
+    }
+
+    #[test]
+    fn cshake() {
+        assert_eq!(
+            cshake128(b"", b"Email Signature", X4),
+            *b"\xc1\xc3\x69\x25\xb6\x40\x9a\x04\xf1\xb5\x04\xfc\xbc\xa9\xd8\x2b\
+               \x40\x17\x27\x7c\xb5\xed\x2b\x20\x65\xfc\x1d\x38\x14\xd5\xaa\xf5"
+        );
+        assert_eq!(
+            cshake128(b"", b"Email Signature", &x200()),
+            *b"\xc5\x22\x1d\x50\xe4\xf8\x22\xd9\x6a\x2e\x88\x81\xa9\x61\x42\x0f\
+               \x29\x4b\x7b\x24\xfe\x3d\x20\x94\xba\xed\x2c\x65\x24\xcc\x16\x6b"
+        );
+        assert_eq!(
+            cshake256(b"", b"Email Signature", X4),
+            *b"\xd0\x08\x82\x8e\x2b\x80\xac\x9d\x22\x18\xff\xee\x1d\x07\x0c\x48\
+               \xb8\xe4\xc8\x7b\xff\x32\xc9\x69\x9d\x5b\x68\x96\xee\xe0\xed\xd1\
+               \x64\x02\x0e\x2b\xe0\x56\x08\x58\xd9\xc0\x0c\x03\x7e\x34\xa9\x69\
+               \x37\xc5\x61\xa7\x4c\x41\x2b\xb4\xc7\x46\x46\x95\x27\x28\x1c\x8c"
+        );
+        assert_eq!(
+            cshake256(b"", b"Email Signature", &x200()),
+            *b"\x07\xdc\x27\xb1\x1e\x51\xfb\xac\x75\xbc\x7b\x3c\x1d\x98\x3e\x8b\
+               \x4b\x85\xfb\x1d\xef\xaf\x21\x89\x12\xac\x86\x43\x02\x73\x09\x17\
+               \x27\xf4\x2b\x17\xed\x1d\xf6\x3e\x8e\xc1\x18\xf0\x4b\x23\x63\x3c\
+               \x1d\xfb\x15\x74\xc8\xfb\x55\xcb\x45\xda\x8e\x25\xaf\xb0\x92\xbb"
+        );
+
+        // empty N and S is SHAKE
+        let mut expected = [0u8; 32];
+        Shake128::new(&[b"hello"]).read(&mut expected);
+        assert_eq!(cshake128(b"", b"", b"hello"), expected);
+        let mut expected = [0u8; 64];
+        Shake256::new(&[b"hello"]).read(&mut expected);
+        assert_eq!(cshake256(b"", b"", b"hello"), expected);
+    }
+
+    #[test]
+    fn kmac() {
+        let kmac128 = |s: &[u8], x: &[u8]| {
+            let mut ctx = Kmac128::new(KEY, s);
+            ctx.update(x);
+            let mut out = [0u8; 32];
+            ctx.finish(&mut out);
+            out
+        };
+        let kmac256 = |s: &[u8], x: &[u8]| {
+            let mut ctx = Kmac256::new(KEY, s);
+            ctx.update(x);
+            let mut out = [0u8; 64];
+            ctx.finish(&mut out);
+            out
+        };
+
+        assert_eq!(
+            kmac128(b"", X4),
+            *b"\xe5\x78\x0b\x0d\x3e\xa6\xf7\xd3\xa4\x29\xc5\x70\x6a\xa4\x3a\x00\
+               \xfa\xdb\xd7\xd4\x96\x28\x83\x9e\x31\x87\x24\x3f\x45\x6e\xe1\x4e"
+        );
+        assert_eq!(
+            kmac128(b"My Tagged Application", X4),
+            *b"\x3b\x1f\xba\x96\x3c\xd8\xb0\xb5\x9e\x8c\x1a\x6d\x71\x88\x8b\x71\
+               \x43\x65\x1a\xf8\xba\x0a\x70\x70\xc0\x97\x9e\x28\x11\x32\x4a\xa5"
+        );
+        assert_eq!(
+            kmac128(b"My Tagged Application", &x200()),
+            *b"\x1f\x5b\x4e\x6c\xca\x02\x20\x9e\x0d\xcb\x5c\xa6\x35\xb8\x9a\x15\
+               \xe2\x71\xec\xc7\x60\x07\x1d\xfd\x80\x5f\xaa\x38\xf9\x72\x92\x30"
+        );
+        assert_eq!(
+            kmac256(b"My Tagged Application", X4),
+            *b"\x20\xc5\x70\xc3\x13\x46\xf7\x03\xc9\xac\x36\xc6\x1c\x03\xcb\x64\
+               \xc3\x97\x0d\x0c\xfc\x78\x7e\x9b\x79\x59\x9d\x27\x3a\x68\xd2\xf7\
+               \xf6\x9d\x4c\xc3\xde\x9d\x10\x4a\x35\x16\x89\xf2\x7c\xf6\xf5\x95\
+               \x1f\x01\x03\xf3\x3f\x4f\x24\x87\x10\x24\xd9\xc2\x77\x73\xa8\xdd"
+        );
+        assert_eq!(
+            kmac256(b"My Tagged Application", &x200()),
+            *b"\xb5\x86\x18\xf7\x1f\x92\xe1\xd5\x6c\x1b\x8c\x55\xdd\xd7\xcd\x18\
+               \x8b\x97\xb4\xca\x4d\x99\x83\x1e\xb2\x69\x9a\x83\x7d\xa2\xe4\xd9\
+               \x70\xfb\xac\xfd\xe5\x00\x33\xae\xa5\x85\xf1\xa2\x70\x85\x10\xc3\
+               \x2d\x07\x88\x08\x01\xbd\x18\x28\x98\xfe\x47\x68\x76\xfc\x89\x65"
+        );
+
+        assert_eq!(
+            kmac256(b"", &x200()),
+            *b"\x75\x35\x8c\xf3\x9e\x41\x49\x4e\x94\x97\x07\x92\x7c\xee\x0a\xf2\
+               \x0a\x3f\xf5\x53\x90\x4c\x86\xb0\x8f\x21\xcc\x41\x4b\xcf\xd6\x91\
+               \x58\x9d\x27\xcf\x5e\x15\x36\x9c\xbb\xff\x8b\x9a\x4c\x2e\xb1\x78\
+               \x00\x85\x5d\x02\x35\xff\x63\x5d\xa8\x25\x33\xec\x6b\x75\x9b\x69"
+        );
+
+        // a key longer than the rate, and a long output, per the definition:
+        // cSHAKE128(bytepad(encode_string(K), 168) || X || right_encode(L), L, "KMAC", S)
+        let key = [b'k'; 300];
+        let mut ctx = Kmac128::new(&key, b"");
+        ctx.update(b"data");
+        let mut out = [0u8; 200];
+        ctx.finish(&mut out);
+
+        let mut reference = CShake128::new(b"KMAC", b"");
+        let rate = left_encode(168);
+        let key_len = left_encode(bit_length(key.len()));
+        reference.update(rate.as_ref());
+        reference.update(key_len.as_ref());
+        reference.update(&key);
+        let used = rate.as_ref().len() + key_len.as_ref().len() + key.len();
+        reference.update(&[0u8; 168][..(168 - used % 168) % 168]);
+        reference.update(b"data");
+        reference.update(right_encode(bit_length(out.len())).as_ref());
+        let mut expected = [0u8; 200];
+        reference.finalize_xof().read(&mut expected);
+        assert_eq!(out, expected);
+
+        // the output length is bound into the result
+        let mut ctx = Kmac128::new(KEY, b"");
+        ctx.update(X4);
+        let mut short = [0u8; 16];
+        ctx.finish(&mut short);
+        assert_ne!(short, kmac128(b"", X4)[..16]);
+    }
+
+    #[test]
+    fn kmac_xof() {
+        let mut ctx = Kmac128::new(KEY, b"");
+        ctx.update(X4);
+        assert_eq!(
+            read128(ctx.finalize_xof()),
+            *b"\xcd\x83\x74\x0b\xbd\x92\xcc\xc8\xcf\x03\x2b\x14\x81\xa0\xf4\x46\
+               \x0e\x7c\xa9\xdd\x12\xb0\x8a\x0c\x40\x31\x17\x8b\xac\xd6\xec\x35"
+        );
+
+        let mut ctx = Kmac256::new(KEY, b"My Tagged Application");
+        ctx.update(&x200());
+        assert_eq!(
+            read256(ctx.finalize_xof()),
+            *b"\xd5\xbe\x73\x1c\x95\x4e\xd7\x73\x28\x46\xbb\x59\xdb\xe3\xa8\xe3\
+               \x0f\x83\xe7\x7a\x4b\xff\x44\x59\xf2\xf1\xc2\xb4\xec\xeb\xb8\xce\
+               \x67\xba\x01\xc6\x2e\x8a\xb8\x57\x8d\x2d\x49\x9b\xd1\xbb\x27\x67\
+               \x68\x78\x11\x90\x02\x0a\x30\x6a\x97\xde\x28\x1d\xcc\x30\x30\x5d"
+        );
+    }
+
+    #[test]
+    fn kmac_verify() {
+        let mut tag = [0u8; 200];
+        let mut ctx = Kmac128::new(&[b'k'; 300], b"");
+        ctx.update(b"data");
+        ctx.clone().finish(&mut tag);
+        ctx.clone().verify(&tag).unwrap();
+
+        // truncation is detected
+        assert_eq!(
+            ctx.clone().verify(&tag[..199]).unwrap_err(),
+            Error::BadSignature
+        );
+        assert_eq!(ctx.clone().verify(&[]).unwrap_err(), Error::BadSignature);
+
+        // as is corruption, anywhere
+        for i in [0, 167, 168, 199] {
+            let mut bad = tag;
+            bad[i] ^= 1;
+            assert_eq!(ctx.clone().verify(&bad).unwrap_err(), Error::BadSignature);
+        }
+
+        let mut tag = [0u8; 64];
+        let mut ctx = Kmac256::new(KEY, b"My Tagged Application");
+        ctx.update(X4);
+        ctx.clone().finish(&mut tag);
+        ctx.clone().verify(&tag).unwrap();
+        tag[63] ^= 0x80;
+        assert_eq!(ctx.verify(&tag).unwrap_err(), Error::BadSignature);
+    }
+
+    #[test]
+    fn tuple_hash() {
+        let items: [&[u8]; 3] = [
+            b"\x00\x01\x02",
+            b"\x10\x11\x12\x13\x14\x15",
+            b"\x20\x21\x22\x23\x24\x25\x26\x27\x28",
+        ];
+
+        let mut ctx = TupleHash128::new(b"");
+        ctx.push(items[0]);
+        ctx.push(items[1]);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xc5\xd8\x78\x6c\x1a\xfb\x9b\x82\x11\x1a\xb3\x4b\x65\xb2\xc0\x04\
+               \x8f\xa6\x4e\x6d\x48\xe2\x63\x26\x4c\xe1\x70\x7d\x3f\xfc\x8e\xd1"
+        );
+
+        let mut ctx = TupleHash128::new(b"My Tuple App");
+        for item in items {
+            ctx.push(item);
+        }
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xe6\x0f\x20\x2c\x89\xa2\x63\x1e\xda\x8d\x4c\x58\x8c\xa5\xfd\x07\
+               \xf3\x9e\x51\x51\x99\x8d\xec\xcf\x97\x3a\xdb\x38\x04\xbb\x6e\x84"
+        );
+
+        let mut ctx = TupleHash256::new(b"");
+        ctx.push(items[0]);
+        ctx.push(items[1]);
+        let mut out = [0u8; 64];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xcf\xb7\x05\x8c\xac\xa5\xe6\x68\xf8\x1a\x12\xa2\x0a\x21\x95\xce\
+               \x97\xa9\x25\xf1\xdb\xa3\xe7\x44\x9a\x56\xf8\x22\x01\xec\x60\x73\
+               \x11\xac\x26\x96\xb1\xab\x5e\xa2\x35\x2d\xf1\x42\x3b\xde\x7b\xd4\
+               \xbb\x78\xc9\xae\xd1\xa8\x53\xc7\x86\x72\xf9\xeb\x23\xbb\xe1\x94"
+        );
+
+        let mut ctx = TupleHash128::new(b"My Tuple App");
+        ctx.push(items[0]);
+        ctx.push(items[1]);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x75\xcd\xb2\x0f\xf4\xdb\x11\x54\xe8\x41\xd7\x58\xe2\x41\x60\xc5\
+               \x4b\xae\x86\xeb\x8c\x13\xe7\xf5\xf4\x0e\xb3\x55\x88\xe9\x6d\xfb"
+        );
+
+        // item boundaries are significant
+        let mut ctx = TupleHash128::new(b"");
+        ctx.push(b"\x00\x01\x02\x10\x11\x12");
+        ctx.push(b"\x13\x14\x15");
+        let mut other = [0u8; 32];
+        ctx.finish(&mut other);
+        let mut ctx = TupleHash128::new(b"");
+        ctx.push(items[0]);
+        ctx.push(items[1]);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_ne!(other, out);
+    }
+
+    #[test]
+    fn tuple_hash_xof() {
+        let mut ctx = TupleHash128::new(b"");
+        ctx.push(b"\x00\x01\x02");
+        ctx.push(b"\x10\x11\x12\x13\x14\x15");
+        assert_eq!(
+            read128(ctx.finalize_xof()),
+            *b"\x2f\x10\x3c\xd7\xc3\x23\x20\x35\x34\x95\xc6\x8d\xe1\xa8\x12\x92\
+               \x45\xc6\x32\x5f\x6f\x2a\x3d\x60\x8d\x92\x17\x9c\x96\xe6\x84\x88"
+        );
+
+        let mut ctx = TupleHash256::new(b"My Tuple App");
+        ctx.push(b"\x00\x01\x02");
+        ctx.push(b"\x10\x11\x12\x13\x14\x15");
+        ctx.push(b"\x20\x21\x22\x23\x24\x25\x26\x27\x28");
+        assert_eq!(
+            read256(ctx.finalize_xof()),
+            *b"\x0c\x59\xb1\x14\x64\xf2\x33\x6c\x34\x66\x3e\xd5\x1b\x2b\x95\x0b\
+               \xec\x74\x36\x10\x85\x6f\x36\xc2\x8d\x1d\x08\x8d\x8a\x24\x46\x28\
+               \x4d\xd0\x98\x30\xa6\xa1\x78\xdc\x75\x23\x76\x19\x9f\xae\x93\x5d\
+               \x86\xcf\xde\xe5\x91\x3d\x49\x22\xdf\xd3\x69\xb6\x6a\x53\xc8\x97"
+        );
+    }
+
+    const PARALLEL_24: &[u8] = b"\x00\x01\x02\x03\x04\x05\x06\x07\x10\x11\x12\x13\x14\x15\x16\x17\
+                                 \x20\x21\x22\x23\x24\x25\x26\x27";
+    const PARALLEL_72: &[u8] = b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x10\x11\x12\x13\
+                                 \x14\x15\x16\x17\x18\x19\x1a\x1b\x20\x21\x22\x23\x24\x25\x26\x27\
+                                 \x28\x29\x2a\x2b\x30\x31\x32\x33\x34\x35\x36\x37\x38\x39\x3a\x3b\
+                                 \x40\x41\x42\x43\x44\x45\x46\x47\x48\x49\x4a\x4b\x50\x51\x52\x53\
+                                 \x54\x55\x56\x57\x58\x59\x5a\x5b";
+
+    #[test]
+    fn parallel_hash() {
+        let mut ctx = ParallelHash128::new(8, b"").unwrap();
+        ctx.update(PARALLEL_24);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xba\x8d\xc1\xd1\xd9\x79\x33\x1d\x3f\x81\x36\x03\xc6\x7f\x72\x60\
+               \x9a\xb5\xe4\x4b\x94\xa0\xb8\xf9\xaf\x46\x51\x44\x54\xa2\xb4\xf5"
+        );
+
+        let mut ctx = ParallelHash128::new(8, b"Parallel Data").unwrap();
+        ctx.update(PARALLEL_24);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xfc\x48\x4d\xcb\x3f\x84\xdc\xee\xdc\x35\x34\x38\x15\x1b\xee\x58\
+               \x15\x7d\x6e\xfe\xd0\x44\x5a\x81\xf1\x65\xe4\x95\x79\x5b\x72\x06"
+        );
+
+        let mut ctx = ParallelHash128::new(12, b"Parallel Data").unwrap();
+        ctx.update(PARALLEL_72);
+        let mut out = [0u8; 32];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xf7\xfd\x53\x12\x89\x6c\x66\x85\xc8\x28\xaf\x7e\x2a\xdb\x97\xe3\
+               \x93\xe7\xf8\xd5\x4e\x3c\x2e\xa4\xb9\x5e\x5a\xca\x37\x96\xe8\xfc"
+        );
+
+        let mut ctx = ParallelHash256::new(8, b"").unwrap();
+        ctx.update(PARALLEL_24);
+        let mut out = [0u8; 64];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\xbc\x1e\xf1\x24\xda\x34\x49\x5e\x94\x8e\xad\x20\x7d\xd9\x84\x22\
+               \x35\xda\x43\x2d\x2b\xbc\x54\xb4\xc1\x10\xe6\x4c\x45\x11\x05\x53\
+               \x1b\x7f\x2a\x3e\x0c\xe0\x55\xc0\x28\x05\xe7\xc2\xde\x1f\xb7\x46\
+               \xaf\x97\xa1\xdd\x01\xf4\x3b\x82\x4e\x31\xb8\x76\x12\x41\x04\x29"
+        );
+
+        let mut ctx = ParallelHash256::new(12, b"Parallel Data").unwrap();
+        ctx.update(PARALLEL_72);
+        let mut out = [0u8; 64];
+        ctx.finish(&mut out);
+        assert_eq!(
+            out,
+            *b"\x69\xd0\xfc\xb7\x64\xea\x05\x5d\xd0\x93\x34\xbc\x60\x21\xcb\x7e\
+               \x4b\x61\x34\x8d\xff\x37\x5d\xa2\x62\x67\x1c\xde\xc3\xef\xfa\x8d\
+               \x1b\x45\x68\xa6\xcc\xe1\x6b\x1c\xad\x94\x6d\xdd\xe2\x7f\x6c\xe2\
+               \xb8\xde\xe4\xcd\x1b\x24\x85\x1e\xbf\x00\xeb\x90\xd4\x38\x13\xe9"
+        );
+
+        // no input at all: zero blocks
+        let mut out = [0u8; 32];
+        ParallelHash128::new(16, b"").unwrap().finish(&mut out);
+        let mut expected = [0u8; 32];
+        parallel_hash128_reference(16, b"", b"", &mut expected);
+        assert_eq!(out, expected);
+
+        assert_eq!(ParallelHash128::new(0, b"").err(), Some(Error::OutOfRange));
+        assert_eq!(ParallelHash256::new(0, b"").err(), Some(Error::OutOfRange));
+    }
+
+    #[test]
+    fn parallel_hash_xof() {
+        let mut ctx = ParallelHash128::new(8, b"").unwrap();
+        ctx.update(PARALLEL_24);
+        assert_eq!(
+            read128(ctx.finalize_xof()),
+            *b"\xfe\x47\xd6\x61\xe4\x9f\xfe\x5b\x7d\x99\x99\x22\xc0\x62\x35\x67\
+               \x50\xca\xf5\x52\x98\x5b\x8e\x8c\xe6\x66\x7f\x27\x27\xc3\xc8\xd3"
+        );
+
+        let mut ctx = ParallelHash256::new(12, b"Parallel Data").unwrap();
+        ctx.update(PARALLEL_72);
+        assert_eq!(
+            read256(ctx.finalize_xof()),
+            *b"\x6b\x3e\x79\x0b\x33\x0c\x88\x9a\x20\x4c\x2f\xbc\x72\x8d\x80\x9f\
+               \x19\x36\x73\x28\xd8\x52\xf4\x00\x2d\xc8\x29\xf7\x3a\xfd\x6b\xce\
+               \xfb\x7f\xe5\xb6\x07\xb1\x3a\x80\x1c\x0b\xe5\xc1\x17\x0b\xdb\x79\
+               \x4e\x33\x94\x58\xfd\xb0\xe6\x2a\x6a\xf3\xd4\x25\x58\x97\x02\x49"
+        );
+    }
+
+    /// ParallelHash128 per the definition: `cSHAKE128(left_encode(B) || z_0 || .. ||
+    /// right_encode(n) || right_encode(L), L, "ParallelHash", S)`, where each
+    /// `z_i` is SHAKE128 of block `i`, one block at a time.
+    fn parallel_hash128_reference(block_size: usize, s: &[u8], x: &[u8], out: &mut [u8]) {
+        let mut outer = CShake128::new(b"ParallelHash", s);
+        outer.update(left_encode(block_size as u64).as_ref());
+        let mut blocks = 0;
+        for block in x.chunks(block_size) {
+            let mut z = [0u8; 32];
+            Shake128::new(&[block]).read(&mut z);
+            outer.update(&z);
+            blocks += 1;
+        }
+        outer.update(right_encode(blocks).as_ref());
+        outer.update(right_encode(bit_length(out.len())).as_ref());
+        outer.finalize_xof().read(out);
+    }
+
+    /// As `parallel_hash128_reference`, for ParallelHash256.
+    fn parallel_hash256_reference(block_size: usize, s: &[u8], x: &[u8], out: &mut [u8]) {
+        let mut outer = CShake256::new(b"ParallelHash", s);
+        outer.update(left_encode(block_size as u64).as_ref());
+        let mut blocks = 0;
+        for block in x.chunks(block_size) {
+            let mut z = [0u8; 64];
+            Shake256::new(&[block]).read(&mut z);
+            outer.update(&z);
+            blocks += 1;
+        }
+        outer.update(right_encode(blocks).as_ref());
+        outer.update(right_encode(bit_length(out.len())).as_ref());
+        outer.finalize_xof().read(out);
+    }
+
+    #[test]
+    fn parallel_hash_update_sizes() {
+        // 15 blocks, each longer than the rate, with a partial final block.
+        // This exercises the four-, two- and one-at-a-time paths.
+        let input: [u8; 3000] = core::array::from_fn(|i| (i * 7 + 3) as u8);
+
+        let mut expected128 = [0u8; 32];
+        parallel_hash128_reference(200, b"big", &input, &mut expected128);
+        let mut expected256 = [0u8; 64];
+        parallel_hash256_reference(200, b"big", &input, &mut expected256);
+
+        for chunk in [1, 7, 199, 200, 201, 400, 800, 999, 1000, 3000] {
+            let mut ctx = ParallelHash128::new(200, b"big").unwrap();
+            for c in input.chunks(chunk) {
+                ctx.update(c);
+            }
+            let mut out = [0u8; 32];
+            ctx.finish(&mut out);
+            assert_eq!(out, expected128);
+
+            let mut ctx = ParallelHash256::new(200, b"big").unwrap();
+            for c in input.chunks(chunk) {
+                ctx.update(c);
+            }
+            let mut out = [0u8; 64];
+            ctx.finish(&mut out);
+            assert_eq!(out, expected256);
+        }
+    }
+
+    #[test]
+    fn encodings() {
+        assert_eq!(left_encode(0).as_ref(), &[1, 0]);
+        assert_eq!(left_encode(168).as_ref(), &[1, 168]);
+        assert_eq!(left_encode(256).as_ref(), &[2, 1, 0]);
+        assert_eq!(
+            left_encode(u64::MAX).as_ref(),
+            &[8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(right_encode(0).as_ref(), &[0, 1]);
+        assert_eq!(right_encode(256).as_ref(), &[1, 0, 2]);
+    }
+}
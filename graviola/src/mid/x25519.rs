@@ -22,10 +22,7 @@ impl PrivateKey {
     /// Fails only if the random source fails.
     pub fn new_random() -> Result<Self, Error> {
         let _entry = low::Entry::new_secret();
-        let mut r = [0u8; Self::BYTES];
-        SystemRandom.fill(&mut r)?;
-        let r = low::ct::into_secret(r);
-        Ok(Self(util::little_endian_to_u64x4(&r)))
+        Self::generate(&mut SystemRandom)
     }
 
     /// Compute the associated public key.
@@ -57,6 +54,13 @@ impl PrivateKey {
             Ok(SharedSecret(util::u64x4_to_little_endian(&res)))
         }
     }
+
+    pub(crate) fn generate(rng: &mut dyn RandomSource) -> Result<Self, Error> {
+        let mut r = [0u8; Self::BYTES];
+        rng.fill(&mut r)?;
+        let r = low::ct::into_secret(r);
+        Ok(Self(util::little_endian_to_u64x4(&r)))
+    }
 }
 
 impl Drop for PrivateKey {
@@ -137,7 +141,7 @@ impl StaticPrivateKey {
 pub struct PublicKey([u64; 4]);
 
 impl PublicKey {
-    const BYTES: usize = 32;
+    pub(crate) const BYTES: usize = 32;
 
     /// Create an X25519 [`PublicKey`] from a byte slice.
     ///
@@ -2,10 +2,15 @@ use std::fs::File;
 use std::io::BufReader;
 
 use graviola::Error;
-use graviola::aead::{AesGcm, ChaCha20Poly1305, XChaCha20Poly1305};
+use graviola::aead::{
+    Aegis128L, Aegis256, AesCcm, AesGcm, AesGcmSiv, AesSiv, ChaCha20Poly1305, XChaCha20Poly1305,
+};
 use graviola::hashing::hmac::Hmac;
-use graviola::hashing::{Sha256, Sha384, Sha512};
+use graviola::hashing::{Hash, Sha256, Sha384, Sha512};
+use graviola::kdf::pbkdf2;
 use graviola::key_agreement::{mlkem768, p256, p384, x25519};
+use graviola::key_wrap::AesKw;
+use graviola::mac::{AesCmac, Gmac};
 use graviola::signing::{ecdsa, eddsa, rsa};
 use serde::Deserialize;
 
@@ -80,6 +85,12 @@ struct Test {
     m: Vec<u8>,
     #[serde(default, with = "hex::serde")]
     c: Vec<u8>,
+    #[serde(default, with = "hex::serde")]
+    password: Vec<u8>,
+    #[serde(default, with = "hex::serde")]
+    salt: Vec<u8>,
+    #[serde(default, rename(deserialize = "iterationCount"))]
+    iteration_count: u32,
     result: ExpectedResult,
 }
 
@@ -254,6 +265,40 @@ fn hmac_sha512_tests() {
     }
 }
 
+#[test]
+fn pbkdf2_tests() {
+    fn check<H: Hash>(file: &str) {
+        let data_file = File::open(format!("../thirdparty/wycheproof/testvectors_v1/{file}"))
+            .expect("failed to open data file");
+
+        let reader = BufReader::new(data_file);
+        let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+        let mut summary = Summary::new();
+
+        for group in tests.groups {
+            summary.group(&group);
+
+            for test in group.tests {
+                summary.start(&test);
+
+                let mut dk = vec![0u8; test.dk.len()];
+                let result = pbkdf2::<H>(&test.password, &test.salt, test.iteration_count, &mut dk);
+
+                match (test.result, result) {
+                    (ExpectedResult::Valid, Ok(())) => assert_eq!(dk, test.dk),
+                    (ExpectedResult::Invalid, Ok(())) => assert_ne!(dk, test.dk),
+                    (ExpectedResult::Invalid, Err(Error::OutOfRange)) => {}
+                    _ => panic!("expected {:?} got {:?}", test.result, result),
+                }
+            }
+        }
+    }
+
+    check::<Sha256>("pbkdf2_hmacsha256_test.json");
+    check::<Sha384>("pbkdf2_hmacsha384_test.json");
+    check::<Sha512>("pbkdf2_hmacsha512_test.json");
+}
+
 #[test]
 fn test_verify_ecdsa_p256() {
     for file in [
@@ -485,6 +530,93 @@ fn test_aes_gcm() {
     let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
     let mut summary = Summary::new();
 
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            let ctx = AesGcm::new(&test.key);
+
+            // try decrypt
+            let mut msg = test.ct.clone();
+            let result = ctx.decrypt_with_iv(&test.iv, &test.aad, &mut msg, &test.tag);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(())) => {
+                    assert_eq!(msg, test.msg);
+                }
+                (ExpectedResult::Invalid, Err(Error::DecryptFailed | Error::WrongLength)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and encrypt
+            let mut ct = test.msg.clone();
+            let mut tag = [0u8; 16];
+
+            let result = ctx.encrypt_with_iv(&test.iv, &test.aad, &mut ct, &mut tag);
+
+            if test.result == ExpectedResult::Valid {
+                result.unwrap();
+                assert_eq!(ct, test.ct);
+                assert_eq!(&tag, &test.tag[..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aes_ccm() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_ccm_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            let ctx = AesCcm::new(&test.key, test.tag.len());
+
+            // try decrypt
+            let mut msg = test.ct.clone();
+            let result = ctx.decrypt(&test.iv, &test.aad, &mut msg, &test.tag);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(())) => {
+                    assert_eq!(msg, test.msg);
+                }
+                (ExpectedResult::Invalid, Err(Error::DecryptFailed)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and encrypt
+            let mut ct = test.msg.clone();
+            let mut tag = vec![0u8; test.tag.len()];
+            let result = ctx.encrypt(&test.iv, &test.aad, &mut ct, &mut tag);
+
+            if test.result == ExpectedResult::Valid {
+                result.unwrap();
+                assert_eq!(ct, test.ct);
+                assert_eq!(tag, test.tag);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aes_gcm_siv() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_gcm_siv_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
     for group in tests.groups {
         summary.group(&group);
 
@@ -492,15 +624,16 @@ fn test_aes_gcm() {
             summary.start(&test);
 
             if test.key.len() == 24 {
-                summary.skipped("aes-192 not supported");
+                summary.skipped("aes-192 not defined for AES-GCM-SIV");
                 continue;
             }
 
-            let ctx = AesGcm::new(&test.key);
+            let ctx = AesGcmSiv::new(&test.key);
             let nonce = match test.iv.len() {
                 12 => test.iv.try_into().unwrap(),
                 _ => {
-                    summary.skipped("gcm nonces must be 96 bits");
+                    assert_eq!(test.result, ExpectedResult::Invalid);
+                    summary.skipped("gcm-siv nonces must be 96 bits");
                     continue;
                 }
             };
@@ -532,8 +665,8 @@ fn test_aes_gcm() {
 }
 
 #[test]
-fn test_aes_gmac() {
-    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_gmac_test.json")
+fn test_aes_siv() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_siv_cmac_test.json")
         .expect("failed to open data file");
 
     let reader = BufReader::new(data_file);
@@ -546,36 +679,190 @@ fn test_aes_gmac() {
         for test in group.tests {
             summary.start(&test);
 
-            if test.key.len() == 24 {
-                summary.skipped("aes-192 not supported");
+            if ![32, 48, 64].contains(&test.key.len()) {
+                summary.skipped("aes-siv key size not supported");
                 continue;
             }
 
-            let ctx = AesGcm::new(&test.key);
-            let nonce = match test.iv.len() {
-                12 => test.iv.try_into().unwrap(),
-                _ => {
-                    summary.skipped("gcm nonces must be 96 bits");
-                    continue;
-                }
-            };
+            let ctx = AesSiv::new(&test.key);
 
             // try decrypt
-            let result = ctx.decrypt(&nonce, &test.msg, &mut [], &test.tag);
+            let (tag, ct) = test.ct.split_at(test.ct.len().min(16));
+            let mut msg = ct.to_vec();
+            let result = ctx.decrypt(&[&test.aad], &mut msg, tag);
 
             match (test.result, &result) {
-                (ExpectedResult::Valid, Ok(())) => {}
+                (ExpectedResult::Valid, Ok(())) => {
+                    assert_eq!(msg, test.msg);
+                }
                 (ExpectedResult::Invalid, Err(Error::DecryptFailed)) => {}
                 _ => panic!("expected {:?} got {:?}", test.result, result.err()),
             }
 
             // and encrypt
-            let mut tag = [0u8; 16];
+            if test.result == ExpectedResult::Valid {
+                let mut ct = test.msg.clone();
+                let mut tag = [0u8; 16];
+                ctx.encrypt(&[&test.aad], &mut ct, &mut tag).unwrap();
+                assert_eq!(&tag[..], &test.ct[..16]);
+                assert_eq!(ct, &test.ct[16..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aes_cmac() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_cmac_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            if ![16, 24, 32].contains(&test.key.len()) {
+                summary.skipped("aes key size not supported");
+                continue;
+            }
+
+            let ctx = AesCmac::new(&test.key);
 
-            ctx.encrypt(&nonce, &test.msg, &mut [], &mut tag);
+            // try verify (truncated tags are not supported)
+            if test.tag.len() == 16 {
+                let result = ctx.verify(&test.msg, &test.tag);
 
+                match (test.result, &result) {
+                    (ExpectedResult::Valid, Ok(())) => {}
+                    (ExpectedResult::Invalid, Err(Error::BadSignature)) => {}
+                    _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+                }
+            }
+
+            // and sign
             if test.result == ExpectedResult::Valid {
-                assert_eq!(&tag, &test.tag[..]);
+                let tag = ctx.sign(&test.msg);
+                assert_eq!(&tag[..test.tag.len()], &test.tag[..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aes_gmac() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_gmac_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            let ctx = Gmac::new(&test.key);
+
+            // try verify
+            let result = ctx.verify(&test.iv, &test.msg, &test.tag);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(())) => {}
+                (ExpectedResult::Invalid, Err(Error::BadSignature | Error::WrongLength)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and sign
+            let result = ctx.sign(&test.iv, &test.msg);
+
+            if test.result == ExpectedResult::Valid {
+                assert_eq!(&result.unwrap()[..], &test.tag[..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aes_kw() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_wrap_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            let ctx = AesKw::new(&test.key);
+
+            // try unwrap
+            let mut out = vec![0u8; test.ct.len()];
+            let result = ctx.unwrap(&test.ct, &mut out);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(msg)) => {
+                    assert_eq!(msg, &test.msg);
+                }
+                (ExpectedResult::Invalid, Err(Error::DecryptFailed)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and wrap
+            if test.result == ExpectedResult::Valid {
+                let mut out = vec![0u8; test.msg.len() + 8];
+                assert_eq!(ctx.wrap(&test.msg, &mut out).unwrap(), &test.ct);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aes_kwp() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aes_kwp_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            let ctx = AesKw::new(&test.key);
+
+            // try unwrap
+            let mut out = vec![0u8; test.ct.len()];
+            let result = ctx.unwrap_with_padding(&test.ct, &mut out);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(msg)) => {
+                    assert_eq!(msg, &test.msg);
+                }
+                (ExpectedResult::Invalid, Err(Error::DecryptFailed)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and wrap
+            if test.result == ExpectedResult::Valid {
+                let mut out = vec![0u8; AesKw::padded_len(test.msg.len())];
+                assert_eq!(
+                    ctx.wrap_with_padding(&test.msg, &mut out).unwrap(),
+                    &test.ct
+                );
             }
         }
     }
@@ -827,6 +1114,104 @@ fn test_xchacha20poly1305() {
     }
 }
 
+#[test]
+fn test_aegis128l() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aegis128L_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            if test.iv.len() != 16 {
+                summary.skipped("aegis128l nonce must be 128 bit");
+                continue;
+            }
+
+            let ctx = Aegis128L::new(test.key.try_into().unwrap());
+            let nonce = test.iv.try_into().unwrap();
+
+            // try decrypt
+            let mut msg = test.ct.clone();
+            let result = ctx.decrypt(&nonce, &test.aad, &mut msg, &test.tag);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(())) => {
+                    assert_eq!(msg, test.msg);
+                }
+                (ExpectedResult::Invalid, Err(Error::DecryptFailed)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and encrypt
+            let mut ct = test.msg.clone();
+            let mut tag = [0u8; 16];
+
+            ctx.encrypt(&nonce, &test.aad, &mut ct, &mut tag);
+
+            if test.result == ExpectedResult::Valid {
+                assert_eq!(ct, test.ct);
+                assert_eq!(&tag, &test.tag[..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aegis256() {
+    let data_file = File::open("../thirdparty/wycheproof/testvectors_v1/aegis256_test.json")
+        .expect("failed to open data file");
+
+    let reader = BufReader::new(data_file);
+    let tests: TestFile = serde_json::from_reader(reader).expect("invalid test JSON");
+    let mut summary = Summary::new();
+
+    for group in tests.groups {
+        summary.group(&group);
+
+        for test in group.tests {
+            summary.start(&test);
+
+            if test.iv.len() != 32 {
+                summary.skipped("aegis256 nonce must be 256 bit");
+                continue;
+            }
+
+            let ctx = Aegis256::new(test.key.try_into().unwrap());
+            let nonce = test.iv.try_into().unwrap();
+
+            // try decrypt
+            let mut msg = test.ct.clone();
+            let result = ctx.decrypt(&nonce, &test.aad, &mut msg, &test.tag);
+
+            match (test.result, &result) {
+                (ExpectedResult::Valid, Ok(())) => {
+                    assert_eq!(msg, test.msg);
+                }
+                (ExpectedResult::Invalid, Err(Error::DecryptFailed)) => {}
+                _ => panic!("expected {:?} got {:?}", test.result, result.err()),
+            }
+
+            // and encrypt
+            let mut ct = test.msg.clone();
+            let mut tag = [0u8; 16];
+
+            ctx.encrypt(&nonce, &test.aad, &mut ct, &mut tag);
+
+            if test.result == ExpectedResult::Valid {
+                assert_eq!(ct, test.ct);
+                assert_eq!(&tag, &test.tag[..]);
+            }
+        }
+    }
+}
+
 #[test]
 fn test_mlkem768_keygen() {
     let data_file =
@@ -120,10 +120,79 @@ fn aes_gcm() {
     let aes128 = AesGcm::new(&[0xffu8; 16]);
     check_zeroed_on_drop(Box::pin(aes128));
 
+    let aes192 = AesGcm::new(&[0xffu8; 24]);
+    check_zeroed_on_drop(Box::pin(aes192));
+
     let aes256 = AesGcm::new(&[0xffu8; 32]);
     check_zeroed_on_drop(Box::pin(aes256));
 }
 
+#[test]
+fn aes_gcm_siv() {
+    use graviola::aead::AesGcmSiv;
+
+    let aes128 = AesGcmSiv::new(&[0xffu8; 16]);
+    check_zeroed_on_drop(Box::pin(aes128));
+
+    let aes256 = AesGcmSiv::new(&[0xffu8; 32]);
+    check_zeroed_on_drop(Box::pin(aes256));
+}
+
+#[test]
+fn aes_modes() {
+    use graviola::cipher::aes::{Cbc, Ecb, Xts};
+
+    let ecb = Ecb::new(&[0xffu8; 16]);
+    check_zeroed_on_drop(Box::pin(ecb));
+
+    let cbc = Cbc::new(&[0xffu8; 32]);
+    check_zeroed_on_drop(Box::pin(cbc));
+
+    let xts = Xts::new(&[[0xffu8; 32], [0xeeu8; 32]].concat());
+    check_zeroed_on_drop(Box::pin(xts));
+}
+
+#[test]
+fn aes_kw() {
+    use graviola::key_wrap::AesKw;
+
+    let kw = AesKw::new(&[0xffu8; 24]);
+    check_zeroed_on_drop(Box::pin(kw));
+}
+
+#[test]
+fn aes_cmac() {
+    use graviola::mac::AesCmac;
+
+    let cmac = AesCmac::new(&[0xffu8; 16]);
+    check_zeroed_on_drop(Box::pin(cmac));
+}
+
+#[test]
+fn aes_siv() {
+    use graviola::aead::AesSiv;
+
+    let siv = AesSiv::new(&[0xffu8; 64]);
+    check_zeroed_on_drop(Box::pin(siv));
+}
+
+#[test]
+fn aegis() {
+    use graviola::aead::{Aegis128L, Aegis128X4, Aegis256};
+
+    check_zeroed_on_drop(Box::pin(Aegis128L::new([0xffu8; 16])));
+    check_zeroed_on_drop(Box::pin(Aegis128X4::new([0xffu8; 16])));
+    check_zeroed_on_drop(Box::pin(Aegis256::new([0xffu8; 32])));
+}
+
+#[test]
+fn ascon_aead128() {
+    use graviola::aead::AsconAead128;
+
+    let ascon = AsconAead128::new([0xffu8; 16]);
+    check_zeroed_on_drop(Box::pin(ascon));
+}
+
 #[test]
 fn chacha20_poly1305() {
     use graviola::aead::ChaCha20Poly1305;